@@ -1,8 +1,4 @@
-use fierceful_atto::action::{ChoiceReturn, Target};
-use fierceful_atto::battle::{self, EndCondition};
-use fierceful_atto::equipment::Equipment;
-use fierceful_atto::member::{Member, MemberIdentifier, Properties, Statistics};
-use fierceful_atto::team::Team;
+use fierceful_atto::prelude::*;
 
 // We will use the `DirectAttack` type from the prefab catalogue to inflict direct damage on our foes.
 use fierceful_atto::catalogue::actions::DirectAttack;
@@ -167,9 +163,10 @@ fn main() {
         Box::new(action_choice),
         EndCondition::LastTeamStanding,
     )
-    .build();
+    .build()
+    .expect("the battle could not be built");
 
-    let resulting_teams = battle.run();
+    let resulting_teams = battle.run().expect("the battle could not be played");
 
     // Output the starting configuration of the battling teams.
     println!("After battle: {resulting_teams:#?}");
@@ -178,6 +175,7 @@ fn main() {
 fn action_choice(
     team_list: &[Team<Player>],
     hint_performer: Option<MemberIdentifier>,
+    _rng: &mut BattleRng,
 ) -> ChoiceReturn<Player> {
     // It should never be `None` in our example, but lets avoid panicking nontheless.
     let hint_performer = hint_performer.unwrap_or_default();
@@ -0,0 +1,204 @@
+// Local PvP example, where two players take turns choosing targets on the same terminal.
+
+use std::io::{self, BufRead, Write};
+
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    name: String,
+    statistics: Stats,
+    properties: Props,
+}
+
+impl Player {
+    pub fn new(name: &str, max_health: u64, attack: u64) -> Self {
+        Self {
+            name: String::from(name),
+            statistics: Stats {
+                max_health,
+                base_attack: attack,
+            },
+            properties: Props {
+                health: max_health,
+                attack,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub max_health: u64,
+    pub base_attack: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Props {
+    pub health: u64,
+    pub attack: u64,
+}
+
+pub struct Gear;
+
+impl Member for Player {
+    type Statistics = Stats;
+    type Properties = Props;
+    type Equipment = Gear;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn member_properties(&self) -> &Props {
+        &self.properties
+    }
+
+    fn member_properties_mut(&mut self) -> &mut Props {
+        &mut self.properties
+    }
+
+    fn statistics(&self) -> &Stats {
+        &self.statistics
+    }
+
+    fn equipment(&self) -> &Self::Equipment {
+        &Gear
+    }
+}
+
+impl Properties for Props {
+    fn health(&self) -> u64 {
+        self.health
+    }
+
+    fn health_mut(&mut self) -> &mut u64 {
+        &mut self.health
+    }
+
+    fn attack(&self) -> u64 {
+        self.attack
+    }
+}
+
+impl Equipment for Gear {
+    type Properties = Props;
+
+    fn associated_properties(&self) -> Self::Properties {
+        Props {
+            health: 0,
+            attack: 0,
+        }
+    }
+}
+
+impl Statistics for Stats {
+    fn reference_health(&self) -> u64 {
+        self.max_health
+    }
+
+    fn base_attack(&self) -> u64 {
+        self.base_attack
+    }
+}
+
+fn main() {
+    let teams = vec![
+        Team::new(
+            String::from("Blue"),
+            vec![Player::new("Picco", 60, 15), Player::new("Nocco", 80, 10)],
+        ),
+        Team::new(
+            String::from("Red"),
+            vec![Player::new("Bacco", 70, 12), Player::new("Lillo", 50, 20)],
+        ),
+    ];
+
+    // Both players choose with the same function, while the gate hides the screen until the device changes hands.
+    let controller = HotSeatController::new(player_choice)
+        .with_seat(0, player_choice)
+        .with_seat(1, player_choice)
+        .with_gate(Box::new(|team_id, teams| {
+            // Clear the terminal, so the next player can't see the previous choices.
+            print!("\x1B[2J\x1B[1;1H");
+            println!(
+                "Pass the device to team {} and press Enter.",
+                teams[team_id].name()
+            );
+            read_line();
+        }));
+
+    let battle = battle::Builder::new(teams, None, controller, EndCondition::LastTeamStanding)
+        .build()
+        .expect("the battle could not be built");
+
+    let resulting_teams = battle.run().expect("the battle could not be played");
+
+    for team in resulting_teams {
+        let alive = team.member_list().iter().any(|m| m.health() > 0);
+
+        if alive {
+            println!("Team {} wins!", team.name());
+        }
+    }
+}
+
+/// Ask the player at the device which enemy the suggested performer should attack.
+fn player_choice(
+    team_list: &[Team<Player>],
+    performer: Option<MemberIdentifier>,
+    _rng: &mut BattleRng,
+) -> ChoiceReturn<Player> {
+    let performer = performer.unwrap_or_default();
+
+    let enemies: Vec<MemberIdentifier> = team_list
+        .iter()
+        .enumerate()
+        .filter(|(team_id, _)| *team_id != performer.team_id)
+        .flat_map(|(team_id, team)| {
+            team.member_list()
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| m.health() > 0)
+                .map(move |(member_id, _)| MemberIdentifier::new(team_id, member_id))
+        })
+        .collect();
+
+    let name = team_list[performer.team_id]
+        .member(performer.member_id)
+        .map_or("?", |m| m.name());
+
+    println!("Who should {name} attack?");
+
+    for (i, id) in enemies.iter().enumerate() {
+        if let Some(enemy) = team_list[id.team_id].member(id.member_id) {
+            println!("{i}) {} ({} HP)", enemy.name(), enemy.health());
+        }
+    }
+
+    let target = loop {
+        print!("> ");
+        let _ = io::stdout().flush();
+
+        match read_line().trim().parse::<usize>() {
+            Ok(i) if i < enemies.len() => break Target::Single(enemies[i]),
+            _ => println!("Choose one of the listed enemies."),
+        }
+    };
+
+    (Box::new(DirectAttack), Target::Single(performer), target)
+}
+
+/// Read a line from the standard input, leaving the game once the input is closed.
+fn read_line() -> String {
+    let mut line = String::new();
+
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => {
+            println!("The players left the game.");
+            std::process::exit(0);
+        }
+        Ok(_) => line,
+    }
+}
@@ -1,11 +1,7 @@
 // Interactive battling example with random encounters that uses a TUI made with Ratatui.
 
-use fierceful_atto::action::{ChoiceReturn, Target};
-use fierceful_atto::battle::{self, EndCondition};
 use fierceful_atto::catalogue::actions::DirectAttack;
-use fierceful_atto::equipment::Equipment;
-use fierceful_atto::member::{Member, MemberIdentifier, Properties, Statistics};
-use fierceful_atto::team::Team;
+use fierceful_atto::prelude::*;
 
 // Ratatui imports to make the TUI
 use ratatui::{
@@ -171,13 +167,16 @@ fn main() {
         Box::new(action_choice),
         EndCondition::LastTeamStanding,
     )
-    .build();
+    .build()
+    .expect("the battle could not be built");
 
     let mut enemy_list_state = ListState::default();
     let mut character_list_state = ListState::default();
 
     while !battle.is_finished() {
-        battle.play_turn();
+        battle
+            .play_turn()
+            .expect("could not play the battle's turn");
 
         terminal
             .draw(|frame| {
@@ -218,10 +217,10 @@ fn main() {
 
                 let friendly_team = &battle
                     .teams()
-                    .get(0)
+                    .first()
                     .expect("battle has no teams available to show");
 
-                let friendly_members_iter = friendly_team.member_list().into_iter();
+                let friendly_members_iter = friendly_team.member_list().iter();
 
                 let name_list: Vec<&str> =
                     friendly_members_iter.clone().map(|m| m.name()).collect();
@@ -258,8 +257,8 @@ fn main() {
         if event::poll(std::time::Duration::from_secs(16)).expect("could not poll terminal events")
         {
             if let event::Event::Key(key) = event::read().expect("could not read terminal events") {
-                match key.kind {
-                    KeyEventKind::Press => match key.code {
+                if key.kind == KeyEventKind::Press {
+                    match key.code {
                         // Exit routine.
                         KeyCode::Char('q') => break,
                         // Select the previus choice in the list.
@@ -267,8 +266,7 @@ fn main() {
                         // Select the next choice in the list.
                         KeyCode::Left => enemy_list_state.select_next(),
                         _ => (),
-                    },
-                    _ => (),
+                    }
                 }
             }
         }
@@ -284,6 +282,7 @@ fn main() {
 fn action_choice(
     team_list: &[Team<Player>],
     hint_performer: Option<MemberIdentifier>,
+    _rng: &mut BattleRng,
 ) -> ChoiceReturn<Player> {
     // It should never be `None` in our example, but in case it is we'll just use the first friendly member.
     let hint_performer = hint_performer.unwrap_or_default();
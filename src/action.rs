@@ -1,63 +1,1224 @@
-use crate::member::{Member, MemberIdentifier};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::any::{Any, TypeId};
+
+use crate::aura::TeamAura;
+use crate::component::{ComponentStore, Tag};
+use crate::control::{self, Control};
+use crate::damage::Damage;
+use crate::element::Element;
+use crate::error::{ActionError, BattleError};
+use crate::event::Event;
+use crate::formation::{Position, Row, Shape, Step};
+use crate::inventory::{Inventory, InventoryOwner, ItemId};
+use crate::member::{Member, MemberHandle, MemberIdentifier, Properties, Statistics};
+use crate::narration::narrate;
+use crate::resource::Cost;
+use crate::rng::BattleRng;
+use crate::stage::Stat;
+use crate::state::BattleState;
+use crate::summon::Lifetime;
 use crate::team::Team;
+use crate::terrain::{Structure, StructureId};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub type ChoiceReturn<M> = (Box<dyn Action<M>>, Target, Target);
+/// Function type to dynamically decide the next [`Action`] to perform.
+///
+/// # Notes
+///
+/// Any random decision should be made using the provided [`BattleRng`] to keep the battle reproducible.
+/// Like any closure with the same signature, it can be used as a [`Controller`](crate::controller::Controller).
+pub type ChoiceCallback<M> =
+    Box<dyn Fn(&[Team<M>], Option<MemberIdentifier>, &mut BattleRng) -> ChoiceReturn<M>>;
+
+/// Future returned by an [`AsyncChoiceCallback`].
+#[cfg(feature = "async")]
+pub type ChoiceFuture<'a, M> =
+    core::pin::Pin<Box<dyn core::future::Future<Output = ChoiceReturn<M>> + 'a>>;
+
+/// Asynchronous variant of [`ChoiceCallback`], useful when the choice depends on user input or network messages.
+///
+/// # Notes
+///
+/// The returned future may borrow the battle's teams and random number generator until it completes.
+#[cfg(feature = "async")]
+pub type AsyncChoiceCallback<M> = Box<
+    dyn for<'a> Fn(
+        &'a [Team<M>],
+        Option<MemberIdentifier>,
+        &'a mut BattleRng,
+    ) -> ChoiceFuture<'a, M>,
+>;
+
+/// Progress of an [`Action`] after being performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionState {
+    /// The action has been completely performed.
+    Finished,
+    /// The action spans multiple turns and must be performed again during the performer's next turn.
+    ///
+    /// # Notes
+    ///
+    /// While an action is continuing, the [`TurnSystem`](crate::battle::TurnSystem) doesn't ask for a new choice
+    /// during the performer's turns. The action is cancelled if the performer takes damage, is interrupted or is defeated
+    /// before its next turn comes.
+    Continuing,
+}
+
+/// Conversion of any `'static` type into a [`&dyn Any`](Any), used to downcast boxed [`Action`]s.
+///
+/// # Notes
+///
+/// This trait is implemented for every `'static` type, so it never needs to be implemented manually.
+/// As a consequence, actions can't borrow any data.
+pub trait AsAny {
+    /// Returns the value as a [`&dyn Any`](Any).
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the boxed value as a [`Box<dyn Any>`](Any), to take ownership of downcast values.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Action that can be performed by team members that affects a specified target.
+///
+/// # Notes
+///
+/// More than one member may be appointed as "action performers".
+/// Even members of different teams or whole teams can perform the same action together!
+///
+/// Actions can be introspected even when boxed, using [`Action::kind()`] or [`AsAny::as_any()`] to downcast them.
+pub trait Action<M>: AsAny {
+    /// Action logic performer.
+    ///
+    /// # Notes
+    ///
+    /// Depending on the action, you may need to damage the interested targets or modify their status.
+    /// You may want to iterate over all performers and targets to retrieve the
+    /// necessary data by using [`Context::performers()`] or [`Context::targets()`].
+    ///
+    /// Changes should be applied through [`Context::apply()`] (or its shorthands, like [`Context::damage()`])
+    /// to be recorded in the battle's event history.
+    ///
+    /// Actions that span multiple turns return [`ActionState::Continuing`] until they are done.
+    fn act(&mut self, context: Context<'_, M>) -> ActionState;
+
+    /// Check whether the action can be performed in the given context.
+    ///
+    /// # Notes
+    ///
+    /// This function is called by the [`TurnSystem`](crate::battle::TurnSystem) right before [`Action::act()`].
+    /// If the validation fails, the action isn't performed and the battle's
+    /// [`InvalidActionFallback`](crate::battle::InvalidActionFallback) is followed instead.
+    ///
+    /// The default implementation only checks that all performers and targets exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ActionError`] describing why the action cannot be performed.
+    fn validate(&self, context: &Context<'_, M>) -> Result<(), ActionError> {
+        context.validate_members()
+    }
+
+    /// Returns the resources every performer must spend to perform the action.
+    ///
+    /// # Notes
+    ///
+    /// The [`TurnSystem`](crate::battle::TurnSystem) rejects the action if any performer can't afford its cost,
+    /// otherwise the cost is deducted right before [`Action::act()`] is called.
+    ///
+    /// The default implementation returns no costs.
+    fn cost(&self) -> Vec<Cost> {
+        Vec::new()
+    }
+
+    /// Returns the identifier of this kind of action.
+    ///
+    /// # Notes
+    ///
+    /// The identifier is used to keep track of per-action data, like cooldowns.
+    /// The default implementation uses the name of the implementing type.
+    fn action_id(&self) -> ActionId {
+        ActionId::new(core::any::type_name::<Self>())
+    }
+
+    /// Returns the amount of turns a performer must wait before performing this action again.
+    ///
+    /// # Notes
+    ///
+    /// The [`TurnSystem`](crate::battle::TurnSystem) rejects the action if any performer is still waiting for its cooldown.
+    /// Cooldowns are decremented at every turn.
+    ///
+    /// The default implementation returns 0 (no cooldown).
+    fn cooldown_turns(&self) -> u32 {
+        0
+    }
+
+    /// Returns the priority of the action when resolved together with other actions during the same turn.
+    ///
+    /// # Notes
+    ///
+    /// Actions with a higher priority are resolved first, and may interrupt the following ones by defeating their
+    /// performers or by calling [`Context::interrupt()`].
+    ///
+    /// The default implementation returns 0.
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    /// Returns whether the action can only affect targets in sight of the performers.
+    ///
+    /// # Notes
+    ///
+    /// The [`TurnSystem`](crate::battle::TurnSystem) rejects the action if any enemy target is hidden in the back row
+    /// of its formation, behind an alive member or an obstacle. Look at [`Context::in_sight()`] for more information.
+    ///
+    /// The default implementation returns `false`.
+    fn requires_line_of_sight(&self) -> bool {
+        false
+    }
+
+    /// Returns the [`Element`] of the damage dealt by the action, if any.
+    ///
+    /// The default implementation returns `None`.
+    fn element(&self) -> Option<Element> {
+        None
+    }
+
+    /// Returns the amount of noise made by the action, adding up to the noise of the whole battle.
+    ///
+    /// # Notes
+    ///
+    /// The noise is recorded as an [`Event::NoiseMade`] right after the action is performed,
+    /// and may call for [reinforcements](crate::reinforcement::Trigger::Noise) or [end the battle](crate::battle::EndCondition::Undetected).
+    ///
+    /// The default implementation returns 0.
+    fn noise(&self) -> u64 {
+        0
+    }
+
+    /// Returns a copy of the action in its current state, if it can be copied.
+    ///
+    /// # Notes
+    ///
+    /// Copies are used to capture the actions still waiting to be resolved (like queued, scheduled or channeled ones)
+    /// in a [`Checkpoint`](crate::checkpoint::Checkpoint). Battles with pending actions that can't be copied can't be checkpointed.
+    /// The copy must be of the same type as the original action.
+    ///
+    /// The default implementation returns `None`.
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        None
+    }
+
+    /// Returns the [`ActionKind`] of the action, identifying its concrete type.
+    fn kind(&self) -> ActionKind {
+        ActionKind {
+            type_id: self.as_any().type_id(),
+            name: core::any::type_name::<Self>(),
+        }
+    }
+}
+
+impl<M> dyn Action<M> {
+    /// Returns whether the boxed action is of type `T`.
+    pub fn is<T: Action<M> + 'static>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Returns a reference to the boxed action if it is of type `T`, or `None` if it isn't.
+    pub fn downcast_ref<T: Action<M> + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+}
+
+/// Discriminant of the concrete type of an [`Action`].
+///
+/// # Notes
+///
+/// Unlike [`ActionId`], which can be customized and is used to track per-action data,
+/// the kind always identifies the type implementing the action. Wrappers (like
+/// [`WithCost`](crate::catalogue::actions::WithCost)) are different kinds than the action they wrap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ActionKind {
+    type_id: TypeId,
+    name: &'static str,
+}
+
+impl ActionKind {
+    /// Returns the [`ActionKind`] of the type `T`.
+    pub fn of<T: ?Sized + Any>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            name: core::any::type_name::<T>(),
+        }
+    }
+
+    /// Returns whether this is the kind of type `T`.
+    pub fn is<T: ?Sized + Any>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Returns the name of the type, for debugging purposes.
+    ///
+    /// # Notes
+    ///
+    /// The name is not guaranteed to be stable across compiler versions, use [`ActionKind::is()`] to compare kinds.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// Identifier of a kind of [`Action`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ActionId(String);
+
+impl ActionId {
+    /// Create a new [`ActionId`] with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Returns this identifier's name.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Condition a member must meet to be affected by a [`Target::Filtered`].
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetFilter {
+    /// Members with some health left.
+    Alive,
+    /// Members with no health left.
+    Defeated,
+    /// Alive members whose health is below a percentage of their [reference health](crate::member::Statistics::reference_health).
+    HealthBelow { percent: u64 },
+    /// Members with at least one stack of a [`Tag`].
+    HasTag(Tag),
+}
+
+impl TargetFilter {
+    /// Returns whether a member meets the condition.
+    pub fn matches<M: Member>(
+        &self,
+        id: MemberIdentifier,
+        member: &M,
+        components: &ComponentStore,
+    ) -> bool {
+        match self {
+            TargetFilter::Alive => !member.is_defeated(),
+            TargetFilter::Defeated => member.is_defeated(),
+            TargetFilter::HealthBelow { percent } => {
+                let reference = member.statistics().reference_health();
+
+                !member.is_defeated()
+                    && member.health().saturating_mul(100) < reference.saturating_mul(*percent)
+            }
+            TargetFilter::HasTag(tag) => components.has_tag(id, tag),
+        }
+    }
+}
+
+/// Single or multiple targets being affected by an action.
+///
+/// It may also refer to the action's performer.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// No target is affected by the action.
+    None,
+    /// A single member is affected by the action.
+    Single(MemberIdentifier),
+    /// A specific choice of members is affected by the action.
+    ///
+    /// # Notes
+    ///
+    /// Any duplicate [`MemberIdentifier`] will be considered only once.
+    DiscreteMultiple(Vec<MemberIdentifier>),
+    /// A whole team is affected by the action.
+    FullTeam { team_id: usize },
+    /// All members placed in a row of a team's [`Formation`](crate::formation::Formation) are affected by the action.
+    ///
+    /// # Notes
+    ///
+    /// No member is affected if the team has no formation.
+    Row { team_id: usize, row: Row },
+    /// All members placed in an area of a team's [`Formation`](crate::formation::Formation) are affected by the action.
+    ///
+    /// # Notes
+    ///
+    /// The area is centered on a cell rather than on a member, so it may be centered on an empty cell.
+    /// No member is affected if the team has no formation.
+    Area {
+        team_id: usize,
+        center: Position,
+        shape: Shape,
+    },
+    /// All members of all teams are affected by the action.
+    All,
+    /// All members of every team other than the given member's one are affected by the action.
+    AllEnemiesOf(MemberIdentifier),
+    /// All members of the given member's team, except the member itself, are affected by the action.
+    AllAlliesOf(MemberIdentifier),
+    /// The performers of the action are affected by it.
+    ///
+    /// # Notes
+    ///
+    /// No member is affected if the performers of the action are themselves described by this target.
+    Performers,
+    /// The members of another target meeting a condition are affected by the action.
+    ///
+    /// # Notes
+    ///
+    /// The condition is checked whenever the target is resolved, so it follows the changes made by the action itself.
+    Filtered {
+        base: Box<Target>,
+        filter: TargetFilter,
+    },
+    /// A random choice of `count` different members of another target is affected by the action.
+    ///
+    /// # Notes
+    ///
+    /// Members are picked using the battle's source of randomness when the [`Context`] of the action is created,
+    /// so the choice stays the same for the whole action. All members of the pool are picked if they are less than `count`.
+    RandomN { pool: Box<Target>, count: usize },
+    /// The alive enemy that generated the most threat against the given member's team is affected by the action.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`BattleState::most_threatening()`] for more information.
+    MostThreatening(MemberIdentifier),
+    /// A [`Structure`] of the battlefield is affected by the action.
+    ///
+    /// # Notes
+    ///
+    /// Structures aren't members, so no member is affected. Actions reach the structure through
+    /// [`Context::target_structures()`].
+    Structure(StructureId),
+}
+
+impl Target {
+    /// Restrict this target to the members meeting a condition.
+    pub fn filtered(self, filter: TargetFilter) -> Self {
+        Target::Filtered {
+            base: Box::new(self),
+            filter,
+        }
+    }
+
+    /// Restrict this target to a random choice of `count` of its members.
+    pub fn random(self, count: usize) -> Self {
+        Target::RandomN {
+            pool: Box::new(self),
+            count,
+        }
+    }
+
+    /// Returns the target after a member has been removed from the battle.
+    ///
+    /// # Notes
+    ///
+    /// The removed member is dropped from the target, while the following members of its team are shifted down by one.
+    pub(crate) fn after_removal(self, removed: MemberIdentifier) -> Self {
+        match self {
+            Target::Single(id) => match id.after_removal(removed) {
+                Some(id) => Target::Single(id),
+                None => Target::None,
+            },
+            Target::DiscreteMultiple(ids) => Target::DiscreteMultiple(
+                ids.into_iter()
+                    .filter_map(|id| id.after_removal(removed))
+                    .collect(),
+            ),
+            // The target is meaningless without the member it is relative to.
+            Target::AllEnemiesOf(id) | Target::AllAlliesOf(id) | Target::MostThreatening(id)
+                if id == removed =>
+            {
+                Target::None
+            }
+            Target::MostThreatening(id) => {
+                Target::MostThreatening(id.after_removal(removed).unwrap_or(id))
+            }
+            Target::AllEnemiesOf(id) => {
+                Target::AllEnemiesOf(id.after_removal(removed).unwrap_or(id))
+            }
+            Target::AllAlliesOf(id) => Target::AllAlliesOf(id.after_removal(removed).unwrap_or(id)),
+            Target::Filtered { base, filter } => Target::Filtered {
+                base: Box::new(base.after_removal(removed)),
+                filter,
+            },
+            Target::RandomN { pool, count } => Target::RandomN {
+                pool: Box::new(pool.after_removal(removed)),
+                count,
+            },
+            target => target,
+        }
+    }
+
+    /// Check whether every member and team referenced by this target exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BattleError::InvalidTarget`] if any referenced member or team is missing.
+    pub fn validate<M>(&self, team_list: &[Team<M>]) -> Result<(), BattleError> {
+        let exists = |id: &MemberIdentifier| {
+            team_list
+                .get(id.team_id)
+                .is_some_and(|t| t.member(id.member_id).is_some())
+        };
+
+        let valid = match self {
+            // Structures are never removed, so only their own actions can find out whether they exist.
+            Target::None | Target::All | Target::Performers | Target::Structure(_) => true,
+            Target::Single(id)
+            | Target::AllEnemiesOf(id)
+            | Target::AllAlliesOf(id)
+            | Target::MostThreatening(id) => exists(id),
+            Target::DiscreteMultiple(targets) => targets.iter().all(exists),
+            Target::Filtered { base, .. } | Target::RandomN { pool: base, .. } => {
+                return base.validate(team_list)
+            }
+            Target::FullTeam { team_id }
+            | Target::Row { team_id, .. }
+            | Target::Area { team_id, .. } => *team_id < team_list.len(),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(BattleError::InvalidTarget(self.clone()))
+        }
+    }
+}
+
+pub struct Context<'team, M> {
+    state: &'team mut BattleState<M>,
+    performers: Target,
+    targets: Target,
+}
+
+impl<M> Context<'_, M> {
+    /// Returns the [`Target`] describing the action's performers.
+    pub fn chosen_performers(&self) -> &Target {
+        &self.performers
+    }
+
+    /// Returns the [`Target`] describing the action's targets.
+    pub fn chosen_targets(&self) -> &Target {
+        &self.targets
+    }
+
+    /// Check whether all performers and targets of the action exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ActionError::MissingMembers`] with the first [`Target`] that references missing members.
+    pub fn validate_members(&self) -> Result<(), ActionError> {
+        for target in [&self.performers, &self.targets] {
+            if target.validate(&self.state.team_list).is_err() {
+                return Err(ActionError::MissingMembers(target.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'i, 's: 'i, 'team: 'i, M: Member> Context<'team, M> {
+    pub fn new(state: &'team mut BattleState<M>, performers: Target, targets: Target) -> Self {
+        let mut context = Self {
+            state,
+            performers: Target::None,
+            targets: Target::None,
+        };
+
+        // Performers are picked first, as targets may refer to them.
+        context.performers = context.roll(performers);
+        context.targets = context.roll(targets);
+
+        context
+    }
+
+    /// Pick the members of every [`Target::RandomN`] found in a target.
+    fn roll(&mut self, target: Target) -> Target {
+        match target {
+            Target::RandomN { pool, count } => {
+                let pool = self.roll(*pool);
+                let mut candidates = self.resolve_ids(&pool);
+                let mut picked = Vec::new();
+
+                while picked.len() < count && !candidates.is_empty() {
+                    let index = self.state.rng.gen_range(0..candidates.len() as u64) as usize;
+
+                    picked.push(candidates.remove(index));
+                }
+
+                Target::DiscreteMultiple(picked)
+            }
+            Target::Filtered { base, filter } => Target::Filtered {
+                base: Box::new(self.roll(*base)),
+                filter,
+            },
+            target => target,
+        }
+    }
+
+    /// Returns a reference to the battling teams.
+    pub fn teams(&self) -> &[Team<M>] {
+        self.state.teams()
+    }
+
+    /// Returns a reference to the battle's [`ComponentStore`].
+    pub fn components(&self) -> &ComponentStore {
+        self.state.components()
+    }
+
+    /// Returns the battle's source of randomness.
+    ///
+    /// # Notes
+    ///
+    /// Actions should never use a different random number generator, or the battle won't be reproducible.
+    pub fn rng(&mut self) -> &mut BattleRng {
+        self.state.rng()
+    }
+
+    /// Apply an [`Event`] to the battle's state.
+    ///
+    /// # Notes
+    ///
+    /// Changes applied through events are recorded in the battle's [`EventLog`](crate::event::EventLog),
+    /// while changes made directly on the members returned by [`Context::performers()`] or [`Context::targets()`] are not.
+    pub fn apply(&mut self, event: Event) {
+        self.state.apply(event);
+    }
+
+    /// Inflict direct damage to a member of the battle.
+    ///
+    /// # Notes
+    ///
+    /// This is a shorthand for applying an [`Event::Damaged`].
+    pub fn damage(&mut self, member: MemberIdentifier, amount: u64) {
+        self.apply(Event::Damaged { member, amount });
+    }
+
+    /// Inflict damage to a member of the battle, calculated through the battle's damage
+    /// [`Pipeline`](crate::damage::Pipeline).
+    ///
+    /// Returns the amount of damage inflicted, or 0 if the member doesn't exist.
+    pub fn deal_damage(&mut self, member: MemberIdentifier, base: u64) -> u64 {
+        self.deal_elemental_damage(member, base, None)
+    }
+
+    /// Inflict damage of an [`Element`] to a member of the battle, calculated through the battle's damage
+    /// [`Pipeline`](crate::damage::Pipeline).
+    ///
+    /// Returns the amount of damage inflicted, or 0 if the member doesn't exist.
+    pub fn deal_elemental_damage(
+        &mut self,
+        member: MemberIdentifier,
+        base: u64,
+        element: Option<Element>,
+    ) -> u64 {
+        self.deal(Damage::new(member, base).with_element(element))
+            .map_or(0, |d| d.amount)
+    }
+
+    /// Inflict a prepared [`Damage`] to its target, after running it through the battle's damage
+    /// [`Pipeline`](crate::damage::Pipeline).
+    ///
+    /// Returns the final damage calculation, or `None` if the target doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// An [`Event::CriticalHit`] is applied before the damage if it was marked as critical,
+    /// either beforehand or by a stage of the pipeline. If the target evaded the damage,
+    /// an [`Event::Missed`] is applied instead of the damage.
+    pub fn deal(&mut self, damage: Damage) -> Option<Damage> {
+        let member = damage.target;
+
+        let Some(damage) = self.state.calculate_damage(damage) else {
+            log::warn!("Could not find damaged member at index {:?}", member);
+
+            return None;
+        };
+
+        if damage.missed {
+            narrate!("The attack missed!");
+
+            self.apply(Event::Missed { member });
+
+            return Some(damage);
+        }
+
+        if damage.critical {
+            narrate!("Critical hit!");
+
+            self.apply(Event::CriticalHit { member });
+        }
+
+        self.damage(member, damage.amount);
+
+        Some(damage)
+    }
+
+    /// Restore the health of a member of the battle.
+    ///
+    /// # Notes
+    ///
+    /// This is a shorthand for applying an [`Event::Healed`]. Defeated members aren't healed, look at
+    /// [`Context::revive()`] to bring them back instead.
+    pub fn heal(&mut self, member: MemberIdentifier, amount: u64) {
+        self.apply(Event::Healed { member, amount });
+    }
+
+    /// Bring a defeated member of the battle back with some health, applying an [`Event::Revived`].
+    ///
+    /// Returns `false` if the member doesn't exist or isn't defeated, in which case nothing is applied.
+    pub fn revive(&mut self, member: MemberIdentifier, health: u64) -> bool {
+        if !self.member(member).is_some_and(Member::is_defeated) {
+            return false;
+        }
+
+        self.apply(Event::Revived { member, health });
+
+        true
+    }
+
+    /// Grant some temporary health to a member of the battle, applying an [`Event::ShieldGained`].
+    ///
+    /// Returns `false` if the member doesn't exist, is defeated or can't hold a [shield](Properties::shield),
+    /// in which case nothing is applied.
+    pub fn shield(&mut self, member: MemberIdentifier, amount: u64) -> bool {
+        let shieldable = self.member(member).is_some_and(|m| {
+            !m.is_defeated() && m.member_properties().clone().shield_mut().is_some()
+        });
+
+        if !shieldable {
+            return false;
+        }
+
+        self.apply(Event::ShieldGained { member, amount });
+
+        true
+    }
+
+    /// Apply one stack of a [`Tag`] to a member of the battle.
+    pub fn apply_tag(&mut self, member: MemberIdentifier, tag: Tag) {
+        self.apply(Event::TagApplied { member, tag });
+    }
+
+    /// Consume one stack of a [`Tag`] from a member of the battle.
+    ///
+    /// Returns `true` if a stack was found and consumed, `false` otherwise.
+    pub fn consume_tag(&mut self, member: MemberIdentifier, tag: &Tag) -> bool {
+        if !self.components().has_tag(member, tag) {
+            return false;
+        }
+
+        self.apply(Event::TagConsumed {
+            member,
+            tag: tag.clone(),
+        });
+
+        true
+    }
+
+    /// Raise (or lower, if negative) a stat of a member of the battle by some stages, applying an [`Event::StageChanged`].
+    ///
+    /// Returns the change actually applied, which is 0 if the member doesn't exist or the stat is already at its limit,
+    /// in which case nothing is applied.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`StatStages::change()`](crate::stage::StatStages::change) for more information.
+    pub fn change_stage(&mut self, member: MemberIdentifier, stat: Stat, amount: i8) -> i8 {
+        if self.member(member).is_none() {
+            return 0;
+        }
+
+        let change = self
+            .components()
+            .stages(member)
+            .cloned()
+            .unwrap_or_default()
+            .change(stat, amount);
+
+        if change != 0 {
+            self.apply(Event::StageChanged {
+                member,
+                stat,
+                change,
+            });
+        }
+
+        change
+    }
+
+    /// Summon a new member into the team of the first performer, returning its [`MemberIdentifier`].
+    ///
+    /// Returns `None` if the action has no performer.
+    ///
+    /// # Notes
+    ///
+    /// The summoned member joins at the end of the team, and is able to act from the next turn.
+    /// Once its [`Lifetime`] expires, it is defeated at the end of the turn.
+    pub fn summon(&mut self, member: M, lifetime: Lifetime) -> Option<MemberIdentifier> {
+        let summoner = self.performer_ids().first().copied()?;
+        let member = self.state.add_member(summoner.team_id, member)?;
+
+        self.apply(Event::Summoned {
+            member,
+            summoner,
+            lifetime,
+        });
+
+        Some(member)
+    }
+
+    /// Move a member by one step across its team's [`Formation`](crate::formation::Formation), returning its new [`Position`].
+    ///
+    /// Returns `None` if the member has no position, or if the move is blocked.
+    ///
+    /// # Notes
+    ///
+    /// The move is blocked if the cell is taken by another member or if it is outside of the grid,
+    /// in which case an [`Event::MoveBlocked`] is applied instead. A member stepping into a [`Hazard`](crate::formation::Hazard)
+    /// is immediately damaged and tagged by it.
+    pub fn move_member(&mut self, member: MemberIdentifier, step: Step) -> Option<Position> {
+        let formation = self.state.team_list.get(member.team_id)?.formation()?;
+        let from = formation.position(member.member_id)?;
+
+        let Some(to) = from.step(step) else {
+            self.apply(Event::MoveBlocked {
+                member,
+                blocker: None,
+            });
 
-pub type ChoiceReturn<M> = (Box<dyn Action<M>>, Target, Target);
-/// Function type to dynamically decide the next [`Action`] to perform.
-pub type ChoiceCallback<M> = Box<dyn Fn(&[Team<M>], Option<MemberIdentifier>) -> ChoiceReturn<M>>;
+            return None;
+        };
 
-/// Action that can be performed by team members that affects a specified target.
-///
-/// # Notes
-///
-/// More than one member may be appointed as "action performers".
-/// Even members of different teams or whole teams can perform the same action together!
-pub trait Action<M> {
-    /// Action logic performer.
+        if formation.is_blocked(to) {
+            self.apply(Event::MoveBlocked {
+                member,
+                blocker: None,
+            });
+
+            return None;
+        }
+
+        if let Some(blocker) = formation.member_at(to) {
+            self.apply(Event::MoveBlocked {
+                member,
+                blocker: Some(MemberIdentifier::new(member.team_id, blocker)),
+            });
+
+            return None;
+        }
+
+        let hazard = formation.hazard(to).cloned();
+
+        // Enemies threatening the cell attack before the member can leave it.
+        if let Some(attacker) = self.provoke_opportunity_attacks(member, from) {
+            narrate!("{:?} was stopped by an attack of opportunity", member);
+
+            self.apply(Event::MoveBlocked {
+                member,
+                blocker: Some(attacker),
+            });
+
+            return None;
+        }
+
+        self.apply(Event::Moved { member, from, to });
+
+        if let Some(hazard) = hazard {
+            narrate!("{:?} stepped into a hazard", member);
+
+            if hazard.damage > 0 {
+                self.damage(member, hazard.damage);
+            }
+
+            if let Some(tag) = hazard.tag {
+                self.apply_tag(member, tag);
+            }
+        }
+
+        Some(to)
+    }
+
+    /// Resolve the attacks of opportunity provoked by a member leaving a cell.
+    ///
+    /// Returns the member whose attack stopped the movement, if any.
+    fn provoke_opportunity_attacks(
+        &mut self,
+        member: MemberIdentifier,
+        from: Position,
+    ) -> Option<MemberIdentifier> {
+        let alive = |state: &BattleState<M>, id: MemberIdentifier| {
+            state
+                .team_list
+                .get(id.team_id)
+                .and_then(|t| t.member(id.member_id))
+                .is_some_and(|m| m.health() > 0)
+        };
+
+        let attacks: Vec<_> = self
+            .state
+            .opportunities
+            .iter()
+            .filter(|o| o.member.team_id != member.team_id && o.zone.contains(member.team_id, from))
+            .filter(|o| alive(self.state, o.member))
+            .filter_map(|o| Some((o.member, (o.handler)(o.member, member, self.state)?)))
+            .collect();
+
+        for (attacker, (mut action, performers, targets)) in attacks {
+            let since = self.state.events.len();
+            let mut context = Context::new(self.state, performers, targets);
+
+            let validation = action
+                .validate(&context)
+                .and_then(|()| context.check_costs(&action.cost()))
+                .and_then(|()| context.check_cooldown(&action.action_id()))
+                .and_then(|()| context.check_line_of_sight(&*action));
+
+            if let Err(e) = validation {
+                log::warn!("The attack of opportunity could not be performed: {}", e);
+
+                continue;
+            }
+
+            narrate!("{:?} attacks {:?} as it moves away", attacker, member);
+
+            context.pay_costs(&action.cost());
+            context.start_cooldown(&action.action_id(), action.cooldown_turns());
+
+            let performers = context.performer_ids();
+            context.apply(Event::ActionPerformed {
+                action: action.action_id(),
+                performers,
+            });
+            context.make_noise(&*action);
+
+            for target in context.target_ids() {
+                context.apply(Event::Targeted { member: target });
+            }
+
+            action.act(context);
+            self.state.resolve_action(action.action_id(), since);
+
+            if !alive(self.state, member) || self.state.is_interrupted(member) {
+                return Some(attacker);
+            }
+        }
+
+        None
+    }
+
+    /// Returns whether a member can be seen by the performers of the action.
     ///
     /// # Notes
     ///
-    /// Depending on the action, you may need to damage the interested targets or modify their status.
-    /// You may want to iterate over all performers and targets to retrieve the
-    /// necessary data by using [`Context::performers()`] or [`Context::targets()`].
-    fn act(&mut self, context: Context<'_, M>);
-}
+    /// Members of the performers' teams are always in sight. Enemies are hidden if they are placed in the back row
+    /// of their formation, behind an alive member or an obstacle in the front row.
+    pub fn in_sight(&self, member: MemberIdentifier) -> bool {
+        let performers = self.performer_ids();
 
-/// Single or multiple targets being affected by an action.
-///
-/// It may also refer to the action's performer.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Target {
-    /// No target is affected by the action.
-    None,
-    /// A single member is affected by the action.
-    Single(MemberIdentifier),
-    /// A specific choice of members is affected by the action.
+        if performers.iter().any(|p| p.team_id == member.team_id) {
+            return true;
+        }
+
+        let Some(team) = self.state.team_list.get(member.team_id) else {
+            return true;
+        };
+
+        let Some(formation) = team.formation() else {
+            return true;
+        };
+
+        let Some(position) = formation.position(member.member_id) else {
+            return true;
+        };
+
+        let Some(front) = position.step(Step::Forward) else {
+            return true;
+        };
+
+        let screened = formation
+            .member_at(front)
+            .and_then(|m| team.member(m))
+            .is_some_and(|m| m.health() > 0);
+
+        !screened && !formation.is_blocked(front)
+    }
+
+    /// Check whether every target of the action is in sight of the performers, if the action requires it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ActionError::Obstructed`] with the first target out of sight.
+    pub fn check_line_of_sight(&self, action: &dyn Action<M>) -> Result<(), ActionError> {
+        if !action.requires_line_of_sight() {
+            return Ok(());
+        }
+
+        match self.target_ids().into_iter().find(|t| !self.in_sight(*t)) {
+            Some(target) => Err(ActionError::Obstructed { target }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check whether every performer of the action can afford the given costs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ActionError::Unaffordable`] with the first performer that lacks the needed resources.
+    pub fn check_costs(&self, costs: &[Cost]) -> Result<(), ActionError> {
+        for id in self.performer_ids() {
+            let Some(member) = self.member(id) else {
+                continue;
+            };
+
+            for cost in costs {
+                // Costs of the same resource are paid together, so they must be affordable together.
+                let amount = costs
+                    .iter()
+                    .filter(|c| c.resource == cost.resource)
+                    .fold(0u64, |sum, c| sum.saturating_add(c.amount));
+
+                let available = member.member_properties().resource(cost.resource);
+
+                if available.unwrap_or(0) < amount {
+                    return Err(ActionError::Unaffordable {
+                        member: id,
+                        cost: Cost::new(cost.resource, amount),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply a control effect to a member, returning its magnitude after resistance and diminishing returns.
+    ///
+    /// Returns 0 if the member doesn't exist or resisted the effect.
     ///
     /// # Notes
     ///
-    /// Any duplicate [`MemberIdentifier`] will be considered only once.
-    DiscreteMultiple(Vec<MemberIdentifier>),
-    /// A whole team is affected by the action.
-    FullTeam { team_id: usize },
-    /// All members of all teams are affected by the action.
-    All,
-}
+    /// The member resists the effect with a chance equal to its [`Properties::control_resistance()`] percentage,
+    /// rolled with the battle's source of randomness, in which case an [`Event::ControlResisted`] is applied.
+    /// Otherwise, the magnitude is shortened by [`control::diminish()`] and an [`Event::ControlApplied`] is applied.
+    ///
+    /// Lasting effects (like stuns) are tracked by the battle, while the others must be carried out by the caller
+    /// with the returned magnitude, like moving a pushed member by that many steps.
+    pub fn apply_control(
+        &mut self,
+        member: MemberIdentifier,
+        control: Control,
+        magnitude: u64,
+    ) -> u64 {
+        let Some(resistance) = self
+            .final_properties(member)
+            .map(|p| p.control_resistance().min(100))
+        else {
+            return 0;
+        };
 
-pub struct Context<'team, M> {
-    team_list: &'team mut Vec<Team<M>>,
-    performers: Target,
-    targets: Target,
-}
+        if magnitude == 0 {
+            return 0;
+        }
 
-impl<'i, 's: 'i, 'team: 'i, M: Member> Context<'team, M> {
-    pub fn new(team_list: &'team mut Vec<Team<M>>, performers: Target, targets: Target) -> Self {
-        Self {
-            team_list,
-            performers,
-            targets,
+        if resistance > 0 && self.state.rng.gen_ratio(resistance, 100) {
+            narrate!("{:?} resisted a {:?} effect", member, control);
+
+            self.apply(Event::ControlResisted { member, control });
+
+            return 0;
+        }
+
+        let applications = self.state.controls.applications(member, control);
+        let magnitude = control::diminish(magnitude, applications);
+
+        self.apply(Event::ControlApplied {
+            member,
+            control,
+            magnitude,
+        });
+
+        magnitude
+    }
+
+    /// Returns the inventory of a member or team.
+    ///
+    /// Look at [`BattleState::inventory()`] for more information.
+    pub fn inventory(&self, owner: InventoryOwner) -> Option<&Inventory> {
+        self.state.inventory(owner)
+    }
+
+    /// Returns the inventory a member would take an item from, or `None` if it has no such item available.
+    ///
+    /// # Notes
+    ///
+    /// Members use their own items first, and then the ones of their team.
+    pub fn item_owner(&self, member: MemberIdentifier, item: &ItemId) -> Option<InventoryOwner> {
+        [
+            InventoryOwner::Member(member),
+            InventoryOwner::Team(member.team_id),
+        ]
+        .into_iter()
+        .find(|owner| self.inventory(*owner).is_some_and(|i| i.contains(item)))
+    }
+
+    /// Take an item out of the inventory available to a member, returning the inventory it was taken from.
+    ///
+    /// Returns `None` if the member has no such item available. Look at [`Context::item_owner()`] for more information.
+    pub fn consume_item(
+        &mut self,
+        member: MemberIdentifier,
+        item: ItemId,
+    ) -> Option<InventoryOwner> {
+        let owner = self.item_owner(member, &item)?;
+
+        self.apply(Event::ItemConsumed { owner, item });
+
+        Some(owner)
+    }
+
+    /// Interrupt a member, cancelling every action it would still perform during this turn.
+    pub fn interrupt(&mut self, member: MemberIdentifier) {
+        self.apply(Event::Interrupted { member });
+    }
+
+    /// Returns whether the action has been interrupted before being performed.
+    ///
+    /// An action is interrupted if any performer has been [interrupted](Context::interrupt) during this turn,
+    /// or if all performers have been defeated.
+    pub fn is_interrupted(&self) -> bool {
+        let performers = self.performer_ids();
+
+        if performers.is_empty() {
+            return false;
+        }
+
+        let interrupted = performers.iter().any(|id| self.state.is_interrupted(*id));
+        let defeated = performers
+            .iter()
+            .all(|id| self.member(*id).is_none_or(|m| m.health() == 0));
+
+        interrupted || defeated
+    }
+
+    /// Check whether no performer of the action is waiting for its cooldown.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ActionError::OnCooldown`] with the first performer that can't perform the action yet.
+    pub fn check_cooldown(&self, action: &ActionId) -> Result<(), ActionError> {
+        for member in self.performer_ids() {
+            let remaining = self.components().cooldown(member, action);
+
+            if remaining > 0 {
+                return Err(ActionError::OnCooldown {
+                    member,
+                    action: action.clone(),
+                    remaining,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Make every performer of the action wait before performing it again.
+    pub(crate) fn start_cooldown(&mut self, action: &ActionId, turns: u32) {
+        if turns == 0 {
+            return;
+        }
+
+        for member in self.performer_ids() {
+            self.apply(Event::CooldownStarted {
+                member,
+                action: action.clone(),
+                turns,
+            });
+        }
+    }
+
+    /// Make every performer of the action spend the given costs.
+    pub(crate) fn pay_costs(&mut self, costs: &[Cost]) {
+        for member in self.performer_ids() {
+            for cost in costs {
+                self.apply(Event::ResourceSpent {
+                    member,
+                    resource: cost.resource,
+                    amount: cost.amount,
+                });
+            }
+        }
+    }
+
+    /// Record the noise made by the action being performed, if any.
+    pub(crate) fn make_noise(&mut self, action: &dyn Action<M>) {
+        let amount = action.noise();
+
+        if amount > 0 {
+            self.apply(Event::NoiseMade {
+                action: action.action_id(),
+                amount,
+            });
         }
     }
 
+    /// Returns the current [`MemberIdentifier`] of the member with the given handle.
+    ///
+    /// Look at [`BattleState::resolve()`] for more information.
+    pub fn resolve(&self, handle: MemberHandle) -> Option<MemberIdentifier> {
+        self.state.resolve(handle)
+    }
+
+    /// Returns the stable [`MemberHandle`] of a member.
+    ///
+    /// Look at [`BattleState::handle()`] for more information.
+    pub fn handle(&self, member: MemberIdentifier) -> Option<MemberHandle> {
+        self.state.handle(member)
+    }
+
+    /// Returns a reference to a specific member of the battle, regardless of it being a performer or a target.
+    pub fn member(&self, id: MemberIdentifier) -> Option<&M> {
+        self.state.team_list.get(id.team_id)?.member(id.member_id)
+    }
+
+    /// Returns the final properties of a member, including the auras of its team and its stat stages.
+    ///
+    /// Look at [`BattleState::final_properties()`] for more information.
+    pub fn final_properties(&self, id: MemberIdentifier) -> Option<M::Properties> {
+        self.state.final_properties(id)
+    }
+
+    /// Let a [`TeamAura`] benefit every member of a team.
+    ///
+    /// Look at [`BattleState::add_aura()`] for more information.
+    pub fn add_aura(&mut self, team_id: usize, aura: TeamAura<M>) {
+        self.state.add_aura(team_id, aura);
+    }
+
     /// Returns a mutable iterator over all [`Member`](crate::team::Member)s that are flagged as action performers.
     ///
     /// # Notes
@@ -67,6 +1228,10 @@ impl<'i, 's: 'i, 'team: 'i, M: Member> Context<'team, M> {
     /// The result of this function depends on the [`Target`]s passed as input in the [`Context`] struct.
     /// If members are not placed where the [`MemberIdentifier`]s are pointing to, either the wrong member
     /// is going to be returned, or no reference will be returned. Beware of the [`Team`]'s ordering.
+    ///
+    /// Changes made through these references aren't recorded as [`Event`]s, so they are lost when the battle is
+    /// [replayed](crate::state::BattleState::replay). Prefer [`Context::deal()`] and [`Context::apply()`] for anything
+    /// that should be replayed.
     pub fn performers(&'s mut self) -> Box<dyn Iterator<Item = &'s mut M> + 'i> {
         self.target_iter(self.performers.clone())
     }
@@ -80,40 +1245,184 @@ impl<'i, 's: 'i, 'team: 'i, M: Member> Context<'team, M> {
     /// The result of this function depends on the [`Target`]s passed as input in the [`Context`] struct.
     /// If members are not placed where the [`MemberIdentifier`]s are pointing to, either the wrong member
     /// is going to be returned, or no reference will be returned. Beware of the [`Team`]'s ordering.
+    ///
+    /// Changes made through these references aren't recorded as [`Event`]s, so they are lost when the battle is
+    /// [replayed](crate::state::BattleState::replay). Prefer [`Context::deal()`] and [`Context::apply()`] for anything
+    /// that should be replayed.
     pub fn targets(&'s mut self) -> Box<dyn Iterator<Item = &'s mut M> + 'i> {
         self.target_iter(self.targets.clone())
     }
 
+    /// Returns the identifiers of all existing members flagged as action targets.
+    ///
+    /// # Notes
+    ///
+    /// Identifiers are sorted by team and member, with duplicates and missing members left out.
+    /// Use them to look up component data or to attribute events to the right member.
+    pub fn target_ids(&self) -> Vec<MemberIdentifier> {
+        self.resolve_ids(&self.targets)
+    }
+
+    /// Returns the identifiers of all existing members flagged as action performers.
+    ///
+    /// # Notes
+    ///
+    /// Identifiers are sorted by team and member, with duplicates and missing members left out.
+    pub fn performer_ids(&self) -> Vec<MemberIdentifier> {
+        self.resolve_ids(&self.performers)
+    }
+
+    /// Returns the identifiers of all targets for which the predicate returns `true`.
+    ///
+    /// # Notes
+    ///
+    /// Identifiers are sorted like the ones returned by [`Context::target_ids()`].
+    /// For conditions chosen together with the action, prefer a [`Target::Filtered`].
+    pub fn targets_where(&self, predicate: impl Fn(&M) -> bool) -> Vec<MemberIdentifier> {
+        self.target_ids()
+            .into_iter()
+            .filter(|id| self.member(*id).is_some_and(&predicate))
+            .collect()
+    }
+
+    /// Returns the IDs of all existing structures flagged as action targets.
+    ///
+    /// # Notes
+    ///
+    /// Destroyed structures are still returned, as they can be targeted like defeated members.
+    pub fn target_structures(&self) -> Vec<StructureId> {
+        match &self.targets {
+            Target::Structure(id) if self.state.structure(*id).is_some() => vec![*id],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns a [`Structure`] of the battlefield.
+    ///
+    /// Returns `None` if the structure doesn't exist.
+    pub fn structure(&self, id: StructureId) -> Option<&Structure> {
+        self.state.structure(id)
+    }
+
+    /// Inflict direct damage to a structure of the battlefield.
+    ///
+    /// # Notes
+    ///
+    /// This is a shorthand for applying an [`Event::StructureDamaged`].
+    /// Structures have no properties, so the damage never goes through the battle's damage [`Pipeline`](crate::damage::Pipeline).
+    pub fn damage_structure(&mut self, structure: StructureId, amount: u64) {
+        self.apply(Event::StructureDamaged { structure, amount });
+    }
+
+    /// Returns the identifiers of all existing members referenced by a [`Target`], without duplicates.
+    fn resolve_ids(&self, target: &Target) -> Vec<MemberIdentifier> {
+        let team_members = |team_id: usize| {
+            self.state
+                .team_list
+                .get(team_id)
+                .into_iter()
+                .flat_map(move |t| {
+                    (0..t.member_list().len()).map(move |m| MemberIdentifier::new(team_id, m))
+                })
+        };
+
+        match target {
+            Target::None => Vec::new(),
+            Target::Single(id) => team_members(id.team_id).filter(|m| m == id).collect(),
+            Target::DiscreteMultiple(targets) => (0..self.state.team_list.len())
+                .flat_map(team_members)
+                .filter(|m| targets.contains(m))
+                .collect(),
+            Target::FullTeam { team_id } => team_members(*team_id).collect(),
+            Target::Row { team_id, row } => team_members(*team_id)
+                .filter(|m| {
+                    self.state.team_list[m.team_id]
+                        .position(m.member_id)
+                        .is_some_and(|p| p.row == *row)
+                })
+                .collect(),
+            Target::All => (0..self.state.team_list.len())
+                .flat_map(team_members)
+                .collect(),
+            Target::Area {
+                team_id,
+                center,
+                shape,
+            } => self
+                .state
+                .team_list
+                .get(*team_id)
+                .and_then(|t| t.formation())
+                .map(|f| {
+                    f.members_in_area(*center, *shape)
+                        .into_iter()
+                        .filter(|m| *m < self.state.team_list[*team_id].member_list().len())
+                        .map(|m| MemberIdentifier::new(*team_id, m))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Target::AllEnemiesOf(id) => (0..self.state.team_list.len())
+                .filter(|team_id| *team_id != id.team_id)
+                .flat_map(team_members)
+                .collect(),
+            Target::AllAlliesOf(id) => team_members(id.team_id).filter(|m| m != id).collect(),
+            Target::Performers => match &self.performers {
+                Target::Performers => Vec::new(),
+                performers => self.resolve_ids(performers),
+            },
+            Target::Filtered { base, filter } => self
+                .resolve_ids(base)
+                .into_iter()
+                .filter(|id| {
+                    self.member(*id)
+                        .is_some_and(|m| filter.matches(*id, m, &self.state.components))
+                })
+                .collect(),
+            Target::MostThreatening(id) => self
+                .state
+                .most_threatening(id.team_id)
+                .into_iter()
+                .collect(),
+            // Random targets are picked when the context is created, so none can be found here.
+            Target::RandomN { .. } => Vec::new(),
+            Target::Structure(_) => Vec::new(),
+        }
+    }
+
     /// Function that iterates over all members targeted.
     fn target_iter(&'s mut self, target: Target) -> Box<dyn Iterator<Item = &'s mut M> + 'i> {
+        // Members may be defeated or revived without any event, so they must be counted again.
+        self.state.alive_stale = true;
+
         match target {
-            // Return an empty iterator if no target was found.
-            Target::None => Box::new(std::iter::empty()),
+            // Return an empty iterator if no target was found. Structures aren't members, so they are never returned.
+            Target::None | Target::Structure(_) => Box::new(core::iter::empty()),
             // Return a `Once` iterator to the single member that is targeted.
             Target::Single(id) => {
-                let team = self.team_list.get_mut(id.team_id);
+                let team = self.state.team_list.get_mut(id.team_id);
 
                 if let Some(t) = team {
                     if let Some(m) = t.member_mut(id.member_id) {
-                        return Box::new(std::iter::once(m));
+                        return Box::new(core::iter::once(m));
                     }
                 }
 
                 log::warn!("Could not find requested member at index {:?}. Returning an empty iterator instead", id);
 
                 // If the member wasn't found, return an empty iterator.
-                Box::new(std::iter::empty())
+                Box::new(core::iter::empty())
             }
             // Return a filtered iterator over all individual targets.
             Target::DiscreteMultiple(targets) => Box::new(
-                self.team_list
+                self.state
+                    .team_list
                     .iter_mut()
                     // Enumerating helps filter which teams/members we are actually targeting.
                     .enumerate()
                     .flat_map(|(i, t)| {
                         // `Repeat` is used to return the same `team_id` number to each member of a team.
                         // We also re-enumerate over the members to keep track of the `member_id`
-                        std::iter::repeat(i).zip(t.member_list_mut().iter_mut().enumerate())
+                        core::iter::repeat(i).zip(t.member_list_mut().iter_mut().enumerate())
                     })
                     .filter(move |(t_id, (m_id, _))| {
                         targets.contains(&MemberIdentifier {
@@ -124,20 +1433,144 @@ impl<'i, 's: 'i, 'team: 'i, M: Member> Context<'team, M> {
                     .map(|(_, (_, m))| m),
             ),
             // Returns an iterator that iterates over every member of a single team.
-            Target::FullTeam { team_id } => match self.team_list.get_mut(team_id) {
+            Target::FullTeam { team_id } => match self.state.team_list.get_mut(team_id) {
                 Some(team) => Box::new(team.member_list_mut().iter_mut()),
                 None => {
                     log::warn!("Could not find requested team at index {}. Returning an empty iterator instead", team_id);
 
-                    Box::new(std::iter::empty())
+                    Box::new(core::iter::empty())
+                }
+            },
+            // Returns an iterator that iterates over the members of a single team placed in the requested row.
+            Target::Row { team_id, row } => match self.state.team_list.get_mut(team_id) {
+                Some(team) => Box::new(team.row_members_mut(row)),
+                None => {
+                    log::warn!("Could not find requested team at index {}. Returning an empty iterator instead", team_id);
+
+                    Box::new(core::iter::empty())
                 }
             },
+            // Targets relative to other members or cells are resolved to the exact members they describe.
+            Target::Area { .. }
+            | Target::AllEnemiesOf(_)
+            | Target::AllAlliesOf(_)
+            | Target::Performers
+            | Target::Filtered { .. }
+            | Target::RandomN { .. }
+            | Target::MostThreatening(_) => {
+                let targets = self.resolve_ids(&target);
+
+                self.target_iter(Target::DiscreteMultiple(targets))
+            }
             // Returns an iterator that iterates over every member of every team. It's pretty simple with `flat_map()`.
             Target::All => Box::new(
-                self.team_list
+                self.state
+                    .team_list
                     .iter_mut()
                     .flat_map(|t| t.member_list_mut().iter_mut()),
             ),
         }
     }
 }
+
+/// Simplified [`Context`] of an action performed by a single member.
+///
+/// # Notes
+///
+/// Every function of [`Context`] is still available through [`Deref`](core::ops::Deref).
+pub struct SoloContext<'team, M> {
+    context: Context<'team, M>,
+    performer: MemberIdentifier,
+}
+
+impl<'team, M: Member> SoloContext<'team, M> {
+    /// Returns the identifier of the member performing the action.
+    pub fn performer_id(&self) -> MemberIdentifier {
+        self.performer
+    }
+
+    /// Returns a reference to the member performing the action.
+    pub fn performer(&self) -> &M {
+        // The performer's existence is checked when the context is created.
+        self.context
+            .member(self.performer)
+            .expect("the solo performer should exist")
+    }
+
+    /// Returns a mutable reference to the member performing the action.
+    pub fn performer_mut(&mut self) -> &mut M {
+        self.context.state.team_list[self.performer.team_id]
+            .member_mut(self.performer.member_id)
+            .expect("the solo performer should exist")
+    }
+
+    /// Unwrap the solo context, returning the full [`Context`] of the action.
+    pub fn into_inner(self) -> Context<'team, M> {
+        self.context
+    }
+}
+
+impl<'team, M> core::ops::Deref for SoloContext<'team, M> {
+    type Target = Context<'team, M>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.context
+    }
+}
+
+impl<M> core::ops::DerefMut for SoloContext<'_, M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.context
+    }
+}
+
+/// Action performed by exactly one member, receiving it directly through a [`SoloContext`].
+///
+/// # Notes
+///
+/// Wrap the action in [`Solo`] to use it wherever an [`Action`] is expected.
+/// This is the natural fit for battles using [`PerformerModel::Solo`](crate::battle::PerformerModel::Solo).
+pub trait SoloAction<M> {
+    /// Action logic performer. Look at [`Action::act()`] for more information.
+    fn act(&mut self, context: SoloContext<'_, M>) -> ActionState;
+}
+
+/// Adapter turning a [`SoloAction`] into an [`Action`].
+///
+/// # Notes
+///
+/// The action fails its validation with [`ActionError::NotSolo`] unless exactly one performer is chosen.
+pub struct Solo<A>(pub A);
+
+impl<M: Member, A: SoloAction<M> + 'static> Action<M> for Solo<A> {
+    fn act(&mut self, context: Context<M>) -> ActionState {
+        match context.performer_ids().as_slice() {
+            [performer] => {
+                let performer = *performer;
+
+                self.0.act(SoloContext { context, performer })
+            }
+            performers => {
+                log::warn!(
+                    "A solo action was performed by {} members and was skipped",
+                    performers.len()
+                );
+
+                ActionState::Finished
+            }
+        }
+    }
+
+    fn validate(&self, context: &Context<'_, M>) -> Result<(), ActionError> {
+        context.validate_members()?;
+
+        match context.performer_ids().len() {
+            1 => Ok(()),
+            performers => Err(ActionError::NotSolo { performers }),
+        }
+    }
+
+    fn action_id(&self) -> ActionId {
+        ActionId::new(core::any::type_name::<A>())
+    }
+}
@@ -0,0 +1,101 @@
+//! Team-wide modifiers of [`Properties`], like buffs shared by a whole party.
+//!
+//! A [`TeamAura`] is summed into the properties of every member of its team, without changing the members themselves,
+//! and wears off by itself once its duration is over.
+//! Auras are held by the [`BattleState`](crate::state::BattleState), which calculates the
+//! [final properties](crate::state::BattleState::final_properties) of members including them.
+
+use alloc::rc::Rc;
+use alloc::string::String;
+
+use crate::member::{Member, Properties};
+
+/// Modifier of the [`Properties`] of every member of a team.
+///
+/// # Notes
+///
+/// The properties of the aura are summed into the ones of every member with [`Properties::sum_properties()`],
+/// before the properties granted by the member's equipment.
+/// Unless a duration is set, the aura lasts for the whole battle.
+pub struct TeamAura<M> {
+    name: String,
+    turns_left: Option<u32>,
+    modifier: Rc<dyn Fn(&mut M)>,
+}
+
+impl<M: Member> TeamAura<M>
+where
+    M::Properties: 'static,
+{
+    /// Create a new [`TeamAura`] summing the given properties into the ones of every member of a team.
+    pub fn new(name: impl Into<String>, properties: M::Properties) -> Self {
+        Self {
+            name: name.into(),
+            turns_left: None,
+            modifier: Rc::new(move |member: &mut M| {
+                let boosted = member.member_properties().sum_properties(&properties);
+
+                *member.member_properties_mut() = boosted;
+            }),
+        }
+    }
+}
+
+impl<M> TeamAura<M> {
+    /// Let the aura wear off after the given amount of turns.
+    ///
+    /// # Notes
+    ///
+    /// Auras tick at the same time as cooldowns, at the start of every turn's resolution.
+    pub fn with_duration(mut self, turns: u32) -> Self {
+        self.turns_left = Some(turns);
+
+        self
+    }
+
+    /// Returns the name of the aura.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the amount of turns left before the aura wears off, or `None` if it lasts for the whole battle.
+    pub fn turns_left(&self) -> Option<u32> {
+        self.turns_left
+    }
+
+    /// Sum the aura into the properties of a member.
+    pub(crate) fn apply(&self, member: &mut M) {
+        (self.modifier)(member);
+    }
+
+    /// Let one turn pass, returning whether the aura has worn off.
+    pub(crate) fn tick(&mut self) -> bool {
+        match &mut self.turns_left {
+            Some(turns) => {
+                *turns = turns.saturating_sub(1);
+
+                *turns == 0
+            }
+            None => false,
+        }
+    }
+}
+
+impl<M> Clone for TeamAura<M> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            turns_left: self.turns_left,
+            modifier: Rc::clone(&self.modifier),
+        }
+    }
+}
+
+impl<M> core::fmt::Debug for TeamAura<M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TeamAura")
+            .field("name", &self.name)
+            .field("turns_left", &self.turns_left)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,24 +1,87 @@
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "async")]
+use crate::action::AsyncChoiceCallback;
 use crate::{
-    action::{ChoiceCallback, Context},
-    member::{Member, MemberIdentifier},
+    action::{ActionId, ActionState, ChoiceReturn, Context, Target},
+    aura::TeamAura,
+    checkpoint::{self, Checkpoint},
+    command::{self, BattleCommand, ValidationReport},
+    component::{ComponentStore, Tag},
+    controller::{ChoicePrompt, Controller},
+    damage::Pipeline,
+    environment::{ContextView, EnvironmentEffect, EnvironmentScript},
+    error::{BattleError, BuildError},
+    event::{Event, EventLog, Retention},
+    formation::ZoneOfControl,
+    inventory::{Inventory, InventoryOwner, ItemId},
+    member::{Levelable, Member, MemberHandle, MemberIdentifier, Properties},
+    narration::{narrate, NarrationGuard},
+    objective::Objective,
+    observer::BattleObserver,
+    outcome::BattleOutcome,
+    persist::BattleDelta,
+    reaction::{self, OpportunityHandler, ReactionHandler, ReactionScope},
+    reinforcement::{Reinforcement, ReinforcementCheckpoint, ReinforcementScheduler},
+    rng::BattleRng,
+    round::RoundOrder,
+    schedule::{ActionSchedule, CancelRule, ScheduleCheckpoint, ScheduleId, ScheduledAction},
     search::SuggestedPerformerCriteria,
+    state::{AliveSummary, BattleState},
+    statistics::CombatStatistics,
     team::Team,
+    terrain::{Structure, StructureId},
+    visibility::FogOfWar,
+    wave::{WaveStatistics, Waves, WavesCheckpoint},
 };
 
 /// Instance of a unique fight between multiple [`Team`]s.
 pub struct Battle<M> {
-    /// List of all teams involved in the battle.
-    team_list: Vec<Team<M>>,
-    #[allow(dead_code)]
-    startup: Option<StartupInfo>,
+    /// Teams, components and everything else that changes during the battle.
+    data: BattleState<M>,
+    /// Information used to prepare the battle's opening.
+    startup: StartupInfo,
+    /// Hooks run once when the battle starts.
+    battle_start_hooks: Vec<BattleStartHook<M>>,
     /// Turn system in charge of handling turns and actions of the battle.
-    turn_system: TurnSystem,
+    turn_system: TurnSystem<M>,
     /// Current battle state.
     state: State,
+    /// State to go back to once the battle is resumed.
+    paused_from: State,
+    /// Amount of events already returned by [`Battle::drain_events()`].
+    drained_events: usize,
     suggested_performer_criteria: SuggestedPerformerCriteria<M>,
-    action_choice_callback: ChoiceCallback<M>,
+    /// Controller choosing every action of the battle.
+    controller: Box<dyn Controller<M>>,
+    /// Controllers choosing the actions of specific teams instead of the battle's controller.
+    team_controllers: BTreeMap<usize, Box<dyn Controller<M>>>,
+    /// Whether the turns of the battle are narrated in the log.
+    narration: bool,
+    #[cfg(feature = "async")]
+    async_action_choice_callback: Option<AsyncChoiceCallback<M>>,
 }
 
+/// Function type run once at the start of a [`Battle`], before the first turn is played.
+///
+/// # Notes
+///
+/// Opening effects should be applied as [`Event`]s using [`BattleState::apply()`].
+pub type BattleStartHook<M> = Box<dyn Fn(&mut BattleState<M>, &StartupInfo)>;
+
+/// Function type run during a [`TurnPhase`] of every turn, given the current turn number.
+///
+/// # Notes
+///
+/// Effects (like status ticks and regeneration) should be applied as [`Event`]s using [`BattleState::apply()`].
+pub type PhaseHook<M> = Box<dyn FnMut(&mut BattleState<M>, u64)>;
+
 pub struct Builder<M> {
     inner: Battle<M>,
 }
@@ -35,13 +98,30 @@ pub enum EndCondition {
     ///
     /// This is the most common end condition for team-to-team fighting.
     LastTeamStanding,
+    /// End the battle once a [`Structure`] is destroyed, like when "destroying the core" is the goal of the battle.
+    ///
+    /// # Notes
+    ///
+    /// The battle doesn't end when every member is defeated, so it is up to the developer to ensure the structure can be destroyed.
+    StructureDestroyed(StructureId),
+    /// End the battle if only one battling team has any "alive" members, or as soon as the noise of the battle
+    /// reaches the limit, like when "defeat them quietly" is the goal of the battle.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`Action::noise()`](crate::action::Action::noise) for more information.
+    /// Whether raising the alarm means losing the battle is up to the developer, by checking [`BattleState::noise()`].
+    Undetected { noise_limit: u64 },
 }
 
 /// Current state of a [`Battle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum State {
     /// The battle has yet to start.
     Preparating,
     InProgress,
+    /// The battle has been paused with [`Battle::pause()`], and won't go on until it's resumed.
+    Paused,
     Finished,
 }
 
@@ -49,17 +129,24 @@ impl<M: Member> Builder<M> {
     pub fn new(
         team_list: Vec<Team<M>>,
         startup: Option<StartupInfo>,
-        action_choice_callback: ChoiceCallback<M>,
+        controller: impl Controller<M> + 'static,
         end_condition: EndCondition,
     ) -> Self {
         Self {
             inner: Battle {
-                team_list,
-                startup,
+                data: BattleState::new(team_list, BattleRng::unseeded()),
+                startup: startup.unwrap_or_default(),
+                battle_start_hooks: Vec::new(),
                 turn_system: TurnSystem::new(MemberIdentifier::zeroed(), end_condition),
                 state: State::Preparating,
+                paused_from: State::Preparating,
+                drained_events: 0,
                 suggested_performer_criteria: SuggestedPerformerCriteria::CycleAlive,
-                action_choice_callback,
+                controller: Box::new(controller),
+                team_controllers: BTreeMap::new(),
+                narration: true,
+                #[cfg(feature = "async")]
+                async_action_choice_callback: None,
             },
         }
     }
@@ -78,243 +165,3274 @@ impl<M: Member> Builder<M> {
         self
     }
 
-    pub fn build(self) -> Battle<M> {
-        self.inner
+    /// Set the [`StartupInfo`] deciding how the battle opens, replacing the one given to [`Builder::new()`].
+    pub fn set_startup_info(mut self, startup: StartupInfo) -> Builder<M> {
+        self.inner.startup = startup;
+
+        self
     }
-}
 
-impl<M: Member> Battle<M> {
-    /// Runs a [`Battle`] to completion, returning the final state of the battling teams.
+    /// Set the seed of the battle's [`BattleRng`].
     ///
-    /// The winner will be declared by the end of this function.
-    pub fn run(mut self) -> Vec<Team<M>> {
-        log::info!("The battle has started and will run until its conclusion");
+    /// # Notes
+    ///
+    /// By default, the seed is generated from the current system time.
+    /// Two battles with the same seed and the same choices will always play out the same way.
+    pub fn set_seed(mut self, seed: u64) -> Builder<M> {
+        self.inner.data.rng = BattleRng::from_seed(seed);
 
-        loop {
-            self.play_turn();
+        self
+    }
 
-            if self.is_finished() {
-                log::info!(
-                    "The battle has concluded after {} turns",
-                    self.turn_system.turn_number
-                );
-                break;
-            }
-        }
+    /// Set the policy deciding how many events the battle's log keeps in memory.
+    ///
+    /// # Notes
+    ///
+    /// By default, every event is kept. Look at [`Retention`] to learn what is lost when the log is truncated.
+    pub fn set_retention(mut self, retention: Retention) -> Builder<M> {
+        self.inner.data.set_retention(retention);
 
-        // Return ending state of the battling teams.
-        self.take_teams()
+        self
     }
 
-    //TODO: Signal end of battle when returning from `play_turn`.
-    /// Runs a [`Battle`] for exactly one turn.
+    /// Enable or disable the tracking of the members' [`CombatStatistics`].
     ///
-    /// Nothing will be done if the battle's state indicates it has already completed.
-    pub fn play_turn(&mut self) {
-        if self.is_finished() {
-            return;
-        }
+    /// # Notes
+    ///
+    /// Tracking is disabled by default. Once enabled, the statistics are returned by [`Battle::statistics()`].
+    pub fn set_statistics_tracking(mut self, enabled: bool) -> Builder<M> {
+        self.inner.data.set_statistics_tracking(enabled);
 
-        self.state = self.turn_system.play_turn(
-            &mut self.team_list,
-            &self.action_choice_callback,
-            &self.suggested_performer_criteria,
-        );
+        self
     }
 
-    pub fn teams(&self) -> &[Team<M>] {
-        &self.team_list
+    /// Enable or disable the narration of every hit, heal and action in the log.
+    ///
+    /// # Notes
+    ///
+    /// Narration is enabled by default. Disabling it skips the formatting of the narrated lines,
+    /// which is meant for headless runs like [simulations](crate::simulate). Look at [`narration`](crate::narration)
+    /// for more information.
+    pub fn set_narration(mut self, enabled: bool) -> Builder<M> {
+        self.inner.narration = enabled;
+
+        self
     }
 
-    /// Unwrap the [`Battle`] instance and return the state of its participants.
-    pub fn take_teams(self) -> Vec<Team<M>> {
-        self.team_list
+    /// Set an asynchronous callback used to choose actions in [`Battle::play_turn_async`].
+    ///
+    /// # Notes
+    ///
+    /// If no asynchronous callback is set, [`Battle::play_turn_async`] falls back to the synchronous one.
+    /// Teams with their own [controller](Builder::set_team_controller) are still prompted through it,
+    /// so the asynchronous callback only chooses for the other teams.
+    #[cfg(feature = "async")]
+    pub fn set_async_action_choice_callback(
+        mut self,
+        callback: AsyncChoiceCallback<M>,
+    ) -> Builder<M> {
+        self.inner.async_action_choice_callback = Some(callback);
+
+        self
     }
-}
 
-impl<M> Battle<M> {
-    /// Returns whether this [`Battle`] has completed or not.
-    pub fn is_finished(&self) -> bool {
-        matches!(self.state, State::Finished)
+    /// Set the [`Pipeline`] every damage calculation of the battle goes through.
+    ///
+    /// # Notes
+    ///
+    /// By default, the pipeline only subtracts the target's defense with [`Mitigation`](crate::damage::Mitigation).
+    /// The new pipeline replaces it, so it should include that stage as well to keep mitigating damage.
+    pub fn set_damage_pipeline(mut self, pipeline: Pipeline<M>) -> Builder<M> {
+        self.inner.data.damage_pipeline = pipeline;
+
+        self
     }
 
-    /// Signal the completion of the [`Battle`] to stop its execution.
+    /// Set the rule deciding which members perform the chosen actions.
     ///
     /// # Notes
     ///
-    /// It is necessary to run at least one more turn using [`Battle::play_turn`] for the battle's end to be properly handled.
-    pub fn set_completed(&mut self) {
-        self.state = State::Finished;
+    /// By default, [`PerformerModel::Any`] is used.
+    pub fn set_performer_model(mut self, model: PerformerModel) -> Builder<M> {
+        self.inner.turn_system.set_performer_model(model);
+
+        self
     }
-}
 
-/// Information needed to start a new [`Battle`].
-///
-/// Here can be stored all sorts of specific infos, like the first team/player that has to play etc.
-#[non_exhaustive]
-pub struct StartupInfo {}
+    /// Limit the time the controller can take to choose an action.
+    ///
+    /// # Notes
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// Choices made after `timeout` has elapsed are discarded and recorded as [`Event::ChoiceTimedOut`],
+    /// then the [`TimeoutFallback`] is followed instead. Turns skipped this way are forfeited, and count towards
+    /// the [idle takeover](Builder::set_idle_takeover).
+    ///
+    /// Controllers can't be interrupted while choosing, so the timeout is checked once they answer.
+    /// Blocking sources should give up by the [`ChoicePrompt::deadline()`] on their own,
+    /// like the [`ChannelController`](crate::controller::ChannelController) does.
+    #[cfg(feature = "std")]
+    pub fn set_choice_timeout(
+        mut self,
+        timeout: Duration,
+        fallback: TimeoutFallback<M>,
+    ) -> Builder<M> {
+        self.inner.turn_system.set_choice_timeout(timeout, fallback);
 
-/// Handler of the turn-based combat.
-///
-/// Stores information about the turn cycle and the current playing member.
-pub struct TurnSystem {
-    turn_number: u64,
-    suggested_performer: Option<MemberIdentifier>,
-    end_condition: EndCondition,
-}
+        self
+    }
 
-impl TurnSystem {
-    pub fn new(starting_member: MemberIdentifier, end_condition: EndCondition) -> Self {
-        Self {
-            turn_number: 0,
-            suggested_performer: Some(starting_member),
-            end_condition,
-        }
+    /// Hand the choices of idle members over to another controller, like an AI.
+    ///
+    /// # Notes
+    ///
+    /// A member is idle when its turn is forfeited, either because the chosen action was skipped
+    /// or because no performer was chosen. After `after` consecutive forfeited turns, its following turns
+    /// are chosen by `controller`, until [`Battle::restore_control()`] is called.
+    ///
+    /// Forfeited turns are always recorded as [`Event::TurnForfeited`], even without an idle takeover.
+    pub fn set_idle_takeover(
+        mut self,
+        after: u32,
+        controller: impl Controller<M> + 'static,
+    ) -> Builder<M> {
+        self.inner.turn_system.set_idle_takeover(after, controller);
+
+        self
     }
-}
 
-// TurnSystem functionality that requires access to teams and members.
-impl TurnSystem {
-    /// Simulate one turn of the battle.
+    /// Give defeated members a limited amount of turns to be revived, after which they are removed from the battle.
     ///
-    /// # Panics
+    /// # Notes
     ///
-    /// The function will panic if the turn counter overflows `u64::MAX` or if teams/members are not found when specified.
-    pub fn play_turn<M: Member>(
-        &mut self,
-        team_list: &mut Vec<Team<M>>,
-        action_choice_callback: &ChoiceCallback<M>,
-        suggested_performer_criteria: &SuggestedPerformerCriteria<M>,
-    ) -> State {
-        // Count the new turn
-        self.turn_number = match self.turn_number.checked_add(1) {
-            Some(t) => t,
-            None => {
-                log::error!("Turn counter overflowed after {} turns", self.turn_number);
+    /// At the end of every turn, each defeated member is given an [`Event::RevivalCountdown`] with the turns left
+    /// to revive it, starting from `turns` at the end of the turn it was defeated in.
+    /// If [rounds](Builder::set_round_order) are enabled, the window is counted in rounds instead,
+    /// at the end of every round.
+    /// [Reviving](crate::action::Context::revive) the member before the countdown reaches 0 (like with the
+    /// [`Revive`](crate::catalogue::actions::Revive) action) keeps it in the battle, recording an [`Event::MemberRevived`].
+    /// Otherwise, the member is removed from the battle just like with [`Battle::remove_member()`].
+    ///
+    /// By default, defeated members stay in the battle forever.
+    pub fn set_revival_window(mut self, turns: u32) -> Builder<M> {
+        self.inner.turn_system.set_revival_window(turns);
 
-                panic!("turn counter overflowed");
-            }
-        };
+        self
+    }
 
-        log::info!("Playing turn number {}.", self.turn_number);
+    /// Register a [`BattleObserver`], notified of every event applied to the battle.
+    ///
+    /// # Notes
+    ///
+    /// Observers are notified in the same order they are added.
+    pub fn add_observer(mut self, observer: Box<dyn BattleObserver<M>>) -> Builder<M> {
+        self.inner.data.add_observer(observer);
 
-        if let Some(performing_member) = self.suggested_performer {
-            // Get the playing team.
-            let playing_team = match team_list.get(performing_member.team_id) {
-                Some(pt) => pt,
-                None => {
-                    log::warn!(
-                        "Playing team with id {:?} was not found",
-                        performing_member.team_id
-                    );
+        self
+    }
 
-                    panic!(
-                        "requested team with id {} was not found",
-                        performing_member.team_id
-                    );
-                }
-            };
+    /// Set the behaviour used when a chosen action fails its [validation](crate::action::Action::validate).
+    ///
+    /// # Notes
+    ///
+    /// By default, [`InvalidActionFallback::Fail`] is used, making [`Battle::play_turn`] return an error.
+    pub fn set_invalid_action_fallback(mut self, fallback: InvalidActionFallback) -> Builder<M> {
+        self.inner.turn_system.set_invalid_action_fallback(fallback);
 
-            log::info!("Plays the team \"{}\"", playing_team.name());
+        self
+    }
 
-            // Get the "active" player of this turn.
-            let playing_member = match playing_team.member(performing_member.member_id) {
-                Some(pm) => pm,
-                None => {
-                    log::warn!(
-                        "Playing member with id {:?} was not found",
-                        performing_member
-                    );
+    /// Add a hook to be run once when the battle starts.
+    ///
+    /// # Notes
+    ///
+    /// Hooks are run in the same order they are added, after each member's own
+    /// [`Member::on_battle_start()`] effects are applied.
+    pub fn add_battle_start_hook(mut self, hook: BattleStartHook<M>) -> Builder<M> {
+        self.inner.battle_start_hooks.push(hook);
 
-                    panic!(
-                        "requested member with id {} was not found",
-                        performing_member.member_id
-                    );
-                }
-            };
+        self
+    }
 
-            log::info!("It's the turn of {}", playing_member.name());
-        }
+    /// Schedule a [`Reinforcement`] to join the battle once its trigger fires.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::add_reinforcement()`] to learn when triggers are checked.
+    pub fn add_reinforcement(mut self, reinforcement: Reinforcement<M>) -> Builder<M> {
+        self.inner.turn_system.add_reinforcement(reinforcement);
 
-        let (mut action, performers, targets) =
-            action_choice_callback(team_list, self.suggested_performer);
+        self
+    }
 
-        // Setup the chosen action
-        let context = Context::new(team_list, performers, targets);
-        action.act(context);
+    /// Register a [`ReactionHandler`] for the members covered by the scope.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::add_reaction()`] to learn when reactions are invoked.
+    pub fn add_reaction(mut self, scope: ReactionScope, handler: ReactionHandler<M>) -> Builder<M> {
+        self.inner.turn_system.add_reaction(scope, handler);
 
-        // TODO: Programmatically decide when the turn should end (after every player acts? after one player acts?)
-        // TODO: Run an "end of turn" custom hook.
+        self
+    }
 
-        // Check whether the battle should continue or whether it's finished.
-        if self.check_end_condition(team_list) {
-            return State::Finished;
-        }
+    /// Let a member threaten a [`ZoneOfControl`], attacking enemies that move out of it.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`OpportunityHandler`] to learn how attacks of opportunity are resolved.
+    pub fn add_opportunity_attack(
+        mut self,
+        member: MemberIdentifier,
+        zone: ZoneOfControl,
+        handler: OpportunityHandler<M>,
+    ) -> Builder<M> {
+        self.inner
+            .data
+            .add_opportunity_attack(member, zone, handler);
 
-        // TODO: custom performer finder (does it even make sense with the "everyone can perform" model? maybe just as default behaviour for a more modular system)
-        self.suggested_performer =
-            self.suggest_next_performer(team_list, suggested_performer_criteria);
+        self
+    }
+
+    /// Build a [`Structure`] on the battlefield.
+    ///
+    /// # Notes
+    ///
+    /// Structures are given [`StructureId`]s in the order they are added, starting from 0.
+    pub fn add_structure(mut self, name: String, health: u64) -> Builder<M> {
+        self.inner.data.add_structure(name, health);
 
-        State::InProgress
+        self
     }
 
-    /// TODO: Subsitute this with an event based check. Iterating every time is slooooooow.
-    /// Returns whether or not the battle should continue.
-    fn check_end_condition<M: Member>(&self, team_list: &[Team<M>]) -> bool {
-        match self.end_condition {
-            EndCondition::LastMemberStanding => {
-                let mut members_alive: u8 = 0;
-
-                for t in team_list {
-                    for m in t.member_list() {
-                        if m.health() > 0 {
-                            members_alive = members_alive.saturating_add(1);
-
-                            // We don't need to check every member. Once we find 2 alive, we know the battle should continue.
-                            if members_alive >= 2 {
-                                return false;
-                            }
-                        }
-                    }
-                }
+    /// Add some consumable items to the inventory of a member or team.
+    pub fn add_items(mut self, owner: InventoryOwner, item: ItemId, amount: u32) -> Builder<M> {
+        self.inner.data.add_items(owner, item, amount);
 
-                true
-            }
-            EndCondition::LastTeamStanding => {
-                let mut teams_alive: u8 = 0;
+        self
+    }
 
-                for t in team_list {
-                    for m in t.member_list() {
-                        if m.health() > 0 {
-                            teams_alive = teams_alive.saturating_add(1);
+    /// Give an [`Objective`] to a team, reported by the battle's [`BattleOutcome`].
+    ///
+    /// # Notes
+    ///
+    /// Objectives don't end the battle by themselves, which is still up to the [`EndCondition`].
+    pub fn add_objective(mut self, team_id: usize, objective: Objective) -> Builder<M> {
+        self.inner.data.add_objective(team_id, objective);
 
-                            // We don't need to check every team. Once we find 2 alive, we know the battle should continue.
-                            if teams_alive >= 2 {
-                                return false;
-                            }
+        self
+    }
 
-                            // If even one member is alive, we know the state of this team (and can go check the next one).
-                            break;
-                        }
-                    }
-                }
+    /// Let a [`TeamAura`] benefit every member of a team from the start of the battle.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`BattleState::add_aura()`] for more information.
+    pub fn add_aura(mut self, team_id: usize, aura: TeamAura<M>) -> Builder<M> {
+        self.inner.data.add_aura(team_id, aura);
 
-                true
-            }
-        }
+        self
     }
 
-    fn suggest_next_performer<M: Member>(
-        &mut self,
-        team_list: &[Team<M>],
-        suggested_performer_criteria: &SuggestedPerformerCriteria<M>,
-    ) -> Option<MemberIdentifier> {
-        suggested_performer_criteria.search(self.suggested_performer, team_list)
+    /// Turn the battle into a wave-based encounter.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::set_waves()`] to learn when waves are advanced.
+    pub fn set_waves(mut self, waves: Waves<M>) -> Builder<M> {
+        self.inner.turn_system.set_waves(waves);
+
+        self
+    }
+
+    /// Register an [`EnvironmentScript`] run at the end of every turn.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::add_environment_script()`] to learn when scripts are run.
+    pub fn add_environment_script(mut self, script: EnvironmentScript<M>) -> Builder<M> {
+        self.inner.turn_system.add_environment_script(script);
+
+        self
+    }
+
+    /// Register a [`PhaseHook`] run during a phase of every turn.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnPhase`] to learn when each phase happens.
+    pub fn add_phase_hook(mut self, phase: TurnPhase, hook: PhaseHook<M>) -> Builder<M> {
+        self.inner.turn_system.add_phase_hook(phase, hook);
+
+        self
+    }
+
+    /// Set the rule deciding how many actions are chosen during a turn.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnMode`] for more information.
+    pub fn set_turn_mode(mut self, mode: TurnMode) -> Builder<M> {
+        self.inner.turn_system.set_turn_mode(mode);
+
+        self
+    }
+
+    /// Let a controller choose the actions of a team, instead of the battle's controller.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`Battle::set_team_controller()`] for more information.
+    pub fn set_team_controller(
+        mut self,
+        team_id: usize,
+        controller: impl Controller<M> + 'static,
+    ) -> Builder<M> {
+        self.inner.set_team_controller(team_id, controller);
+
+        self
+    }
+
+    /// Set the criteria choosing which member of a team performs, instead of the battle's criteria.
+    ///
+    /// # Notes
+    ///
+    /// The battle's criteria still decides when the team performs: whenever it suggests one of the team's members,
+    /// the team's criteria picks which member performs instead.
+    /// Together with [`Builder::set_team_controller()`], this lets every team be driven independently,
+    /// for example by user input for the player's team and by an AI for the enemy ones.
+    pub fn set_team_performer_criteria(
+        mut self,
+        team_id: usize,
+        criteria: SuggestedPerformerCriteria<M>,
+    ) -> Builder<M> {
+        self.inner
+            .turn_system
+            .set_team_performer_criteria(team_id, criteria);
+
+        self
+    }
+
+    /// Enable rounds, in which every alive member is suggested to perform once, following the given order.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::set_round_order()`] for more information.
+    pub fn set_round_order(mut self, order: RoundOrder) -> Builder<M> {
+        self.inner.turn_system.set_round_order(order);
+
+        self
+    }
+
+    /// Hide some information about the members of the other teams from the team making each choice.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::set_fog_of_war()`] for more information.
+    pub fn set_fog_of_war(mut self, fog: FogOfWar) -> Builder<M> {
+        self.inner.turn_system.set_fog_of_war(fog);
+
+        self
+    }
+
+    /// Queue an action to be resolved during the first turn.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::queue_action()`] to learn how queued actions are resolved.
+    pub fn queue_action(mut self, choice: ChoiceReturn<M>) -> Builder<M> {
+        self.inner.turn_system.queue_action(choice);
+
+        self
+    }
+
+    /// Schedule an action to be resolved after the given amount of turns, starting from the first one.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::schedule_action()`] to learn how scheduled actions are resolved.
+    pub fn schedule_action(
+        mut self,
+        choice: ChoiceReturn<M>,
+        turns: u64,
+        rule: CancelRule,
+    ) -> Builder<M> {
+        self.inner.turn_system.schedule_action(choice, turns, rule);
+
+        self
+    }
+
+    /// Build the [`Battle`], making sure it can be played.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BuildError`] if there are no teams, if a team has no members,
+    /// or if the [`StartupInfo`] or a team controller references a member or team that doesn't exist.
+    pub fn build(self) -> Result<Battle<M>, BuildError> {
+        let battle = self.inner;
+        let teams = battle.data.teams();
+
+        if teams.is_empty() {
+            return Err(BuildError::NoTeams);
+        }
+
+        if let Some(team_id) = teams.iter().position(|t| t.member_list().is_empty()) {
+            return Err(BuildError::EmptyTeam { team_id });
+        }
+
+        let startup = &battle.startup;
+
+        let team_ids = startup
+            .ambushing_team
+            .iter()
+            .chain(battle.team_controllers.keys())
+            .chain(battle.turn_system.team_criteria.keys())
+            .chain(battle.data.auras.iter().map(|(team_id, _)| team_id));
+
+        if let Some(&team_id) = team_ids.into_iter().find(|&&id| id >= teams.len()) {
+            return Err(BuildError::UnknownTeam { team_id });
+        }
+
+        let members = startup
+            .first_performer
+            .iter()
+            .chain(&startup.initiative)
+            .chain(startup.starting_tags.iter().map(|(member, _)| member));
+
+        for &member in members {
+            if teams
+                .get(member.team_id)
+                .and_then(|t| t.member(member.member_id))
+                .is_none()
+            {
+                return Err(BuildError::UnknownMember(member));
+            }
+        }
+
+        Ok(battle)
+    }
+}
+
+impl<M: Member> Battle<M> {
+    /// Runs a [`Battle`] to completion, returning the final state of the battling teams.
+    ///
+    /// The winner will be declared by the end of this function.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`BattleError`] raised while playing a turn.
+    pub fn run(mut self) -> Result<Vec<Team<M>>, BattleError> {
+        let _narration = NarrationGuard::new(self.narration);
+
+        narrate!("The battle has started and will run until its conclusion");
+
+        loop {
+            self.play_turn()?;
+
+            if self.is_finished() {
+                narrate!(
+                    "The battle has concluded after {} turns",
+                    self.turn_system.turn_number
+                );
+                break;
+            }
+        }
+
+        // Return ending state of the battling teams.
+        Ok(self.take_teams())
+    }
+
+    /// Runs a [`Battle`] to completion, returning the final state of the battling teams together with its [`BattleOutcome`].
+    ///
+    /// # Notes
+    ///
+    /// Look at [`BattleOutcome::winner`] to know who won the battle.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`BattleError`] raised while playing a turn.
+    pub fn run_with_outcome(mut self) -> Result<(Vec<Team<M>>, BattleOutcome), BattleError> {
+        while !self.is_finished() {
+            self.play_turn()?;
+        }
+
+        Ok(self.take_teams_with_outcome())
+    }
+
+    /// Runs a [`Battle`] for exactly one turn, returning what happened during it.
+    ///
+    /// Nothing will be done if the battle's state indicates it has already completed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BattleError`] if the turn couldn't be played, or [`BattleError::Paused`] if the battle is paused.
+    pub fn play_turn(&mut self) -> Result<TurnResult, BattleError> {
+        let since = self.data.events().len();
+
+        if self.is_finished() {
+            return Ok(self.turn_result(since, None));
+        }
+
+        if self.is_paused() {
+            return Err(BattleError::Paused);
+        }
+
+        let _narration = NarrationGuard::new(self.narration);
+
+        self.start();
+
+        let performer = self.turn_system.suggested_performer;
+
+        self.state = self.turn_system.play_turn(
+            &mut self.data,
+            &mut TeamControllers {
+                teams: &mut self.team_controllers,
+                fallback: self.controller.as_mut(),
+            },
+            &self.suggested_performer_criteria,
+        )?;
+
+        Ok(self.turn_result(since, performer))
+    }
+
+    /// Returns an iterator playing the turns of the [`Battle`] one by one, yielding what happened during each of them.
+    ///
+    /// # Notes
+    ///
+    /// The iterator ends once the battle has finished or is paused, or right after yielding an error.
+    pub fn turns(&mut self) -> Turns<'_, M> {
+        Turns {
+            battle: self,
+            failed: false,
+        }
+    }
+
+    /// Sum up the turn that recorded the events following the first `since` ones.
+    fn turn_result(&self, since: usize, performer: Option<MemberIdentifier>) -> TurnResult {
+        let events = self.data.events().since(since).to_vec();
+
+        // The targets of an action are recorded right after it's performed.
+        let mut performed = events
+            .iter()
+            .skip_while(|e| !matches!(e, Event::ActionPerformed { .. }));
+
+        let action = match performed.next() {
+            Some(Event::ActionPerformed { action, .. }) => Some(action.clone()),
+            _ => None,
+        };
+
+        let targets = performed
+            .map_while(|e| match e {
+                Event::Targeted { member } => Some(*member),
+                _ => None,
+            })
+            .collect();
+
+        TurnResult {
+            turn_number: self.turn_system.turn_number,
+            performer,
+            action,
+            targets,
+            events,
+            state: self.state,
+        }
+    }
+
+    /// Runs a [`Battle`] to completion, awaiting every action choice without blocking.
+    ///
+    /// The winner will be declared by the end of this function.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`BattleError`] raised while playing a turn.
+    #[cfg(feature = "async")]
+    pub async fn run_async(mut self) -> Result<Vec<Team<M>>, BattleError> {
+        let _narration = NarrationGuard::new(self.narration);
+
+        narrate!("The battle has started and will run until its conclusion");
+
+        while !self.is_finished() {
+            self.play_turn_async().await?;
+        }
+
+        narrate!(
+            "The battle has concluded after {} turns",
+            self.turn_system.turn_number
+        );
+
+        Ok(self.take_teams())
+    }
+
+    /// Runs a [`Battle`] for exactly one turn, awaiting the action choice without blocking.
+    ///
+    /// Nothing will be done if the battle's state indicates it has already completed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BattleError`] if the turn couldn't be played, or [`BattleError::Paused`] if the battle is paused.
+    #[cfg(feature = "async")]
+    pub async fn play_turn_async(&mut self) -> Result<TurnResult, BattleError> {
+        let since = self.data.events().len();
+
+        if self.is_finished() {
+            return Ok(self.turn_result(since, None));
+        }
+
+        if self.is_paused() {
+            return Err(BattleError::Paused);
+        }
+
+        let _narration = NarrationGuard::new(self.narration);
+
+        self.start();
+
+        let performer = self.turn_system.suggested_performer;
+
+        self.state = match &self.async_action_choice_callback {
+            Some(callback) => {
+                self.turn_system
+                    .play_turn_async(
+                        &mut self.data,
+                        &mut self.team_controllers,
+                        callback,
+                        &self.suggested_performer_criteria,
+                    )
+                    .await
+            }
+            None => self.turn_system.play_turn(
+                &mut self.data,
+                &mut TeamControllers {
+                    teams: &mut self.team_controllers,
+                    fallback: self.controller.as_mut(),
+                },
+                &self.suggested_performer_criteria,
+            ),
+        }?;
+
+        Ok(self.turn_result(since, performer))
+    }
+
+    /// Prepare the next turn without blocking, returning what the battle needs to go on.
+    ///
+    /// Turns that don't need a choice (like the ones of channeling members) are played right away.
+    /// The battle then waits for the next choice, which must be given with [`Battle::resume_with_choice()`].
+    ///
+    /// # Notes
+    ///
+    /// Together with [`Battle::resume_with_choice()`], this lets the battle be driven by the host's event loop,
+    /// like in single-threaded WASM where blocking on [`Battle::run()`] or a [`Controller`] is not an option.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BattleError`] if a turn couldn't be played.
+    pub fn step(&mut self) -> Result<BattleStep, BattleError> {
+        let _narration = NarrationGuard::new(self.narration);
+
+        loop {
+            if self.is_finished() {
+                return Ok(BattleStep::Finished);
+            }
+
+            if self.is_paused() {
+                return Ok(BattleStep::Paused);
+            }
+
+            self.start();
+
+            let performer = self.turn_system.suggested_performer;
+
+            if !performer.is_some_and(|member| self.turn_system.channels.contains_key(&member)) {
+                return Ok(BattleStep::AwaitingChoice { performer });
+            }
+
+            self.play_turn()?;
+        }
+    }
+
+    /// Play the next turn with the given choice, then prepare the following one like [`Battle::step()`] does.
+    ///
+    /// # Notes
+    ///
+    /// The choice answers the first prompt of the turn. Any other prompt (like the choices of the other teams
+    /// in [`TurnMode::Simultaneous`], or retries after an invalid choice) is answered by the battle's controllers.
+    /// Nothing is played if the battle has already finished or is paused.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BattleError`] if a turn couldn't be played.
+    pub fn resume_with_choice(
+        &mut self,
+        choice: ChoiceReturn<M>,
+    ) -> Result<BattleStep, BattleError> {
+        if self.is_finished() {
+            return Ok(BattleStep::Finished);
+        }
+
+        if self.is_paused() {
+            return Ok(BattleStep::Paused);
+        }
+
+        {
+            let _narration = NarrationGuard::new(self.narration);
+
+            self.start();
+
+            self.state = self.turn_system.play_turn(
+                &mut self.data,
+                &mut GivenChoice {
+                    choice: Some(choice),
+                    fallback: &mut TeamControllers {
+                        teams: &mut self.team_controllers,
+                        fallback: self.controller.as_mut(),
+                    },
+                },
+                &self.suggested_performer_criteria,
+            )?;
+        }
+
+        self.step()
+    }
+
+    /// Run the opening phase of the battle, if it hasn't started yet.
+    ///
+    /// The starting tags of the [`StartupInfo`] are applied first, followed by every member's
+    /// [`Member::on_battle_start()`] effects and the registered [`BattleStartHook`]s.
+    /// Finally, the [`StartupInfo`] decides the opening turn order.
+    fn start(&mut self) {
+        if !matches!(self.state, State::Preparating) {
+            return;
+        }
+
+        narrate!("The battle is starting");
+
+        let mut opening_effects = Vec::new();
+
+        for (team_id, team) in self.data.team_list.iter().enumerate() {
+            for (member_id, member) in team.member_list().iter().enumerate() {
+                let id = MemberIdentifier::new(team_id, member_id);
+
+                opening_effects.extend(member.on_battle_start(id, &self.startup));
+            }
+        }
+
+        // Starting statuses are applied before any other effect, so that they can be consumed right away.
+        for (member, tag) in self.startup.starting_tags.clone() {
+            self.data.apply(Event::TagApplied { member, tag });
+        }
+
+        for event in opening_effects {
+            self.data.apply(event);
+        }
+
+        for hook in &self.battle_start_hooks {
+            hook(&mut self.data, &self.startup);
+        }
+
+        // Prepare the opening turn order, with the surprise round (if any) followed by the initiative overrides.
+        if self.startup.surprise_round {
+            if let Some(team_id) = self.startup.ambushing_team {
+                if let Some(team) = self.data.team_list.get(team_id) {
+                    for (member_id, member) in team.member_list().iter().enumerate() {
+                        if member.health() > 0 {
+                            self.turn_system
+                                .opening_queue
+                                .push_back(MemberIdentifier::new(team_id, member_id));
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.startup.initiative_by_speed {
+            self.startup.initiative = speed_order(&self.data);
+        }
+
+        self.turn_system
+            .opening_queue
+            .extend(self.startup.initiative.iter().copied());
+
+        let first_performer = match self.startup.first_performer {
+            Some(member) => Some(member),
+            None => match self.turn_system.next_queued(&self.data.team_list) {
+                Some(member) => Some(member),
+                None => self.turn_system.next_in_round(&mut self.data),
+            },
+        };
+
+        if let Some(member) = first_performer {
+            self.turn_system.emit(
+                &mut self.data,
+                Event::PerformerSuggested {
+                    member: Some(member),
+                },
+            );
+        }
+
+        let seed = self.data.rng.seed();
+
+        self.turn_system
+            .emit(&mut self.data, Event::BattleStarted { seed });
+        self.state = State::InProgress;
+    }
+
+    /// Check every rule against a submitted [`BattleCommand`], without performing it.
+    ///
+    /// # Notes
+    ///
+    /// The command is checked against the current state of the battle and the member whose turn it is.
+    /// Use this to validate commands received from untrusted sources before feeding them to the battle.
+    pub fn validate_command(&mut self, command: &BattleCommand<M>) -> ValidationReport {
+        let finished = self.is_finished();
+
+        command::validate(
+            &mut self.data,
+            command,
+            self.turn_system.suggested_performer,
+            finished,
+        )
+    }
+
+    /// Queue an action to be resolved during the next turn.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::queue_action()`] to learn how queued actions are resolved.
+    pub fn queue_action(&mut self, choice: ChoiceReturn<M>) {
+        self.turn_system.queue_action(choice);
+    }
+
+    /// Schedule an action to be resolved after the given amount of turns, returning its identifier.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::schedule_action()`] to learn how scheduled actions are resolved.
+    pub fn schedule_action(
+        &mut self,
+        choice: ChoiceReturn<M>,
+        turns: u64,
+        rule: CancelRule,
+    ) -> ScheduleId {
+        self.turn_system.schedule_action(choice, turns, rule)
+    }
+
+    /// Cancel a scheduled action before it's resolved.
+    ///
+    /// Returns `false` if no action with the given identifier is waiting to be resolved.
+    pub fn cancel_scheduled(&mut self, id: ScheduleId) -> bool {
+        let Some((action, _, _)) = self.turn_system.schedule.cancel(id) else {
+            return false;
+        };
+
+        narrate!(
+            "The scheduled action {:?} was cancelled",
+            action.action_id()
+        );
+
+        self.turn_system.emit(
+            &mut self.data,
+            Event::ActionInterrupted {
+                action: action.action_id(),
+            },
+        );
+
+        true
+    }
+
+    /// Returns the actions scheduled to be resolved during a future turn, in the same order they were scheduled.
+    pub fn scheduled_actions(&self) -> &[ScheduledAction<M>] {
+        self.turn_system.schedule.pending()
+    }
+
+    /// Capture the current state of the battle, to be restored later with [`Battle::rollback()`].
+    ///
+    /// # Notes
+    ///
+    /// Look at the [`checkpoint`] module to learn what is captured.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BattleError::UncopyableAction`] if an action waiting to be resolved can't be
+    /// [copied](crate::action::Action::duplicate).
+    pub fn checkpoint(&self) -> Result<Checkpoint<M>, BattleError>
+    where
+        M: Clone,
+    {
+        Ok(Checkpoint {
+            data: self.data.checkpoint(),
+            turns: self.turn_system.checkpoint()?,
+            state: self.state,
+            drained_events: self.drained_events,
+        })
+    }
+
+    /// Bring the battle back to the state captured by a [`Checkpoint`], undoing every turn played since then.
+    ///
+    /// # Notes
+    ///
+    /// The checkpoint is consumed. To roll back to the same state more than once, capture a new checkpoint
+    /// right after rolling back.
+    pub fn rollback(&mut self, checkpoint: Checkpoint<M>) {
+        narrate!(
+            "The battle was rolled back to turn {}",
+            checkpoint.turn_number()
+        );
+
+        self.data.rollback(checkpoint.data);
+        self.turn_system.rollback(checkpoint.turns);
+        self.state = checkpoint.state;
+        self.drained_events = checkpoint.drained_events;
+    }
+
+    /// Add a new member to a team, even while the battle is in progress, returning its [`MemberIdentifier`].
+    ///
+    /// Returns `None` if the team doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// The new member is appended at the end of its team, so no other identifier changes.
+    /// If no member was suggested to perform the next turn, the new member is suggested instead.
+    pub fn add_member(&mut self, team_id: usize, member: M) -> Option<MemberIdentifier> {
+        let id = self.data.add_member(team_id, member)?;
+
+        if self.turn_system.suggested_performer.is_none() && !self.is_finished() {
+            self.turn_system.emit(
+                &mut self.data,
+                Event::PerformerSuggested { member: Some(id) },
+            );
+        }
+
+        Some(id)
+    }
+
+    /// Remove a member from the battle, even while it is in progress, returning it.
+    ///
+    /// Returns `None` if the member doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// The IDs of the members following the removed one in the same team are shifted down by one.
+    /// Queued actions, channeled actions and the suggested performer are updated accordingly:
+    /// the removed member is dropped from their targets, and actions it was the only performer of are cancelled.
+    /// If the removed member was suggested to perform the next turn, a new performer is suggested.
+    ///
+    /// The battle ends right away if the end condition is met after the removal.
+    pub fn remove_member(&mut self, member: MemberIdentifier) -> Option<M> {
+        // The next performer must be searched before the member leaves, while the turn order is still intact.
+        let next = if self.turn_system.suggested_performer == Some(member) {
+            self.turn_system
+                .search_performer(
+                    &mut self.data,
+                    &self.suggested_performer_criteria,
+                    Some(member),
+                )
+                .and_then(|next| next.after_removal(member))
+        } else {
+            None
+        };
+
+        let removed = self.data.remove_member(member)?;
+
+        self.turn_system.forget_member(&mut self.data, member, next);
+
+        let started = match self.state {
+            State::InProgress => true,
+            State::Paused => self.paused_from == State::InProgress,
+            State::Preparating | State::Finished => false,
+        };
+
+        if started && self.turn_system.check_end_condition(&self.data) {
+            self.turn_system.emit(&mut self.data, Event::BattleFinished);
+            self.state = State::Finished;
+        }
+
+        Some(removed)
+    }
+
+    pub fn teams(&self) -> &[Team<M>] {
+        self.data.teams()
+    }
+
+    /// Returns the [`StartupInfo`] the battle opens with.
+    pub fn startup_info(&self) -> &StartupInfo {
+        &self.startup
+    }
+
+    /// Returns the member suggested to perform the next action, if any.
+    pub fn suggested_performer(&self) -> Option<MemberIdentifier> {
+        self.turn_system.suggested_performer
+    }
+
+    /// Returns the [`TurnSystem`] handling the turns of the battle.
+    pub fn turn_system(&self) -> &TurnSystem<M> {
+        &self.turn_system
+    }
+
+    /// Returns the IDs of the teams that will be asked for a choice during the next turn.
+    ///
+    /// # Notes
+    ///
+    /// With [`TurnMode::Sequential`], only the suggested performer's team makes a choice.
+    /// With [`TurnMode::Simultaneous`], every team with alive members does.
+    /// No team is asked while the suggested performer is channeling an action, or while the battle is paused.
+    pub fn choosing_teams(&self) -> Vec<usize> {
+        if self.is_finished() || self.is_paused() {
+            return Vec::new();
+        }
+
+        let suggested = self.turn_system.suggested_performer;
+        let channeling = |member: &MemberIdentifier| self.turn_system.channels.contains_key(member);
+
+        match self.turn_system.turn_mode {
+            TurnMode::Sequential => suggested
+                .filter(|member| !channeling(member))
+                .map(|member| member.team_id)
+                .into_iter()
+                .collect(),
+            TurnMode::Simultaneous => team_performers(&self.data.team_list, suggested)
+                .into_iter()
+                .filter(|member| !channeling(member))
+                .map(|member| member.team_id)
+                .collect(),
+        }
+    }
+
+    /// Returns the seed used by this battle's source of randomness.
+    pub fn seed(&self) -> u64 {
+        self.data.rng.seed()
+    }
+
+    /// Create an independent source of randomness for a subsystem, derived from this battle's seed.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`BattleRng::derive()`] for more information.
+    pub fn derive_rng(&self, namespace: &str) -> BattleRng {
+        self.data.rng.derive(namespace)
+    }
+
+    /// Returns the [`ComponentStore`] holding the additional per-member data of this battle.
+    pub fn components(&self) -> &ComponentStore {
+        self.data.components()
+    }
+
+    /// Returns the statistics of every wave cleared so far, in order.
+    ///
+    /// # Notes
+    ///
+    /// The list is always empty if the battle isn't a wave-based encounter.
+    pub fn wave_statistics(&self) -> &[WaveStatistics] {
+        self.turn_system
+            .waves()
+            .map(Waves::statistics)
+            .unwrap_or_default()
+    }
+
+    /// Returns the history of all events that happened during this battle.
+    pub fn events(&self) -> &EventLog {
+        self.data.events()
+    }
+
+    /// Returns the history of all events that happened during this battle, as seen by a team.
+    ///
+    /// # Notes
+    ///
+    /// If the battle has no [`FogOfWar`], every event is returned as is.
+    pub fn events_seen_by(&self, team_id: usize) -> Vec<Event> {
+        let fog = self
+            .turn_system
+            .fog_of_war()
+            .unwrap_or_else(|| FogOfWar::new().with_visible_health().with_visible_actions());
+
+        fog.redact_events(self.events().events(), Some(team_id))
+            .collect()
+    }
+
+    /// Returns the events recorded since the last call to this function.
+    ///
+    /// # Notes
+    ///
+    /// The events are not removed from the battle's [`EventLog`], which keeps the whole history of the battle.
+    /// This is meant for frontends that react to every new event, like animations or network updates.
+    pub fn drain_events(&mut self) -> &[Event] {
+        let start = self.drained_events;
+        self.drained_events = self.data.events().len();
+
+        self.data.events().since(start)
+    }
+
+    /// Returns the current [`MemberIdentifier`] of the member with the given handle.
+    ///
+    /// Look at [`BattleState::resolve()`] for more information.
+    pub fn resolve(&self, handle: MemberHandle) -> Option<MemberIdentifier> {
+        self.data.resolve(handle)
+    }
+
+    /// Returns the inventory of a member or team.
+    ///
+    /// Look at [`BattleState::inventory()`] for more information.
+    pub fn inventory(&self, owner: InventoryOwner) -> Option<&Inventory> {
+        self.data.inventory(owner)
+    }
+
+    /// Returns the total noise made by the actions performed during the battle.
+    ///
+    /// Look at [`BattleState::noise()`] for more information.
+    pub fn noise(&self) -> u64 {
+        self.data.noise()
+    }
+
+    /// Returns the number of the current round, or `0` if rounds aren't enabled or haven't started yet.
+    ///
+    /// Look at [`Builder::set_round_order()`] for more information.
+    pub fn round_number(&self) -> u64 {
+        self.turn_system.round_number
+    }
+
+    /// Returns the amount of alive members of every team.
+    ///
+    /// Look at [`BattleState::alive_summary()`] for more information.
+    pub fn alive_summary(&self) -> AliveSummary {
+        self.data.alive_summary()
+    }
+
+    /// Returns the combat statistics of every member, or `None` if they aren't tracked.
+    ///
+    /// Look at [`Builder::set_statistics_tracking()`] for more information.
+    pub fn statistics(&self) -> Option<&CombatStatistics> {
+        self.data.statistics()
+    }
+
+    /// Returns the turns (or rounds) left to revive a defeated member, or `None` if it isn't waiting to be revived.
+    ///
+    /// Look at [`Builder::set_revival_window()`] for more information.
+    pub fn revival_countdown(&self, member: MemberIdentifier) -> Option<u32> {
+        self.turn_system.knocked_out.get(&member).copied()
+    }
+
+    /// Returns whether the choices of a member are made by the idle takeover controller.
+    ///
+    /// Look at [`Builder::set_idle_takeover()`] for more information.
+    pub fn is_taken_over(&self, member: MemberIdentifier) -> bool {
+        self.turn_system.taken_over.contains(&member)
+    }
+
+    /// Give the choices of a member taken over by the idle takeover controller back to the team's controller.
+    ///
+    /// Returns `false` if the member wasn't taken over.
+    pub fn restore_control(&mut self, member: MemberIdentifier) -> bool {
+        if !self.is_taken_over(member) {
+            return false;
+        }
+
+        narrate!("{:?} is back in control", member);
+
+        self.turn_system
+            .emit(&mut self.data, Event::ControlRestored { member });
+
+        true
+    }
+
+    /// Stop the [`Battle`] from going on until [`Battle::resume()`] is called, recording an [`Event::BattlePaused`].
+    ///
+    /// Returns `false` if the battle was already paused or has finished.
+    ///
+    /// # Notes
+    ///
+    /// Turns can't be played while the battle is paused: [`Battle::play_turn()`] returns [`BattleError::Paused`],
+    /// while [`Battle::step()`] returns [`BattleStep::Paused`]. The battle can still be looked at and changed,
+    /// and it finishes right away if removing a member meets its end condition.
+    pub fn pause(&mut self) -> bool {
+        if matches!(self.state, State::Paused | State::Finished) {
+            return false;
+        }
+
+        narrate!("The battle is paused");
+
+        self.paused_from = self.state;
+        self.state = State::Paused;
+        self.turn_system.emit(&mut self.data, Event::BattlePaused);
+
+        true
+    }
+
+    /// Let a paused [`Battle`] go on from where it stopped, recording an [`Event::BattleResumed`].
+    ///
+    /// Returns `false` if the battle wasn't paused.
+    pub fn resume(&mut self) -> bool {
+        if !self.is_paused() {
+            return false;
+        }
+
+        narrate!("The battle is resumed");
+
+        self.state = self.paused_from;
+        self.turn_system.emit(&mut self.data, Event::BattleResumed);
+
+        true
+    }
+
+    /// Returns the stable [`MemberHandle`] of a member.
+    ///
+    /// Look at [`BattleState::handle()`] for more information.
+    pub fn handle(&self, member: MemberIdentifier) -> Option<MemberHandle> {
+        self.data.handle(member)
+    }
+
+    /// Returns every [`Structure`] of the battlefield, in the order they were built.
+    pub fn structures(&self) -> &[Structure] {
+        self.data.structures()
+    }
+
+    /// Returns the aggregate statistics of the battle so far.
+    pub fn outcome(&self) -> BattleOutcome {
+        BattleOutcome::from_state(&self.data)
+    }
+
+    /// Returns what persists from the battle so far, like the health left to every member and the items used up.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`BattleDelta`] for more information.
+    pub fn delta(&self) -> BattleDelta<M::Properties> {
+        BattleDelta::from_state(&self.data)
+    }
+
+    /// Returns the current [`BattleState`], holding all data that changes during the battle.
+    pub fn battle_state(&self) -> &BattleState<M> {
+        &self.data
+    }
+
+    /// Unwrap the [`Battle`] instance and return the state of its participants.
+    pub fn take_teams(self) -> Vec<Team<M>> {
+        self.data.take_teams()
+    }
+
+    /// Unwrap the [`Battle`] instance and return the state of its participants, together with the [`BattleOutcome`].
+    pub fn take_teams_with_outcome(self) -> (Vec<Team<M>>, BattleOutcome) {
+        let outcome = self.outcome();
+
+        (self.take_teams(), outcome)
+    }
+}
+
+impl<M: Levelable> Battle<M> {
+    /// Unwrap the [`Battle`] instance, letting every member gain the experience it earned.
+    ///
+    /// Returns the state of the participants after their level-ups, together with the [`BattleOutcome`]
+    /// reporting the experience and levels gained by each member.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`BattleOutcome`] to learn how experience is earned.
+    pub fn claim_rewards(self) -> (Vec<Team<M>>, BattleOutcome) {
+        let (mut teams, mut outcome) = self.take_teams_with_outcome();
+
+        for report in &mut outcome.members {
+            if report.experience == 0 {
+                continue;
+            }
+
+            let Some(member) = teams
+                .get_mut(report.member.team_id)
+                .and_then(|t| t.member_mut(report.member.member_id))
+            else {
+                continue;
+            };
+
+            report.levels_gained = member.gain_experience(report.experience);
+
+            log::info!(
+                "Member {} gained {} experience and {} level(s)",
+                member.name(),
+                report.experience,
+                report.levels_gained
+            );
+        }
+
+        (teams, outcome)
+    }
+}
+
+impl<M> Battle<M> {
+    /// Let a controller choose the actions of a team, instead of the battle's controller.
+    ///
+    /// # Notes
+    ///
+    /// Whenever the suggested performer belongs to the team, its controller is prompted instead of the battle's one.
+    /// Setting a new controller for the same team replaces the previous one.
+    pub fn set_team_controller(
+        &mut self,
+        team_id: usize,
+        controller: impl Controller<M> + 'static,
+    ) {
+        self.team_controllers.insert(team_id, Box::new(controller));
+    }
+
+    /// Returns whether this [`Battle`] has completed or not.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, State::Finished)
+    }
+
+    /// Returns whether this [`Battle`] is paused.
+    pub fn is_paused(&self) -> bool {
+        matches!(self.state, State::Paused)
+    }
+
+    /// Signal the completion of the [`Battle`] to stop its execution.
+    ///
+    /// # Notes
+    ///
+    /// It is necessary to run at least one more turn using [`Battle::play_turn`] for the battle's end to be properly handled.
+    pub fn set_completed(&mut self) {
+        self.state = State::Finished;
+    }
+}
+
+/// Information needed to start a new [`Battle`].
+///
+/// # Notes
+///
+/// The opening turn order is decided once the battle starts: the [first performer](StartupInfo::first_performer)
+/// (if any) acts first, followed by the [surprise round](StartupInfo::surprise_round) of the ambushing team
+/// and by the [initiative](StartupInfo::initiative) order. The battle's [`SuggestedPerformerCriteria`] takes over afterwards.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StartupInfo {
+    /// Member performing the first turn of the battle.
+    ///
+    /// If not specified, the first member of the first team starts.
+    pub first_performer: Option<MemberIdentifier>,
+    /// Team that caught its opponents by surprise, if any.
+    ///
+    /// # Notes
+    ///
+    /// This value only has an effect on the turn order if [`StartupInfo::surprise_round`] is set,
+    /// but can be read by [`Member::on_battle_start()`] and [`BattleStartHook`]s to apply ambush advantages.
+    pub ambushing_team: Option<usize>,
+    /// Whether every alive member of the ambushing team acts once before anybody else (pre-emptive strike/back attack).
+    pub surprise_round: bool,
+    /// Members performing the opening turns, in order, after the surprise round (if any).
+    ///
+    /// # Notes
+    ///
+    /// Members that are defeated before their turn comes are skipped.
+    /// Once all listed members have acted, the battle's [`SuggestedPerformerCriteria`] takes over.
+    pub initiative: Vec<MemberIdentifier>,
+    /// Whether the initiative is rolled from the speed of the members once the battle starts.
+    ///
+    /// Look at [`StartupInfo::with_initiative_by_speed()`] for more information.
+    pub initiative_by_speed: bool,
+    /// Statuses applied to members before the battle starts.
+    pub starting_tags: Vec<(MemberIdentifier, Tag)>,
+}
+
+impl StartupInfo {
+    /// Create a new [`StartupInfo`] with no special opening.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the member performing the first turn of the battle (first-strike).
+    pub fn with_first_performer(mut self, member: MemberIdentifier) -> Self {
+        self.first_performer = Some(member);
+
+        self
+    }
+
+    /// Set the team that caught its opponents by surprise.
+    pub fn with_ambushing_team(mut self, team_id: usize) -> Self {
+        self.ambushing_team = Some(team_id);
+
+        self
+    }
+
+    /// Let the ambushing team act for a full round before anybody else.
+    ///
+    /// # Notes
+    ///
+    /// This has no effect if no ambushing team is set.
+    pub fn with_surprise_round(mut self) -> Self {
+        self.surprise_round = true;
+
+        self
+    }
+
+    /// Set the members performing the opening turns, in order.
+    pub fn with_initiative(mut self, initiative: Vec<MemberIdentifier>) -> Self {
+        self.initiative = initiative;
+
+        self
+    }
+
+    /// Roll the initiative from the final [speed](Properties::speed) of the alive members once the battle starts,
+    /// fastest first.
+    ///
+    /// # Notes
+    ///
+    /// The speed includes the auras and stat stages of every member, as well as the effects applied when the battle
+    /// starts. Look at [`BattleState::final_properties()`] for more information.
+    /// The rolled order replaces [`StartupInfo::initiative`], and members with the same speed keep their team
+    /// and member order.
+    pub fn with_initiative_by_speed(mut self) -> Self {
+        self.initiative_by_speed = true;
+
+        self
+    }
+
+    /// Add a status applied to a member before the battle starts.
+    pub fn with_starting_tag(mut self, member: MemberIdentifier, tag: Tag) -> Self {
+        self.starting_tags.push((member, tag));
+
+        self
+    }
+}
+
+/// Handler of the turn-based combat.
+///
+/// Stores information about the turn cycle and the current playing member.
+pub struct TurnSystem<M> {
+    turn_number: u64,
+    suggested_performer: Option<MemberIdentifier>,
+    end_condition: EndCondition,
+    invalid_action_fallback: InvalidActionFallback,
+    performer_model: PerformerModel,
+    /// Members that must perform the next turns, before the suggested performer criteria is used again.
+    opening_queue: VecDeque<MemberIdentifier>,
+    /// Actions waiting to be resolved during the next turn, together with the chosen one.
+    action_queue: Vec<QueuedAction<M>>,
+    /// Actions spanning multiple turns, waiting for the next turn of their performer.
+    channels: BTreeMap<MemberIdentifier, Channel<M>>,
+    /// Members waiting to join the battle.
+    reinforcements: ReinforcementScheduler<M>,
+    /// Actions waiting to be resolved during a future turn.
+    schedule: ActionSchedule<M>,
+    /// Waves of enemies fought during the battle, if any.
+    waves: Option<Waves<M>>,
+    /// Handlers invoked when members are affected by an action.
+    reactions: Vec<(ReactionScope, ReactionHandler<M>)>,
+    /// Consecutive turns forfeited by each member.
+    idle_turns: BTreeMap<MemberIdentifier, u32>,
+    /// Controller choosing for members that have been idle for too long, if any.
+    idle_takeover: Option<IdleTakeover<M>>,
+    /// Longest time a choice can take, together with what to do once it has elapsed, if any.
+    #[cfg(feature = "std")]
+    choice_timeout: Option<ChoiceTimeout<M>>,
+    /// Members whose choices are made by the idle takeover controller.
+    taken_over: BTreeSet<MemberIdentifier>,
+    /// Turns defeated members have to be revived before being removed, if they can only be revived for a while.
+    revival_window: Option<u32>,
+    /// Turns left to revive each defeated member.
+    knocked_out: BTreeMap<MemberIdentifier, u32>,
+    /// Scripts run by the battlefield at the end of every turn.
+    environment: Vec<EnvironmentScript<M>>,
+    /// Hooks run during every turn, together with their phase.
+    phase_hooks: Vec<(TurnPhase, PhaseHook<M>)>,
+    /// Rule deciding how many actions are chosen during a turn.
+    turn_mode: TurnMode,
+    /// Order of the members acting in every round, if rounds are enabled.
+    round_order: Option<RoundOrder>,
+    /// Information hidden from every team about the members of the other teams, if any.
+    fog_of_war: Option<FogOfWar>,
+    /// Number of the current round, or `0` if no round has started yet.
+    round_number: u64,
+    /// Whether the current round has started and not ended yet.
+    round_open: bool,
+    /// Members of the current round still waiting to perform.
+    round_queue: VecDeque<MemberIdentifier>,
+    /// Amount of events of the battle's log already checked for defeated members dropping loot.
+    looted_events: usize,
+    /// Criteria choosing which member of a team performs whenever the team is suggested, overriding the battle's one.
+    team_criteria: BTreeMap<usize, SuggestedPerformerCriteria<M>>,
+    /// Last member of each team that was suggested to perform.
+    team_performers: BTreeMap<usize, MemberIdentifier>,
+}
+
+/// Controller taking over the choices of idle members.
+struct IdleTakeover<M> {
+    /// Consecutive forfeited turns after which a member is taken over.
+    after: u32,
+    controller: Box<dyn Controller<M>>,
+}
+
+/// Longest time a choice can take, together with what to do once it has elapsed.
+#[cfg(feature = "std")]
+struct ChoiceTimeout<M> {
+    duration: Duration,
+    fallback: TimeoutFallback<M>,
+}
+
+/// Behaviour of the [`TurnSystem`] when the controller takes too long to choose an action.
+///
+/// Look at [`Builder::set_choice_timeout()`] for more information.
+#[cfg(feature = "std")]
+#[non_exhaustive]
+pub enum TimeoutFallback<M> {
+    /// Skip the turn without performing any action.
+    SkipTurn,
+    /// Let another controller, like an AI, choose the action in place of the late one.
+    Controller(Box<dyn Controller<M>>),
+}
+
+/// Behaviour of the [`TurnSystem`] when the chosen action fails its [validation](crate::action::Action::validate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidActionFallback {
+    /// Stop the turn and return the validation error.
+    Fail,
+    /// Skip the turn without performing any action.
+    SkipTurn,
+    /// Ask the choice callback for a new action, up to a maximum amount of attempts.
+    ///
+    /// # Notes
+    ///
+    /// The validation error is returned if the callback fails to provide a valid action after all attempts.
+    AskAgain { attempts: u32 },
+}
+
+/// Action waiting to be resolved by the [`TurnSystem`].
+struct QueuedAction<M> {
+    choice: ChoiceReturn<M>,
+    /// Whether the action is continuing from a previous turn, having already paid its costs.
+    continued: bool,
+    /// Whether the action is a reaction to another one, and so can't trigger further reactions.
+    reaction: bool,
+}
+
+impl<M> QueuedAction<M> {
+    fn new(choice: ChoiceReturn<M>) -> Self {
+        Self {
+            choice,
+            continued: false,
+            reaction: false,
+        }
+    }
+}
+
+/// Progress of a [`TurnSystem`] captured by a [`Checkpoint`].
+pub(crate) struct TurnCheckpoint<M> {
+    pub(crate) turn_number: u64,
+    suggested_performer: Option<MemberIdentifier>,
+    opening_queue: VecDeque<MemberIdentifier>,
+    action_queue: Vec<QueuedAction<M>>,
+    channels: BTreeMap<MemberIdentifier, Channel<M>>,
+    schedule: ScheduleCheckpoint<M>,
+    reinforcements: ReinforcementCheckpoint,
+    waves: Option<WavesCheckpoint>,
+    idle_turns: BTreeMap<MemberIdentifier, u32>,
+    taken_over: BTreeSet<MemberIdentifier>,
+    knocked_out: BTreeMap<MemberIdentifier, u32>,
+    round_number: u64,
+    round_open: bool,
+    round_queue: VecDeque<MemberIdentifier>,
+    looted_events: usize,
+}
+
+/// Action that returned [`ActionState::Continuing`], waiting for the next turn of its performer.
+struct Channel<M> {
+    choice: ChoiceReturn<M>,
+    /// Length of the event log when the action was last performed.
+    since: usize,
+}
+
+/// Rule deciding which members perform the actions chosen during a turn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PerformerModel {
+    /// Any member (or group of members) chosen by the choice callback performs the action.
+    #[default]
+    Any,
+    /// Exactly one member acts per turn: the suggested performer.
+    ///
+    /// # Notes
+    ///
+    /// The performers returned by the choice callback are ignored, and the engine appoints the suggested performer instead.
+    /// The turn is skipped if no performer is suggested. Queued actions and reactions keep their chosen performers.
+    Solo,
+}
+
+/// What a [`Battle`] needs to go on, returned by [`Battle::step()`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleStep {
+    /// The battle is waiting for the next choice, to be given with [`Battle::resume_with_choice()`].
+    AwaitingChoice {
+        /// Member suggested to perform the next action, if any.
+        performer: Option<MemberIdentifier>,
+    },
+    /// The battle is paused, and won't go on until [`Battle::resume()`] is called.
+    Paused,
+    /// The battle has finished.
+    Finished,
+}
+
+/// What happened during a turn, returned by [`Battle::play_turn()`].
+///
+/// # Notes
+///
+/// The action and its targets are the first ones performed during the turn, if any. Every other action
+/// (like the ones of the other teams in [`TurnMode::Simultaneous`], reactions or scheduled actions)
+/// can be found among the events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnResult {
+    /// Number of the turn, or of the last one played if no turn was played.
+    pub turn_number: u64,
+    /// Member suggested to perform during the turn, if any.
+    pub performer: Option<MemberIdentifier>,
+    /// Action performed during the turn, if any.
+    pub action: Option<ActionId>,
+    /// Members targeted by the action.
+    pub targets: Vec<MemberIdentifier>,
+    /// Events recorded during the turn, from the oldest to the newest.
+    ///
+    /// # Notes
+    ///
+    /// Events dropped by the battle's [`Retention`] policy during the turn are missing.
+    pub events: Vec<Event>,
+    /// State of the battle after the turn.
+    pub state: State,
+}
+
+/// Iterator playing the turns of a [`Battle`], returned by [`Battle::turns()`].
+pub struct Turns<'a, M> {
+    battle: &'a mut Battle<M>,
+    /// Whether the last turn failed, ending the iterator.
+    failed: bool,
+}
+
+impl<M: Member> Iterator for Turns<'_, M> {
+    type Item = Result<TurnResult, BattleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.battle.is_finished() || self.battle.is_paused() {
+            return None;
+        }
+
+        let result = self.battle.play_turn();
+        self.failed = result.is_err();
+
+        Some(result)
+    }
+}
+
+/// Rule deciding how many actions are chosen during a turn.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TurnMode {
+    /// A single action is chosen per turn, for the suggested performer.
+    #[default]
+    Sequential,
+    /// Every team with alive members chooses an action, and all of them are resolved together within the same turn.
+    ///
+    /// # Notes
+    ///
+    /// Teams choose in team order, each for its first alive member (or for the suggested performer, if it's one of its members).
+    /// The chosen actions are resolved starting from the ones with the highest [priority](crate::action::Action::priority),
+    /// followed by the ones chosen for the fastest members (look at [`Properties::speed()`](crate::member::Properties::speed)),
+    /// with any remaining tie broken by team order.
+    Simultaneous,
+}
+
+/// Phase of a turn, during which the registered [`PhaseHook`]s are run.
+///
+/// # Notes
+///
+/// Turn phases happen in the order they are listed. Turns in which no action is chosen (like the ones resuming a
+/// channeled action) skip the [`ChooseAction`](TurnPhase::ChooseAction) phase.
+/// Look at [`round`](crate::round) to learn about the round phases.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TurnPhase {
+    /// Right after the turn has started, before any action is chosen.
+    UpkeepStart,
+    /// Right before the controller is asked to choose the action of the turn.
+    ChooseAction,
+    /// Right after cooldowns tick, before the queued actions are resolved.
+    ResolveAction,
+    /// After every action of the turn has been resolved, before environment scripts run and the end condition is checked.
+    EndOfTurn,
+    /// Right after a round has started, before its first turn. Only happens if rounds are enabled.
+    RoundStart,
+    /// Right after a round has ended, at the end of its last turn before the end condition is checked.
+    /// Only happens if rounds are enabled.
+    RoundEnd,
+}
+
+/// Outcome of the validation of a chosen action.
+enum ChoiceCheck {
+    Valid,
+    Retry,
+    Skip,
+}
+
+impl<M> TurnSystem<M> {
+    pub fn new(starting_member: MemberIdentifier, end_condition: EndCondition) -> Self {
+        Self {
+            turn_number: 0,
+            suggested_performer: Some(starting_member),
+            end_condition,
+            invalid_action_fallback: InvalidActionFallback::Fail,
+            performer_model: PerformerModel::Any,
+            opening_queue: VecDeque::new(),
+            action_queue: Vec::new(),
+            channels: BTreeMap::new(),
+            reinforcements: ReinforcementScheduler::new(),
+            schedule: ActionSchedule::new(),
+            waves: None,
+            reactions: Vec::new(),
+            idle_turns: BTreeMap::new(),
+            idle_takeover: None,
+            #[cfg(feature = "std")]
+            choice_timeout: None,
+            taken_over: BTreeSet::new(),
+            revival_window: None,
+            knocked_out: BTreeMap::new(),
+            environment: Vec::new(),
+            phase_hooks: Vec::new(),
+            turn_mode: TurnMode::Sequential,
+            round_order: None,
+            fog_of_war: None,
+            round_number: 0,
+            round_open: false,
+            round_queue: VecDeque::new(),
+            looted_events: 0,
+            team_criteria: BTreeMap::new(),
+            team_performers: BTreeMap::new(),
+        }
+    }
+
+    /// Register a [`ReactionHandler`] for the members covered by the scope.
+    ///
+    /// # Notes
+    ///
+    /// Handlers are invoked after every action, once for each member that was targeted or damaged by it,
+    /// in the same order they are added.
+    pub fn add_reaction(&mut self, scope: ReactionScope, handler: ReactionHandler<M>) {
+        self.reactions.push((scope, handler));
+    }
+
+    /// Set the [`Waves`] fought during the battle.
+    ///
+    /// # Notes
+    ///
+    /// Waves are checked at the end of every turn, before reinforcements and the end condition,
+    /// so that the battle only ends once the last wave has been cleared.
+    pub fn set_waves(&mut self, waves: Waves<M>) {
+        self.waves = Some(waves);
+    }
+
+    /// Returns the [`Waves`] fought during the battle, if any.
+    pub fn waves(&self) -> Option<&Waves<M>> {
+        self.waves.as_ref()
+    }
+
+    /// Hide some information about the members of the other teams from the team making each choice.
+    ///
+    /// # Notes
+    ///
+    /// The fog of war only changes what controllers are shown, not the battle itself.
+    /// Look at [`FogOfWar`] for more information.
+    pub fn set_fog_of_war(&mut self, fog: FogOfWar) {
+        self.fog_of_war = Some(fog);
+    }
+
+    /// Returns the fog of war of the battle, if any.
+    pub fn fog_of_war(&self) -> Option<FogOfWar> {
+        self.fog_of_war
+    }
+
+    /// Schedule a [`Reinforcement`] to join the battle once its trigger fires.
+    ///
+    /// # Notes
+    ///
+    /// Triggers are checked at the end of every turn, before the end condition,
+    /// so that a new wave can join before its team is declared defeated.
+    pub fn add_reinforcement(&mut self, reinforcement: Reinforcement<M>) {
+        self.reinforcements.add(reinforcement);
+    }
+
+    /// Returns the reinforcements that have yet to join the battle.
+    pub fn reinforcements(&self) -> &ReinforcementScheduler<M> {
+        &self.reinforcements
+    }
+
+    /// Register an [`EnvironmentScript`] run at the end of every turn.
+    ///
+    /// # Notes
+    ///
+    /// Scripts run in the same order they are added, after every action of the turn has been resolved
+    /// and before summons expire, waves advance and the end condition is checked.
+    pub fn add_environment_script(&mut self, script: EnvironmentScript<M>) {
+        self.environment.push(script);
+    }
+
+    /// Register a [`PhaseHook`] run during a phase of every turn.
+    ///
+    /// # Notes
+    ///
+    /// Hooks of the same phase run in the same order they are added.
+    pub fn add_phase_hook(&mut self, phase: TurnPhase, hook: PhaseHook<M>) {
+        self.phase_hooks.push((phase, hook));
+    }
+
+    /// Set the rule deciding how many actions are chosen during a turn.
+    pub fn set_turn_mode(&mut self, mode: TurnMode) {
+        self.turn_mode = mode;
+    }
+
+    /// Set the criteria choosing which member of a team performs, whenever the battle's criteria suggests one of its members.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`SuggestedPerformerCriteria::search_in_team()`] for more information about how the member is chosen.
+    pub fn set_team_performer_criteria(
+        &mut self,
+        team_id: usize,
+        criteria: SuggestedPerformerCriteria<M>,
+    ) {
+        self.team_criteria.insert(team_id, criteria);
+    }
+
+    /// Enable rounds, in which every alive member is suggested to perform once, following the given order.
+    ///
+    /// # Notes
+    ///
+    /// Members in the opening queue (like the ones of a surprise round) act before the first round starts.
+    /// Members joining the battle mid-round wait for the next round, while defeated members are skipped.
+    /// Rounds only suggest the performers, so use [`PerformerModel::Solo`] to make sure nobody else acts.
+    pub fn set_round_order(&mut self, order: RoundOrder) {
+        self.round_order = Some(order);
+    }
+
+    /// Queue an action to be resolved during the next turn, together with the one chosen by the choice callback.
+    ///
+    /// # Notes
+    ///
+    /// All actions of a turn are resolved in order of [priority](crate::action::Action::priority),
+    /// with actions of equal priority being resolved in the same order they were queued.
+    /// The action chosen by the callback is queued last.
+    pub fn queue_action(&mut self, choice: ChoiceReturn<M>) {
+        self.action_queue.push(QueuedAction::new(choice));
+    }
+
+    /// Returns the amount of actions waiting to be resolved during the next turn.
+    pub fn queued_actions(&self) -> usize {
+        self.action_queue.len()
+    }
+
+    /// Schedule an action to be resolved after the given amount of turns, returning its identifier.
+    ///
+    /// # Notes
+    ///
+    /// With `turns` set to 1 (or 0) the action is resolved during the next turn, with 2 during the one after and so on.
+    /// When its turn comes, the action is resolved together with the other actions of the turn, before the ones
+    /// queued or chosen during that turn if they have the same [priority](crate::action::Action::priority).
+    /// Its costs and cooldown are checked and paid only then.
+    ///
+    /// Until then, the action is cancelled following its [`CancelRule`], checked at the end of every turn and
+    /// right before it's resolved. Cancelled actions are recorded as an [`Event::ActionInterrupted`].
+    pub fn schedule_action(
+        &mut self,
+        choice: ChoiceReturn<M>,
+        turns: u64,
+        rule: CancelRule,
+    ) -> ScheduleId {
+        let turn = self.turn_number.saturating_add(turns.max(1));
+
+        self.schedule.add(choice, turn, rule)
+    }
+
+    /// Returns the actions waiting to be resolved during a future turn.
+    pub fn schedule(&self) -> &ActionSchedule<M> {
+        &self.schedule
+    }
+
+    /// Returns the members known to perform the next turns, in order.
+    ///
+    /// # Notes
+    ///
+    /// The suggested performer comes first, followed by the members waiting in the opening queue and by the ones
+    /// still waiting in the current round. Performers picked later by the suggested performer criteria aren't included.
+    pub fn upcoming_performers(&self) -> Vec<MemberIdentifier> {
+        self.suggested_performer
+            .into_iter()
+            .chain(self.opening_queue.iter().copied())
+            .chain(self.round_queue.iter().copied())
+            .collect()
+    }
+
+    /// Capture the progress of the turn cycle and every action waiting to be resolved.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BattleError::UncopyableAction`] if a pending action can't be copied.
+    fn checkpoint(&self) -> Result<TurnCheckpoint<M>, BattleError> {
+        let action_queue = self
+            .action_queue
+            .iter()
+            .map(|q| {
+                Ok(QueuedAction {
+                    choice: checkpoint::duplicate(&q.choice)?,
+                    ..*q
+                })
+            })
+            .collect::<Result<_, BattleError>>()?;
+
+        let channels = self
+            .channels
+            .iter()
+            .map(|(member, c)| {
+                let choice = checkpoint::duplicate(&c.choice)?;
+
+                Ok((*member, Channel { choice, ..*c }))
+            })
+            .collect::<Result<_, BattleError>>()?;
+
+        Ok(TurnCheckpoint {
+            turn_number: self.turn_number,
+            suggested_performer: self.suggested_performer,
+            opening_queue: self.opening_queue.clone(),
+            action_queue,
+            channels,
+            schedule: self.schedule.checkpoint()?,
+            reinforcements: self.reinforcements.checkpoint(),
+            waves: self.waves.as_ref().map(Waves::checkpoint),
+            idle_turns: self.idle_turns.clone(),
+            taken_over: self.taken_over.clone(),
+            knocked_out: self.knocked_out.clone(),
+            round_number: self.round_number,
+            round_open: self.round_open,
+            round_queue: self.round_queue.clone(),
+            looted_events: self.looted_events,
+        })
+    }
+
+    /// Restore the progress of the turn cycle captured by a checkpoint.
+    fn rollback(&mut self, checkpoint: TurnCheckpoint<M>) {
+        self.turn_number = checkpoint.turn_number;
+        self.suggested_performer = checkpoint.suggested_performer;
+        self.opening_queue = checkpoint.opening_queue;
+        self.action_queue = checkpoint.action_queue;
+        self.channels = checkpoint.channels;
+        self.schedule.rollback(checkpoint.schedule);
+        self.reinforcements.rollback(checkpoint.reinforcements);
+
+        if let (Some(waves), Some(checkpoint)) = (&mut self.waves, checkpoint.waves) {
+            waves.rollback(checkpoint);
+        }
+
+        self.idle_turns = checkpoint.idle_turns;
+        self.taken_over = checkpoint.taken_over;
+        self.knocked_out = checkpoint.knocked_out;
+        self.round_number = checkpoint.round_number;
+        self.round_open = checkpoint.round_open;
+        self.round_queue = checkpoint.round_queue;
+        self.looted_events = checkpoint.looted_events;
+    }
+
+    /// Set the behaviour used when a chosen action fails its validation.
+    ///
+    /// # Notes
+    ///
+    /// By default, [`InvalidActionFallback::Fail`] is used.
+    pub fn set_invalid_action_fallback(&mut self, fallback: InvalidActionFallback) {
+        self.invalid_action_fallback = fallback;
+    }
+
+    /// Set the rule deciding which members perform the chosen actions.
+    ///
+    /// # Notes
+    ///
+    /// By default, [`PerformerModel::Any`] is used.
+    pub fn set_performer_model(&mut self, model: PerformerModel) {
+        self.performer_model = model;
+    }
+
+    /// Hand the choices of members idle for `after` consecutive turns over to another controller.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`Builder::set_idle_takeover()`] for more information.
+    pub fn set_idle_takeover(&mut self, after: u32, controller: impl Controller<M> + 'static) {
+        self.idle_takeover = Some(IdleTakeover {
+            after,
+            controller: Box::new(controller),
+        });
+    }
+
+    /// Discard choices taking longer than `timeout`, following the fallback instead.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`Builder::set_choice_timeout()`] for more information.
+    #[cfg(feature = "std")]
+    pub fn set_choice_timeout(&mut self, timeout: Duration, fallback: TimeoutFallback<M>) {
+        self.choice_timeout = Some(ChoiceTimeout {
+            duration: timeout,
+            fallback,
+        });
+    }
+
+    /// Returns the time by which the choice started at `started` must be made, if choices have a timeout.
+    #[cfg(feature = "std")]
+    fn choice_deadline(&self, started: Instant) -> Option<Instant> {
+        self.choice_timeout
+            .as_ref()
+            .map(|timeout| started + timeout.duration)
+    }
+
+    /// Give defeated members `turns` (or rounds) to be revived before removing them from the battle.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`Builder::set_revival_window()`] for more information.
+    pub fn set_revival_window(&mut self, turns: u32) {
+        self.revival_window = Some(turns);
+    }
+
+    /// Returns the idle takeover controller, if it must choose for the suggested performer.
+    fn takeover_controller(&mut self) -> Option<&mut dyn Controller<M>> {
+        let member = self.suggested_performer?;
+
+        if !self.taken_over.contains(&member) {
+            return None;
+        }
+
+        self.idle_takeover
+            .as_mut()
+            .map(|t| t.controller.as_mut() as &mut dyn Controller<M>)
+    }
+}
+
+// TurnSystem functionality that requires access to teams and members.
+impl<M: Member> TurnSystem<M> {
+    /// Simulate one turn of the battle.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BattleError`] if the turn counter overflows `u64::MAX`, if the suggested performer
+    /// is not found or if the chosen action fails its validation (depending on the [`InvalidActionFallback`]).
+    pub fn play_turn(
+        &mut self,
+        data: &mut BattleState<M>,
+        controller: &mut dyn Controller<M>,
+        suggested_performer_criteria: &SuggestedPerformerCriteria<M>,
+    ) -> Result<State, BattleError> {
+        self.begin_turn(data)?;
+
+        if self.turn_mode == TurnMode::Simultaneous {
+            let choices = self.choose_per_team(data, controller)?;
+
+            return self.resolve_turn(data, choices, suggested_performer_criteria);
+        }
+
+        if let Some(choice) = self.resume_channel(data) {
+            return self.resolve_turn(data, vec![choice], suggested_performer_criteria);
+        }
+
+        if self.performer_model == PerformerModel::Solo && self.suggested_performer.is_none() {
+            narrate!("No member can perform during this turn");
+
+            return self.resolve_turn(data, Vec::new(), suggested_performer_criteria);
+        }
+
+        self.run_phase(data, TurnPhase::ChooseAction);
+
+        let choice = self.choose(data, controller)?;
+
+        self.track_idle(data, choice.as_ref());
+
+        self.resolve_turn(
+            data,
+            choice.into_iter().collect(),
+            suggested_performer_criteria,
+        )
+    }
+
+    /// Ask the controller to choose an action for the suggested performer, until a valid one is chosen
+    /// or the [`InvalidActionFallback`] gives up.
+    fn choose(
+        &mut self,
+        data: &mut BattleState<M>,
+        controller: &mut dyn Controller<M>,
+    ) -> Result<Option<QueuedAction<M>>, BattleError> {
+        let mut attempt = 0;
+
+        loop {
+            #[cfg(feature = "std")]
+            let started = Instant::now();
+
+            let redacted = self.redacted_teams(&data.team_list, self.suggested_performer);
+            let prompt = ChoicePrompt::new(
+                redacted.as_deref().unwrap_or(&data.team_list),
+                &data.auras,
+                &data.components,
+                self.suggested_performer,
+                &mut data.rng,
+            );
+            #[cfg(feature = "std")]
+            let prompt = prompt.with_deadline(self.choice_deadline(started));
+
+            // Members taken over by the idle takeover controller ignore the battle's controller.
+            let choice = match self.takeover_controller() {
+                Some(takeover) => takeover.choose(prompt),
+                None => {
+                    let choice = controller.choose(prompt);
+
+                    #[cfg(feature = "std")]
+                    let Some(choice) = self.check_timeout(data, started, choice) else {
+                        return Ok(None);
+                    };
+
+                    choice
+                }
+            };
+            let choice = self.appoint_performer(choice);
+
+            attempt += 1;
+
+            match self.check_choice(data, &choice, attempt)? {
+                ChoiceCheck::Valid => return Ok(Some(QueuedAction::new(choice))),
+                ChoiceCheck::Retry => continue,
+                ChoiceCheck::Skip => return Ok(None),
+            }
+        }
+    }
+
+    /// Let every team with alive members choose an action, returning them in the order they must be resolved.
+    ///
+    /// Look at [`TurnMode::Simultaneous`] for more information.
+    fn choose_per_team(
+        &mut self,
+        data: &mut BattleState<M>,
+        controller: &mut dyn Controller<M>,
+    ) -> Result<Vec<QueuedAction<M>>, BattleError> {
+        let suggested = self.suggested_performer;
+        let mut choices = Vec::new();
+
+        self.run_phase(data, TurnPhase::ChooseAction);
+
+        for member in team_performers(&data.team_list, suggested) {
+            // Every choice is made as if its member was the suggested performer.
+            self.suggested_performer = Some(member);
+
+            let choice = match self.resume_channel(data) {
+                Some(channel) => Ok(Some(channel)),
+                None => self.choose(data, controller).inspect(|choice| {
+                    self.track_idle(data, choice.as_ref());
+                }),
+            };
+
+            self.suggested_performer = suggested;
+
+            choices.extend(choice?.map(|c| (member, c)));
+        }
+
+        Ok(order_by_speed(data, choices))
+    }
+
+    /// Simulate one turn of the battle, awaiting the action choice.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BattleError`] if the turn counter overflows `u64::MAX`, if the suggested performer
+    /// is not found or if the chosen action fails its validation (depending on the [`InvalidActionFallback`]).
+    #[cfg(feature = "async")]
+    pub async fn play_turn_async(
+        &mut self,
+        data: &mut BattleState<M>,
+        team_controllers: &mut BTreeMap<usize, Box<dyn Controller<M>>>,
+        action_choice_callback: &AsyncChoiceCallback<M>,
+        suggested_performer_criteria: &SuggestedPerformerCriteria<M>,
+    ) -> Result<State, BattleError> {
+        self.begin_turn(data)?;
+
+        if self.turn_mode == TurnMode::Simultaneous {
+            let choices = self
+                .choose_per_team_async(data, team_controllers, action_choice_callback)
+                .await?;
+
+            return self.resolve_turn(data, choices, suggested_performer_criteria);
+        }
+
+        if let Some(choice) = self.resume_channel(data) {
+            return self.resolve_turn(data, vec![choice], suggested_performer_criteria);
+        }
+
+        if self.performer_model == PerformerModel::Solo && self.suggested_performer.is_none() {
+            narrate!("No member can perform during this turn");
+
+            return self.resolve_turn(data, Vec::new(), suggested_performer_criteria);
+        }
+
+        self.run_phase(data, TurnPhase::ChooseAction);
+
+        let choice = self
+            .choose_async(data, team_controllers, action_choice_callback)
+            .await?;
+
+        self.track_idle(data, choice.as_ref());
+
+        self.resolve_turn(
+            data,
+            choice.into_iter().collect(),
+            suggested_performer_criteria,
+        )
+    }
+
+    /// Await the choice of an action for the suggested performer, until a valid one is chosen
+    /// or the [`InvalidActionFallback`] gives up.
+    #[cfg(feature = "async")]
+    async fn choose_async(
+        &mut self,
+        data: &mut BattleState<M>,
+        team_controllers: &mut BTreeMap<usize, Box<dyn Controller<M>>>,
+        action_choice_callback: &AsyncChoiceCallback<M>,
+    ) -> Result<Option<QueuedAction<M>>, BattleError> {
+        let mut attempt = 0;
+
+        loop {
+            let performer = self.suggested_performer;
+
+            let redacted = self.redacted_teams(&data.team_list, performer);
+            let teams = redacted.as_deref().unwrap_or(&data.team_list);
+
+            // Members taken over by the idle takeover controller don't wait for the asynchronous choice.
+            let choice = match self.takeover_controller() {
+                Some(controller) => controller.choose(ChoicePrompt::new(
+                    teams,
+                    &data.auras,
+                    &data.components,
+                    performer,
+                    &mut data.rng,
+                )),
+                None => {
+                    #[cfg(feature = "std")]
+                    let started = Instant::now();
+
+                    // Teams with their own controller don't wait for the asynchronous choice.
+                    let choice = match performer.and_then(|p| team_controllers.get_mut(&p.team_id))
+                    {
+                        Some(controller) => {
+                            let prompt = ChoicePrompt::new(
+                                teams,
+                                &data.auras,
+                                &data.components,
+                                performer,
+                                &mut data.rng,
+                            );
+                            #[cfg(feature = "std")]
+                            let prompt = prompt.with_deadline(self.choice_deadline(started));
+
+                            controller.choose(prompt)
+                        }
+                        None => action_choice_callback(teams, performer, &mut data.rng).await,
+                    };
+
+                    #[cfg(feature = "std")]
+                    let Some(choice) = self.check_timeout(data, started, choice) else {
+                        return Ok(None);
+                    };
+
+                    choice
+                }
+            };
+            let choice = self.appoint_performer(choice);
+
+            attempt += 1;
+
+            match self.check_choice(data, &choice, attempt)? {
+                ChoiceCheck::Valid => return Ok(Some(QueuedAction::new(choice))),
+                ChoiceCheck::Retry => continue,
+                ChoiceCheck::Skip => return Ok(None),
+            }
+        }
+    }
+
+    /// Await the choice of an action for every team with alive members, returning them in the order they must be resolved.
+    ///
+    /// Look at [`TurnMode::Simultaneous`] for more information.
+    #[cfg(feature = "async")]
+    async fn choose_per_team_async(
+        &mut self,
+        data: &mut BattleState<M>,
+        team_controllers: &mut BTreeMap<usize, Box<dyn Controller<M>>>,
+        action_choice_callback: &AsyncChoiceCallback<M>,
+    ) -> Result<Vec<QueuedAction<M>>, BattleError> {
+        let suggested = self.suggested_performer;
+        let mut choices = Vec::new();
+
+        self.run_phase(data, TurnPhase::ChooseAction);
+
+        for member in team_performers(&data.team_list, suggested) {
+            // Every choice is made as if its member was the suggested performer.
+            self.suggested_performer = Some(member);
+
+            let choice = match self.resume_channel(data) {
+                Some(channel) => Ok(Some(channel)),
+                None => self
+                    .choose_async(data, team_controllers, action_choice_callback)
+                    .await
+                    .inspect(|choice| self.track_idle(data, choice.as_ref())),
+            };
+
+            self.suggested_performer = suggested;
+
+            choices.extend(choice?.map(|c| (member, c)));
+        }
+
+        Ok(order_by_speed(data, choices))
+    }
+
+    /// Returns the teams as seen by the team of a member, if the battle has a fog of war.
+    fn redacted_teams(
+        &self,
+        teams: &[Team<M>],
+        member: Option<MemberIdentifier>,
+    ) -> Option<Vec<Team<M>>> {
+        self.fog_of_war
+            .map(|fog| fog.redact_teams(teams, member.map(|m| m.team_id)))
+    }
+
+    /// Count the new turn and announce the suggested performer.
+    fn begin_turn(&mut self, data: &mut BattleState<M>) -> Result<(), BattleError> {
+        // Count the new turn
+        let turn_number = match self.turn_number.checked_add(1) {
+            Some(t) => t,
+            None => {
+                log::error!("Turn counter overflowed after {} turns", self.turn_number);
+
+                return Err(BattleError::TurnOverflow);
+            }
+        };
+
+        if let Some(performing_member) = self.suggested_performer {
+            // Get the playing team.
+            let playing_team = match data.team_list.get(performing_member.team_id) {
+                Some(pt) => pt,
+                None => {
+                    log::warn!(
+                        "Playing team with id {:?} was not found",
+                        performing_member.team_id
+                    );
+
+                    return Err(BattleError::UnknownTeam {
+                        team_id: performing_member.team_id,
+                    });
+                }
+            };
+
+            // Get the "active" player of this turn.
+            let playing_member = match playing_team.member(performing_member.member_id) {
+                Some(pm) => pm,
+                None => {
+                    log::warn!(
+                        "Playing member with id {:?} was not found",
+                        performing_member
+                    );
+
+                    return Err(BattleError::UnknownMember(performing_member));
+                }
+            };
+
+            narrate!("Playing turn number {}.", turn_number);
+            narrate!("Plays the team \"{}\"", playing_team.name());
+            narrate!("It's the turn of {}", playing_member.name());
+        } else {
+            narrate!("Playing turn number {}.", turn_number);
+        }
+
+        self.emit(data, Event::TurnStarted { turn_number });
+        self.run_phase(data, TurnPhase::UpkeepStart);
+
+        Ok(())
+    }
+
+    /// Validate the chosen action and decide how to proceed following the [`InvalidActionFallback`].
+    fn check_choice(
+        &self,
+        data: &mut BattleState<M>,
+        choice: &ChoiceReturn<M>,
+        attempt: u32,
+    ) -> Result<ChoiceCheck, BattleError> {
+        let (action, performers, targets) = choice;
+
+        let context = Context::new(data, performers.clone(), targets.clone());
+
+        let validation = action
+            .validate(&context)
+            .and_then(|()| context.check_costs(&action.cost()))
+            .and_then(|()| context.check_cooldown(&action.action_id()))
+            .and_then(|()| context.check_line_of_sight(&**action));
+
+        let error = match validation {
+            Ok(()) => return Ok(ChoiceCheck::Valid),
+            Err(e) => e,
+        };
+
+        log::warn!("The chosen action is invalid: {}", error);
+
+        match self.invalid_action_fallback {
+            InvalidActionFallback::Fail => Err(error.into()),
+            InvalidActionFallback::SkipTurn => Ok(ChoiceCheck::Skip),
+            InvalidActionFallback::AskAgain { attempts } if attempt < attempts => {
+                Ok(ChoiceCheck::Retry)
+            }
+            InvalidActionFallback::AskAgain { .. } => Err(error.into()),
+        }
+    }
+
+    /// Discard a choice started at `started` if it took longer than the choice timeout,
+    /// returning the fallback choice instead, or `None` if the turn must be skipped.
+    #[cfg(feature = "std")]
+    fn check_timeout(
+        &mut self,
+        data: &mut BattleState<M>,
+        started: Instant,
+        choice: ChoiceReturn<M>,
+    ) -> Option<ChoiceReturn<M>> {
+        let timed_out = self
+            .choice_timeout
+            .as_ref()
+            .is_some_and(|timeout| started.elapsed() > timeout.duration);
+
+        if !timed_out {
+            return Some(choice);
+        }
+
+        let member = self.suggested_performer;
+
+        narrate!(
+            "The choice for {:?} took too long and was discarded",
+            member
+        );
+
+        self.emit(data, Event::ChoiceTimedOut { member });
+
+        match self.choice_timeout.as_mut().map(|t| &mut t.fallback) {
+            Some(TimeoutFallback::Controller(controller)) => {
+                let redacted = self
+                    .fog_of_war
+                    .map(|fog| fog.redact_teams(&data.team_list, member.map(|m| m.team_id)));
+
+                Some(controller.choose(ChoicePrompt::new(
+                    redacted.as_deref().unwrap_or(&data.team_list),
+                    &data.auras,
+                    &data.components,
+                    member,
+                    &mut data.rng,
+                )))
+            }
+            _ => None,
+        }
+    }
+
+    /// Count the turns forfeited by the suggested performer, handing it over to the idle takeover controller if needed.
+    ///
+    /// A turn is forfeited if no action was chosen, or if the chosen action has no performers.
+    fn track_idle(&mut self, data: &mut BattleState<M>, choice: Option<&QueuedAction<M>>) {
+        let Some(member) = self.suggested_performer else {
+            return;
+        };
+
+        let forfeited = match choice {
+            Some(queued) => has_no_members(&queued.choice.1),
+            None => true,
+        };
+
+        if !forfeited {
+            self.idle_turns.remove(&member);
+            return;
+        }
+
+        let idle_turns = self
+            .idle_turns
+            .get(&member)
+            .map_or(1, |turns| turns.saturating_add(1));
+
+        narrate!("{:?} forfeited its turn ({} in a row)", member, idle_turns);
+
+        self.emit(data, Event::TurnForfeited { member, idle_turns });
+
+        let take_over = self
+            .idle_takeover
+            .as_ref()
+            .is_some_and(|t| idle_turns >= t.after)
+            && !self.taken_over.contains(&member);
+
+        if take_over {
+            narrate!("{:?} has been idle for too long and is taken over", member);
+
+            self.emit(data, Event::ControlTakenOver { member });
+        }
+    }
+
+    /// Replace the performers of a chosen action with the suggested performer, if required by the [`PerformerModel`].
+    fn appoint_performer(&self, choice: ChoiceReturn<M>) -> ChoiceReturn<M> {
+        match (self.performer_model, self.suggested_performer) {
+            (PerformerModel::Solo, Some(performer)) => {
+                let (action, _, targets) = choice;
+
+                (action, Target::Single(performer), targets)
+            }
+            _ => choice,
+        }
+    }
+
+    /// Update every reference to the members of the battle after one of them has been removed.
+    ///
+    /// `next` is suggested as performer if the removed member was the suggested one.
+    fn forget_member(
+        &mut self,
+        data: &mut BattleState<M>,
+        removed: MemberIdentifier,
+        next: Option<MemberIdentifier>,
+    ) {
+        self.opening_queue = core::mem::take(&mut self.opening_queue)
+            .into_iter()
+            .filter_map(|id| id.after_removal(removed))
+            .collect();
+
+        self.round_queue = core::mem::take(&mut self.round_queue)
+            .into_iter()
+            .filter_map(|id| id.after_removal(removed))
+            .collect();
+
+        self.idle_turns = core::mem::take(&mut self.idle_turns)
+            .into_iter()
+            .filter_map(|(id, turns)| Some((id.after_removal(removed)?, turns)))
+            .collect();
+
+        self.taken_over = core::mem::take(&mut self.taken_over)
+            .into_iter()
+            .filter_map(|id| id.after_removal(removed))
+            .collect();
+
+        self.knocked_out = core::mem::take(&mut self.knocked_out)
+            .into_iter()
+            .filter_map(|(id, turns)| Some((id.after_removal(removed)?, turns)))
+            .collect();
+
+        self.team_performers = core::mem::take(&mut self.team_performers)
+            .into_iter()
+            .filter_map(|(team_id, id)| Some((team_id, id.after_removal(removed)?)))
+            .collect();
+
+        let mut cancelled = Vec::new();
+
+        for queued in core::mem::take(&mut self.action_queue) {
+            let (action, performers, targets) = queued.choice;
+            let performers = performers.after_removal(removed);
+
+            if has_no_members(&performers) {
+                cancelled.push(action.action_id());
+                continue;
+            }
+
+            self.action_queue.push(QueuedAction {
+                choice: (action, performers, targets.after_removal(removed)),
+                ..queued
+            });
+        }
+
+        cancelled.extend(
+            self.schedule
+                .after_removal(removed)
+                .into_iter()
+                .map(|(action, _, _)| action.action_id()),
+        );
+
+        for (member, channel) in core::mem::take(&mut self.channels) {
+            let (action, performers, targets) = channel.choice;
+
+            match member.after_removal(removed) {
+                Some(member) => {
+                    self.channels.insert(
+                        member,
+                        Channel {
+                            choice: (
+                                action,
+                                performers.after_removal(removed),
+                                targets.after_removal(removed),
+                            ),
+                            ..channel
+                        },
+                    );
+                }
+                None => cancelled.push(action.action_id()),
+            }
+        }
+
+        for action in cancelled {
+            narrate!(
+                "The action {:?} was cancelled as its performer left",
+                action
+            );
+
+            self.emit(data, Event::ActionInterrupted { action });
+        }
+
+        if let Some(suggested) = self.suggested_performer {
+            let member = match suggested.after_removal(removed) {
+                Some(member) => Some(member),
+                None => next,
+            };
+
+            if member != self.suggested_performer {
+                self.emit(data, Event::PerformerSuggested { member });
+            }
+        }
+    }
+
+    /// Count down the turns (or rounds) left to revive every defeated member, removing the ones whose revival window is over.
+    fn count_down_revivals(&mut self, data: &mut BattleState<M>) {
+        let Some(window) = self.revival_window else {
+            return;
+        };
+
+        let mut revived = Vec::new();
+        let mut countdowns = Vec::new();
+
+        for (team_id, team) in data.teams().iter().enumerate() {
+            for (member_id, member) in team.member_list().iter().enumerate() {
+                let id = MemberIdentifier::new(team_id, member_id);
+
+                match (member.health(), self.knocked_out.get(&id)) {
+                    (0, Some(remaining)) => countdowns.push((id, remaining.saturating_sub(1))),
+                    (0, None) => countdowns.push((id, window)),
+                    (_, Some(_)) => revived.push(id),
+                    (_, None) => (),
+                }
+            }
+        }
+
+        for member in revived {
+            narrate!("{:?} was revived in time", member);
+
+            self.emit(data, Event::MemberRevived { member });
+        }
+
+        let mut removed = Vec::new();
+
+        for (member, remaining) in countdowns {
+            self.emit(data, Event::RevivalCountdown { member, remaining });
+
+            if remaining == 0 {
+                removed.push(member);
+            }
+        }
+
+        // Members are removed from the last one, so the identifiers of the others don't change.
+        for member in removed.into_iter().rev() {
+            narrate!(
+                "{:?} can no longer be revived and leaves the battle",
+                member
+            );
+
+            if data.remove_member(member).is_some() {
+                self.forget_member(data, member, None);
+            }
+        }
+    }
+
+    /// Roll the drop tables of the members defeated since the last check.
+    fn drop_loot(&mut self, data: &mut BattleState<M>) {
+        let defeated: Vec<MemberIdentifier> = data
+            .events()
+            .since(self.looted_events)
+            .iter()
+            .filter_map(|e| match e {
+                Event::MemberDefeated { member } => Some(*member),
+                _ => None,
+            })
+            .collect();
+
+        for member in defeated {
+            let Some(table) = data
+                .team_list
+                .get(member.team_id)
+                .and_then(|t| t.member(member.member_id))
+                .and_then(|m| m.drop_table())
+            else {
+                continue;
+            };
+
+            for (item, amount) in table.roll(&mut data.rng) {
+                narrate!("{:?} dropped {} {:?}", member, amount, item);
+
+                data.apply(Event::LootDropped {
+                    member,
+                    item,
+                    amount,
+                });
+            }
+        }
+
+        self.looted_events = data.events().len();
+    }
+
+    /// Run the environment scripts, applying their effects and queueing their actions for the next turn.
+    fn run_environment(&mut self, data: &mut BattleState<M>) {
+        for script in &mut self.environment {
+            let Some(effects) = script.run(&ContextView::new(data, self.turn_number)) else {
+                continue;
+            };
+
+            narrate!("The environment script \"{}\" was triggered", script.name());
+
+            data.apply(Event::EnvironmentTriggered {
+                script: script.name().to_string(),
+            });
+
+            for effect in effects {
+                match effect {
+                    EnvironmentEffect::QueueAction(choice) => {
+                        self.action_queue.push(QueuedAction::new(choice))
+                    }
+                    EnvironmentEffect::Apply(event) => data.apply(event),
+                }
+            }
+        }
+    }
+
+    /// Defeat every summoned member whose [`Lifetime`](crate::summon::Lifetime) has expired.
+    fn expire_summons(&mut self, data: &mut BattleState<M>) {
+        let alive = |id: MemberIdentifier| {
+            data.teams()
+                .get(id.team_id)
+                .and_then(|t| t.member(id.member_id))
+                .is_some_and(|m| m.health() > 0)
+        };
+
+        let expired: Vec<MemberIdentifier> = data
+            .components()
+            .summons()
+            .filter(|(_, s)| s.is_expired(s.summoner.is_some_and(alive)))
+            .map(|(member, _)| member)
+            .collect();
+
+        for member in expired {
+            narrate!("The summon {:?} has expired", member);
+
+            let health = data
+                .teams()
+                .get(member.team_id)
+                .and_then(|t| t.member(member.member_id))
+                .map_or(0, Member::health);
+
+            self.emit(data, Event::SummonExpired { member });
+
+            if health > 0 {
+                self.emit(
+                    data,
+                    Event::Damaged {
+                        member,
+                        amount: health,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Take the action channeled by the suggested performer, if it can continue.
+    ///
+    /// The action is cancelled if the performer has been damaged, interrupted or defeated since it was last performed.
+    fn resume_channel(&mut self, data: &mut BattleState<M>) -> Option<QueuedAction<M>> {
+        let member = self.suggested_performer?;
+        let channel = self.channels.remove(&member)?;
+
+        let disrupted = data.events().since(channel.since).iter().any(|e| match e {
+            Event::Damaged { member: m, amount } => *m == member && *amount > 0,
+            Event::Interrupted { member: m } => *m == member,
+            _ => false,
+        });
+
+        let alive = data
+            .teams()
+            .get(member.team_id)
+            .and_then(|t| t.member(member.member_id))
+            .is_some_and(|m| m.health() > 0);
+
+        if disrupted || !alive {
+            narrate!("The channeled action of {:?} was interrupted", member);
+
+            self.emit(
+                data,
+                Event::ActionInterrupted {
+                    action: channel.choice.0.action_id(),
+                },
+            );
+
+            return None;
+        }
+
+        Some(QueuedAction {
+            choice: channel.choice,
+            continued: true,
+            reaction: false,
+        })
+    }
+
+    /// Perform the chosen actions and prepare the next turn.
+    ///
+    /// If no action is given, the turn is skipped.
+    fn resolve_turn(
+        &mut self,
+        data: &mut BattleState<M>,
+        choices: Vec<QueuedAction<M>>,
+        suggested_performer_criteria: &SuggestedPerformerCriteria<M>,
+    ) -> Result<State, BattleError> {
+        // Time passes for every action waiting for its cooldown.
+        self.emit(data, Event::CooldownsTicked);
+        self.run_phase(data, TurnPhase::ResolveAction);
+
+        if choices.is_empty() {
+            narrate!("The turn was skipped");
+        }
+
+        // Scheduled actions come before the ones queued during this turn.
+        self.cancel_disrupted_schedule(data);
+
+        let due = self.schedule.take_due(self.turn_number);
+        self.action_queue
+            .splice(0..0, due.into_iter().map(QueuedAction::new));
+        self.action_queue.extend(choices);
+
+        // Resolve all queued actions, starting from the ones with the highest priority.
+        // The sort is stable, so actions with the same priority keep their queueing order.
+        let mut queue = core::mem::take(&mut self.action_queue);
+        queue.sort_by_key(|q| core::cmp::Reverse(q.choice.0.priority()));
+
+        let mut queue = VecDeque::from(queue);
+
+        while let Some(queued) = queue.pop_front() {
+            let (mut action, performers, targets) = queued.choice;
+            let first_event = data.events().len();
+            let mut context = Context::new(data, performers.clone(), targets.clone());
+
+            // Higher priority actions may have interrupted the performers of this one.
+            if context.is_interrupted() {
+                narrate!("The action was interrupted before it could be performed");
+
+                context.apply(Event::ActionInterrupted {
+                    action: action.action_id(),
+                });
+
+                continue;
+            }
+
+            // The state may have changed since the action was chosen, so it must be checked again.
+            // Continuing actions already paid their costs when they were started.
+            let validation = if queued.continued {
+                action
+                    .validate(&context)
+                    .and_then(|()| context.check_line_of_sight(&*action))
+            } else {
+                action
+                    .validate(&context)
+                    .and_then(|()| context.check_costs(&action.cost()))
+                    .and_then(|()| context.check_cooldown(&action.action_id()))
+                    .and_then(|()| context.check_line_of_sight(&*action))
+            };
+
+            if let Err(e) = validation {
+                log::warn!("The queued action could not be performed: {}", e);
+
+                continue;
+            }
+
+            if !queued.continued {
+                context.pay_costs(&action.cost());
+                context.start_cooldown(&action.action_id(), action.cooldown_turns());
+            }
+
+            let performer_ids = context.performer_ids();
+            let channeler = performer_ids.first().copied();
+
+            context.apply(Event::ActionPerformed {
+                action: action.action_id(),
+                performers: performer_ids,
+            });
+            context.make_noise(&*action);
+
+            for member in context.target_ids() {
+                context.apply(Event::Targeted { member });
+            }
+
+            let progress = action.act(context);
+            data.resolve_action(action.action_id(), first_event);
+
+            if progress == ActionState::Continuing {
+                match channeler {
+                    Some(member) => {
+                        narrate!("{:?} is channeling an action", member);
+
+                        self.channels.insert(
+                            member,
+                            Channel {
+                                choice: (action, performers, targets),
+                                since: data.events().len(),
+                            },
+                        );
+                    }
+                    None => log::warn!("A continuing action has no performer and was dropped"),
+                }
+            }
+
+            if queued.reaction {
+                continue;
+            }
+
+            // Let the affected members react, resolving their counter actions right after this one.
+            let produced = data.events().since(first_event);
+            let mut counters = Vec::new();
+
+            for member in reaction::affected_members(produced) {
+                for (scope, handler) in &self.reactions {
+                    if !scope.contains(member) {
+                        continue;
+                    }
+
+                    if let Some(choice) = handler(member, produced, data) {
+                        narrate!("{:?} reacts to the action", member);
+
+                        counters.push(QueuedAction {
+                            choice,
+                            continued: false,
+                            reaction: true,
+                        });
+                    }
+                }
+            }
+
+            for counter in counters.into_iter().rev() {
+                queue.push_front(counter);
+            }
+        }
+
+        self.run_phase(data, TurnPhase::EndOfTurn);
+        self.run_environment(data);
+        self.drop_loot(data);
+        self.expire_summons(data);
+
+        // With rounds, the revival window is counted down when the round ends instead.
+        if self.round_order.is_none() {
+            self.count_down_revivals(data);
+        }
+
+        self.cancel_disrupted_schedule(data);
+
+        // TODO: Programmatically decide when the turn should end (after every player acts? after one player acts?)
+
+        if let Some(waves) = &mut self.waves {
+            waves.advance(data, self.turn_number);
+        }
+
+        self.reinforcements.spawn_due(data, self.turn_number);
+
+        self.end_round(data);
+
+        // Check whether the battle should continue or whether it's finished.
+        data.refresh_alive();
+
+        if self.check_end_condition(data) {
+            self.emit(data, Event::BattleFinished);
+
+            return Ok(State::Finished);
+        }
+
+        // TODO: custom performer finder (does it even make sense with the "everyone can perform" model? maybe just as default behaviour for a more modular system)
+        let member = match self.next_queued(&data.team_list) {
+            Some(m) => Some(m),
+            None if self.round_order.is_some() => self.next_in_round(data),
+            None => {
+                self.search_performer(data, suggested_performer_criteria, self.suggested_performer)
+            }
+        };
+        self.emit(data, Event::PerformerSuggested { member });
+
+        Ok(State::InProgress)
+    }
+
+    /// Search the next performer with the battle's criteria, letting the criteria of its team, if any, choose among its members.
+    fn search_performer(
+        &self,
+        data: &mut BattleState<M>,
+        suggested_performer_criteria: &SuggestedPerformerCriteria<M>,
+        current: Option<MemberIdentifier>,
+    ) -> Option<MemberIdentifier> {
+        let member =
+            suggested_performer_criteria.search(current, &data.team_list, &mut data.rng)?;
+
+        match self.team_criteria.get(&member.team_id) {
+            Some(criteria) => criteria.search_in_team(
+                member.team_id,
+                self.team_performers.get(&member.team_id).copied(),
+                &data.team_list,
+                &mut data.rng,
+            ),
+            None => Some(member),
+        }
+    }
+
+    /// Cancel the scheduled actions whose performers were defeated or disrupted, following their [`CancelRule`].
+    fn cancel_disrupted_schedule(&mut self, data: &mut BattleState<M>) {
+        for (action, _, _) in self.schedule.cancel_disrupted(data) {
+            self.emit(
+                data,
+                Event::ActionInterrupted {
+                    action: action.action_id(),
+                },
+            );
+        }
+    }
+
+    /// Run the hooks registered for a phase of the current turn.
+    fn run_phase(&mut self, data: &mut BattleState<M>, phase: TurnPhase) {
+        for (_, hook) in self.phase_hooks.iter_mut().filter(|(p, _)| *p == phase) {
+            hook(data, self.turn_number);
+        }
+    }
+
+    /// Apply an [`Event`] to the turn system and the battle's state.
+    fn emit(&mut self, data: &mut BattleState<M>, event: Event) {
+        match event {
+            Event::TurnStarted { turn_number } => self.turn_number = turn_number,
+            Event::PerformerSuggested { member } => {
+                if let Some(member) = member {
+                    self.team_performers.insert(member.team_id, member);
+                }
+
+                self.suggested_performer = member;
+            }
+            Event::TurnForfeited { member, idle_turns } => {
+                self.idle_turns.insert(member, idle_turns);
+            }
+            Event::ControlTakenOver { member } => {
+                self.taken_over.insert(member);
+            }
+            Event::ControlRestored { member } => {
+                self.taken_over.remove(&member);
+                self.idle_turns.remove(&member);
+            }
+            Event::RevivalCountdown { member, remaining } => {
+                self.knocked_out.insert(member, remaining);
+            }
+            Event::MemberRevived { member } => {
+                self.knocked_out.remove(&member);
+            }
+            Event::RoundStarted { round } => {
+                self.round_number = round;
+                self.round_open = true;
+            }
+            Event::RoundEnded { .. } => self.round_open = false,
+            _ => (),
+        }
+
+        data.apply(event);
+    }
+
+    /// Returns whether or not the battle should end.
+    ///
+    /// # Notes
+    ///
+    /// Members are never looked at, since the alive counts are kept up to date by the [`BattleState`].
+    fn check_end_condition(&self, data: &BattleState<M>) -> bool {
+        let alive = data.alive_summary();
+
+        match self.end_condition {
+            EndCondition::LastMemberStanding => alive.total() < 2,
+            EndCondition::Undetected { noise_limit } if data.noise() >= noise_limit => true,
+            EndCondition::LastTeamStanding | EndCondition::Undetected { .. } => {
+                alive.teams_alive() < 2
+            }
+            EndCondition::StructureDestroyed(id) => {
+                data.structure(id).is_some_and(|s| s.is_destroyed())
+            }
+        }
+    }
+
+    /// Returns the next alive member of the current round, starting a new round if the current one has ended.
+    ///
+    /// Returns `None` if rounds aren't enabled or if no member is alive.
+    fn next_in_round(&mut self, data: &mut BattleState<M>) -> Option<MemberIdentifier> {
+        let order = self.round_order?;
+
+        if !self.round_open {
+            self.round_queue = order.arrange(&data.team_list, &mut data.rng);
+
+            if self.round_queue.is_empty() {
+                return None;
+            }
+
+            narrate!("Round number {} begins", self.round_number + 1);
+
+            self.emit(
+                data,
+                Event::RoundStarted {
+                    round: self.round_number + 1,
+                },
+            );
+            self.run_phase(data, TurnPhase::RoundStart);
+        }
+
+        while let Some(id) = self.round_queue.pop_front() {
+            if is_alive(&data.team_list, id) {
+                return Some(id);
+            }
+        }
+
+        None
+    }
+
+    /// End the current round if none of its members is left waiting to perform.
+    fn end_round(&mut self, data: &mut BattleState<M>) {
+        if !self.round_open {
+            return;
+        }
+
+        self.round_queue.retain(|id| is_alive(&data.team_list, *id));
+
+        if self.round_queue.is_empty() {
+            self.emit(
+                data,
+                Event::RoundEnded {
+                    round: self.round_number,
+                },
+            );
+            self.run_phase(data, TurnPhase::RoundEnd);
+            self.count_down_revivals(data);
+        }
+    }
+
+    /// Returns the next alive member waiting in the opening queue, if any.
+    fn next_queued(&mut self, team_list: &[Team<M>]) -> Option<MemberIdentifier> {
+        while let Some(id) = self.opening_queue.pop_front() {
+            let alive = team_list
+                .get(id.team_id)
+                .and_then(|t| t.member(id.member_id))
+                .is_some_and(|m| m.health() > 0);
+
+            if alive {
+                return Some(id);
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the members the teams choose for in simultaneous turns, in team order.
+///
+/// Every team with alive members chooses for the suggested performer if it's one of them, otherwise for its first alive member.
+fn team_performers<M: Member>(
+    team_list: &[Team<M>],
+    suggested: Option<MemberIdentifier>,
+) -> Vec<MemberIdentifier> {
+    team_list
+        .iter()
+        .enumerate()
+        .filter_map(|(team_id, team)| {
+            if let Some(id) = suggested.filter(|id| id.team_id == team_id) {
+                if is_alive(team_list, id) {
+                    return Some(id);
+                }
+            }
+
+            let member_id = team.member_list().iter().position(|m| m.health() > 0)?;
+
+            Some(MemberIdentifier::new(team_id, member_id))
+        })
+        .collect()
+}
+
+/// Sort the actions chosen for the given members starting from the fastest ones.
+///
+/// The sort is stable, so ties keep their order. Priorities are sorted once the actions are resolved.
+fn order_by_speed<M: Member>(
+    data: &BattleState<M>,
+    mut choices: Vec<(MemberIdentifier, QueuedAction<M>)>,
+) -> Vec<QueuedAction<M>> {
+    choices.sort_by_cached_key(|(id, _)| {
+        let speed = data.final_properties(*id).map_or(0, |p| p.speed());
+
+        core::cmp::Reverse(speed)
+    });
+
+    choices.into_iter().map(|(_, c)| c).collect()
+}
+
+/// Returns the alive members of the battle, fastest first.
+///
+/// Members with the same final speed keep their team and member order.
+fn speed_order<M: Member>(data: &BattleState<M>) -> Vec<MemberIdentifier> {
+    let mut order: Vec<_> = data
+        .team_list
+        .iter()
+        .enumerate()
+        .flat_map(|(team_id, team)| {
+            team.member_list()
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| m.health() > 0)
+                .map(move |(member_id, _)| MemberIdentifier::new(team_id, member_id))
+        })
+        .collect();
+
+    order.sort_by_cached_key(|id| {
+        core::cmp::Reverse(data.final_properties(*id).map_or(0, |p| p.speed()))
+    });
+
+    order
+}
+
+/// Returns whether a member exists and is alive.
+fn is_alive<M: Member>(team_list: &[Team<M>], id: MemberIdentifier) -> bool {
+    team_list
+        .get(id.team_id)
+        .and_then(|t| t.member(id.member_id))
+        .is_some_and(|m| m.health() > 0)
+}
+
+/// Returns whether a target doesn't reference any member.
+fn has_no_members(target: &Target) -> bool {
+    match target {
+        Target::None => true,
+        Target::DiscreteMultiple(ids) => ids.is_empty(),
+        _ => false,
+    }
+}
+
+/// Controller prompting the team controllers of a [`Battle`], or its main controller for teams without one.
+struct TeamControllers<'a, M> {
+    teams: &'a mut BTreeMap<usize, Box<dyn Controller<M>>>,
+    fallback: &'a mut dyn Controller<M>,
+}
+
+impl<M> Controller<M> for TeamControllers<'_, M> {
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        match prompt.team_id().and_then(|id| self.teams.get_mut(&id)) {
+            Some(controller) => prompt.delegate(controller.as_mut()),
+            None => prompt.delegate(self.fallback),
+        }
+    }
+}
+
+/// Controller answering the first prompt with a choice given in advance, and the following ones with another controller.
+struct GivenChoice<'a, M> {
+    choice: Option<ChoiceReturn<M>>,
+    fallback: &'a mut dyn Controller<M>,
+}
+
+impl<M> Controller<M> for GivenChoice<'_, M> {
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        match self.choice.take() {
+            Some(choice) => choice,
+            None => prompt.delegate(self.fallback),
+        }
     }
 }
 
 /// Defaults to using the first given team and its fist given member as starters of the [`Battle`]`, with a [`LastTeamStanding`](EndCondition::LastTeamStanding) end condition.
-impl Default for TurnSystem {
+impl<M> Default for TurnSystem<M> {
     fn default() -> Self {
         Self::new(MemberIdentifier::zeroed(), EndCondition::LastTeamStanding)
     }
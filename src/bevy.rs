@@ -0,0 +1,194 @@
+//! Integration with the [Bevy](https://bevyengine.org) game engine.
+//!
+//! The [`BattlePlugin`] plays the turns of the [`ActiveBattle`] from a Bevy schedule, forwards every recorded
+//! [`Event`] as a [`BattleEvent`], and keeps a [`MemberState`] entity up to date for every member of the battle,
+//! so that game systems can react to the battle with queries and event readers.
+//!
+//! # Notes
+//!
+//! Battles own their controllers, which aren't required to be thread-safe, so the [`ActiveBattle`] is stored as a
+//! non-send resource and its turns are played on the main thread:
+//!
+//! ```ignore
+//! app.add_plugins(BattlePlugin::<MyMember>::default())
+//!     .insert_non_send_resource(ActiveBattle::new(battle));
+//! ```
+
+use core::marker::PhantomData;
+
+use alloc::collections::BTreeMap;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::prelude::{Commands, Component, Entity, EventWriter, NonSend, NonSendMut, Query};
+use bevy_ecs::schedule::{InternedScheduleLabel, IntoSystemConfigs, ScheduleLabel};
+
+use crate::battle::Battle;
+use crate::event::Event;
+use crate::member::{Member, MemberIdentifier};
+
+/// Battle played by the [`BattlePlugin`], stored as a non-send resource.
+pub struct ActiveBattle<M> {
+    battle: Battle<M>,
+    paused: bool,
+}
+
+impl<M> ActiveBattle<M> {
+    /// Create a new [`ActiveBattle`], playing the battle right away.
+    pub fn new(battle: Battle<M>) -> Self {
+        Self {
+            battle,
+            paused: false,
+        }
+    }
+
+    /// Returns the played battle.
+    pub fn battle(&self) -> &Battle<M> {
+        &self.battle
+    }
+
+    /// Returns a mutable reference to the played battle.
+    pub fn battle_mut(&mut self) -> &mut Battle<M> {
+        &mut self.battle
+    }
+
+    /// Stop or resume playing turns, like while an animation is running.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Returns whether turns are currently not played.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stop playing the battle, returning it.
+    pub fn into_inner(self) -> Battle<M> {
+        self.battle
+    }
+}
+
+/// Bevy event carrying an [`Event`] recorded by the [`ActiveBattle`].
+#[derive(bevy_ecs::event::Event, Debug, Clone, PartialEq, Eq)]
+pub struct BattleEvent(pub Event);
+
+/// Component mirroring the state of a member of the [`ActiveBattle`].
+///
+/// # Notes
+///
+/// Entities are spawned for members joining the battle and despawned for members leaving it.
+/// Changes made to the component aren't applied back to the battle.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct MemberState<P: Send + Sync + 'static> {
+    pub id: MemberIdentifier,
+    pub health: u64,
+    pub properties: P,
+}
+
+/// Plugin playing the [`ActiveBattle`] with members of type `M`.
+///
+/// # Notes
+///
+/// One turn is played every time the schedule runs, unless the battle is paused or finished.
+/// Nothing is played until an [`ActiveBattle`] is inserted.
+pub struct BattlePlugin<M> {
+    schedule: InternedScheduleLabel,
+    _member: PhantomData<fn() -> M>,
+}
+
+impl<M> BattlePlugin<M> {
+    /// Create a new [`BattlePlugin`] playing turns during the given schedule.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+            _member: PhantomData,
+        }
+    }
+}
+
+impl<M> Default for BattlePlugin<M> {
+    /// Create a new [`BattlePlugin`] playing turns during the [`Update`] schedule.
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl<M> Plugin for BattlePlugin<M>
+where
+    M: Member + 'static,
+    M::Properties: Send + Sync,
+{
+    fn build(&self, app: &mut App) {
+        app.add_event::<BattleEvent>()
+            .add_systems(self.schedule, (play_turn::<M>, mirror_members::<M>).chain());
+    }
+}
+
+/// Play a turn of the battle, forwarding the recorded events.
+fn play_turn<M: Member + 'static>(
+    active: Option<NonSendMut<ActiveBattle<M>>>,
+    mut events: EventWriter<BattleEvent>,
+) {
+    let Some(mut active) = active else {
+        return;
+    };
+
+    if active.paused || active.battle.is_paused() || active.battle.is_finished() {
+        return;
+    }
+
+    if let Err(e) = active.battle.play_turn() {
+        log::error!("{}, pausing the battle", e);
+
+        active.paused = true;
+    }
+
+    for event in active.battle.drain_events() {
+        events.send(BattleEvent(event.clone()));
+    }
+}
+
+/// Update the [`MemberState`] entities to match the members of the battle.
+fn mirror_members<M>(
+    active: Option<NonSend<ActiveBattle<M>>>,
+    mut commands: Commands,
+    mut mirrors: Query<(Entity, &mut MemberState<M::Properties>)>,
+) where
+    M: Member + 'static,
+    M::Properties: Send + Sync,
+{
+    let Some(active) = active else {
+        return;
+    };
+
+    let mut members = BTreeMap::new();
+
+    for (team_id, team) in active.battle.teams().iter().enumerate() {
+        for (member_id, member) in team.member_list().iter().enumerate() {
+            members.insert(MemberIdentifier::new(team_id, member_id), member);
+        }
+    }
+
+    for (entity, mut state) in &mut mirrors {
+        match members.remove(&state.id) {
+            Some(member) => {
+                let health = member.health();
+                let properties = member.member_properties();
+
+                // Only write changed states, so that change detection can be relied upon.
+                if state.health != health || state.properties != *properties {
+                    state.health = health;
+                    state.properties = properties.clone();
+                }
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+
+    for (id, member) in members {
+        commands.spawn(MemberState {
+            id,
+            health: member.health(),
+            properties: member.member_properties().clone(),
+        });
+    }
+}
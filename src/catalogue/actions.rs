@@ -1,31 +1,960 @@
 //! Pre-made actions using generic implementation for all needs.
 
-use crate::action::{Action, Context};
-use crate::member::{Member, Properties};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::action::{Action, ActionId, ActionState, Context};
+use crate::component::Tag;
+use crate::control::Control;
+use crate::damage::{Critical, Damage};
+use crate::element::Element;
+use crate::error::ActionError;
+use crate::event::Event;
+use crate::formation::{Row, Step};
+use crate::inventory::Consumable;
+use crate::member::{Member, MemberIdentifier, Properties, Statistics};
+use crate::narration::narrate;
+use crate::resource::Cost;
+use crate::rng::BattleRng;
+use crate::spread::{self, Spread};
+use crate::stage::Stat;
+use crate::summon::Lifetime;
 
 /// Simple action that inflicts direct damage on targets.
 ///
 /// # Notes
 ///
-/// Defense and status ailments are NOT taken into consideration when calculating the inflicted damage.
+/// The summed attack is used as base damage. It then goes through the battle's damage [`Pipeline`](crate::damage::Pipeline),
+/// which subtracts each target's [defense](Properties::defense) and applies resistances and any other modifier.
 ///
 /// If multiple members are appointed as performers, their attack will be summed up together.
 /// If multiple members are appointed as targets, each will be damaged by the *total* of the summed attack.
+/// A targeted [`Structure`](crate::terrain::Structure) is damaged by the summed attack, without going through the pipeline.
+#[derive(Clone)]
 pub struct DirectAttack;
 
 impl<M: Member> Action<M> for DirectAttack {
-    fn act(&mut self, mut context: Context<M>) {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
         let mut damage_sum: u64 = 0;
 
-        for p in context.performers() {
+        for id in context.performer_ids() {
             // Calculate the sum of all performers' attacks.
-            // In this case, we use the "final" calculated properties based on member equipped gear and other variables.
-            damage_sum = damage_sum.saturating_add(p.final_properties().attack());
+            // In this case, we use the "final" calculated properties based on member equipped gear, team auras and other variables.
+            let attack = context.final_properties(id).map_or(0, |p| p.attack());
+
+            damage_sum = damage_sum.saturating_add(attack);
         }
 
-        for t in context.targets() {
+        let accuracy = performers_accuracy(&mut context);
+
+        for id in context.target_ids() {
             // Unleash the combined damage on all targets.
-            t.damage(damage_sum);
+            context.deal(attack_damage(id, damage_sum, accuracy));
+        }
+
+        strike_structures(&mut context, damage_sum);
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Close-range attack, dealing reduced damage to targets placed in the back row of their team's formation.
+///
+/// # Notes
+///
+/// Damage is calculated the same way as [`DirectAttack`], then reduced by `back_row_reduction` percent
+/// for targets in the [back row](crate::formation::Row::Back). Targets of teams without formation receive full damage.
+#[derive(Clone)]
+pub struct MeleeAttack {
+    pub back_row_reduction: u64,
+}
+
+impl<M: Member> Action<M> for MeleeAttack {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let damage_sum = performers_attack(&mut context);
+        let accuracy = performers_accuracy(&mut context);
+
+        for id in context.target_ids() {
+            let back_row = context
+                .teams()
+                .get(id.team_id)
+                .and_then(|t| t.position(id.member_id))
+                .is_some_and(|p| p.row == Row::Back);
+
+            let damage = if back_row {
+                damage_sum.saturating_mul(100 - self.back_row_reduction.min(100)) / 100
+            } else {
+                damage_sum
+            };
+
+            context.deal(attack_damage(id, damage, accuracy));
+        }
+
+        strike_structures(&mut context, damage_sum);
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that moves every performer by some steps across its team's formation.
+///
+/// # Notes
+///
+/// Each performer stops at the first blocked step. Targets are ignored.
+#[derive(Clone)]
+pub struct Move {
+    pub step: Step,
+    pub distance: u32,
+}
+
+impl<M: Member> Action<M> for Move {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        for id in context.performer_ids() {
+            displace(&mut context, id, self.step, self.distance);
+        }
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that pushes every target towards the back row of its team's formation.
+///
+/// # Notes
+///
+/// Targets placed in the back row, or blocked by a member behind them, don't move.
+/// The push is a [`Control::ForcedMovement`], so targets may resist it.
+#[derive(Clone)]
+pub struct Push;
+
+impl<M: Member> Action<M> for Push {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        for id in context.target_ids() {
+            force(&mut context, id, Step::Backward, 1);
+        }
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that pulls every target towards the front row of its team's formation.
+///
+/// # Notes
+///
+/// Targets placed in the front row, or blocked by a member in front of them, don't move.
+/// The pull is a [`Control::ForcedMovement`], so targets may resist it.
+#[derive(Clone)]
+pub struct Pull;
+
+impl<M: Member> Action<M> for Pull {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        for id in context.target_ids() {
+            force(&mut context, id, Step::Forward, 1);
+        }
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that does nothing, letting its performers pass their turn.
+///
+/// # Notes
+///
+/// Choosing no performers at all (with [`Target::None`](crate::action::Target::None)) leaves the members idle instead.
+#[derive(Clone)]
+pub struct Pass;
+
+impl<M: Member> Action<M> for Pass {
+    fn act(&mut self, _context: Context<M>) -> ActionState {
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that inflicts direct damage on targets, with a chance of landing critical hits.
+///
+/// # Notes
+///
+/// Damage is calculated the same way as [`DirectAttack`]. The critical hit is rolled separately for each target,
+/// using the battle's source of randomness, before the damage goes through the battle's damage pipeline.
+#[derive(Clone)]
+pub struct CriticalAttack {
+    pub critical: Critical,
+}
+
+impl<M: Member> Action<M> for CriticalAttack {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let damage_sum = performers_attack(&mut context);
+        let accuracy = performers_accuracy(&mut context);
+
+        for id in context.target_ids() {
+            let mut damage = attack_damage(id, damage_sum, accuracy);
+            self.critical.roll(&mut damage, context.rng());
+
+            context.deal(damage);
+        }
+
+        strike_structures(&mut context, damage_sum);
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that inflicts direct damage of an [`Element`] on targets.
+///
+/// # Notes
+///
+/// Damage is calculated the same way as [`DirectAttack`]. The effectiveness of the element against each target
+/// is applied by the [`ElementalAffinity`](crate::element::ElementalAffinity) stage of the battle's damage pipeline,
+/// if registered.
+#[derive(Clone)]
+pub struct ElementalAttack {
+    pub element: Element,
+}
+
+impl<M: Member> Action<M> for ElementalAttack {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let damage_sum = performers_attack(&mut context);
+        let accuracy = performers_accuracy(&mut context);
+
+        for id in context.target_ids() {
+            let damage =
+                attack_damage(id, damage_sum, accuracy).with_element(Some(self.element.clone()));
+
+            context.deal(damage);
+        }
+
+        strike_structures(&mut context, damage_sum);
+
+        ActionState::Finished
+    }
+
+    fn element(&self) -> Option<Element> {
+        Some(self.element.clone())
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that inflicts direct damage on targets and leaves a [`Tag`] on each of them.
+///
+/// # Notes
+///
+/// Damage is calculated the same way as [`DirectAttack`]. The tag is applied after the damage, so it can be consumed
+/// by following actions, like [`ConsumingAttack`]. Targets that evade the attack don't receive the tag.
+#[derive(Clone)]
+pub struct TaggingAttack {
+    pub tag: Tag,
+}
+
+impl<M: Member> Action<M> for TaggingAttack {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let damage_sum = performers_attack(&mut context);
+        let accuracy = performers_accuracy(&mut context);
+
+        for id in context.target_ids() {
+            let hit = context
+                .deal(attack_damage(id, damage_sum, accuracy))
+                .is_some_and(|d| !d.missed);
+
+            if hit {
+                context.apply_tag(id, self.tag.clone());
+            }
+        }
+
+        strike_structures(&mut context, damage_sum);
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that inflicts direct damage on targets, consuming a [`Tag`] from each of them for additional damage.
+///
+/// # Notes
+///
+/// Only one stack of the tag is consumed per target. Targets that don't hold the tag only receive the base damage,
+/// calculated the same way as [`DirectAttack`].
+#[derive(Clone)]
+pub struct ConsumingAttack {
+    pub tag: Tag,
+    pub bonus_damage: u64,
+}
+
+impl<M: Member> Action<M> for ConsumingAttack {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let damage_sum = performers_attack(&mut context);
+        let accuracy = performers_accuracy(&mut context);
+
+        for id in context.target_ids() {
+            let damage = if context.consume_tag(id, &self.tag) {
+                narrate!("Tag \"{}\" was consumed!", self.tag.name());
+
+                damage_sum.saturating_add(self.bonus_damage)
+            } else {
+                damage_sum
+            };
+
+            context.deal(attack_damage(id, damage, accuracy));
+        }
+
+        strike_structures(&mut context, damage_sum);
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that inflicts direct damage on all targets, optionally reduced the farther they are from an epicenter.
+///
+/// # Notes
+///
+/// Damage is calculated the same way as [`DirectAttack`]. If a [`Spread`] rule is given, each target receives
+/// the damage returned by the rule based on its [distance](crate::spread::formation_distance) from the epicenter,
+/// measured across the team's formation if the members are positioned.
+/// Targets that are out of reach of the epicenter (like the members of other teams) are not damaged at all.
+///
+/// Without a spread rule, all targets receive the full damage.
+pub struct AreaAttack {
+    pub epicenter: MemberIdentifier,
+    pub spread: Option<Box<dyn Spread>>,
+}
+
+impl<M: Member> Action<M> for AreaAttack {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let damage_sum = performers_attack(&mut context);
+        let accuracy = performers_accuracy(&mut context);
+
+        for id in context.target_ids() {
+            let damage = match &self.spread {
+                Some(rule) => match spread::formation_distance(context.teams(), self.epicenter, id)
+                {
+                    Some(distance) => rule.spread(damage_sum, distance),
+                    None => continue,
+                },
+                None => damage_sum,
+            };
+
+            context.deal(attack_damage(id, damage, accuracy));
+        }
+
+        ActionState::Finished
+    }
+}
+
+/// Action that draws the attention of the targets' teams to the performers.
+///
+/// # Notes
+///
+/// Every performer generates `threat` against each team with at least one target.
+/// Look at [`ThreatTable`](crate::threat::ThreatTable) for more information.
+///
+/// The taunt is a [`Control::Taunt`] applied to every target. Each team is taunted by the strongest effect
+/// among its targets, and teams whose targets all resisted aren't taunted at all.
+#[derive(Clone)]
+pub struct Taunt {
+    pub threat: u64,
+}
+
+impl<M: Member> Action<M> for Taunt {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let mut teams: BTreeMap<usize, u64> = BTreeMap::new();
+
+        for target in context.target_ids() {
+            let threat = context.apply_control(target, Control::Taunt, self.threat);
+            let strongest = teams.entry(target.team_id).or_default();
+
+            *strongest = (*strongest).max(threat);
+        }
+
+        for member in context.performer_ids() {
+            for (team_id, amount) in teams.iter().filter(|(_, amount)| **amount > 0) {
+                context.apply(Event::ThreatGenerated {
+                    member,
+                    team_id: *team_id,
+                    amount: *amount,
+                });
+            }
+        }
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that stuns every target, cancelling their actions for some turns.
+///
+/// # Notes
+///
+/// Targets are stunned for the rest of the current turn and for the following `turns`.
+/// The stun is a [`Control::Stun`], so targets may resist it and repeated stuns get shorter.
+#[derive(Clone)]
+pub struct Stun {
+    pub turns: u32,
+}
+
+impl<M: Member> Action<M> for Stun {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        for id in context.target_ids() {
+            context.apply_control(id, Control::Stun, u64::from(self.turns));
+        }
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that shields every target with some temporary health, absorbing damage before their health does.
+///
+/// # Notes
+///
+/// Defeated targets aren't shielded. Shields stack with the ones the targets already hold and expire at the end of
+/// the round. Look at [`Event::ShieldGained`] for more information.
+#[derive(Clone)]
+pub struct Shield {
+    pub amount: u64,
+}
+
+impl<M: Member> Action<M> for Shield {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        for id in context.target_ids() {
+            context.shield(id, self.amount);
+        }
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that raises a [`Stat`] of every target by some stages.
+///
+/// # Notes
+///
+/// Stages only last for the battle and never change the targets' [`Properties`].
+/// Look at [`StatStages`](crate::stage::StatStages) for more information.
+#[derive(Clone)]
+pub struct Buff {
+    pub stat: Stat,
+    pub stages: u8,
+}
+
+impl<M: Member> Action<M> for Buff {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let amount = i8::try_from(self.stages).unwrap_or(i8::MAX);
+
+        for id in context.target_ids() {
+            if context.change_stage(id, self.stat, amount) == 0 {
+                narrate!(
+                    "The {:?} of member {:?} won't go any higher!",
+                    self.stat,
+                    id
+                );
+            }
+        }
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that lowers a [`Stat`] of every target by some stages.
+///
+/// # Notes
+///
+/// Look at [`Buff`] for more information.
+#[derive(Clone)]
+pub struct Debuff {
+    pub stat: Stat,
+    pub stages: u8,
+}
+
+impl<M: Member> Action<M> for Debuff {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let amount = i8::try_from(self.stages).map_or(i8::MIN, |s| -s);
+
+        for id in context.target_ids() {
+            if context.change_stage(id, self.stat, amount) == 0 {
+                narrate!("The {:?} of member {:?} won't go any lower!", self.stat, id);
+            }
+        }
+
+        ActionState::Finished
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that revives every defeated target with a percentage of its [reference health](Statistics::reference_health).
+///
+/// # Notes
+///
+/// Targets are revived with at least 1 health, while the ones that aren't defeated are left untouched.
+/// The action is rejected if no target is defeated.
+#[derive(Clone)]
+pub struct Revive {
+    pub percent: u64,
+}
+
+impl<M: Member> Action<M> for Revive {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        for id in context.target_ids() {
+            let Some(reference) = context
+                .member(id)
+                .map(|m| m.statistics().reference_health())
+            else {
+                continue;
+            };
+
+            context.revive(id, reference.saturating_mul(self.percent) / 100);
+        }
+
+        ActionState::Finished
+    }
+
+    fn validate(&self, context: &Context<'_, M>) -> Result<(), ActionError> {
+        context.validate_members()?;
+
+        let defeated = context
+            .target_ids()
+            .into_iter()
+            .any(|id| context.member(id).is_some_and(Member::is_defeated));
+
+        if defeated {
+            Ok(())
+        } else {
+            Err(ActionError::Rejected(String::from("no target is defeated")))
+        }
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Action that summons new members into the team of the first performer.
+///
+/// # Notes
+///
+/// A new member is created by the template for each use of the action, using the battle's source of randomness.
+/// Targets are ignored.
+pub struct Summon<M> {
+    pub template: Box<dyn Fn(&mut BattleRng) -> M>,
+    pub lifetime: Lifetime,
+}
+
+impl<M: Member + 'static> Action<M> for Summon<M> {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let member = (self.template)(context.rng());
+
+        match context.summon(member, self.lifetime) {
+            Some(id) => narrate!("{:?} was summoned", id),
+            None => log::warn!("Nothing could be summoned without a performer"),
+        }
+
+        ActionState::Finished
+    }
+}
+
+/// Action that uses up a consumable item, applying its effect on the targets.
+///
+/// # Notes
+///
+/// The item is taken from the first performer's inventory, or from its team's one if the performer has none left.
+/// The action is rejected if no such item is available.
+pub struct UseItem<M> {
+    pub item: Box<dyn Consumable<M>>,
+}
+
+impl<M: Member + 'static> Action<M> for UseItem<M> {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let Some(&member) = context.performer_ids().first() else {
+            log::warn!("No item can be used without a performer");
+
+            return ActionState::Finished;
+        };
+
+        let item = self.item.item_id();
+
+        match context.consume_item(member, item.clone()) {
+            Some(_) => self.item.use_effect(&mut context),
+            None => log::warn!("{:?} has no {} left", member, item.name()),
+        }
+
+        ActionState::Finished
+    }
+
+    fn validate(&self, context: &Context<'_, M>) -> Result<(), ActionError> {
+        context.validate_members()?;
+
+        let item = self.item.item_id();
+
+        match context.performer_ids().first() {
+            Some(&member) if context.item_owner(member, &item).is_none() => {
+                Err(ActionError::MissingItem { member, item })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Wrapper that adds a resource cost to another action.
+///
+/// # Notes
+///
+/// Useful to turn any pre-made action into a spell or special move, like a [`DirectAttack`] that costs stamina.
+/// The wrapped action's own costs are kept and summed up to the new ones.
+pub struct WithCost<A> {
+    pub action: A,
+    pub costs: Vec<Cost>,
+}
+
+impl<M: Member, A: Action<M> + 'static> Action<M> for WithCost<A> {
+    fn act(&mut self, context: Context<M>) -> ActionState {
+        self.action.act(context)
+    }
+
+    fn validate(&self, context: &Context<'_, M>) -> Result<(), ActionError> {
+        self.action.validate(context)
+    }
+
+    fn cost(&self) -> Vec<Cost> {
+        let mut costs = self.action.cost();
+        costs.extend_from_slice(&self.costs);
+
+        costs
+    }
+
+    fn action_id(&self) -> ActionId {
+        self.action.action_id()
+    }
+
+    fn cooldown_turns(&self) -> u32 {
+        self.action.cooldown_turns()
+    }
+
+    fn priority(&self) -> i32 {
+        self.action.priority()
+    }
+
+    fn requires_line_of_sight(&self) -> bool {
+        self.action.requires_line_of_sight()
+    }
+
+    fn element(&self) -> Option<Element> {
+        self.action.element()
+    }
+
+    fn noise(&self) -> u64 {
+        self.action.noise()
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        let action = self.action.duplicate()?.into_any().downcast::<A>().ok()?;
+
+        Some(Box::new(Self {
+            action: *action,
+            costs: self.costs.clone(),
+        }))
+    }
+}
+
+/// Wrapper that makes performers wait some turns before performing another action again.
+pub struct WithCooldown<A> {
+    pub action: A,
+    pub turns: u32,
+}
+
+impl<M: Member, A: Action<M> + 'static> Action<M> for WithCooldown<A> {
+    fn act(&mut self, context: Context<M>) -> ActionState {
+        self.action.act(context)
+    }
+
+    fn validate(&self, context: &Context<'_, M>) -> Result<(), ActionError> {
+        self.action.validate(context)
+    }
+
+    fn cost(&self) -> Vec<Cost> {
+        self.action.cost()
+    }
+
+    fn action_id(&self) -> ActionId {
+        self.action.action_id()
+    }
+
+    fn cooldown_turns(&self) -> u32 {
+        self.turns
+    }
+
+    fn priority(&self) -> i32 {
+        self.action.priority()
+    }
+
+    fn requires_line_of_sight(&self) -> bool {
+        self.action.requires_line_of_sight()
+    }
+
+    fn element(&self) -> Option<Element> {
+        self.action.element()
+    }
+
+    fn noise(&self) -> u64 {
+        self.action.noise()
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        let action = self.action.duplicate()?.into_any().downcast::<A>().ok()?;
+
+        Some(Box::new(Self {
+            action: *action,
+            turns: self.turns,
+        }))
+    }
+}
+
+/// Wrapper that makes another action noisy, like an explosive arrow alerting nearby guards.
+///
+/// # Notes
+///
+/// The wrapped action's own noise is summed up to the new one.
+pub struct WithNoise<A> {
+    pub action: A,
+    pub noise: u64,
+}
+
+impl<M: Member, A: Action<M> + 'static> Action<M> for WithNoise<A> {
+    fn act(&mut self, context: Context<M>) -> ActionState {
+        self.action.act(context)
+    }
+
+    fn validate(&self, context: &Context<'_, M>) -> Result<(), ActionError> {
+        self.action.validate(context)
+    }
+
+    fn cost(&self) -> Vec<Cost> {
+        self.action.cost()
+    }
+
+    fn action_id(&self) -> ActionId {
+        self.action.action_id()
+    }
+
+    fn cooldown_turns(&self) -> u32 {
+        self.action.cooldown_turns()
+    }
+
+    fn priority(&self) -> i32 {
+        self.action.priority()
+    }
+
+    fn requires_line_of_sight(&self) -> bool {
+        self.action.requires_line_of_sight()
+    }
+
+    fn element(&self) -> Option<Element> {
+        self.action.element()
+    }
+
+    fn noise(&self) -> u64 {
+        self.action.noise().saturating_add(self.noise)
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        let action = self.action.duplicate()?.into_any().downcast::<A>().ok()?;
+
+        Some(Box::new(Self {
+            action: *action,
+            noise: self.noise,
+        }))
+    }
+}
+
+/// Wrapper that makes another action charge for some turns before being released.
+///
+/// # Notes
+///
+/// The performers spend `charge_turns` turns charging, and the wrapped action is performed on the following one.
+/// Costs and cooldowns are only handled when the charge starts.
+pub struct Charged<A> {
+    pub action: A,
+    pub charge_turns: u32,
+    charged: u32,
+}
+
+impl<A> Charged<A> {
+    /// Create a new [`Charged`] action that releases the wrapped action after charging for the given turns.
+    pub fn new(action: A, charge_turns: u32) -> Self {
+        Self {
+            action,
+            charge_turns,
+            charged: 0,
+        }
+    }
+}
+
+impl<M: Member, A: Action<M> + 'static> Action<M> for Charged<A> {
+    fn act(&mut self, context: Context<M>) -> ActionState {
+        if self.charged < self.charge_turns {
+            self.charged += 1;
+
+            narrate!("Charging... ({}/{})", self.charged, self.charge_turns);
+
+            return ActionState::Continuing;
+        }
+
+        self.charged = 0;
+
+        self.action.act(context)
+    }
+
+    fn validate(&self, context: &Context<'_, M>) -> Result<(), ActionError> {
+        self.action.validate(context)
+    }
+
+    fn cost(&self) -> Vec<Cost> {
+        self.action.cost()
+    }
+
+    fn action_id(&self) -> ActionId {
+        self.action.action_id()
+    }
+
+    fn cooldown_turns(&self) -> u32 {
+        self.action.cooldown_turns()
+    }
+
+    fn priority(&self) -> i32 {
+        self.action.priority()
+    }
+
+    fn requires_line_of_sight(&self) -> bool {
+        self.action.requires_line_of_sight()
+    }
+
+    fn element(&self) -> Option<Element> {
+        self.action.element()
+    }
+
+    fn noise(&self) -> u64 {
+        self.action.noise()
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn Action<M>>> {
+        let action = self.action.duplicate()?.into_any().downcast::<A>().ok()?;
+
+        Some(Box::new(Self {
+            action: *action,
+            charge_turns: self.charge_turns,
+            charged: self.charged,
+        }))
+    }
+}
+
+/// Prepare the damage of an attack against a target, leaving its mitigation to the damage pipeline.
+fn attack_damage(target: MemberIdentifier, base: u64, accuracy: u64) -> Damage {
+    Damage::new(target, base).with_accuracy(accuracy)
+}
+
+/// Returns the average of all performers' final accuracy values, or 100 if there are no performers.
+fn performers_accuracy<M: Member>(context: &mut Context<M>) -> u64 {
+    let (sum, count) = context
+        .performer_ids()
+        .into_iter()
+        .filter_map(|id| context.final_properties(id))
+        .fold((0u64, 0u64), |(sum, count), p| {
+            (sum.saturating_add(p.accuracy()), count + 1)
+        });
+
+    sum.checked_div(count).unwrap_or(100)
+}
+
+/// Returns the sum of all performers' final attack values.
+fn performers_attack<M: Member>(context: &mut Context<M>) -> u64 {
+    context
+        .performer_ids()
+        .into_iter()
+        .filter_map(|id| context.final_properties(id))
+        .fold(0, |sum, p| sum.saturating_add(p.attack()))
+}
+
+/// Damage every targeted structure by the given amount.
+fn strike_structures<M: Member>(context: &mut Context<M>, damage: u64) {
+    for id in context.target_structures() {
+        context.damage_structure(id, damage);
+    }
+}
+
+/// Forcefully move a member by up to `distance` steps, letting it resist the movement.
+fn force<M: Member>(context: &mut Context<M>, member: MemberIdentifier, step: Step, distance: u32) {
+    let distance = context.apply_control(member, Control::ForcedMovement, u64::from(distance));
+
+    displace(
+        context,
+        member,
+        step,
+        u32::try_from(distance).unwrap_or(u32::MAX),
+    );
+}
+
+/// Move a member by up to `distance` steps, stopping at the first blocked one.
+fn displace<M: Member>(
+    context: &mut Context<M>,
+    member: MemberIdentifier,
+    step: Step,
+    distance: u32,
+) {
+    for _ in 0..distance {
+        if context.move_member(member, step).is_none() {
+            break;
         }
     }
 }
@@ -0,0 +1,425 @@
+//! Pre-made AI controllers, ready to take control of enemy teams.
+//!
+//! Every controller implements [`AiController`], and can control a [`Battle`](crate::battle::Battle) once wrapped
+//! in an [`Automated`] adapter, or turned into a [`ChoiceCallback`] with [`AiController::into_callback()`]. Simple strategies are provided by
+//! [`Aggressive`], [`Defensive`], [`HealerSupport`] and [`Random`], while the [`PersonalityController`]
+//! can be tuned through [`Personality`] parameters.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::action::{ChoiceCallback, ChoiceReturn, Target};
+use crate::catalogue::actions::Pass;
+use crate::controller::{ChoicePrompt, Controller};
+use crate::member::{Member, MemberIdentifier, Properties, Statistics};
+use crate::rng::BattleRng;
+use crate::team::Team;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use crate::registry::ActionFactory;
+
+/// Automated decision maker, choosing the action of the suggested performer from the state of the battle.
+pub trait AiController<M> {
+    /// Choose the action of the suggested performer.
+    ///
+    /// # Notes
+    ///
+    /// Any random decision should be made using the provided [`BattleRng`] to keep the battle reproducible.
+    fn choose(
+        &self,
+        team_list: &[Team<M>],
+        performer: Option<MemberIdentifier>,
+        rng: &mut BattleRng,
+    ) -> ChoiceReturn<M>;
+
+    /// Choose the action of the suggested performer from a [`ChoicePrompt`], like [`Automated`] controllers do.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation only looks at the teams of the prompt. Strategies comparing the properties of
+    /// members override it, so that the auras and stat stages returned by [`ChoicePrompt::final_properties()`]
+    /// are considered.
+    fn choose_from_prompt(&self, prompt: &mut ChoicePrompt<M>) -> ChoiceReturn<M> {
+        let team_list = prompt.teams();
+        let performer = prompt.performer();
+
+        self.choose(team_list, performer, prompt.rng())
+    }
+
+    /// Convert the controller into a callback usable by a [`Battle`](crate::battle::Battle).
+    ///
+    /// # Notes
+    ///
+    /// Callbacks are only given the teams, so the auras and stat stages of the battle aren't considered.
+    /// Use [`AiController::into_controller()`] to take them into account.
+    fn into_callback(self) -> ChoiceCallback<M>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(move |team_list, performer, rng| self.choose(team_list, performer, rng))
+    }
+
+    /// Wrap the controller in an [`Automated`] adapter, usable as a [`Controller`].
+    fn into_controller(self) -> Automated<Self>
+    where
+        Self: Sized,
+    {
+        Automated(self)
+    }
+}
+
+/// Adapter letting an [`AiController`] make the choices of a [`Battle`](crate::battle::Battle).
+pub struct Automated<A>(pub A);
+
+impl<M, A: AiController<M>> Controller<M> for Automated<A> {
+    fn choose(&mut self, mut prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        self.0.choose_from_prompt(&mut prompt)
+    }
+}
+
+/// Strategy that always attacks the enemy with the lowest health.
+pub struct Aggressive<M> {
+    pub attack: ActionFactory<M>,
+}
+
+impl<M: Member> AiController<M> for Aggressive<M> {
+    fn choose(
+        &self,
+        team_list: &[Team<M>],
+        performer: Option<MemberIdentifier>,
+        _rng: &mut BattleRng,
+    ) -> ChoiceReturn<M> {
+        let performer = performer.unwrap_or_default();
+
+        (
+            (self.attack)(),
+            Target::Single(performer),
+            single_or_none(weakest_enemy(team_list, performer)),
+        )
+    }
+}
+
+/// Strategy that defends whenever the performer's health falls under a threshold,
+/// and otherwise attacks the enemy with the highest attack.
+///
+/// # Notes
+///
+/// When controlling a battle as an [`Automated`] controller, the attack of the enemies includes the auras
+/// of their teams and their stat stages.
+pub struct Defensive<M> {
+    pub attack: ActionFactory<M>,
+    /// Action used to defend, always targeted at the performer itself.
+    pub defend: ActionFactory<M>,
+    /// Health percentage under which the performer defends.
+    pub threshold: u8,
+}
+
+impl<M: Member> Defensive<M> {
+    /// Choose between defending and attacking the enemy with the highest attack, as returned by `attack_of`.
+    fn decide(
+        &self,
+        team_list: &[Team<M>],
+        performer: Option<MemberIdentifier>,
+        attack_of: impl Fn(MemberIdentifier, &M) -> u64,
+    ) -> ChoiceReturn<M> {
+        let performer = performer.unwrap_or_default();
+
+        let in_danger = team_list
+            .get(performer.team_id)
+            .and_then(|t| t.member(performer.member_id))
+            .is_some_and(|m| health_percentage(m) < u64::from(self.threshold.min(100)));
+
+        if in_danger {
+            return (
+                (self.defend)(),
+                Target::Single(performer),
+                Target::Single(performer),
+            );
+        }
+
+        // The first enemy wins ties, so that the choice doesn't jump around between equals.
+        let target = alive_enemies(team_list, performer)
+            .into_iter()
+            .rev()
+            .max_by_key(|(id, m)| attack_of(*id, m))
+            .map(|(id, _)| id);
+
+        (
+            (self.attack)(),
+            Target::Single(performer),
+            single_or_none(target),
+        )
+    }
+}
+
+impl<M: Member> AiController<M> for Defensive<M> {
+    fn choose(
+        &self,
+        team_list: &[Team<M>],
+        performer: Option<MemberIdentifier>,
+        _rng: &mut BattleRng,
+    ) -> ChoiceReturn<M> {
+        self.decide(team_list, performer, |_, m| m.final_properties().attack())
+    }
+
+    fn choose_from_prompt(&self, prompt: &mut ChoicePrompt<M>) -> ChoiceReturn<M> {
+        let prompt = &*prompt;
+
+        self.decide(prompt.teams(), prompt.performer(), |id, m| {
+            prompt
+                .final_properties(id)
+                .unwrap_or_else(|| m.final_properties())
+                .attack()
+        })
+    }
+}
+
+/// Strategy that heals the most wounded ally under a health threshold, and otherwise attacks the enemy with the lowest health.
+pub struct HealerSupport<M> {
+    pub attack: ActionFactory<M>,
+    pub heal: ActionFactory<M>,
+    /// Health percentage under which allies (the performer included) are healed.
+    pub threshold: u8,
+}
+
+impl<M: Member> AiController<M> for HealerSupport<M> {
+    fn choose(
+        &self,
+        team_list: &[Team<M>],
+        performer: Option<MemberIdentifier>,
+        _rng: &mut BattleRng,
+    ) -> ChoiceReturn<M> {
+        let performer = performer.unwrap_or_default();
+
+        let wounded = team_list
+            .get(performer.team_id)
+            .into_iter()
+            .flat_map(|t| t.member_list().iter().enumerate())
+            .filter(|(_, m)| m.health() > 0)
+            .map(|(member_id, m)| (member_id, health_percentage(m)))
+            .filter(|(_, health)| *health < u64::from(self.threshold.min(100)))
+            .min_by_key(|(_, health)| *health);
+
+        match wounded {
+            Some((member_id, _)) => (
+                (self.heal)(),
+                Target::Single(performer),
+                Target::Single(MemberIdentifier::new(performer.team_id, member_id)),
+            ),
+            None => (
+                (self.attack)(),
+                Target::Single(performer),
+                single_or_none(weakest_enemy(team_list, performer)),
+            ),
+        }
+    }
+}
+
+/// Strategy that uses a random action against a random enemy.
+///
+/// # Notes
+///
+/// Without any action to choose from, the member stays idle and [`Pass`] is returned with no performers.
+pub struct Random<M> {
+    pub actions: Vec<ActionFactory<M>>,
+}
+
+impl<M: Member> AiController<M> for Random<M> {
+    fn choose(
+        &self,
+        team_list: &[Team<M>],
+        performer: Option<MemberIdentifier>,
+        rng: &mut BattleRng,
+    ) -> ChoiceReturn<M> {
+        let performer = performer.unwrap_or_default();
+
+        let enemies = alive_enemies(team_list, performer);
+        let target = rng.choose(&enemies).map(|(id, _)| *id);
+
+        let Some(action) = rng.choose(&self.actions) else {
+            return (Box::new(Pass), Target::None, Target::None);
+        };
+
+        (action(), Target::Single(performer), single_or_none(target))
+    }
+}
+
+/// Tunable knobs describing how an AI-controlled member behaves.
+///
+/// # Notes
+///
+/// Every parameter is a percentage, from 0 to 100. Values over 100 are treated as 100.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Personality {
+    /// Chance of attacking instead of defending when not in danger.
+    pub aggression: u8,
+    /// Health percentage under which the member always defends itself.
+    pub self_preservation: u8,
+    /// Chance of attacking the weakest enemy instead of a random one.
+    pub focus_fire: u8,
+}
+
+/// Defaults to a balanced personality, which attacks most of the time and defends when in serious danger.
+impl Default for Personality {
+    fn default() -> Self {
+        Self {
+            aggression: 75,
+            self_preservation: 25,
+            focus_fire: 50,
+        }
+    }
+}
+
+/// AI controller choosing between attacking and defending based on each member's [`Personality`].
+pub struct PersonalityController<M> {
+    attack: ActionFactory<M>,
+    defend: Option<ActionFactory<M>>,
+    personalities: BTreeMap<MemberIdentifier, Personality>,
+    default_personality: Personality,
+}
+
+impl<M: Member + 'static> PersonalityController<M> {
+    /// Create a new [`PersonalityController`] that attacks enemies with the given action.
+    pub fn new(attack: ActionFactory<M>) -> Self {
+        Self {
+            attack,
+            defend: None,
+            personalities: BTreeMap::new(),
+            default_personality: Personality::default(),
+        }
+    }
+
+    /// Set the action used by members to defend themselves. It is always targeted at the performer itself.
+    ///
+    /// # Notes
+    ///
+    /// Without a defensive action, members always attack.
+    pub fn with_defend(mut self, defend: ActionFactory<M>) -> Self {
+        self.defend = Some(defend);
+
+        self
+    }
+
+    /// Set the personality of a specific member.
+    pub fn with_personality(mut self, member: MemberIdentifier, personality: Personality) -> Self {
+        self.personalities.insert(member, personality);
+
+        self
+    }
+
+    /// Set the personality of members without a specific one.
+    pub fn with_default_personality(mut self, personality: Personality) -> Self {
+        self.default_personality = personality;
+
+        self
+    }
+
+    /// Returns the personality of a member.
+    pub fn personality(&self, member: MemberIdentifier) -> Personality {
+        self.personalities
+            .get(&member)
+            .copied()
+            .unwrap_or(self.default_personality)
+    }
+}
+
+impl<M: Member + 'static> AiController<M> for PersonalityController<M> {
+    fn choose(
+        &self,
+        team_list: &[Team<M>],
+        performer: Option<MemberIdentifier>,
+        rng: &mut BattleRng,
+    ) -> ChoiceReturn<M> {
+        let performer = performer.unwrap_or_default();
+        let personality = self.personality(performer);
+
+        if let Some(defend) = &self.defend {
+            let in_danger = team_list
+                .get(performer.team_id)
+                .and_then(|t| t.member(performer.member_id))
+                .is_some_and(|m| {
+                    health_percentage(m) < u64::from(personality.self_preservation.min(100))
+                });
+
+            let calm = !rng.gen_ratio(u64::from(personality.aggression.min(100)), 100);
+
+            if in_danger || calm {
+                return (
+                    defend(),
+                    Target::Single(performer),
+                    Target::Single(performer),
+                );
+            }
+        }
+
+        let enemies = alive_enemies(team_list, performer);
+
+        let target = if rng.gen_ratio(u64::from(personality.focus_fire.min(100)), 100) {
+            enemies
+                .iter()
+                .min_by_key(|(_, m)| m.health())
+                .map(|(id, _)| *id)
+        } else {
+            rng.choose(&enemies).map(|(id, _)| *id)
+        };
+
+        (
+            (self.attack)(),
+            Target::Single(performer),
+            single_or_none(target),
+        )
+    }
+}
+
+/// Returns every alive member of the teams other than the performer's one.
+fn alive_enemies<M: Member>(
+    team_list: &[Team<M>],
+    performer: MemberIdentifier,
+) -> Vec<(MemberIdentifier, &M)> {
+    team_list
+        .iter()
+        .enumerate()
+        .filter(|(team_id, _)| *team_id != performer.team_id)
+        .flat_map(|(team_id, t)| {
+            t.member_list()
+                .iter()
+                .enumerate()
+                .map(move |(member_id, m)| (MemberIdentifier::new(team_id, member_id), m))
+        })
+        .filter(|(_, m)| m.health() > 0)
+        .collect()
+}
+
+/// Returns the alive enemy with the lowest health.
+fn weakest_enemy<M: Member>(
+    team_list: &[Team<M>],
+    performer: MemberIdentifier,
+) -> Option<MemberIdentifier> {
+    alive_enemies(team_list, performer)
+        .into_iter()
+        .min_by_key(|(_, m)| m.health())
+        .map(|(id, _)| id)
+}
+
+/// Returns a target for the given member, if any.
+fn single_or_none(member: Option<MemberIdentifier>) -> Target {
+    match member {
+        Some(id) => Target::Single(id),
+        None => Target::None,
+    }
+}
+
+/// Returns the current health of a member as a percentage of its reference health.
+fn health_percentage<M: Member>(member: &M) -> u64 {
+    let reference = member.statistics().reference_health();
+
+    if reference == 0 {
+        return 0;
+    }
+
+    member.health().saturating_mul(100) / reference
+}
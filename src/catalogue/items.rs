@@ -0,0 +1,33 @@
+//! Pre-made [`Consumable`] items.
+
+use crate::action::Context;
+use crate::inventory::{Consumable, ItemId};
+use crate::member::Member;
+
+/// Item that restores a fixed amount of health to every target.
+pub struct Potion {
+    pub id: ItemId,
+    pub heal: u64,
+}
+
+impl Potion {
+    /// Create a new [`Potion`] named "Potion".
+    pub fn new(heal: u64) -> Self {
+        Self {
+            id: ItemId::new("Potion"),
+            heal,
+        }
+    }
+}
+
+impl<M: Member> Consumable<M> for Potion {
+    fn item_id(&self) -> ItemId {
+        self.id.clone()
+    }
+
+    fn use_effect(&mut self, context: &mut Context<'_, M>) {
+        for id in context.target_ids() {
+            context.heal(id, self.heal);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Ready-made [`Member`] type, to start battling without implementing any trait.
+//!
+//! ```
+//! use fierceful_atto::catalogue::actions::DirectAttack;
+//! use fierceful_atto::catalogue::members::SimpleMember;
+//! use fierceful_atto::prelude::*;
+//!
+//! let teams = vec![
+//!     Team::new(String::from("Heroes"), vec![SimpleMember::new("Picco", 100, 15)]),
+//!     Team::new(String::from("Slimes"), vec![SimpleMember::new("Bacco", 60, 5)]),
+//! ];
+//!
+//! // Every performer attacks the first member of the other team.
+//! let controller: ChoiceCallback<SimpleMember> = Box::new(|_, performer, _| {
+//!     let performer = performer.unwrap_or_default();
+//!     let target = MemberIdentifier::new(1 - performer.team_id, 0);
+//!
+//!     (Box::new(DirectAttack), Target::Single(performer), Target::Single(target))
+//! });
+//!
+//! let battle = Builder::new(teams, None, controller, EndCondition::LastTeamStanding)
+//!     .build()
+//!     .unwrap();
+//! let teams = battle.run().unwrap();
+//!
+//! assert_eq!(teams[1].member(0).unwrap().health(), 0);
+//! ```
+
+use alloc::string::String;
+
+use crate::equipment::Equipment;
+use crate::member::{Member, Properties, Statistics};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// General purpose [`Member`], with plain health, attack and defense values and no equipment.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimpleMember {
+    name: String,
+    statistics: SimpleStats,
+    properties: SimpleProps,
+}
+
+impl SimpleMember {
+    /// Create a new [`SimpleMember`] at full health, without any defense.
+    pub fn new(name: &str, max_health: u64, attack: u64) -> Self {
+        Self::from_statistics(
+            name,
+            SimpleStats {
+                max_health,
+                attack,
+                defense: 0,
+            },
+        )
+    }
+
+    /// Create a new [`SimpleMember`] at full health, with properties matching the given statistics.
+    pub fn from_statistics(name: &str, statistics: SimpleStats) -> Self {
+        Self {
+            name: String::from(name),
+            statistics,
+            properties: SimpleProps::from(statistics),
+        }
+    }
+
+    /// Set the defense of the member, both as its statistic and its current value.
+    pub fn with_defense(mut self, defense: u64) -> Self {
+        self.statistics.defense = defense;
+        self.properties.defense = defense;
+
+        self
+    }
+}
+
+impl Member for SimpleMember {
+    type Statistics = SimpleStats;
+    type Properties = SimpleProps;
+    type Equipment = NoEquipment;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn statistics(&self) -> &SimpleStats {
+        &self.statistics
+    }
+
+    fn member_properties(&self) -> &SimpleProps {
+        &self.properties
+    }
+
+    fn member_properties_mut(&mut self) -> &mut SimpleProps {
+        &mut self.properties
+    }
+
+    fn equipment(&self) -> &NoEquipment {
+        &NoEquipment
+    }
+}
+
+/// [`Statistics`] of a [`SimpleMember`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SimpleStats {
+    pub max_health: u64,
+    pub attack: u64,
+    pub defense: u64,
+}
+
+impl Statistics for SimpleStats {
+    fn reference_health(&self) -> u64 {
+        self.max_health
+    }
+
+    fn base_attack(&self) -> u64 {
+        self.attack
+    }
+
+    fn base_defense(&self) -> u64 {
+        self.defense
+    }
+}
+
+/// [`Properties`] of a [`SimpleMember`].
+///
+/// # Notes
+///
+/// Summing properties adds up all of their values.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SimpleProps {
+    pub health: u64,
+    pub attack: u64,
+    pub defense: u64,
+}
+
+impl From<SimpleStats> for SimpleProps {
+    /// Returns the properties of a member at full health.
+    fn from(statistics: SimpleStats) -> Self {
+        Self {
+            health: statistics.max_health,
+            attack: statistics.attack,
+            defense: statistics.defense,
+        }
+    }
+}
+
+impl Properties for SimpleProps {
+    fn sum_properties(&self, rhs: &Self) -> Self {
+        Self {
+            health: self.health.saturating_add(rhs.health),
+            attack: self.attack.saturating_add(rhs.attack),
+            defense: self.defense.saturating_add(rhs.defense),
+        }
+    }
+
+    fn health(&self) -> u64 {
+        self.health
+    }
+
+    fn health_mut(&mut self) -> &mut u64 {
+        &mut self.health
+    }
+
+    fn attack(&self) -> u64 {
+        self.attack
+    }
+
+    fn defense(&self) -> u64 {
+        self.defense
+    }
+}
+
+/// [`Equipment`] of members that don't wear anything, never changing their properties.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NoEquipment;
+
+impl Equipment for NoEquipment {
+    type Properties = SimpleProps;
+
+    fn associated_properties(&self) -> SimpleProps {
+        SimpleProps::default()
+    }
+}
@@ -1,3 +1,8 @@
 //! Catalogue of pre-made actions and structures to aid in developmet/testing.
 
 pub mod actions;
+pub mod ai;
+pub mod items;
+pub mod members;
+pub mod pattern;
+pub mod spread;
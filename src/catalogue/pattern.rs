@@ -0,0 +1,296 @@
+//! Scripted controllers following fixed rotations of actions, a staple of boss design.
+//!
+//! A [`Pattern`] is a repeating list of [`PatternStep`]s referencing actions by [`ActionId`]. Patterns are plain data
+//! (and can be loaded from encounter files with the `serde` feature), so they are resolved against an
+//! [`ActionRegistry`] and validated when added to a [`PatternController`].
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use crate::action::{ActionId, ChoiceCallback, ChoiceReturn, Target};
+use crate::controller::{ChoicePrompt, Controller};
+use crate::error::PatternError;
+use crate::member::{Member, MemberIdentifier, Statistics};
+use crate::registry::ActionRegistry;
+use crate::rng::BattleRng;
+use crate::team::Team;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Members targeted by a [`PatternStep`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternTarget {
+    /// The performer itself.
+    Performer,
+    /// A random alive enemy.
+    RandomEnemy,
+    /// The alive enemy with the lowest health.
+    WeakestEnemy,
+    /// The whole team of the first enemy found alive.
+    EnemyTeam,
+}
+
+/// Condition checked by a [`PatternStep::Branch`] on the performer.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternCondition {
+    /// The performer's health is below a percentage of its reference health.
+    HealthBelow(u8),
+    /// The performer's health is at least a percentage of its reference health.
+    HealthAtLeast(u8),
+}
+
+/// Single entry of a [`Pattern`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternStep {
+    /// Use an action on the given target.
+    Use {
+        action: ActionId,
+        target: PatternTarget,
+    },
+    /// Follow one of two steps depending on a condition.
+    Branch {
+        condition: PatternCondition,
+        then: Box<PatternStep>,
+        otherwise: Box<PatternStep>,
+    },
+}
+
+impl PatternStep {
+    /// Create a new step using an action on the given target.
+    pub fn use_action(action: ActionId, target: PatternTarget) -> Self {
+        Self::Use { action, target }
+    }
+
+    /// Create a new step following `then` if the condition holds, `otherwise` if it doesn't.
+    pub fn branch(condition: PatternCondition, then: PatternStep, otherwise: PatternStep) -> Self {
+        Self::Branch {
+            condition,
+            then: Box::new(then),
+            otherwise: Box::new(otherwise),
+        }
+    }
+
+    /// Returns every action referenced by the step, including the branches.
+    fn actions(&self) -> Vec<&ActionId> {
+        match self {
+            Self::Use { action, .. } => vec![action],
+            Self::Branch {
+                then, otherwise, ..
+            } => {
+                let mut actions = then.actions();
+                actions.extend(otherwise.actions());
+
+                actions
+            }
+        }
+    }
+}
+
+/// Repeating sequence of [`PatternStep`]s.
+///
+/// # Notes
+///
+/// "Every 3rd turn use Big Slam, unless below 30% health" can be written as two steps using a basic attack,
+/// followed by a branch on [`PatternCondition::HealthBelow`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub steps: Vec<PatternStep>,
+}
+
+impl Pattern {
+    /// Create a new [`Pattern`] from its steps.
+    pub fn new(steps: Vec<PatternStep>) -> Self {
+        Self { steps }
+    }
+
+    /// Check that the pattern isn't empty and that every referenced action is registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PatternError`] describing the first problem found.
+    pub fn validate<M>(&self, registry: &ActionRegistry<M>) -> Result<(), PatternError> {
+        if self.steps.is_empty() {
+            return Err(PatternError::Empty);
+        }
+
+        for action in self.steps.iter().flat_map(PatternStep::actions) {
+            if !registry.contains(action) {
+                return Err(PatternError::UnknownAction(action.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Controller making members follow their [`Pattern`]s, one step per turn.
+///
+/// # Notes
+///
+/// Members without a pattern are controlled by the fallback callback.
+pub struct PatternController<M> {
+    registry: ActionRegistry<M>,
+    patterns: BTreeMap<MemberIdentifier, Pattern>,
+    /// Index of the next step of every member's pattern.
+    positions: RefCell<BTreeMap<MemberIdentifier, usize>>,
+    fallback: ChoiceCallback<M>,
+}
+
+impl<M: Member + 'static> PatternController<M> {
+    /// Create a new [`PatternController`] resolving actions against the registry.
+    pub fn new(registry: ActionRegistry<M>, fallback: ChoiceCallback<M>) -> Self {
+        Self {
+            registry,
+            patterns: BTreeMap::new(),
+            positions: RefCell::new(BTreeMap::new()),
+            fallback,
+        }
+    }
+
+    /// Assign a pattern to a member, after validating it against the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PatternError`] if the pattern is empty or references unregistered actions.
+    pub fn with_pattern(
+        mut self,
+        member: MemberIdentifier,
+        pattern: Pattern,
+    ) -> Result<Self, PatternError> {
+        pattern.validate(&self.registry)?;
+
+        self.patterns.insert(member, pattern);
+
+        Ok(self)
+    }
+
+    /// Choose the action of the suggested performer, advancing its pattern.
+    pub fn choose(
+        &self,
+        team_list: &[Team<M>],
+        performer: Option<MemberIdentifier>,
+        rng: &mut BattleRng,
+    ) -> ChoiceReturn<M> {
+        let Some((member, pattern)) =
+            performer.and_then(|p| self.patterns.get(&p).map(|pattern| (p, pattern)))
+        else {
+            return (self.fallback)(team_list, performer, rng);
+        };
+
+        let step = {
+            let mut positions = self.positions.borrow_mut();
+            let position = positions.entry(member).or_insert(0);
+            let step = &pattern.steps[*position % pattern.steps.len()];
+            *position = (*position + 1) % pattern.steps.len();
+
+            step
+        };
+
+        let (action, target) = resolve_step(step, team_list, member);
+
+        let target = resolve_target(target, team_list, member, rng);
+
+        // Patterns are validated when added, so the action is always registered.
+        match self.registry.create(action) {
+            Some(action) => (action, Target::Single(member), target),
+            None => (self.fallback)(team_list, performer, rng),
+        }
+    }
+
+    /// Convert the controller into a callback usable by a [`Battle`](crate::battle::Battle).
+    pub fn into_callback(self) -> ChoiceCallback<M> {
+        Box::new(move |team_list, performer, rng| self.choose(team_list, performer, rng))
+    }
+}
+
+impl<M: Member + 'static> Controller<M> for PatternController<M> {
+    fn choose(&mut self, mut prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        let team_list = prompt.teams();
+        let performer = prompt.performer();
+
+        PatternController::choose(self, team_list, performer, prompt.rng())
+    }
+}
+
+/// Follow the branches of a step, returning the action to use and its target.
+fn resolve_step<'a, M: Member>(
+    step: &'a PatternStep,
+    team_list: &[Team<M>],
+    member: MemberIdentifier,
+) -> (&'a ActionId, PatternTarget) {
+    match step {
+        PatternStep::Use { action, target } => (action, *target),
+        PatternStep::Branch {
+            condition,
+            then,
+            otherwise,
+        } => {
+            let health = team_list
+                .get(member.team_id)
+                .and_then(|t| t.member(member.member_id))
+                .map_or(0, |m| {
+                    let reference = m.statistics().reference_health();
+
+                    m.health()
+                        .saturating_mul(100)
+                        .checked_div(reference)
+                        .unwrap_or(0)
+                });
+
+            let holds = match condition {
+                PatternCondition::HealthBelow(p) => health < u64::from(*p),
+                PatternCondition::HealthAtLeast(p) => health >= u64::from(*p),
+            };
+
+            resolve_step(if holds { then } else { otherwise }, team_list, member)
+        }
+    }
+}
+
+/// Convert a [`PatternTarget`] into the actual [`Target`] of the action.
+fn resolve_target<M: Member>(
+    target: PatternTarget,
+    team_list: &[Team<M>],
+    member: MemberIdentifier,
+    rng: &mut BattleRng,
+) -> Target {
+    let enemies: Vec<(MemberIdentifier, u64)> = team_list
+        .iter()
+        .enumerate()
+        .filter(|(team_id, _)| *team_id != member.team_id)
+        .flat_map(|(team_id, t)| {
+            t.member_list()
+                .iter()
+                .enumerate()
+                .map(move |(member_id, m)| (MemberIdentifier::new(team_id, member_id), m.health()))
+        })
+        .filter(|(_, health)| *health > 0)
+        .collect();
+
+    let chosen = match target {
+        PatternTarget::Performer => return Target::Single(member),
+        PatternTarget::RandomEnemy => rng.choose(&enemies).map(|(id, _)| *id),
+        PatternTarget::WeakestEnemy => enemies.iter().min_by_key(|(_, h)| *h).map(|(id, _)| *id),
+        PatternTarget::EnemyTeam => {
+            return match enemies.first() {
+                Some((id, _)) => Target::FullTeam {
+                    team_id: id.team_id,
+                },
+                None => Target::None,
+            };
+        }
+    };
+
+    match chosen {
+        Some(id) => Target::Single(id),
+        None => Target::None,
+    }
+}
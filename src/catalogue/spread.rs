@@ -0,0 +1,38 @@
+//! Pre-made [`Spread`] rules for area actions.
+
+use crate::spread::Spread;
+
+/// Reduces the damage by a fixed amount for every step away from the epicenter.
+pub struct LinearFalloff {
+    pub step: u64,
+}
+
+impl Spread for LinearFalloff {
+    fn spread(&self, damage: u64, distance: usize) -> u64 {
+        let reduction = self.step.saturating_mul(distance as u64);
+
+        damage.saturating_sub(reduction)
+    }
+}
+
+/// Halves the damage for every step away from the epicenter.
+pub struct HalvingFalloff;
+
+impl Spread for HalvingFalloff {
+    fn spread(&self, damage: u64, distance: usize) -> u64 {
+        damage.checked_shr(distance as u32).unwrap_or(0)
+    }
+}
+
+/// Only members right next to the epicenter are affected, with the full damage.
+pub struct Adjacent;
+
+impl Spread for Adjacent {
+    fn spread(&self, damage: u64, distance: usize) -> u64 {
+        if distance <= 1 {
+            damage
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Snapshots of a running [`Battle`](crate::battle::Battle), used to undo turns.
+//!
+//! A [`Checkpoint`] is captured with [`Battle::checkpoint()`](crate::battle::Battle::checkpoint) and restored with
+//! [`Battle::rollback()`](crate::battle::Battle::rollback), bringing back the teams, the turn counter, the battle's
+//! source of randomness, the event log and every pending effect (like queued, scheduled and channeled actions,
+//! controls, cooldowns, attacks of opportunity, and waves or reinforcements yet to join). This lets puzzle battles undo
+//! moves, and AI search explore a turn and come back.
+//!
+//! # Notes
+//!
+//! Rollback is opt-in: members must implement [`Clone`], and every pending action must be copyable through
+//! [`Action::duplicate()`](crate::action::Action::duplicate).
+//!
+//! Only the data changing while the battle is played is captured. Configuration (like hooks, reactions and the
+//! damage pipeline) is kept as it is, and so is the internal state of controllers, hooks and observers, which are
+//! not notified of the rollback.
+
+use crate::action::ChoiceReturn;
+use crate::battle::{State, TurnCheckpoint};
+use crate::error::BattleError;
+use crate::state::StateCheckpoint;
+use crate::team::Team;
+
+/// Snapshot of a [`Battle`](crate::battle::Battle), restored with [`Battle::rollback()`](crate::battle::Battle::rollback).
+///
+/// # Notes
+///
+/// A checkpoint should only be restored into the same battle it was captured from.
+pub struct Checkpoint<M> {
+    pub(crate) data: StateCheckpoint<M>,
+    pub(crate) turns: TurnCheckpoint<M>,
+    pub(crate) state: State,
+    pub(crate) drained_events: usize,
+}
+
+impl<M> Checkpoint<M> {
+    /// Returns the number of the last turn played before the checkpoint was captured.
+    pub fn turn_number(&self) -> u64 {
+        self.turns.turn_number
+    }
+
+    /// Returns the teams as they were when the checkpoint was captured.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`BattleDiff`](crate::diff::BattleDiff) to compare them with the current ones.
+    pub fn teams(&self) -> &[Team<M>] {
+        &self.data.team_list
+    }
+
+    /// Returns the amount of events recorded before the checkpoint was captured.
+    pub fn events(&self) -> usize {
+        self.data.events.len()
+    }
+}
+
+/// Copy a pending choice, to capture it in a [`Checkpoint`].
+///
+/// # Errors
+///
+/// Returns [`BattleError::UncopyableAction`] if the chosen action can't be copied.
+pub(crate) fn duplicate<M>(choice: &ChoiceReturn<M>) -> Result<ChoiceReturn<M>, BattleError> {
+    let (action, performers, targets) = choice;
+
+    match action.duplicate() {
+        Some(copy) => Ok((copy, performers.clone(), targets.clone())),
+        None => Err(BattleError::UncopyableAction(action.action_id())),
+    }
+}
@@ -0,0 +1,164 @@
+//! Commands submitted to a [`Battle`](crate::battle::Battle) and their validation.
+//!
+//! In server-authoritative setups, clients only submit [`BattleCommand`]s. The server can then ask the battle for a
+//! [`ValidationReport`], listing every rule checked against the command, to log and reject tampered submissions.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::action::{Action, ChoiceReturn, Context, Target};
+use crate::error::ActionError;
+use crate::member::{Member, MemberIdentifier};
+use crate::state::BattleState;
+
+/// Action submitted for a turn, together with its performers and targets.
+pub struct BattleCommand<M> {
+    pub action: Box<dyn Action<M>>,
+    pub performers: Target,
+    pub targets: Target,
+}
+
+impl<M> BattleCommand<M> {
+    /// Create a new [`BattleCommand`].
+    pub fn new(action: Box<dyn Action<M>>, performers: Target, targets: Target) -> Self {
+        Self {
+            action,
+            performers,
+            targets,
+        }
+    }
+
+    /// Unwrap the command into the choice format used by the [`TurnSystem`](crate::battle::TurnSystem).
+    pub fn into_choice(self) -> ChoiceReturn<M> {
+        (self.action, self.performers, self.targets)
+    }
+}
+
+impl<M> From<ChoiceReturn<M>> for BattleCommand<M> {
+    fn from((action, performers, targets): ChoiceReturn<M>) -> Self {
+        Self::new(action, performers, targets)
+    }
+}
+
+/// Rule checked while validating a [`BattleCommand`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    /// The command is submitted while the battle is running, by the member whose turn it is.
+    Timing,
+    /// All performers and targets exist.
+    Targeting,
+    /// The action accepts the chosen performers and targets (see [`Action::validate()`]).
+    Legality,
+    /// All performers can afford the action's costs.
+    Costs,
+    /// No performer is waiting for the action's cooldown.
+    Cooldown,
+    /// All targets are in sight, if the action requires it (see [`Action::requires_line_of_sight()`]).
+    LineOfSight,
+}
+
+/// Outcome of a single [`Rule`] check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleCheck {
+    pub rule: Rule,
+    pub result: Result<(), ActionError>,
+}
+
+/// List of every rule checked against a [`BattleCommand`], in order.
+///
+/// # Notes
+///
+/// All rules are checked, even after one of them fails, so that the report can describe every problem at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    checks: Vec<RuleCheck>,
+}
+
+impl ValidationReport {
+    /// Returns every check performed, in order.
+    pub fn checks(&self) -> &[RuleCheck] {
+        &self.checks
+    }
+
+    /// Returns whether every check passed.
+    pub fn is_valid(&self) -> bool {
+        self.checks.iter().all(|c| c.result.is_ok())
+    }
+
+    /// Returns an iterator over the failed checks and their errors.
+    pub fn failures(&self) -> impl Iterator<Item = (Rule, &ActionError)> {
+        self.checks
+            .iter()
+            .filter_map(|c| c.result.as_ref().err().map(|e| (c.rule, e)))
+    }
+
+    /// Convert the report into the error of its first failed check, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`ActionError`] of the first failed check.
+    pub fn into_result(self) -> Result<(), ActionError> {
+        self.checks
+            .into_iter()
+            .map(|c| c.result)
+            .find(Result::is_err)
+            .unwrap_or(Ok(()))
+    }
+}
+
+/// Check every [`Rule`] against a command.
+///
+/// `suggested_performer` is the member whose turn it is, if any.
+pub(crate) fn validate<M: Member>(
+    data: &mut BattleState<M>,
+    command: &BattleCommand<M>,
+    suggested_performer: Option<MemberIdentifier>,
+    finished: bool,
+) -> ValidationReport {
+    let context = Context::new(data, command.performers.clone(), command.targets.clone());
+    let action = &command.action;
+
+    let timing = if finished {
+        Err(ActionError::Rejected(String::from(
+            "the battle has already finished",
+        )))
+    } else {
+        match suggested_performer {
+            Some(expected) if !context.performer_ids().contains(&expected) => {
+                Err(ActionError::OutOfTurn { expected })
+            }
+            _ => Ok(()),
+        }
+    };
+
+    let checks = vec![
+        RuleCheck {
+            rule: Rule::Timing,
+            result: timing,
+        },
+        RuleCheck {
+            rule: Rule::Targeting,
+            result: context.validate_members(),
+        },
+        RuleCheck {
+            rule: Rule::Legality,
+            result: action.validate(&context),
+        },
+        RuleCheck {
+            rule: Rule::Costs,
+            result: context.check_costs(&action.cost()),
+        },
+        RuleCheck {
+            rule: Rule::Cooldown,
+            result: context.check_cooldown(&action.action_id()),
+        },
+        RuleCheck {
+            rule: Rule::LineOfSight,
+            result: context.check_line_of_sight(&**action),
+        },
+    ];
+
+    ValidationReport { checks }
+}
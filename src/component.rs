@@ -0,0 +1,262 @@
+//! Battle-scoped storage of additional data attached to [`Member`](crate::member::Member)s.
+//!
+//! The [`ComponentStore`] lives inside a [`Battle`](crate::battle::Battle) and is accessible to actions through
+//! their [`Context`](crate::action::Context). It holds information that shouldn't be part of a member's own
+//! [`Properties`](crate::member::Properties), like marks left by previous actions or action cooldowns.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+
+use crate::action::ActionId;
+use crate::member::MemberIdentifier;
+use crate::stage::{Stat, StatStages};
+use crate::summon::Summoning;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Named mark that can be attached to a member and later consumed by other actions.
+///
+/// # Notes
+///
+/// Tags are the building blocks of action chains, like applying "Wet" on a target and then detonating it with "Shock".
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tag(String);
+
+impl Tag {
+    /// Create a new [`Tag`] with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Returns this tag's name.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Storage of per-member components for the duration of a battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ComponentStore {
+    /// Stack count of every tag applied to each member.
+    tags: BTreeMap<MemberIdentifier, BTreeMap<Tag, u32>>,
+    /// Remaining cooldown turns of every action used by each member.
+    cooldowns: BTreeMap<MemberIdentifier, BTreeMap<ActionId, u32>>,
+    /// Summoning record of every summoned member.
+    #[cfg_attr(feature = "serde", serde(default))]
+    summons: BTreeMap<MemberIdentifier, Summoning>,
+    /// Stat stages of every buffed or debuffed member.
+    #[cfg_attr(feature = "serde", serde(default))]
+    stages: BTreeMap<MemberIdentifier, StatStages>,
+    /// Actions every member has been seen performing.
+    #[cfg_attr(feature = "serde", serde(default))]
+    revealed: BTreeMap<MemberIdentifier, BTreeSet<ActionId>>,
+}
+
+impl ComponentStore {
+    /// Create a new, empty [`ComponentStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one stack of a [`Tag`] to a member.
+    pub fn apply_tag(&mut self, member: MemberIdentifier, tag: Tag) {
+        let stacks = self.tags.entry(member).or_default().entry(tag).or_insert(0);
+        *stacks = stacks.saturating_add(1);
+    }
+
+    /// Returns whether the member currently holds at least one stack of a [`Tag`].
+    pub fn has_tag(&self, member: MemberIdentifier, tag: &Tag) -> bool {
+        self.tag_stacks(member, tag) > 0
+    }
+
+    /// Returns the amount of stacks of a [`Tag`] currently held by a member.
+    pub fn tag_stacks(&self, member: MemberIdentifier, tag: &Tag) -> u32 {
+        self.tags
+            .get(&member)
+            .and_then(|t| t.get(tag))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Consume one stack of a [`Tag`] from a member.
+    ///
+    /// Returns `true` if a stack was found and consumed, `false` otherwise.
+    pub fn consume_tag(&mut self, member: MemberIdentifier, tag: &Tag) -> bool {
+        let Some(member_tags) = self.tags.get_mut(&member) else {
+            return false;
+        };
+
+        let consumed = match member_tags.get_mut(tag) {
+            Some(stacks) if *stacks > 1 => {
+                *stacks -= 1;
+                true
+            }
+            Some(_) => {
+                member_tags.remove(tag);
+                true
+            }
+            None => false,
+        };
+
+        if member_tags.is_empty() {
+            self.tags.remove(&member);
+        }
+
+        consumed
+    }
+
+    /// Returns an iterator over all tags (and their stack count) held by a member.
+    pub fn tags(&self, member: MemberIdentifier) -> impl Iterator<Item = (&Tag, u32)> {
+        self.tags
+            .get(&member)
+            .into_iter()
+            .flat_map(|t| t.iter().map(|(tag, stacks)| (tag, *stacks)))
+    }
+
+    /// Returns the amount of turns a member must wait before performing an action again.
+    pub fn cooldown(&self, member: MemberIdentifier, action: &ActionId) -> u32 {
+        self.cooldowns
+            .get(&member)
+            .and_then(|c| c.get(action))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns whether a member is free to perform an action (it isn't waiting for its cooldown).
+    pub fn is_available(&self, member: MemberIdentifier, action: &ActionId) -> bool {
+        self.cooldown(member, action) == 0
+    }
+
+    /// Returns an iterator over all actions a member is waiting to perform again, and their remaining turns.
+    pub fn cooldowns(&self, member: MemberIdentifier) -> impl Iterator<Item = (&ActionId, u32)> {
+        self.cooldowns
+            .get(&member)
+            .into_iter()
+            .flat_map(|c| c.iter().map(|(action, turns)| (action, *turns)))
+    }
+
+    /// Make a member wait a certain amount of turns before performing an action again.
+    ///
+    /// # Notes
+    ///
+    /// If the action is already on cooldown, the longest of the two cooldowns is kept.
+    pub fn start_cooldown(&mut self, member: MemberIdentifier, action: ActionId, turns: u32) {
+        if turns == 0 {
+            return;
+        }
+
+        let remaining = self
+            .cooldowns
+            .entry(member)
+            .or_default()
+            .entry(action)
+            .or_insert(0);
+        *remaining = (*remaining).max(turns);
+    }
+
+    /// Decrement every cooldown by one turn, removing the expired ones.
+    pub fn tick_cooldowns(&mut self) {
+        for member_cooldowns in self.cooldowns.values_mut() {
+            member_cooldowns.retain(|_, turns| {
+                *turns -= 1;
+                *turns > 0
+            });
+        }
+
+        self.cooldowns.retain(|_, c| !c.is_empty());
+    }
+
+    /// Returns the stat stages of a member, or `None` if none of its stats was ever changed.
+    pub fn stages(&self, member: MemberIdentifier) -> Option<&StatStages> {
+        self.stages.get(&member)
+    }
+
+    /// Raise (or lower, if negative) a stat of a member by some stages, returning the change actually applied.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`StatStages::change()`] for more information.
+    pub fn change_stage(&mut self, member: MemberIdentifier, stat: Stat, amount: i8) -> i8 {
+        let stages = self.stages.entry(member).or_default();
+        let change = stages.change(stat, amount);
+
+        if stages.is_neutral() {
+            self.stages.remove(&member);
+        }
+
+        change
+    }
+
+    /// Record an action as performed by a member, revealing it to the other teams.
+    pub fn reveal_action(&mut self, member: MemberIdentifier, action: ActionId) {
+        self.revealed.entry(member).or_default().insert(action);
+    }
+
+    /// Returns an iterator over all actions a member has been seen performing, ordered by ID.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`FogOfWar`](crate::visibility::FogOfWar) for more information.
+    pub fn revealed_actions(&self, member: MemberIdentifier) -> impl Iterator<Item = &ActionId> {
+        self.revealed.get(&member).into_iter().flatten()
+    }
+
+    /// Remove every component of a member removed from the battle, shifting the IDs of the following members.
+    pub(crate) fn remove_member(&mut self, member: MemberIdentifier) {
+        fn shift<V>(map: &mut BTreeMap<MemberIdentifier, V>, removed: MemberIdentifier) {
+            *map = core::mem::take(map)
+                .into_iter()
+                .filter_map(|(id, value)| id.after_removal(removed).map(|id| (id, value)))
+                .collect();
+        }
+
+        shift(&mut self.tags, member);
+        shift(&mut self.cooldowns, member);
+        shift(&mut self.summons, member);
+        shift(&mut self.stages, member);
+        shift(&mut self.revealed, member);
+
+        for summoning in self.summons.values_mut() {
+            summoning.summoner = summoning.summoner.and_then(|s| s.after_removal(member));
+        }
+    }
+
+    /// Record a member as summoned.
+    pub fn add_summon(&mut self, member: MemberIdentifier, summoning: Summoning) {
+        self.summons.insert(member, summoning);
+    }
+
+    /// Returns the summoning record of a member, if it was summoned.
+    pub fn summoning(&self, member: MemberIdentifier) -> Option<&Summoning> {
+        self.summons.get(&member)
+    }
+
+    /// Returns an iterator over all summoned members still recorded, and their summoning record.
+    pub fn summons(&self) -> impl Iterator<Item = (MemberIdentifier, &Summoning)> {
+        self.summons
+            .iter()
+            .map(|(member, summoning)| (*member, summoning))
+    }
+
+    /// Forget the summoning record of a member, returning whether it was summoned.
+    pub fn remove_summon(&mut self, member: MemberIdentifier) -> bool {
+        self.summons.remove(&member).is_some()
+    }
+
+    /// Count one more turn spent in the battle by every summoned member.
+    pub fn tick_summons(&mut self) {
+        self.summons.values_mut().for_each(Summoning::tick);
+    }
+
+    /// Remove every component associated with a member.
+    pub fn clear_member(&mut self, member: MemberIdentifier) {
+        self.tags.remove(&member);
+        self.cooldowns.remove(&member);
+        self.summons.remove(&member);
+        self.stages.remove(&member);
+        self.revealed.remove(&member);
+    }
+}
@@ -0,0 +1,163 @@
+//! Control effects (like stuns or pushes) and the resistance of members against them.
+//!
+//! Every control effect goes through [`Context::apply_control()`](crate::action::Context::apply_control),
+//! which lets the target resist it with its [`Properties::control_resistance()`](crate::member::Properties::control_resistance)
+//! and shortens it with diminishing returns. The [`ControlTracker`] of a battle is fed by the resulting events,
+//! and keeps track of how many times each member has been controlled and of the lasting effects.
+
+use alloc::collections::BTreeMap;
+
+use crate::event::Event;
+use crate::member::MemberIdentifier;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kind of effect taking control away from a member.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Control {
+    /// The member can't act for some turns. Its magnitude is the amount of turns.
+    Stun,
+    /// The member is moved across its formation, like with a push. Its magnitude is the amount of steps.
+    ForcedMovement,
+    /// The member's team is forced to focus on someone. Its magnitude is the amount of threat generated.
+    Taunt,
+    /// The member fights on the side of someone else for some turns. Its magnitude is the amount of turns.
+    Charm,
+    /// Any other game-specific control effect, identified by a custom index.
+    Custom(u16),
+}
+
+impl Control {
+    /// Returns whether the effect lasts some turns after being applied, instead of being resolved immediately.
+    pub fn is_lasting(&self) -> bool {
+        matches!(self, Self::Stun | Self::Charm)
+    }
+}
+
+/// Returns the magnitude of a control effect after diminishing returns.
+///
+/// # Notes
+///
+/// The magnitude is halved (rounding up) for each time the same kind of control has already been applied to the member
+/// during the battle, so a 4 turns stun lasts 4, 2 and then 1 turn. Magnitudes of 0 stay 0.
+pub fn diminish(magnitude: u64, applications: u32) -> u64 {
+    if magnitude == 0 {
+        return 0;
+    }
+
+    let divisor = 1u64.checked_shl(applications).unwrap_or(0);
+
+    if divisor == 0 {
+        1
+    } else {
+        magnitude.div_ceil(divisor)
+    }
+}
+
+/// Control effects applied to every member during a battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ControlTracker {
+    /// Amount of times each kind of control has been applied to each member.
+    applications: BTreeMap<MemberIdentifier, BTreeMap<Control, u32>>,
+    /// Remaining turns of the lasting effects of each member.
+    active: BTreeMap<MemberIdentifier, BTreeMap<Control, u64>>,
+}
+
+impl ControlTracker {
+    /// Create a new, empty [`ControlTracker`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the amount of times a kind of control has been applied to a member during the battle.
+    ///
+    /// # Notes
+    ///
+    /// Resisted effects don't count.
+    pub fn applications(&self, member: MemberIdentifier, control: Control) -> u32 {
+        self.applications
+            .get(&member)
+            .and_then(|c| c.get(&control))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns whether a member is under a lasting control effect, like a stun.
+    pub fn is_controlled(&self, member: MemberIdentifier, control: Control) -> bool {
+        self.active
+            .get(&member)
+            .is_some_and(|c| c.contains_key(&control))
+    }
+
+    /// Returns the amount of turns a lasting control effect will keep affecting a member after the current one.
+    pub fn remaining(&self, member: MemberIdentifier, control: Control) -> u64 {
+        self.active
+            .get(&member)
+            .and_then(|c| c.get(&control))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Update the tracker with a newly applied event.
+    ///
+    /// # Notes
+    ///
+    /// Lasting effects cover the rest of the turn they are applied in, and as many following turns as their magnitude.
+    /// A new effect of the same kind replaces the remaining turns of the previous one if it lasts longer.
+    pub(crate) fn observe(&mut self, event: &Event) {
+        match event {
+            Event::ControlApplied {
+                member,
+                control,
+                magnitude,
+            } => {
+                let applications = self
+                    .applications
+                    .entry(*member)
+                    .or_default()
+                    .entry(*control)
+                    .or_default();
+                *applications = applications.saturating_add(1);
+
+                if control.is_lasting() && *magnitude > 0 {
+                    let remaining = self
+                        .active
+                        .entry(*member)
+                        .or_default()
+                        .entry(*control)
+                        .or_default();
+                    *remaining = (*remaining).max(*magnitude);
+                }
+            }
+            Event::TurnStarted { .. } => {
+                for controls in self.active.values_mut() {
+                    controls.retain(|_, remaining| *remaining > 0);
+
+                    for remaining in controls.values_mut() {
+                        *remaining -= 1;
+                    }
+                }
+
+                self.active.retain(|_, controls| !controls.is_empty());
+            }
+            _ => (),
+        }
+    }
+
+    /// Forget the effects of a member removed from the battle, shifting the following members of its team.
+    pub(crate) fn remove_member(&mut self, removed: MemberIdentifier) {
+        self.applications = core::mem::take(&mut self.applications)
+            .into_iter()
+            .filter_map(|(id, c)| Some((id.after_removal(removed)?, c)))
+            .collect();
+
+        self.active = core::mem::take(&mut self.active)
+            .into_iter()
+            .filter_map(|(id, c)| Some((id.after_removal(removed)?, c)))
+            .collect();
+    }
+}
@@ -0,0 +1,380 @@
+//! Controllers deciding the actions performed during a [`Battle`](crate::battle::Battle).
+//!
+//! Every source of choices, be it player input, an AI or a scripted sequence, implements [`Controller`].
+//! Closures with the signature of a [`ChoiceCallback`](crate::action::ChoiceCallback) are controllers too,
+//! while other sources can be plugged in through the adapters of this module:
+//! [`ChannelController`] receives choices from another thread, [`ScriptedController`] plays a fixed sequence of choices
+//! and [`HotSeatController`] lets players of different teams share the same device.
+//! AI controllers are adapted by [`Automated`](crate::catalogue::ai::Automated).
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, VecDeque};
+#[cfg(feature = "std")]
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::action::{ActionId, ChoiceReturn, Target};
+use crate::aura::TeamAura;
+use crate::component::ComponentStore;
+use crate::member::{Member, MemberIdentifier};
+#[cfg(feature = "std")]
+use crate::registry::ActionRegistry;
+use crate::rng::BattleRng;
+use crate::state;
+use crate::team::Team;
+
+/// Source of the actions performed during a battle.
+pub trait Controller<M> {
+    /// Choose the next action to perform, together with its performers and targets.
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M>;
+}
+
+impl<M, F> Controller<M> for F
+where
+    F: FnMut(&[Team<M>], Option<MemberIdentifier>, &mut BattleRng) -> ChoiceReturn<M>,
+{
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        self(prompt.teams, prompt.performer, prompt.rng)
+    }
+}
+
+/// Everything a [`Controller`] can look at to make its choice.
+pub struct ChoicePrompt<'a, M> {
+    teams: &'a [Team<M>],
+    auras: &'a [(usize, TeamAura<M>)],
+    components: &'a ComponentStore,
+    performer: Option<MemberIdentifier>,
+    rng: &'a mut BattleRng,
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+}
+
+impl<'a, M> ChoicePrompt<'a, M> {
+    pub(crate) fn new(
+        teams: &'a [Team<M>],
+        auras: &'a [(usize, TeamAura<M>)],
+        components: &'a ComponentStore,
+        performer: Option<MemberIdentifier>,
+        rng: &'a mut BattleRng,
+    ) -> Self {
+        Self {
+            teams,
+            auras,
+            components,
+            performer,
+            rng,
+            #[cfg(feature = "std")]
+            deadline: None,
+        }
+    }
+
+    /// Set the time by which the choice must be made.
+    #[cfg(feature = "std")]
+    pub(crate) fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+
+        self
+    }
+
+    /// Returns a reference to the battling teams.
+    ///
+    /// # Notes
+    ///
+    /// If the battle has a [`FogOfWar`](crate::visibility::FogOfWar), the teams are shown as seen by the acting team.
+    pub fn teams(&self) -> &'a [Team<M>] {
+        self.teams
+    }
+
+    /// Returns an iterator over all actions a member has been seen performing.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`ComponentStore::revealed_actions()`] for more information.
+    pub fn revealed_actions(&self, member: MemberIdentifier) -> impl Iterator<Item = &ActionId> {
+        self.components.revealed_actions(member)
+    }
+
+    /// Returns the member suggested to perform the next action, if any.
+    pub fn performer(&self) -> Option<MemberIdentifier> {
+        self.performer
+    }
+
+    /// Returns the ID of the team acting during this choice, which is the suggested performer's one.
+    pub fn team_id(&self) -> Option<usize> {
+        self.performer.map(|p| p.team_id)
+    }
+
+    /// Returns the time by which the choice must be made, if any.
+    ///
+    /// # Notes
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// Choices made after the deadline are discarded by the battle.
+    /// Look at [`Builder::set_choice_timeout()`](crate::battle::Builder::set_choice_timeout) for more information.
+    #[cfg(feature = "std")]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Returns the battle's source of randomness.
+    ///
+    /// # Notes
+    ///
+    /// Any random decision should be made using this generator to keep the battle reproducible.
+    pub fn rng(&mut self) -> &mut BattleRng {
+        self.rng
+    }
+
+    /// Hand the prompt over to another controller.
+    pub fn delegate(self, controller: &mut dyn Controller<M>) -> ChoiceReturn<M> {
+        controller.choose(self)
+    }
+}
+
+impl<M: Member> ChoicePrompt<'_, M> {
+    /// Returns the final properties of a member, including the auras of its team and its stat stages.
+    ///
+    /// Returns `None` if the member doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// The properties are based on the teams returned by [`ChoicePrompt::teams()`].
+    /// Look at [`BattleState::final_properties()`](crate::state::BattleState::final_properties) for more information.
+    pub fn final_properties(&self, member: MemberIdentifier) -> Option<M::Properties> {
+        state::final_properties(self.teams, self.auras, self.components, member)
+    }
+}
+
+/// Request for a choice sent by a [`ChannelController`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChoiceRequest {
+    /// Member suggested to perform the next action, if any.
+    pub performer: Option<MemberIdentifier>,
+}
+
+/// Choice sent back to a [`ChannelController`], referring to its action by [`ActionId`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelChoice {
+    pub action: ActionId,
+    pub performers: Target,
+    pub targets: Target,
+}
+
+/// Controller waiting for choices made on another thread, like a UI or a network connection.
+///
+/// # Notes
+///
+/// Only available with the `std` feature.
+///
+/// Every choice is requested by sending a [`ChoiceRequest`], then blocks until a [`ChannelChoice`] is received,
+/// or until the [deadline](ChoicePrompt::deadline) of the choice, if any. Choices received after their deadline
+/// are ignored. Actions are created from the registry. If the channel is disconnected, no choice is received in time
+/// or the action is not registered, the choice is made by the fallback controller instead.
+#[cfg(feature = "std")]
+pub struct ChannelController<M> {
+    registry: ActionRegistry<M>,
+    requests: Sender<ChoiceRequest>,
+    choices: Receiver<ChannelChoice>,
+    fallback: Box<dyn Controller<M>>,
+    /// Requests whose deadline passed before their choice was received.
+    unanswered: usize,
+}
+
+#[cfg(feature = "std")]
+impl<M> ChannelController<M> {
+    /// Create a new [`ChannelController`] resolving received actions against the registry.
+    pub fn new(
+        registry: ActionRegistry<M>,
+        requests: Sender<ChoiceRequest>,
+        choices: Receiver<ChannelChoice>,
+        fallback: impl Controller<M> + 'static,
+    ) -> Self {
+        Self {
+            registry,
+            requests,
+            choices,
+            fallback: Box::new(fallback),
+            unanswered: 0,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<M> Controller<M> for ChannelController<M> {
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        let request = ChoiceRequest {
+            performer: prompt.performer(),
+        };
+
+        // Choices answering previous requests after their deadline are stale.
+        while self.unanswered > 0 && self.choices.try_recv().is_ok() {
+            self.unanswered -= 1;
+        }
+
+        if self.requests.send(request).is_err() {
+            log::warn!("The choice channel was disconnected, using the fallback controller");
+
+            return prompt.delegate(self.fallback.as_mut());
+        }
+
+        let received = match prompt.deadline() {
+            Some(deadline) => self
+                .choices
+                .recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => self
+                .choices
+                .recv()
+                .map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        let choice = match received {
+            Ok(choice) => choice,
+            Err(RecvTimeoutError::Timeout) => {
+                self.unanswered += 1;
+
+                log::warn!("No choice was received in time, using the fallback controller");
+
+                return prompt.delegate(self.fallback.as_mut());
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                log::warn!("The choice channel was disconnected, using the fallback controller");
+
+                return prompt.delegate(self.fallback.as_mut());
+            }
+        };
+
+        match self.registry.create(&choice.action) {
+            Some(action) => (action, choice.performers, choice.targets),
+            None => {
+                log::warn!(
+                    "Received unknown action {:?}, using the fallback controller",
+                    choice.action
+                );
+
+                prompt.delegate(self.fallback.as_mut())
+            }
+        }
+    }
+}
+
+/// Controller playing a fixed sequence of choices, like in tutorials or cutscenes.
+///
+/// # Notes
+///
+/// Choices are played in the same order they are added, whoever the suggested performer is.
+/// Once the sequence is over, every choice is made by the fallback controller.
+pub struct ScriptedController<M> {
+    script: VecDeque<ChoiceReturn<M>>,
+    fallback: Box<dyn Controller<M>>,
+}
+
+impl<M> ScriptedController<M> {
+    /// Create a new [`ScriptedController`] with an empty sequence.
+    pub fn new(fallback: impl Controller<M> + 'static) -> Self {
+        Self {
+            script: VecDeque::new(),
+            fallback: Box::new(fallback),
+        }
+    }
+
+    /// Add a choice at the end of the sequence.
+    pub fn with_choice(mut self, choice: ChoiceReturn<M>) -> Self {
+        self.script.push_back(choice);
+
+        self
+    }
+
+    /// Returns the amount of choices left in the sequence.
+    pub fn remaining(&self) -> usize {
+        self.script.len()
+    }
+}
+
+impl<M> Controller<M> for ScriptedController<M> {
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        match self.script.pop_front() {
+            Some(choice) => choice,
+            None => prompt.delegate(self.fallback.as_mut()),
+        }
+    }
+}
+
+/// Function run before the device is handed over to the player of another team.
+///
+/// # Notes
+///
+/// The gate receives the ID of the team about to choose, together with the battling teams.
+/// It should block until the player confirms to have taken the device, like by waiting for a key press.
+pub type HandOverGate<M> = Box<dyn FnMut(usize, &[Team<M>])>;
+
+/// Controller letting the players of multiple teams take turns on the same device.
+///
+/// # Notes
+///
+/// Every team with a seat is controlled by its own controller, while the other teams (like AI enemies)
+/// are controlled by the fallback. Whenever a seated team acts after another seated team, the [`HandOverGate`] is run first,
+/// so that players don't see each other's choices.
+pub struct HotSeatController<M> {
+    seats: BTreeMap<usize, Box<dyn Controller<M>>>,
+    fallback: Box<dyn Controller<M>>,
+    gate: Option<HandOverGate<M>>,
+    /// Seated team that made the last choice.
+    current: Option<usize>,
+}
+
+impl<M> HotSeatController<M> {
+    /// Create a new [`HotSeatController`] without seats.
+    pub fn new(fallback: impl Controller<M> + 'static) -> Self {
+        Self {
+            seats: BTreeMap::new(),
+            fallback: Box::new(fallback),
+            gate: None,
+            current: None,
+        }
+    }
+
+    /// Give a team its own seat, controlled by the given controller.
+    pub fn with_seat(mut self, team_id: usize, controller: impl Controller<M> + 'static) -> Self {
+        self.seats.insert(team_id, Box::new(controller));
+
+        self
+    }
+
+    /// Set the gate run before the device is handed over to another seated team.
+    pub fn with_gate(mut self, gate: HandOverGate<M>) -> Self {
+        self.gate = Some(gate);
+
+        self
+    }
+
+    /// Returns the ID of the seated team that made the last choice, if any.
+    pub fn current_team(&self) -> Option<usize> {
+        self.current
+    }
+}
+
+impl<M> Controller<M> for HotSeatController<M> {
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        let seat = prompt
+            .team_id()
+            .and_then(|team_id| Some((team_id, self.seats.get_mut(&team_id)?)));
+
+        let Some((team_id, controller)) = seat else {
+            return prompt.delegate(self.fallback.as_mut());
+        };
+
+        if self.current != Some(team_id) {
+            log::info!("Handing the device over to team {}", team_id);
+
+            if let Some(gate) = &mut self.gate {
+                gate(team_id, prompt.teams());
+            }
+
+            self.current = Some(team_id);
+        }
+
+        prompt.delegate(controller.as_mut())
+    }
+}
@@ -0,0 +1,262 @@
+//! Calculation of the damage inflicted by actions.
+//!
+//! The base damage of an action flows through the ordered [`Stage`]s of the battle's [`Pipeline`]
+//! (like defense, resistances, critical hits and clamping) before being applied to the target.
+//! Actions can route their damage through the pipeline using [`Context::deal_damage()`](crate::action::Context::deal_damage).
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::element::Element;
+use crate::member::MemberIdentifier;
+use crate::rng::BattleRng;
+
+/// Damage being calculated for a single target.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Damage {
+    /// Member receiving the damage.
+    pub target: MemberIdentifier,
+    /// Damage before any stage was applied.
+    pub base: u64,
+    /// Current amount of damage.
+    pub amount: u64,
+    /// Whether the damage was marked as a critical hit.
+    pub critical: bool,
+    /// Element of the damage, if any.
+    pub element: Option<Element>,
+    /// Chance of hitting the target, as a percentage, before the target's evasion is considered.
+    pub accuracy: u64,
+    /// Whether the target evaded the damage.
+    pub missed: bool,
+    /// Final defense of the target, including the auras of its team and its stat stages.
+    ///
+    /// The battle fills it in right before running the pipeline.
+    pub defense: u64,
+    /// Final evasion of the target, including the auras of its team and its stat stages.
+    ///
+    /// The battle fills it in right before running the pipeline.
+    pub evasion: u64,
+}
+
+impl Damage {
+    /// Create a new [`Damage`] calculation starting from a base amount.
+    pub fn new(target: MemberIdentifier, base: u64) -> Self {
+        Self {
+            target,
+            base,
+            amount: base,
+            critical: false,
+            element: None,
+            accuracy: 100,
+            missed: false,
+            defense: 0,
+            evasion: 0,
+        }
+    }
+
+    /// Set the accuracy of the damage, as a percentage.
+    pub fn with_accuracy(mut self, accuracy: u64) -> Self {
+        self.accuracy = accuracy;
+
+        self
+    }
+
+    /// Set the [`Element`] of the damage.
+    pub fn with_element(mut self, element: Option<Element>) -> Self {
+        self.element = element;
+
+        self
+    }
+}
+
+/// Single step of a damage [`Pipeline`].
+///
+/// # Notes
+///
+/// Any closure with a matching signature can be used as a stage.
+///
+/// The target is given with the auras of its team summed into its properties, but without its stat stages.
+/// The final values considering both (like [`Damage::defense`]) are stored in the [`Damage`] itself.
+pub trait Stage<M> {
+    /// Modify the damage inflicted to the target.
+    fn process(&self, damage: &mut Damage, target: &M, rng: &mut BattleRng);
+}
+
+impl<M, F: Fn(&mut Damage, &M, &mut BattleRng)> Stage<M> for F {
+    fn process(&self, damage: &mut Damage, target: &M, rng: &mut BattleRng) {
+        self(damage, target, rng)
+    }
+}
+
+/// Ordered list of [`Stage`]s every damage calculation goes through.
+///
+/// # Notes
+///
+/// An empty pipeline inflicts the base damage as is.
+/// Battles start with a pipeline only made of [`Mitigation`].
+pub struct Pipeline<M> {
+    stages: Vec<Box<dyn Stage<M>>>,
+}
+
+impl<M> Pipeline<M> {
+    /// Create a new, empty [`Pipeline`].
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    /// Add a stage at the end of the pipeline.
+    pub fn with_stage(mut self, stage: impl Stage<M> + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+
+        self
+    }
+
+    /// Returns the amount of stages in the pipeline.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns whether the pipeline has no stages.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Run a damage calculation through every stage, in order.
+    pub fn calculate(&self, mut damage: Damage, target: &M, rng: &mut BattleRng) -> Damage {
+        for stage in &self.stages {
+            stage.process(&mut damage, target, rng);
+        }
+
+        damage
+    }
+}
+
+impl<M> Default for Pipeline<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Stage checking whether the damage hits the target, based on its accuracy and the target's evasion.
+///
+/// # Notes
+///
+/// The chance of hitting is the damage's accuracy minus the target's final [evasion](Damage::evasion), as a percentage.
+/// Missed damage is reduced to 0 and can't become a critical hit.
+/// This stage should usually be the first of the pipeline.
+pub struct HitCheck;
+
+impl<M> Stage<M> for HitCheck {
+    fn process(&self, damage: &mut Damage, _target: &M, rng: &mut BattleRng) {
+        if damage.missed {
+            return;
+        }
+
+        let chance = damage.accuracy.saturating_sub(damage.evasion).min(100);
+
+        if !rng.gen_ratio(chance, 100) {
+            damage.missed = true;
+            damage.critical = false;
+            damage.amount = 0;
+        }
+    }
+}
+
+/// Stage subtracting the target's final [defense](Damage::defense) from the damage.
+///
+/// # Notes
+///
+/// This is the only stage of the battle's default pipeline. Custom pipelines should include it,
+/// unless they mitigate damage in some other way (for example with [`Defense`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mitigation;
+
+impl<M> Stage<M> for Mitigation {
+    fn process(&self, damage: &mut Damage, _target: &M, _rng: &mut BattleRng) {
+        damage.amount = damage.amount.saturating_sub(damage.defense);
+    }
+}
+
+/// Stage subtracting a custom defense value from the damage.
+///
+/// # Notes
+///
+/// Use this stage in place of [`Mitigation`] for any custom defense calculation.
+pub struct Defense<F> {
+    /// Function returning the defense value of the target.
+    pub defense: F,
+}
+
+impl<M, F: Fn(&M) -> u64> Stage<M> for Defense<F> {
+    fn process(&self, damage: &mut Damage, target: &M, _rng: &mut BattleRng) {
+        damage.amount = damage.amount.saturating_sub((self.defense)(target));
+    }
+}
+
+/// Stage reducing the damage by a percentage depending on the target.
+///
+/// # Notes
+///
+/// Resistances over 100% are treated as immunities.
+pub struct Resistance<F> {
+    /// Function returning the resistance percentage of the target.
+    pub resistance: F,
+}
+
+impl<M, F: Fn(&M) -> u64> Stage<M> for Resistance<F> {
+    fn process(&self, damage: &mut Damage, target: &M, _rng: &mut BattleRng) {
+        let resistance = (self.resistance)(target).min(100);
+
+        damage.amount = damage.amount.saturating_mul(100 - resistance) / 100;
+    }
+}
+
+/// Stage randomly turning the damage into a critical hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Critical {
+    /// Chance of a critical hit, as a ratio of `chance.0` over `chance.1`.
+    pub chance: (u64, u64),
+    /// Percentage multiplier applied to critical hits (150 means +50% damage).
+    pub multiplier: u64,
+}
+
+impl Critical {
+    /// Roll for a critical hit, applying the multiplier to the damage on success.
+    ///
+    /// # Notes
+    ///
+    /// Damage that is already critical isn't rolled again, so that critical hits are never applied twice.
+    pub fn roll(&self, damage: &mut Damage, rng: &mut BattleRng) {
+        if damage.critical || damage.missed {
+            return;
+        }
+
+        if rng.gen_ratio(self.chance.0, self.chance.1) {
+            damage.critical = true;
+            damage.amount = damage.amount.saturating_mul(self.multiplier) / 100;
+        }
+    }
+}
+
+impl<M> Stage<M> for Critical {
+    fn process(&self, damage: &mut Damage, _target: &M, rng: &mut BattleRng) {
+        self.roll(damage, rng);
+    }
+}
+
+/// Stage keeping the damage within some bounds.
+///
+/// # Notes
+///
+/// The upper bound wins if the bounds overlap.
+pub struct Clamp {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl<M> Stage<M> for Clamp {
+    fn process(&self, damage: &mut Damage, _target: &M, _rng: &mut BattleRng) {
+        damage.amount = damage.amount.max(self.min).min(self.max);
+    }
+}
@@ -0,0 +1,121 @@
+//! Differences between two snapshots of the battling teams, like the ones taken before and after a turn.
+//!
+//! Networked clients can reconcile their copy of the battle with the server's one by diffing the teams they hold
+//! against the received ones, and only updating (or animating) the members that changed.
+
+use alloc::vec::Vec;
+
+use crate::member::{Member, MemberIdentifier};
+use crate::team::Team;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Changes of a single member between two snapshots.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberDiff<P> {
+    pub member: MemberIdentifier,
+    /// Health of the member before and after, if it changed.
+    pub health: Option<(u64, u64)>,
+    /// Properties of the member before and after, if any of them changed (health included).
+    pub properties: Option<(P, P)>,
+    /// Whether the member was alive before and is defeated after.
+    pub defeated: bool,
+    /// Whether the member was defeated before and is alive after.
+    pub revived: bool,
+}
+
+/// Differences between two snapshots of the same battling teams.
+///
+/// # Notes
+///
+/// Members are compared by [`MemberIdentifier`], so the snapshots should be taken without members leaving
+/// the battle in between, which would shift the identifiers of the following members of the same team.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BattleDiff<P> {
+    /// Members that changed between the snapshots, in team and member order.
+    pub members: Vec<MemberDiff<P>>,
+    /// Members only found in the second snapshot.
+    pub joined: Vec<MemberIdentifier>,
+    /// Members only found in the first snapshot.
+    pub left: Vec<MemberIdentifier>,
+}
+
+impl<P: Clone + PartialEq> BattleDiff<P> {
+    /// Compare two snapshots of the battling teams.
+    pub fn new<M>(before: &[Team<M>], after: &[Team<M>]) -> Self
+    where
+        M: Member<Properties = P>,
+    {
+        let mut diff = Self {
+            members: Vec::new(),
+            joined: Vec::new(),
+            left: Vec::new(),
+        };
+
+        for team_id in 0..before.len().max(after.len()) {
+            let before = before.get(team_id).map_or(&[][..], |t| t.member_list());
+            let after = after.get(team_id).map_or(&[][..], |t| t.member_list());
+
+            for member_id in 0..before.len().max(after.len()) {
+                let member = MemberIdentifier::new(team_id, member_id);
+
+                match (before.get(member_id), after.get(member_id)) {
+                    (Some(before), Some(after)) => {
+                        diff.members.extend(compare(member, before, after))
+                    }
+                    (None, Some(_)) => diff.joined.push(member),
+                    (Some(_), None) => diff.left.push(member),
+                    (None, None) => {}
+                }
+            }
+        }
+
+        diff
+    }
+
+    /// Returns whether nothing changed between the snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty() && self.joined.is_empty() && self.left.is_empty()
+    }
+
+    /// Returns the changes of a member, if it changed.
+    pub fn member(&self, member: MemberIdentifier) -> Option<&MemberDiff<P>> {
+        self.members.iter().find(|d| d.member == member)
+    }
+
+    /// Returns the members defeated between the snapshots.
+    pub fn defeated(&self) -> impl Iterator<Item = MemberIdentifier> + '_ {
+        self.members.iter().filter(|d| d.defeated).map(|d| d.member)
+    }
+
+    /// Returns the members revived between the snapshots.
+    pub fn revived(&self) -> impl Iterator<Item = MemberIdentifier> + '_ {
+        self.members.iter().filter(|d| d.revived).map(|d| d.member)
+    }
+}
+
+/// Compare two snapshots of the same member, returning `None` if nothing changed.
+fn compare<M: Member>(
+    member: MemberIdentifier,
+    before: &M,
+    after: &M,
+) -> Option<MemberDiff<M::Properties>> {
+    let (old_health, new_health) = (before.health(), after.health());
+    let (old_properties, new_properties) = (before.member_properties(), after.member_properties());
+
+    if old_health == new_health && old_properties == new_properties {
+        return None;
+    }
+
+    Some(MemberDiff {
+        member,
+        health: (old_health != new_health).then_some((old_health, new_health)),
+        properties: (old_properties != new_properties)
+            .then(|| (old_properties.clone(), new_properties.clone())),
+        defeated: old_health > 0 && new_health == 0,
+        revived: old_health == 0 && new_health > 0,
+    })
+}
@@ -0,0 +1,220 @@
+//! Optional pre-battle phase where teams alternately pick or ban entries from a shared pool.
+//!
+//! A [`Draft`] can be used to assemble the teams of a [`Battle`](crate::battle::Battle) (by drafting members)
+//! or any other list of options, like the actions each team is allowed to use. Every team is controlled by
+//! a [`DraftCallback`], which is prompted whenever it's the team's turn to pick or ban.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::DraftError;
+use crate::team::Team;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kind of choice a team has to make during its draft turn.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftStep {
+    /// Take an entry from the pool for the team.
+    Pick,
+    /// Remove an entry from the pool, so that no team can pick it.
+    Ban,
+}
+
+/// Information given to a [`DraftCallback`] when its team has to choose.
+#[derive(Debug)]
+pub struct DraftPrompt<'a, T> {
+    /// Team making the choice.
+    pub team_id: usize,
+    /// Kind of choice to make.
+    pub step: DraftStep,
+    /// Entries that can still be picked or banned.
+    pub available: &'a [T],
+    /// Entries picked so far by every team.
+    pub picks: &'a [Vec<T>],
+    /// Entries banned so far.
+    pub bans: &'a [T],
+    /// Number of the current attempt, starting from 1.
+    pub attempt: u32,
+}
+
+/// Function type used by a team to choose an entry, returning its index in [`DraftPrompt::available`].
+pub type DraftCallback<T> = Box<dyn FnMut(&DraftPrompt<'_, T>) -> usize>;
+
+/// Function type used to refuse a choice made by a team.
+///
+/// # Errors
+///
+/// Returns the reason why the entry cannot be chosen.
+pub type DraftValidator<T> = Box<dyn Fn(&DraftPrompt<'_, T>, &T) -> Result<(), String>>;
+
+/// Final result of a [`Draft`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DraftResult<T> {
+    /// Entries picked by every team, in order of choice.
+    pub picks: Vec<Vec<T>>,
+    /// Entries banned by all teams, in order of choice.
+    pub bans: Vec<T>,
+    /// Entries that nobody picked or banned.
+    pub leftover: Vec<T>,
+}
+
+impl<T> DraftResult<T> {
+    /// Build the battling teams out of the drafted members, using the given team names.
+    ///
+    /// # Notes
+    ///
+    /// Teams without a name in the list are called after their ID.
+    pub fn into_teams(self, names: &[&str]) -> Vec<Team<T>> {
+        self.picks
+            .into_iter()
+            .enumerate()
+            .map(|(team_id, members)| {
+                let name = names
+                    .get(team_id)
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| format!("Team {team_id}"));
+
+                Team::new(name, members)
+            })
+            .collect()
+    }
+}
+
+/// Manager of a pick/ban phase over a shared pool of entries.
+pub struct Draft<T> {
+    pool: Vec<T>,
+    turns: Vec<(usize, DraftStep)>,
+    controllers: Vec<DraftCallback<T>>,
+    validators: Vec<DraftValidator<T>>,
+    attempts: u32,
+}
+
+impl<T> Draft<T> {
+    /// Create a new [`Draft`] over a pool of entries, with one controller per team.
+    ///
+    /// # Notes
+    ///
+    /// By default, every controller has only one attempt to make a valid choice.
+    pub fn new(pool: Vec<T>, controllers: Vec<DraftCallback<T>>) -> Self {
+        Self {
+            pool,
+            turns: Vec::new(),
+            controllers,
+            validators: Vec::new(),
+            attempts: 1,
+        }
+    }
+
+    /// Add a single draft turn for a team.
+    pub fn with_turn(mut self, team_id: usize, step: DraftStep) -> Self {
+        self.turns.push((team_id, step));
+
+        self
+    }
+
+    /// Add some rounds in which every team, in order, makes the same kind of choice.
+    pub fn with_rounds(mut self, step: DraftStep, rounds: usize) -> Self {
+        for _ in 0..rounds {
+            for team_id in 0..self.controllers.len() {
+                self.turns.push((team_id, step));
+            }
+        }
+
+        self
+    }
+
+    /// Add a rule that every choice must respect.
+    pub fn with_validator(mut self, validator: DraftValidator<T>) -> Self {
+        self.validators.push(validator);
+
+        self
+    }
+
+    /// Set how many times a controller is prompted again after making an invalid choice.
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts.max(1);
+
+        self
+    }
+
+    /// Run every draft turn in order, returning the teams' picks.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DraftError`] if a turn references a team without controller, if the pool runs out
+    /// or if a controller fails to make a valid choice within the allowed attempts.
+    pub fn run(mut self) -> Result<DraftResult<T>, DraftError> {
+        let mut picks: Vec<Vec<T>> = self.controllers.iter().map(|_| Vec::new()).collect();
+        let mut bans = Vec::new();
+
+        for (team_id, step) in core::mem::take(&mut self.turns) {
+            let Some(controller) = self.controllers.get_mut(team_id) else {
+                return Err(DraftError::UnknownTeam { team_id });
+            };
+
+            if self.pool.is_empty() {
+                return Err(DraftError::EmptyPool);
+            }
+
+            let mut attempt = 0;
+
+            let index = loop {
+                attempt += 1;
+
+                let prompt = DraftPrompt {
+                    team_id,
+                    step,
+                    available: &self.pool,
+                    picks: &picks,
+                    bans: &bans,
+                    attempt,
+                };
+
+                let index = controller(&prompt);
+
+                let validation = match self.pool.get(index) {
+                    Some(entry) => self
+                        .validators
+                        .iter()
+                        .try_for_each(|v| v(&prompt, entry))
+                        .map_err(|reason| DraftError::Rejected { team_id, reason }),
+                    None => Err(DraftError::InvalidIndex { team_id, index }),
+                };
+
+                match validation {
+                    Ok(()) => break index,
+                    Err(e) if attempt < self.attempts => {
+                        log::warn!("Invalid draft choice: {}", e);
+                    }
+                    Err(e) => return Err(e),
+                }
+            };
+
+            let entry = self.pool.remove(index);
+
+            match step {
+                DraftStep::Pick => {
+                    log::info!("Team with id {} picked entry {}", team_id, index);
+
+                    picks[team_id].push(entry);
+                }
+                DraftStep::Ban => {
+                    log::info!("Team with id {} banned entry {}", team_id, index);
+
+                    bans.push(entry);
+                }
+            }
+        }
+
+        Ok(DraftResult {
+            picks,
+            bans,
+            leftover: self.pool,
+        })
+    }
+}
@@ -0,0 +1,123 @@
+//! Elemental types of actions and members, and their effectiveness against each other.
+//!
+//! [`Member`]s may hold any amount of [`Element`]s, while each [`Action`](crate::action::Action) deals damage of
+//! at most one element. How effective an element is against another is described by an [`EffectivenessChart`],
+//! which is consulted by the [`ElementalAffinity`] stage of the damage [`Pipeline`](crate::damage::Pipeline).
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use crate::damage::{Damage, Stage};
+use crate::member::Member;
+use crate::rng::BattleRng;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Named elemental type, like "Fire" or "Water".
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Element(String);
+
+impl Element {
+    /// Create a new [`Element`] with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Returns this element's name.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Effectiveness of an attacking element against a defending one.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Affinity {
+    /// Damage is left unchanged.
+    #[default]
+    Neutral,
+    /// Damage is doubled.
+    Weak,
+    /// Damage is halved.
+    Resist,
+    /// No damage is dealt.
+    Immune,
+}
+
+impl Affinity {
+    /// Returns the percentage multiplier applied to damage (200 means double damage).
+    pub fn multiplier(&self) -> u64 {
+        match self {
+            Self::Neutral => 100,
+            Self::Weak => 200,
+            Self::Resist => 50,
+            Self::Immune => 0,
+        }
+    }
+}
+
+/// Table describing the [`Affinity`] of every defending element to every attacking element.
+///
+/// # Notes
+///
+/// Pairs of elements that aren't in the chart are [`Affinity::Neutral`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EffectivenessChart {
+    entries: BTreeMap<(Element, Element), Affinity>,
+}
+
+impl EffectivenessChart {
+    /// Create a new, empty [`EffectivenessChart`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the affinity of a defending element to an attacking element.
+    pub fn with(mut self, attacking: Element, defending: Element, affinity: Affinity) -> Self {
+        self.entries.insert((attacking, defending), affinity);
+
+        self
+    }
+
+    /// Returns the affinity of a defending element to an attacking element.
+    pub fn affinity(&self, attacking: &Element, defending: &Element) -> Affinity {
+        self.entries
+            .get(&(attacking.clone(), defending.clone()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the percentage multiplier of an attacking element against all the elements of a defender.
+    ///
+    /// # Notes
+    ///
+    /// The multipliers of each defending element are combined, so a defender weak to the attack through two of its
+    /// elements receives four times the damage.
+    pub fn multiplier(&self, attacking: &Element, defending: &[Element]) -> u64 {
+        defending.iter().fold(100, |multiplier, d| {
+            multiplier.saturating_mul(self.affinity(attacking, d).multiplier()) / 100
+        })
+    }
+}
+
+/// Damage stage applying the [`EffectivenessChart`] between the damage's element and the target's elements.
+///
+/// # Notes
+///
+/// Damage without an element is left unchanged.
+pub struct ElementalAffinity {
+    pub chart: EffectivenessChart,
+}
+
+impl<M: Member> Stage<M> for ElementalAffinity {
+    fn process(&self, damage: &mut Damage, target: &M, _rng: &mut BattleRng) {
+        if let Some(element) = &damage.element {
+            let multiplier = self.chart.multiplier(element, target.elements());
+
+            damage.amount = damage.amount.saturating_mul(multiplier) / 100;
+        }
+    }
+}
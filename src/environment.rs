@@ -0,0 +1,123 @@
+//! Scripts run by the battlefield itself at the end of every turn, like rising lava or a collapsing ceiling.
+//!
+//! [`EnvironmentScript`]s are the place for bespoke per-encounter logic: they inspect the battle through a read-only
+//! [`ContextView`] and answer with [`EnvironmentEffect`]s, which the [`TurnSystem`](crate::battle::TurnSystem) applies
+//! once the script has run. With the `scripting` feature, scripts can also be written in Rhai through
+//! [`ScriptEngine::compile_environment()`](crate::script::ScriptEngine::compile_environment).
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::action::ChoiceReturn;
+use crate::component::ComponentStore;
+use crate::event::{Event, EventLog};
+use crate::member::MemberIdentifier;
+use crate::state::BattleState;
+use crate::team::Team;
+
+/// Function type deciding whether an [`EnvironmentScript`] should run at the end of a turn.
+pub type EnvironmentCondition<M> = Box<dyn Fn(&ContextView<'_, M>) -> bool>;
+
+/// Function type run by an [`EnvironmentScript`], returning the effects to apply to the battle.
+pub type EnvironmentBody<M> = Box<dyn FnMut(&ContextView<'_, M>) -> Vec<EnvironmentEffect<M>>>;
+
+/// Read-only view of a battle, given to [`EnvironmentScript`]s.
+pub struct ContextView<'a, M> {
+    state: &'a BattleState<M>,
+    turn_number: u64,
+}
+
+impl<'a, M> ContextView<'a, M> {
+    /// Create a new [`ContextView`] over the state of a battle during the given turn.
+    pub fn new(state: &'a BattleState<M>, turn_number: u64) -> Self {
+        Self { state, turn_number }
+    }
+
+    /// Returns the number of the turn that is ending.
+    pub fn turn_number(&self) -> u64 {
+        self.turn_number
+    }
+
+    /// Returns the whole state of the battle.
+    pub fn state(&self) -> &'a BattleState<M> {
+        self.state
+    }
+
+    /// Returns a reference to all teams of the battle.
+    pub fn teams(&self) -> &'a [Team<M>] {
+        &self.state.team_list
+    }
+
+    /// Returns a reference to a specific member of the battle.
+    pub fn member(&self, id: MemberIdentifier) -> Option<&'a M> {
+        self.state.team_list.get(id.team_id)?.member(id.member_id)
+    }
+
+    /// Returns a reference to the per-member components of the battle.
+    pub fn components(&self) -> &'a ComponentStore {
+        &self.state.components
+    }
+
+    /// Returns the history of all events of the battle.
+    pub fn events(&self) -> &'a EventLog {
+        &self.state.events
+    }
+}
+
+/// Change requested by an [`EnvironmentScript`].
+#[non_exhaustive]
+pub enum EnvironmentEffect<M> {
+    /// Queue an action to be resolved during the next turn, like a boulder rolling towards the heroes.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`TurnSystem::queue_action()`](crate::battle::TurnSystem::queue_action) to learn how queued actions are resolved.
+    QueueAction(ChoiceReturn<M>),
+    /// Apply an event right away, like scorching ground damaging or tagging the members standing on it.
+    ///
+    /// # Notes
+    ///
+    /// Events are applied as they are, without going through the damage pipeline.
+    /// Events recorded automatically by the battle (like [`Event::MemberDefeated`]) must never be requested.
+    Apply(Event),
+}
+
+/// Named piece of logic run by the battlefield at the end of every turn.
+pub struct EnvironmentScript<M> {
+    name: String,
+    condition: Option<EnvironmentCondition<M>>,
+    body: EnvironmentBody<M>,
+}
+
+impl<M> EnvironmentScript<M> {
+    /// Create a new [`EnvironmentScript`] running the body at the end of every turn.
+    pub fn new(name: impl Into<String>, body: EnvironmentBody<M>) -> Self {
+        Self {
+            name: name.into(),
+            condition: None,
+            body,
+        }
+    }
+
+    /// Only run the script at the end of the turns satisfying the condition.
+    pub fn with_condition(mut self, condition: EnvironmentCondition<M>) -> Self {
+        self.condition = Some(condition);
+
+        self
+    }
+
+    /// Returns the name of the script.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Run the script, returning its effects, or `None` if its condition isn't satisfied.
+    pub(crate) fn run(&mut self, view: &ContextView<'_, M>) -> Option<Vec<EnvironmentEffect<M>>> {
+        if self.condition.as_ref().is_some_and(|c| !c(view)) {
+            return None;
+        }
+
+        Some((self.body)(view))
+    }
+}
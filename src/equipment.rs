@@ -1,7 +1,15 @@
 //! Generic equipment management system applicable to [members](crate::member::Member).
+//!
+//! Members can implement [`Equipment`] with any type, or wear an [`EquipmentSet`] of [`Item`]s in named [`Slot`]s.
 
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use crate::error::EquipError;
 use crate::member::Properties;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Equipment trait to interoperate with a [`Member`](crate::member::Member)'s [`Properties`](crate::member::Properties).
 pub trait Equipment {
     type Properties: Properties;
@@ -14,4 +22,274 @@ pub trait Equipment {
     /// or be automatically summed up with a [`Member`](crate::member::Member)'s associated properties using
     /// [`Member::final_properties()`](crate::member::Member::final_properties).
     fn associated_properties(&self) -> Self::Properties;
+
+    /// Wear the equipment down, returning whether a piece of it broke.
+    ///
+    /// # Notes
+    ///
+    /// This is called by the battle whenever the owner damages someone or is damaged,
+    /// as long as [`Member::equipment_mut()`](crate::member::Member::equipment_mut) gives access to the equipment.
+    ///
+    /// The default implementation never wears the equipment down.
+    #[allow(unused_variables)]
+    fn wear(&mut self, wear: Wear) -> bool {
+        false
+    }
+}
+
+/// Cause of the wear of [`Equipment`].
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Wear {
+    /// The owner has damaged someone.
+    Attacking,
+    /// The owner has been damaged.
+    Hit,
+}
+
+/// Durability of an [`Item`], which breaks once it's worn down completely.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Durability {
+    current: u64,
+    max: u64,
+}
+
+impl Durability {
+    /// Create a new, intact [`Durability`].
+    pub fn new(max: u64) -> Self {
+        Self { current: max, max }
+    }
+
+    /// Returns the durability left.
+    pub fn current(&self) -> u64 {
+        self.current
+    }
+
+    /// Returns the durability of the intact item.
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Returns whether the item is broken.
+    pub fn is_broken(&self) -> bool {
+        self.current == 0
+    }
+
+    /// Wear the item down, returning whether it broke because of it.
+    pub fn wear(&mut self, amount: u64) -> bool {
+        let intact = !self.is_broken();
+        self.current = self.current.saturating_sub(amount);
+
+        intact && self.is_broken()
+    }
+
+    /// Restore the durability of the item, never going past the maximum.
+    pub fn repair(&mut self, amount: u64) {
+        self.current = self.current.saturating_add(amount).min(self.max);
+    }
+}
+
+/// Slot of an [`EquipmentSet`] holding a single [`Item`].
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Slot {
+    /// Main weapon, like a sword or a staff.
+    Weapon,
+    /// Secondary hand, like a shield or a second weapon.
+    OffHand,
+    /// Worn protection, like a robe or a chestplate.
+    Armor,
+    /// Trinkets, like rings or amulets.
+    Accessory,
+    /// Any other game-specific slot, identified by a custom index.
+    Custom(u16),
+}
+
+/// Single piece of equipment, worn in one [`Slot`] of an [`EquipmentSet`].
+pub trait Item {
+    type Properties: Properties;
+
+    /// Returns the [`Slot`] this item is worn in.
+    fn slot(&self) -> Slot;
+
+    /// Returns the property values granted by this item while it's worn.
+    fn properties(&self) -> Self::Properties;
+
+    /// Returns the slots that must stay empty while this item is worn, like the off-hand of a two-handed sword.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation doesn't block any slot.
+    fn blocked_slots(&self) -> &[Slot] {
+        &[]
+    }
+
+    /// Returns the durability of this item, or `None` if it never breaks.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation doesn't track durability.
+    fn durability(&self) -> Option<&Durability> {
+        None
+    }
+
+    /// Returns a mutable reference to the durability of this item, or `None` if it never breaks.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation doesn't track durability.
+    fn durability_mut(&mut self) -> Option<&mut Durability> {
+        None
+    }
+
+    /// Returns how much durability this item loses because of some [`Wear`].
+    ///
+    /// # Notes
+    ///
+    /// The default implementation wears weapons by 1 when attacking, and armor and off-hand items by 1 when hit.
+    fn wear_amount(&self, wear: Wear) -> u64 {
+        match (wear, self.slot()) {
+            (Wear::Attacking, Slot::Weapon) | (Wear::Hit, Slot::Armor | Slot::OffHand) => 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns whether this item is broken, no longer granting its properties.
+    fn is_broken(&self) -> bool {
+        self.durability().is_some_and(Durability::is_broken)
+    }
+}
+
+/// [`Equipment`] made of [`Item`]s worn in named [`Slot`]s.
+///
+/// # Notes
+///
+/// Every slot holds at most one item. Items can only be worn in the slots available to the set,
+/// and never together with items blocking their slot (or whose slots they block).
+/// Broken items stay worn, but don't grant their properties until they are repaired.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EquipmentSet<I> {
+    slots: BTreeSet<Slot>,
+    items: BTreeMap<Slot, I>,
+}
+
+impl<I: Item> EquipmentSet<I> {
+    /// Create a new empty [`EquipmentSet`] with the weapon, off-hand, armor and accessory slots.
+    pub fn new() -> Self {
+        Self::with_slots([Slot::Weapon, Slot::OffHand, Slot::Armor, Slot::Accessory])
+    }
+
+    /// Create a new empty [`EquipmentSet`] with only the given slots available.
+    pub fn with_slots(slots: impl IntoIterator<Item = Slot>) -> Self {
+        Self {
+            slots: slots.into_iter().collect(),
+            items: BTreeMap::new(),
+        }
+    }
+
+    /// Returns whether an item can be worn, replacing the one currently in its slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EquipError::UnavailableSlot`] if the set doesn't have the item's slot,
+    /// or [`EquipError::Blocked`] if the item conflicts with another worn item.
+    pub fn can_equip(&self, item: &I) -> Result<(), EquipError> {
+        let slot = item.slot();
+
+        if !self.slots.contains(&slot) {
+            return Err(EquipError::UnavailableSlot(slot));
+        }
+
+        for (&worn_slot, worn) in self.items.iter().filter(|(s, _)| **s != slot) {
+            if worn.blocked_slots().contains(&slot) {
+                return Err(EquipError::Blocked {
+                    slot,
+                    by: worn_slot,
+                });
+            }
+
+            if item.blocked_slots().contains(&worn_slot) {
+                return Err(EquipError::Blocked {
+                    slot: worn_slot,
+                    by: slot,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wear an item, returning the one previously worn in its slot.
+    ///
+    /// # Errors
+    ///
+    /// Fails in the same cases as [`EquipmentSet::can_equip()`], in which case the item is dropped.
+    /// Check beforehand to keep it.
+    pub fn equip(&mut self, item: I) -> Result<Option<I>, EquipError> {
+        self.can_equip(&item)?;
+
+        Ok(self.items.insert(item.slot(), item))
+    }
+
+    /// Remove the item worn in a slot, returning it.
+    pub fn unequip(&mut self, slot: Slot) -> Option<I> {
+        self.items.remove(&slot)
+    }
+
+    /// Returns a reference to the item worn in a slot, if any.
+    pub fn item(&self, slot: Slot) -> Option<&I> {
+        self.items.get(&slot)
+    }
+
+    /// Returns an iterator over the worn items, together with their slots.
+    pub fn items(&self) -> impl Iterator<Item = (Slot, &I)> {
+        self.items.iter().map(|(slot, item)| (*slot, item))
+    }
+
+    /// Returns an iterator over the slots available to this set, whether they are empty or not.
+    pub fn slots(&self) -> impl Iterator<Item = Slot> + '_ {
+        self.slots.iter().copied()
+    }
+}
+
+impl<I: Item> Default for EquipmentSet<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> Equipment for EquipmentSet<I>
+where
+    I: Item,
+    I::Properties: Default,
+{
+    type Properties = I::Properties;
+
+    /// Returns the sum of the properties of all worn items that aren't broken, starting from the default properties.
+    fn associated_properties(&self) -> Self::Properties {
+        self.items
+            .values()
+            .filter(|item| !item.is_broken())
+            .fold(I::Properties::default(), |sum, item| {
+                sum.sum_properties(&item.properties())
+            })
+    }
+
+    fn wear(&mut self, wear: Wear) -> bool {
+        let mut broke = false;
+
+        for item in self.items.values_mut() {
+            let amount = item.wear_amount(wear);
+
+            if let Some(durability) = item.durability_mut() {
+                broke |= durability.wear(amount);
+            }
+        }
+
+        broke
+    }
 }
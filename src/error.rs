@@ -0,0 +1,365 @@
+//! Errors that can occur while running a [`Battle`](crate::battle::Battle).
+
+use alloc::string::String;
+
+use crate::action::{ActionId, Target};
+use crate::equipment::Slot;
+use crate::inventory::ItemId;
+use crate::member::MemberIdentifier;
+use crate::resource::Cost;
+
+/// Error raised when a battle cannot continue as requested.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BattleError {
+    /// The requested team doesn't exist.
+    UnknownTeam { team_id: usize },
+    /// The requested member doesn't exist.
+    UnknownMember(MemberIdentifier),
+    /// The turn counter cannot go past `u64::MAX`.
+    TurnOverflow,
+    /// The chosen performers or targets reference members or teams that don't exist.
+    InvalidTarget(Target),
+    /// The chosen action refused to be performed.
+    InvalidAction(ActionError),
+    /// A pending action can't be copied into a [`Checkpoint`](crate::checkpoint::Checkpoint).
+    UncopyableAction(ActionId),
+    /// The battle is paused and can't go on until it's resumed.
+    Paused,
+    /// The battle couldn't be built.
+    InvalidSetup(BuildError),
+}
+
+impl core::fmt::Display for BattleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownTeam { team_id } => {
+                write!(f, "requested team with id {team_id} was not found")
+            }
+            Self::UnknownMember(id) => write!(
+                f,
+                "requested member with id {} was not found in team with id {}",
+                id.member_id, id.team_id
+            ),
+            Self::TurnOverflow => write!(f, "turn counter overflowed"),
+            Self::InvalidTarget(target) => {
+                write!(f, "target {target:?} references missing members")
+            }
+            Self::InvalidAction(error) => write!(f, "invalid action: {error}"),
+            Self::UncopyableAction(action) => {
+                write!(f, "pending action {} can't be copied", action.name())
+            }
+            Self::Paused => write!(f, "the battle is paused"),
+            Self::InvalidSetup(error) => write!(f, "invalid battle setup: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for BattleError {}
+
+impl From<ActionError> for BattleError {
+    fn from(error: ActionError) -> Self {
+        match error {
+            ActionError::MissingMembers(target) => Self::InvalidTarget(target),
+            e => Self::InvalidAction(e),
+        }
+    }
+}
+
+impl From<BuildError> for BattleError {
+    fn from(error: BuildError) -> Self {
+        Self::InvalidSetup(error)
+    }
+}
+
+/// Reason why a [`Builder`](crate::battle::Builder) cannot build a playable battle.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// The battle has no teams.
+    NoTeams,
+    /// The team has no members.
+    EmptyTeam { team_id: usize },
+    /// The [`StartupInfo`](crate::battle::StartupInfo) references a member that doesn't exist.
+    UnknownMember(MemberIdentifier),
+    /// The [`StartupInfo`](crate::battle::StartupInfo), a team controller, a team performer criteria or a team aura
+    /// references a team that doesn't exist.
+    UnknownTeam { team_id: usize },
+}
+
+impl core::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NoTeams => write!(f, "the battle has no teams"),
+            Self::EmptyTeam { team_id } => write!(f, "team with id {team_id} has no members"),
+            Self::UnknownMember(id) => write!(
+                f,
+                "starting member with id {} was not found in team with id {}",
+                id.member_id, id.team_id
+            ),
+            Self::UnknownTeam { team_id } => {
+                write!(f, "team with id {team_id} was not found")
+            }
+        }
+    }
+}
+
+impl core::error::Error for BuildError {}
+
+/// Reason why an [`Action`](crate::action::Action) cannot be performed with the chosen performers and targets.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionError {
+    /// The target references members or teams that don't exist.
+    MissingMembers(Target),
+    /// A performer doesn't have enough resources to pay the action's cost.
+    Unaffordable {
+        member: MemberIdentifier,
+        cost: Cost,
+    },
+    /// A performer must wait some more turns before performing the action again.
+    OnCooldown {
+        member: MemberIdentifier,
+        action: ActionId,
+        remaining: u32,
+    },
+    /// The performers don't include the member whose turn it is.
+    OutOfTurn { expected: MemberIdentifier },
+    /// The action must be performed by exactly one member, but a different amount of performers was chosen.
+    NotSolo { performers: usize },
+    /// The action needs a clear line of sight, but a target is hidden behind an obstruction.
+    Obstructed { target: MemberIdentifier },
+    /// The performer doesn't have the item it wants to use, neither in its own inventory nor in its team's one.
+    MissingItem {
+        member: MemberIdentifier,
+        item: ItemId,
+    },
+    /// The action can't be performed for a custom reason.
+    Rejected(String),
+}
+
+impl core::fmt::Display for ActionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingMembers(target) => {
+                write!(f, "target {target:?} references missing members")
+            }
+            Self::Unaffordable { member, cost } => write!(
+                f,
+                "member {member:?} cannot afford to spend {} {:?}",
+                cost.amount, cost.resource
+            ),
+            Self::OnCooldown {
+                member,
+                action,
+                remaining,
+            } => write!(
+                f,
+                "member {member:?} must wait {remaining} turn(s) to perform {} again",
+                action.name()
+            ),
+            Self::OutOfTurn { expected } => {
+                write!(f, "it's the turn of member {expected:?}")
+            }
+            Self::NotSolo { performers } => {
+                write!(
+                    f,
+                    "the action needs exactly 1 performer, {performers} were chosen"
+                )
+            }
+            Self::Obstructed { target } => {
+                write!(f, "member {target:?} is out of sight")
+            }
+            Self::MissingItem { member, item } => {
+                write!(f, "member {member:?} has no {} left", item.name())
+            }
+            Self::Rejected(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl core::error::Error for ActionError {}
+
+/// Error raised when a [`Draft`](crate::draft::Draft) cannot be completed.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DraftError {
+    /// A draft turn references a team without controller.
+    UnknownTeam { team_id: usize },
+    /// There are no entries left to pick or ban.
+    EmptyPool,
+    /// The chosen index is out of the available entries.
+    InvalidIndex { team_id: usize, index: usize },
+    /// The chosen entry was refused by a validator.
+    Rejected { team_id: usize, reason: String },
+}
+
+impl core::fmt::Display for DraftError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownTeam { team_id } => {
+                write!(f, "team with id {team_id} has no draft controller")
+            }
+            Self::EmptyPool => write!(f, "no entries left in the draft pool"),
+            Self::InvalidIndex { team_id, index } => write!(
+                f,
+                "team with id {team_id} chose entry {index}, which is not available"
+            ),
+            Self::Rejected { team_id, reason } => {
+                write!(f, "choice of team with id {team_id} was refused: {reason}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DraftError {}
+
+/// Error raised when an [`Item`](crate::equipment::Item) cannot be worn in an [`EquipmentSet`](crate::equipment::EquipmentSet).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquipError {
+    /// The equipment set doesn't have the item's slot.
+    UnavailableSlot(Slot),
+    /// The slot is blocked by the item worn in another slot.
+    Blocked { slot: Slot, by: Slot },
+}
+
+impl core::fmt::Display for EquipError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnavailableSlot(slot) => write!(f, "slot {slot:?} is not available"),
+            Self::Blocked { slot, by } => {
+                write!(
+                    f,
+                    "slot {slot:?} is blocked by the item worn in slot {by:?}"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for EquipError {}
+
+/// Error raised when a [`Pattern`](crate::catalogue::pattern::Pattern) cannot be used.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    /// The pattern has no steps.
+    Empty,
+    /// The pattern references an action that isn't registered.
+    UnknownAction(ActionId),
+}
+
+impl core::fmt::Display for PatternError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the pattern has no steps"),
+            Self::UnknownAction(action) => {
+                write!(f, "action {} is not registered", action.name())
+            }
+        }
+    }
+}
+
+impl core::error::Error for PatternError {}
+
+/// Error raised when a [`BattleHost`](crate::host::BattleHost) refuses a submission.
+#[cfg(feature = "std")]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostError {
+    /// No client controls the team.
+    UnknownClient { team_id: usize },
+    /// The team isn't asked for a choice, or it already submitted one.
+    NotAwaited { team_id: usize },
+    /// The performers include members the team doesn't control.
+    NotControlled { team_id: usize, performers: Target },
+    /// The submitted command broke a rule of the battle.
+    Rejected(ActionError),
+    /// The battle failed while playing a turn.
+    Battle(BattleError),
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for HostError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownClient { team_id } => {
+                write!(f, "team with id {team_id} has no client")
+            }
+            Self::NotAwaited { team_id } => {
+                write!(f, "team with id {team_id} is not asked for a choice")
+            }
+            Self::NotControlled {
+                team_id,
+                performers,
+            } => write!(
+                f,
+                "team with id {team_id} doesn't control performers {performers:?}"
+            ),
+            Self::Rejected(error) => write!(f, "command rejected: {error}"),
+            Self::Battle(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for HostError {}
+
+#[cfg(feature = "std")]
+impl From<BattleError> for HostError {
+    fn from(error: BattleError) -> Self {
+        Self::Battle(error)
+    }
+}
+
+/// Error raised when an action script cannot be used.
+#[cfg(feature = "scripting")]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    /// The script isn't valid code.
+    Compile(String),
+    /// The script failed while running.
+    Runtime(String),
+}
+
+#[cfg(feature = "scripting")]
+impl core::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Compile(message) => write!(f, "the script could not be compiled: {message}"),
+            Self::Runtime(message) => write!(f, "the script failed: {message}"),
+        }
+    }
+}
+
+#[cfg(feature = "scripting")]
+impl core::error::Error for ScriptError {}
+
+/// Error raised when the connection to a remote peer fails.
+#[cfg(feature = "network")]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    /// The connection was closed by the remote peer.
+    Closed,
+    /// Reading from or writing to the connection failed.
+    Io(String),
+    /// A message could not be encoded, or a received message could not be decoded.
+    Malformed(String),
+}
+
+#[cfg(feature = "network")]
+impl core::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Closed => write!(f, "the connection was closed"),
+            Self::Io(message) => write!(f, "the connection failed: {message}"),
+            Self::Malformed(message) => write!(f, "malformed message: {message}"),
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+impl core::error::Error for NetworkError {}
@@ -0,0 +1,427 @@
+//! Events describing every change happening during a [`Battle`](crate::battle::Battle).
+//!
+//! The battle's state is only ever mutated by applying [`Event`]s, which are then recorded in order in an [`EventLog`].
+//! Since the log is the authoritative history of the battle, replays, snapshots and observers can all be derived from it.
+//! Long battles on constrained platforms can bound the memory used by the log with a [`Retention`] policy.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::action::ActionId;
+use crate::component::Tag;
+use crate::control::Control;
+use crate::formation::Position;
+use crate::inventory::{InventoryOwner, ItemId};
+use crate::member::MemberIdentifier;
+use crate::resource::Resource;
+use crate::stage::Stat;
+use crate::summon::Lifetime;
+use crate::terrain::StructureId;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Single change of a battle's state.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// The battle's opening phase has completed and the first turn is about to be played.
+    ///
+    /// # Notes
+    ///
+    /// `seed` is the one the battle's [`BattleRng`](crate::rng::BattleRng) was created with,
+    /// so that [replays](crate::state::BattleState::replay) can reseed their own generator.
+    BattleStarted { seed: u64 },
+    /// A new turn has started.
+    TurnStarted { turn_number: u64 },
+    /// The member suggested to perform the next action has changed.
+    PerformerSuggested { member: Option<MemberIdentifier> },
+    /// A new member has joined the battle.
+    ///
+    /// # Notes
+    ///
+    /// The member's data isn't part of the event, so joining members can't be replayed.
+    MemberJoined { member: MemberIdentifier },
+    /// An action is about to be performed.
+    ActionPerformed {
+        action: ActionId,
+        performers: Vec<MemberIdentifier>,
+    },
+    /// An action has been performed, summing up its effects on all of its targets.
+    ///
+    /// # Notes
+    ///
+    /// It always follows the events caused by the action since its [`Event::ActionPerformed`].
+    /// The effects of nested actions, like attacks of opportunity, are summed up by their own event.
+    ActionResolved {
+        action: ActionId,
+        summary: ActionSummary,
+    },
+    /// A piece of a member's equipment has broken, after the member damaged someone or was damaged.
+    /// It always follows the [`Event::Damaged`] that wore it down.
+    EquipmentBroken { member: MemberIdentifier },
+    /// A member has been summoned by another one. It always follows the [`Event::MemberJoined`] of the same member.
+    Summoned {
+        member: MemberIdentifier,
+        summoner: MemberIdentifier,
+        lifetime: Lifetime,
+    },
+    /// The [`Lifetime`] of a summoned member has expired, and it's about to leave the fight.
+    SummonExpired { member: MemberIdentifier },
+    /// A member has been removed from the battle.
+    ///
+    /// # Notes
+    ///
+    /// The IDs of the members following it in the same team are shifted down by one.
+    MemberLeft { member: MemberIdentifier },
+    /// A member has been chosen as target of an action that is about to be performed.
+    Targeted { member: MemberIdentifier },
+    /// A member has evaded an attack.
+    Missed { member: MemberIdentifier },
+    /// A member is about to receive a critical hit.
+    CriticalHit { member: MemberIdentifier },
+    /// A member has been damaged.
+    ///
+    /// # Notes
+    ///
+    /// `amount` is the damage inflicted before any absorption by the member's shield and any saturation to its health.
+    Damaged {
+        member: MemberIdentifier,
+        amount: u64,
+    },
+    /// A member's health has been depleted by the previous [`Event::Damaged`].
+    ///
+    /// # Notes
+    ///
+    /// This event is recorded automatically when applying the damage, so it must never be applied manually.
+    MemberDefeated { member: MemberIdentifier },
+    /// A member has been healed.
+    ///
+    /// # Notes
+    ///
+    /// `amount` is the health restored before any saturation to the member's reference health.
+    /// Defeated members can't be healed, and must be [revived](Event::Revived) instead.
+    Healed {
+        member: MemberIdentifier,
+        amount: u64,
+    },
+    /// A defeated member has been brought back to the battle with some health.
+    ///
+    /// # Notes
+    ///
+    /// `health` is the health the member is revived with, before any saturation to the member's reference health.
+    Revived {
+        member: MemberIdentifier,
+        health: u64,
+    },
+    /// A member has been granted an amount of temporary health, added to its [shield](crate::member::Properties::shield).
+    ///
+    /// # Notes
+    ///
+    /// Shields absorb damage until they are depleted or until the end of the current round, when they expire
+    /// with an [`Event::ShieldExpired`]. Without a [`RoundOrder`](crate::round::RoundOrder), shields only
+    /// wear off by absorbing damage.
+    ShieldGained {
+        member: MemberIdentifier,
+        amount: u64,
+    },
+    /// Part of the damage taken by a member has been absorbed by its shield. It always follows its [`Event::Damaged`].
+    ShieldAbsorbed {
+        member: MemberIdentifier,
+        amount: u64,
+    },
+    /// The shield left to a member has expired at the end of a round.
+    ShieldExpired {
+        member: MemberIdentifier,
+        amount: u64,
+    },
+    /// A member has spent an amount of a [`Resource`].
+    ResourceSpent {
+        member: MemberIdentifier,
+        resource: Resource,
+        amount: u64,
+    },
+    /// A stack of a [`Tag`] has been applied to a member.
+    TagApplied { member: MemberIdentifier, tag: Tag },
+    /// A stack of a [`Tag`] has been consumed from a member.
+    TagConsumed { member: MemberIdentifier, tag: Tag },
+    /// A [`Stat`] of a member has been raised (or lowered, if `change` is negative) by some stages.
+    /// Look at [`StatStages`](crate::stage::StatStages) for more information.
+    StageChanged {
+        member: MemberIdentifier,
+        stat: Stat,
+        change: i8,
+    },
+    /// A member has been interrupted, cancelling the actions it would still perform during this turn.
+    Interrupted { member: MemberIdentifier },
+    /// A queued action has been cancelled because its performers were interrupted or defeated.
+    ActionInterrupted { action: ActionId },
+    /// A member must wait some turns before performing an action again.
+    CooldownStarted {
+        member: MemberIdentifier,
+        action: ActionId,
+        turns: u32,
+    },
+    /// Every cooldown and aura has been decremented by one turn.
+    CooldownsTicked,
+    /// A team started benefiting from an aura.
+    /// Look at [`TeamAura`](crate::aura::TeamAura) for more information.
+    AuraApplied { team_id: usize, aura: String },
+    /// An aura of a team wore off.
+    AuraExpired { team_id: usize, aura: String },
+    /// A member has drawn the attention of a team, like with a taunt.
+    /// Look at [`ThreatTable`](crate::threat::ThreatTable) for more information.
+    ThreatGenerated {
+        member: MemberIdentifier,
+        team_id: usize,
+        amount: u64,
+    },
+    /// A member has moved to another cell of its team's [`Formation`](crate::formation::Formation).
+    Moved {
+        member: MemberIdentifier,
+        from: Position,
+        to: Position,
+    },
+    /// A member couldn't move, either because the cell was taken by another member or because it was outside of the grid.
+    MoveBlocked {
+        member: MemberIdentifier,
+        blocker: Option<MemberIdentifier>,
+    },
+    /// The suggested performer didn't perform any action during its turn.
+    ///
+    /// # Notes
+    ///
+    /// `idle_turns` counts the consecutive turns forfeited by the member, including this one.
+    TurnForfeited {
+        member: MemberIdentifier,
+        idle_turns: u32,
+    },
+    /// The choice for the suggested performer took too long and was discarded.
+    /// Look at [`Builder::set_choice_timeout()`](crate::battle::Builder::set_choice_timeout) for more information.
+    ChoiceTimedOut { member: Option<MemberIdentifier> },
+    /// The choices of an idle member are now made by the idle takeover controller.
+    /// Look at [`Builder::set_idle_takeover()`](crate::battle::Builder::set_idle_takeover) for more information.
+    ControlTakenOver { member: MemberIdentifier },
+    /// The choices of a member taken over by the idle takeover controller are made by the choice callback again.
+    ControlRestored { member: MemberIdentifier },
+    /// A defeated member has some turns (or rounds) left to be revived, before being removed from the battle.
+    /// Look at [`Builder::set_revival_window()`](crate::battle::Builder::set_revival_window) for more information.
+    RevivalCountdown {
+        member: MemberIdentifier,
+        remaining: u32,
+    },
+    /// A defeated member has been [revived](Event::Revived) within its revival window, so it's no longer counted down.
+    MemberRevived { member: MemberIdentifier },
+    /// A new [`Structure`](crate::terrain::Structure) has been built on the battlefield.
+    ///
+    /// # Notes
+    ///
+    /// Structures are numbered in the order they are built, so replaying this event builds the same structure.
+    StructureBuilt { name: String, health: u64 },
+    /// A structure has been damaged.
+    StructureDamaged { structure: StructureId, amount: u64 },
+    /// A structure has lost all of its health. It always follows the [`Event::StructureDamaged`] that destroyed it.
+    StructureDestroyed { structure: StructureId },
+    /// A control effect has been applied to a member, after diminishing returns.
+    /// Look at [`ControlTracker`](crate::control::ControlTracker) for more information.
+    ControlApplied {
+        member: MemberIdentifier,
+        control: Control,
+        magnitude: u64,
+    },
+    /// A member has resisted a control effect.
+    ControlResisted {
+        member: MemberIdentifier,
+        control: Control,
+    },
+    /// Some items have been added to an [`Inventory`](crate::inventory::Inventory).
+    ItemsStocked {
+        owner: InventoryOwner,
+        item: ItemId,
+        amount: u32,
+    },
+    /// An item has been taken out of an [`Inventory`](crate::inventory::Inventory) to be used.
+    ItemConsumed { owner: InventoryOwner, item: ItemId },
+    /// A defeated member has dropped some items, rolled from its [`DropTable`](crate::loot::DropTable).
+    LootDropped {
+        member: MemberIdentifier,
+        item: ItemId,
+        amount: u32,
+    },
+    /// An action has made some noise, raising the noise of the whole battle. It always follows its [`Event::ActionPerformed`].
+    NoiseMade { action: ActionId, amount: u64 },
+    /// An [`EnvironmentScript`](crate::environment::EnvironmentScript) has run at the end of a turn.
+    /// It always precedes the events applied by the script.
+    EnvironmentTriggered { script: String },
+    /// A wave of enemies has been defeated. Look at [`Waves`](crate::wave::Waves) for more information.
+    WaveCleared { wave: usize },
+    /// A new round has started. Look at [`round`](crate::round) for more information.
+    RoundStarted { round: u64 },
+    /// Every member of a round has been suggested to perform.
+    RoundEnded { round: u64 },
+    /// The oldest events of the log have been dropped, following the battle's [`Retention`] policy.
+    ///
+    /// # Notes
+    ///
+    /// This event is recorded automatically, so it must never be applied manually.
+    LogTruncated { dropped: usize },
+    /// The battle has come to an end.
+    BattleFinished,
+    /// The battle has been paused, and no turn is played until it's resumed.
+    BattlePaused,
+    /// The paused battle goes on from where it stopped.
+    BattleResumed,
+}
+
+/// Aggregated effects of a single action, recorded by an [`Event::ActionResolved`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ActionSummary {
+    /// Total damage inflicted to all targets.
+    pub damage: u64,
+    /// Total health restored to all targets.
+    pub healing: u64,
+    /// Members defeated by the action, in the order they were defeated.
+    pub defeated: Vec<MemberIdentifier>,
+    /// Amount of targets that evaded the action.
+    pub misses: u32,
+    /// Amount of critical hits landed by the action.
+    pub critical_hits: u32,
+}
+
+impl ActionSummary {
+    /// Sum up the events caused by an action, starting right after its [`Event::ActionPerformed`].
+    ///
+    /// # Notes
+    ///
+    /// Events between the [`Event::ActionPerformed`] and the [`Event::ActionResolved`] of nested actions are skipped.
+    pub fn from_events<'a>(events: impl IntoIterator<Item = &'a Event>) -> Self {
+        let mut summary = Self::default();
+        let mut nested: u32 = 0;
+
+        for event in events {
+            match event {
+                Event::ActionPerformed { .. } => nested += 1,
+                Event::ActionResolved { .. } => nested = nested.saturating_sub(1),
+                _ if nested > 0 => {}
+                Event::Damaged { amount, .. } => {
+                    summary.damage = summary.damage.saturating_add(*amount)
+                }
+                Event::Healed { amount, .. } => {
+                    summary.healing = summary.healing.saturating_add(*amount)
+                }
+                Event::MemberDefeated { member } => summary.defeated.push(*member),
+                Event::Missed { .. } => summary.misses += 1,
+                Event::CriticalHit { .. } => summary.critical_hits += 1,
+                _ => {}
+            }
+        }
+
+        summary
+    }
+
+    /// Returns the amount of members defeated by the action.
+    pub fn kills(&self) -> usize {
+        self.defeated.len()
+    }
+}
+
+/// Policy deciding how many events an [`EventLog`] keeps in memory.
+///
+/// # Notes
+///
+/// Dropped events are gone for good: battles whose log has been truncated can't be [replayed](crate::state::BattleState::replay),
+/// and statistics derived from the log (like the [`BattleOutcome`](crate::outcome::BattleOutcome)) only cover the kept events.
+/// Every truncation is recorded as an [`Event::LogTruncated`].
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Retention {
+    /// Keep every event, so the battle can always be replayed.
+    #[default]
+    Full,
+    /// Keep at most the given amount of events, dropping the oldest half of the log once it's exceeded.
+    ///
+    /// # Notes
+    ///
+    /// The limit should be larger than the amount of events of a single turn, as the turn system looks back at them.
+    MaxEvents(usize),
+    /// Only keep the events of the current turn, dropping the previous ones whenever a new turn starts.
+    CurrentTurn,
+}
+
+/// Ordered record of all [`Event`]s applied to a battle.
+///
+/// # Notes
+///
+/// Events are indexed from the start of the battle, even after the oldest ones have been dropped
+/// by the battle's [`Retention`] policy.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EventLog {
+    events: Vec<Event>,
+    /// Amount of events dropped from the start of the log.
+    dropped: usize,
+}
+
+impl EventLog {
+    /// Create a new, empty [`EventLog`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new event at the end of the log.
+    pub(crate) fn push(&mut self, event: Event) {
+        log::trace!("Recorded event {:?}", event);
+
+        self.events.push(event);
+    }
+
+    /// Returns all kept events, from the oldest to the newest.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Returns all events recorded after the given amount of events, from the oldest to the newest.
+    ///
+    /// # Notes
+    ///
+    /// Observers can keep track of the log's [length](EventLog::len) to only process the events they haven't seen yet.
+    /// If some of those events have been dropped, only the kept ones are returned.
+    pub fn since(&self, index: usize) -> &[Event] {
+        self.events
+            .get(index.saturating_sub(self.dropped)..)
+            .unwrap_or_default()
+    }
+
+    /// Returns the amount of recorded events, including the dropped ones.
+    pub fn len(&self) -> usize {
+        self.dropped + self.events.len()
+    }
+
+    /// Returns whether no events have been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the amount of events dropped from the start of the log.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns whether the log still holds every recorded event, so that it can be replayed.
+    pub fn is_complete(&self) -> bool {
+        self.dropped == 0
+    }
+
+    /// Drop the oldest kept events, returning how many were dropped.
+    pub(crate) fn drop_oldest(&mut self, amount: usize) -> usize {
+        let amount = amount.min(self.events.len());
+
+        self.events.drain(..amount);
+        self.dropped += amount;
+
+        amount
+    }
+}
@@ -0,0 +1,317 @@
+//! Optional positioning of the [`Member`](crate::member::Member)s of a [`Team`](crate::team::Team).
+//!
+//! Members can be placed on a grid made of a front and a back [`Row`], enabling position-aware targeting
+//! (like [`Target::Row`](crate::action::Target::Row) or [`Target::Area`](crate::action::Target::Area))
+//! and damage modifiers (like reduced melee damage on the back row).
+//!
+//! Members can also be moved around the grid by actions (like [`Push`](crate::catalogue::actions::Push)),
+//! colliding with other members and obstacles, and stepping into [`Hazard`]s.
+//! Members and obstacles in the front row also block the line of sight to the back row, for actions that need it
+//! (look at [`Action::requires_line_of_sight()`](crate::action::Action::requires_line_of_sight)).
+
+use alloc::vec::Vec;
+
+use crate::component::Tag;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Row of a [`Formation`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Row {
+    Front,
+    Back,
+}
+
+impl Row {
+    /// Returns the distance of the row from the front of the formation.
+    fn depth(&self) -> usize {
+        match self {
+            Row::Front => 0,
+            Row::Back => 1,
+        }
+    }
+}
+
+/// Place of a member within its team's [`Formation`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub row: Row,
+    pub column: usize,
+}
+
+impl Position {
+    /// Create a new [`Position`].
+    pub fn new(row: Row, column: usize) -> Self {
+        Self { row, column }
+    }
+
+    /// Create a new [`Position`] in the front row.
+    pub fn front(column: usize) -> Self {
+        Self::new(Row::Front, column)
+    }
+
+    /// Create a new [`Position`] in the back row.
+    pub fn back(column: usize) -> Self {
+        Self::new(Row::Back, column)
+    }
+
+    /// Returns whether two positions are next to each other, either in the same row or in the same column.
+    pub fn is_adjacent(&self, other: &Position) -> bool {
+        if self.row == other.row {
+            self.column.abs_diff(other.column) == 1
+        } else {
+            self.column == other.column
+        }
+    }
+
+    /// Returns the position reached by taking a step, or `None` if it leads outside of the grid.
+    pub fn step(&self, step: Step) -> Option<Position> {
+        match (step, self.row) {
+            (Step::Forward, Row::Back) => Some(Position::front(self.column)),
+            (Step::Backward, Row::Front) => Some(Position::back(self.column)),
+            (Step::Forward, Row::Front) | (Step::Backward, Row::Back) => None,
+            (Step::Left, _) => Some(Position::new(self.row, self.column.checked_sub(1)?)),
+            (Step::Right, _) => Some(Position::new(self.row, self.column.checked_add(1)?)),
+        }
+    }
+
+    /// Returns the amount of steps needed to reach another position, moving across rows and columns.
+    pub fn distance(&self, other: &Position) -> usize {
+        self.row.depth().abs_diff(other.row.depth()) + self.column.abs_diff(other.column)
+    }
+}
+
+/// Single movement of a member across its team's [`Formation`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    /// Towards the front row, closer to the enemies.
+    Forward,
+    /// Towards the back row, away from the enemies.
+    Backward,
+    /// Towards the previous column.
+    Left,
+    /// Towards the next column.
+    Right,
+}
+
+/// Dangerous cell of a [`Formation`], affecting every member that steps into it.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Hazard {
+    /// Direct damage inflicted to the member.
+    pub damage: u64,
+    /// Tag applied to the member, if any.
+    pub tag: Option<Tag>,
+}
+
+impl Hazard {
+    /// Create a new [`Hazard`] inflicting direct damage.
+    pub fn new(damage: u64) -> Self {
+        Self { damage, tag: None }
+    }
+
+    /// Apply a [`Tag`] to the members stepping into the hazard.
+    pub fn with_tag(mut self, tag: Tag) -> Self {
+        self.tag = Some(tag);
+
+        self
+    }
+}
+
+/// Area of a [`Formation`] covered by an ability, centered on a cell.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+    /// Only the center cell.
+    Cell,
+    /// Every cell of the center's column, piercing from the front to the back row.
+    Line,
+    /// The center cell and every cell next to it.
+    Cross,
+    /// Every cell within the given [distance](Position::distance) from the center.
+    Radius(usize),
+}
+
+impl Shape {
+    /// Returns whether the shape, centered on `center`, covers a position.
+    pub fn contains(&self, center: Position, position: Position) -> bool {
+        match self {
+            Shape::Cell => position == center,
+            Shape::Line => position.column == center.column,
+            Shape::Cross => center.distance(&position) <= 1,
+            Shape::Radius(radius) => center.distance(&position) <= *radius,
+        }
+    }
+}
+
+/// Cells of a team's [`Formation`] threatened by a member, which can't be left without provoking its
+/// [opportunity attack](crate::reaction::OpportunityHandler).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZoneOfControl {
+    /// Team whose formation is threatened.
+    pub team_id: usize,
+    pub center: Position,
+    pub shape: Shape,
+}
+
+impl ZoneOfControl {
+    /// Create a new [`ZoneOfControl`] covering an area of a team's formation.
+    pub fn new(team_id: usize, center: Position, shape: Shape) -> Self {
+        Self {
+            team_id,
+            center,
+            shape,
+        }
+    }
+
+    /// Returns whether a cell of a team's formation is threatened.
+    pub fn contains(&self, team_id: usize, position: Position) -> bool {
+        self.team_id == team_id && self.shape.contains(self.center, position)
+    }
+}
+
+/// Positions of every member of a team, indexed by member ID.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Formation {
+    positions: Vec<Option<Position>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    hazards: Vec<(Position, Hazard)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    obstacles: Vec<Position>,
+}
+
+impl Formation {
+    /// Create a new [`Formation`], with the positions listed in the same order as the team's members.
+    pub fn new(positions: Vec<Position>) -> Self {
+        Self {
+            positions: positions.into_iter().map(Some).collect(),
+            hazards: Vec::new(),
+            obstacles: Vec::new(),
+        }
+    }
+
+    /// Block a cell with an obstacle, like a wall or a boulder.
+    ///
+    /// # Notes
+    ///
+    /// Members can't move into blocked cells, and obstacles in the front row hide the cell behind them.
+    pub fn with_obstacle(mut self, position: Position) -> Self {
+        self.set_obstacle(position);
+
+        self
+    }
+
+    /// Block a cell with an obstacle. Look at [`Formation::with_obstacle()`] for more information.
+    pub fn set_obstacle(&mut self, position: Position) {
+        if !self.is_blocked(position) {
+            self.obstacles.push(position);
+        }
+    }
+
+    /// Clear a cell from its obstacle, returning whether there was one.
+    pub fn remove_obstacle(&mut self, position: Position) -> bool {
+        let len = self.obstacles.len();
+        self.obstacles.retain(|p| *p != position);
+
+        self.obstacles.len() != len
+    }
+
+    /// Returns whether a cell is blocked by an obstacle.
+    pub fn is_blocked(&self, position: Position) -> bool {
+        self.obstacles.contains(&position)
+    }
+
+    /// Place a [`Hazard`] on a cell, replacing any previous one.
+    pub fn with_hazard(mut self, position: Position, hazard: Hazard) -> Self {
+        self.set_hazard(position, hazard);
+
+        self
+    }
+
+    /// Place a [`Hazard`] on a cell, replacing any previous one.
+    pub fn set_hazard(&mut self, position: Position, hazard: Hazard) {
+        self.remove_hazard(position);
+        self.hazards.push((position, hazard));
+    }
+
+    /// Clear a cell from its [`Hazard`], returning it.
+    pub fn remove_hazard(&mut self, position: Position) -> Option<Hazard> {
+        let index = self.hazards.iter().position(|(p, _)| *p == position)?;
+
+        Some(self.hazards.remove(index).1)
+    }
+
+    /// Returns the [`Hazard`] placed on a cell, if any.
+    pub fn hazard(&self, position: Position) -> Option<&Hazard> {
+        self.hazards
+            .iter()
+            .find(|(p, _)| *p == position)
+            .map(|(_, h)| h)
+    }
+
+    /// Returns the ID of the member placed on a cell, if any.
+    pub fn member_at(&self, position: Position) -> Option<usize> {
+        self.positions.iter().position(|p| *p == Some(position))
+    }
+
+    /// Returns the position of a member, if it has one.
+    pub fn position(&self, member_id: usize) -> Option<Position> {
+        self.positions.get(member_id).copied().flatten()
+    }
+
+    /// Move a member to a new position.
+    pub fn set_position(&mut self, member_id: usize, position: Position) {
+        if self.positions.len() <= member_id {
+            self.positions.resize(member_id + 1, None);
+        }
+
+        self.positions[member_id] = Some(position);
+    }
+
+    /// Forget the position of a member removed from the team, shifting the following members down by one.
+    pub(crate) fn remove(&mut self, member_id: usize) {
+        if member_id < self.positions.len() {
+            self.positions.remove(member_id);
+        }
+    }
+
+    /// Returns the IDs of all members placed in a row.
+    pub fn members_in_row(&self, row: Row) -> Vec<usize> {
+        self.positions
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_some_and(|p| p.row == row))
+            .map(|(member_id, _)| member_id)
+            .collect()
+    }
+
+    /// Returns the IDs of all members placed in the area covered by a shape centered on a cell.
+    pub fn members_in_area(&self, center: Position, shape: Shape) -> Vec<usize> {
+        self.positions
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_some_and(|p| shape.contains(center, p)))
+            .map(|(member_id, _)| member_id)
+            .collect()
+    }
+
+    /// Returns the IDs of all members placed next to a member.
+    pub fn adjacent(&self, member_id: usize) -> Vec<usize> {
+        let Some(position) = self.position(member_id) else {
+            return Vec::new();
+        };
+
+        self.positions
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_some_and(|p| p.is_adjacent(&position)))
+            .map(|(id, _)| id)
+            .collect()
+    }
+}
@@ -0,0 +1,262 @@
+//! Server-authoritative hosting of a [`Battle`] played by multiple clients.
+//!
+//! A [`BattleHost`] owns the authoritative battle. Every client controls a team and submits [`BattleCommand`]s for it,
+//! which are validated before being accepted. Once every team asked for a choice has submitted one, the host plays the
+//! turn and broadcasts the resulting [`Event`]s to its subscribers, so that clients can keep their copy of the battle
+//! up to date.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use crate::action::{ChoiceReturn, Target};
+use crate::battle::Battle;
+use crate::command::{BattleCommand, Rule};
+use crate::controller::{ChoicePrompt, Controller};
+use crate::error::HostError;
+use crate::event::Event;
+use crate::member::Member;
+
+/// Authoritative [`Battle`] accepting choices from the clients controlling its teams.
+///
+/// # Notes
+///
+/// Teams without a client are controlled by the battle's own controller.
+/// Look at [`Battle::choosing_teams()`] to know which teams are asked for a choice during a turn.
+pub struct BattleHost<M> {
+    battle: Battle<M>,
+    submissions: Rc<RefCell<Submissions<M>>>,
+    subscribers: Vec<Sender<Event>>,
+    /// Amount of events of the battle's log already broadcast.
+    broadcast: usize,
+}
+
+/// Choices submitted by the clients, waiting for their turn to be played.
+struct Submissions<M> {
+    clients: BTreeMap<usize, Option<ChoiceReturn<M>>>,
+    /// Controller choosing for a client's team when it had no chance to submit a choice.
+    fallback: Box<dyn Controller<M>>,
+}
+
+/// Controller of a team, playing the choices submitted by its client.
+struct Client<M> {
+    team_id: usize,
+    submissions: Rc<RefCell<Submissions<M>>>,
+}
+
+impl<M> Controller<M> for Client<M> {
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        let mut submissions = self.submissions.borrow_mut();
+
+        let submitted = submissions
+            .clients
+            .get_mut(&self.team_id)
+            .and_then(Option::take);
+
+        match submitted {
+            Some(choice) => choice,
+            None => {
+                log::warn!(
+                    "Team {} has no submitted choice, using the fallback controller",
+                    self.team_id
+                );
+
+                prompt.delegate(submissions.fallback.as_mut())
+            }
+        }
+    }
+}
+
+impl<M: Member + 'static> BattleHost<M> {
+    /// Create a new [`BattleHost`] owning the battle, without clients.
+    ///
+    /// # Notes
+    ///
+    /// The fallback controller chooses for a client's team when the battle asks for a choice that couldn't be submitted,
+    /// like when the action of a channeling member is interrupted.
+    pub fn new(battle: Battle<M>, fallback: impl Controller<M> + 'static) -> Self {
+        let broadcast = battle.events().len();
+
+        Self {
+            battle,
+            submissions: Rc::new(RefCell::new(Submissions {
+                clients: BTreeMap::new(),
+                fallback: Box::new(fallback),
+            })),
+            subscribers: Vec::new(),
+            broadcast,
+        }
+    }
+
+    /// Let a client control a team, submitting its choices through [`BattleHost::submit()`].
+    pub fn with_client(mut self, team_id: usize) -> Self {
+        self.add_client(team_id);
+
+        self
+    }
+
+    /// Let a client control a team, submitting its choices through [`BattleHost::submit()`].
+    pub fn add_client(&mut self, team_id: usize) {
+        self.submissions.borrow_mut().clients.insert(team_id, None);
+
+        self.battle.set_team_controller(
+            team_id,
+            Client {
+                team_id,
+                submissions: self.submissions.clone(),
+            },
+        );
+    }
+
+    /// Returns the IDs of the teams controlled by a client.
+    pub fn clients(&self) -> Vec<usize> {
+        self.submissions.borrow().clients.keys().copied().collect()
+    }
+
+    /// Subscribe to the events of the battle, which are broadcast after every turn.
+    ///
+    /// # Notes
+    ///
+    /// Only the events recorded after subscribing are received. Subscribers are dropped once their receiver is.
+    pub fn subscribe(&mut self) -> Receiver<Event> {
+        let (sender, receiver) = mpsc::channel();
+
+        self.subscribers.push(sender);
+
+        receiver
+    }
+
+    /// Returns the IDs of the teams controlled by a client that must submit a choice before the next turn is played.
+    pub fn awaiting(&self) -> Vec<usize> {
+        let submissions = self.submissions.borrow();
+
+        self.battle
+            .choosing_teams()
+            .into_iter()
+            .filter(|team_id| matches!(submissions.clients.get(team_id), Some(None)))
+            .collect()
+    }
+
+    /// Submit the choice of a client's team, playing every turn that can be played afterwards.
+    ///
+    /// Returns the events broadcast to the subscribers.
+    ///
+    /// # Notes
+    ///
+    /// The command is validated against the battle (look at [`Battle::validate_command()`]),
+    /// and its performers must be members of the submitting team.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HostError`] if the submission is refused, or if the battle fails while playing a turn.
+    pub fn submit(
+        &mut self,
+        team_id: usize,
+        command: BattleCommand<M>,
+    ) -> Result<Vec<Event>, HostError> {
+        if !self.submissions.borrow().clients.contains_key(&team_id) {
+            return Err(HostError::UnknownClient { team_id });
+        }
+
+        if !self.awaiting().contains(&team_id) {
+            return Err(HostError::NotAwaited { team_id });
+        }
+
+        if !controls(team_id, &command.performers) {
+            return Err(HostError::NotControlled {
+                team_id,
+                performers: command.performers,
+            });
+        }
+
+        // Teams choosing simultaneously aren't bound to the suggested performer of another team.
+        let simultaneous = self
+            .battle
+            .suggested_performer()
+            .is_some_and(|member| member.team_id != team_id);
+
+        let report = self.battle.validate_command(&command);
+        let failure = report
+            .failures()
+            .find(|(rule, _)| !(simultaneous && *rule == Rule::Timing));
+
+        if let Some((_, error)) = failure {
+            return Err(HostError::Rejected(error.clone()));
+        }
+
+        self.submissions
+            .borrow_mut()
+            .clients
+            .insert(team_id, Some(command.into_choice()));
+
+        self.advance()
+    }
+
+    /// Play every turn that doesn't need a client's choice, until a client is awaited or the battle is finished.
+    ///
+    /// Returns the events broadcast to the subscribers.
+    ///
+    /// # Notes
+    ///
+    /// Turns are played with the battle's own controller while the suggested performer's team has no client,
+    /// so a battle without clients is played until its conclusion.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HostError`] if the battle fails while playing a turn.
+    pub fn advance(&mut self) -> Result<Vec<Event>, HostError> {
+        let result = self.play_available();
+        let events = self.broadcast();
+
+        result.map(|()| events)
+    }
+
+    /// Returns the hosted battle.
+    pub fn battle(&self) -> &Battle<M> {
+        &self.battle
+    }
+
+    /// Stop hosting the battle, returning it.
+    ///
+    /// # Notes
+    ///
+    /// The clients' teams keep being controlled by the host's controllers, without new submissions.
+    pub fn into_battle(self) -> Battle<M> {
+        self.battle
+    }
+
+    /// Play turns until a client is awaited or the battle is finished.
+    fn play_available(&mut self) -> Result<(), HostError> {
+        while !self.battle.is_finished() && self.awaiting().is_empty() {
+            self.battle.play_turn()?;
+        }
+
+        Ok(())
+    }
+
+    /// Send the events recorded since the last broadcast to every subscriber, returning them.
+    fn broadcast(&mut self) -> Vec<Event> {
+        let events = self.battle.events().since(self.broadcast).to_vec();
+        self.broadcast = self.battle.events().len();
+
+        self.subscribers
+            .retain(|subscriber| events.iter().all(|e| subscriber.send(e.clone()).is_ok()));
+
+        events
+    }
+}
+
+/// Returns whether all the performers are members of the team.
+fn controls(team_id: usize, performers: &Target) -> bool {
+    match performers {
+        Target::Single(member) | Target::AllAlliesOf(member) => member.team_id == team_id,
+        Target::DiscreteMultiple(members) => {
+            !members.is_empty() && members.iter().all(|m| m.team_id == team_id)
+        }
+        Target::FullTeam { team_id: id }
+        | Target::Row { team_id: id, .. }
+        | Target::Area { team_id: id, .. } => *id == team_id,
+        _ => false,
+    }
+}
@@ -0,0 +1,107 @@
+//! Consumable items (like potions) and the inventories holding them.
+//!
+//! Inventories belong either to a single member or to a whole team, and only keep track of how many items of each
+//! [`ItemId`] they hold. A team's inventory is shared by all of its members and is owned by the
+//! [`Team`](crate::team::Team) itself, so it's carried from one battle to the next. What an item does is up to its [`Consumable`] implementation, used through the
+//! [`UseItem`](crate::catalogue::actions::UseItem) action.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use crate::action::Context;
+use crate::member::MemberIdentifier;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Identifier of a kind of consumable item.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemId(String);
+
+impl ItemId {
+    /// Create a new [`ItemId`] with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Returns this identifier's name.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Owner of an [`Inventory`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InventoryOwner {
+    /// The inventory of a single member, only usable by itself.
+    Member(MemberIdentifier),
+    /// The inventory shared by every member of a team, held by the [`Team`](crate::team::Team) itself.
+    Team(usize),
+}
+
+/// Amount of items of each kind held by an [`InventoryOwner`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Inventory {
+    items: BTreeMap<ItemId, u32>,
+}
+
+impl Inventory {
+    /// Create a new, empty [`Inventory`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the amount of items of a kind held in the inventory.
+    pub fn count(&self, item: &ItemId) -> u32 {
+        self.items.get(item).copied().unwrap_or(0)
+    }
+
+    /// Returns whether the inventory holds at least one item of a kind.
+    pub fn contains(&self, item: &ItemId) -> bool {
+        self.count(item) > 0
+    }
+
+    /// Returns an iterator over the kinds of items held in the inventory, together with their amount.
+    pub fn items(&self) -> impl Iterator<Item = (&ItemId, u32)> {
+        self.items.iter().map(|(item, amount)| (item, *amount))
+    }
+
+    /// Add some items of a kind to the inventory.
+    pub fn add(&mut self, item: ItemId, amount: u32) {
+        let count = self.items.entry(item).or_insert(0);
+        *count = count.saturating_add(amount);
+    }
+
+    /// Take one item of a kind out of the inventory.
+    ///
+    /// Returns `true` if an item was found and taken, `false` otherwise.
+    pub fn take(&mut self, item: &ItemId) -> bool {
+        let Some(count) = self.items.get_mut(item) else {
+            return false;
+        };
+
+        *count -= 1;
+
+        if *count == 0 {
+            self.items.remove(item);
+        }
+
+        true
+    }
+}
+
+/// Item that is used up to apply an effect, like a potion healing its targets.
+///
+/// # Notes
+///
+/// The item is taken out of the inventory by [`UseItem`](crate::catalogue::actions::UseItem) before its effect is applied.
+pub trait Consumable<M> {
+    /// Returns the identifier of this kind of item, used to find it in inventories.
+    fn item_id(&self) -> ItemId;
+
+    /// Apply the effect of the item, used by the performers of the action on its targets.
+    fn use_effect(&mut self, context: &mut Context<'_, M>);
+}
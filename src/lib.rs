@@ -1,7 +1,62 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod action;
+pub mod aura;
 pub mod battle;
+#[cfg(feature = "bevy")]
+pub mod bevy;
 pub mod catalogue;
+pub mod checkpoint;
+pub mod command;
+pub mod component;
+pub mod control;
+pub mod controller;
+pub mod damage;
+pub mod diff;
+pub mod draft;
+pub mod element;
+pub mod environment;
 pub mod equipment;
+pub mod error;
+pub mod event;
+pub mod formation;
+#[cfg(feature = "std")]
+pub mod host;
+pub mod inventory;
+pub mod loot;
 pub mod member;
+pub mod narration;
+#[cfg(feature = "network")]
+pub mod network;
+pub mod objective;
+pub mod observer;
+pub mod outcome;
+pub mod persist;
+pub mod prelude;
+pub mod reaction;
+pub mod registry;
+pub mod reinforcement;
+pub mod resource;
+pub mod rng;
+pub mod round;
+#[cfg(feature = "scaffold")]
+pub mod scaffold;
+pub mod schedule;
+#[cfg(feature = "scripting")]
+pub mod script;
 pub mod search;
+pub mod simulate;
+pub mod spread;
+pub mod stage;
+pub mod state;
+pub mod statistics;
+pub mod summon;
 pub mod team;
+pub mod terrain;
+pub mod threat;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod visibility;
+pub mod wave;
@@ -0,0 +1,144 @@
+//! Items dropped by defeated members, rolled from weighted [`DropTable`]s.
+//!
+//! Members give their table through [`Member::drop_table()`](crate::member::Member::drop_table).
+//! The [`TurnSystem`](crate::battle::TurnSystem) rolls it with the battle's source of randomness at the end
+//! of the turn the member is defeated in, recording every drop as an [`Event::LootDropped`](crate::event::Event::LootDropped).
+//! The dropped loot is then listed by the [`BattleOutcome`](crate::outcome::BattleOutcome).
+
+use alloc::vec::Vec;
+
+use crate::inventory::ItemId;
+use crate::member::MemberIdentifier;
+use crate::rng::BattleRng;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Single weighted entry of a [`DropTable`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropEntry {
+    /// Item dropped by the entry, or `None` if the entry drops nothing.
+    pub item: Option<ItemId>,
+    pub amount: u32,
+    /// Chance of the entry being rolled, relative to the sum of the weights of all entries.
+    pub weight: u32,
+}
+
+/// Weighted list of the items a member may drop when defeated.
+///
+/// # Notes
+///
+/// The table is rolled as many times as its rolls, and each roll picks exactly one entry.
+/// Use [`DropTable::with_nothing()`] to give each roll a chance of dropping nothing.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropTable {
+    entries: Vec<DropEntry>,
+    rolls: u32,
+}
+
+impl DropTable {
+    /// Create a new, empty [`DropTable`] rolled once.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            rolls: 1,
+        }
+    }
+
+    /// Add an entry dropping an amount of items of a kind.
+    pub fn with_entry(mut self, item: ItemId, amount: u32, weight: u32) -> Self {
+        self.entries.push(DropEntry {
+            item: Some(item),
+            amount,
+            weight,
+        });
+
+        self
+    }
+
+    /// Add an entry dropping nothing.
+    pub fn with_nothing(mut self, weight: u32) -> Self {
+        self.entries.push(DropEntry {
+            item: None,
+            amount: 0,
+            weight,
+        });
+
+        self
+    }
+
+    /// Set the amount of times the table is rolled.
+    pub fn with_rolls(mut self, rolls: u32) -> Self {
+        self.rolls = rolls;
+
+        self
+    }
+
+    /// Returns the entries of the table, in the order they were added.
+    pub fn entries(&self) -> &[DropEntry] {
+        &self.entries
+    }
+
+    /// Returns the amount of times the table is rolled.
+    pub fn rolls(&self) -> u32 {
+        self.rolls
+    }
+
+    /// Returns the sum of the weights of all entries.
+    pub fn total_weight(&self) -> u64 {
+        self.entries.iter().map(|e| u64::from(e.weight)).sum()
+    }
+
+    /// Roll the table, returning the dropped items together with their amount, in the order they were rolled.
+    ///
+    /// # Notes
+    ///
+    /// Entries dropping nothing (or no items) are left out. Tables without any weight never drop anything.
+    pub fn roll(&self, rng: &mut BattleRng) -> Vec<(ItemId, u32)> {
+        let total = self.total_weight();
+
+        if total == 0 {
+            return Vec::new();
+        }
+
+        (0..self.rolls)
+            .filter_map(|_| {
+                let mut value = rng.gen_range(0..total);
+
+                let entry = self.entries.iter().find(|e| {
+                    let weight = u64::from(e.weight);
+
+                    if value < weight {
+                        true
+                    } else {
+                        value -= weight;
+                        false
+                    }
+                })?;
+
+                match &entry.item {
+                    Some(item) if entry.amount > 0 => Some((item.clone(), entry.amount)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for DropTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Items dropped by a defeated member, as listed by the [`BattleOutcome`](crate::outcome::BattleOutcome).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LootDrop {
+    /// Identifier of the member at the time it dropped the items.
+    pub member: MemberIdentifier,
+    pub item: ItemId,
+    pub amount: u32,
+}
@@ -1,6 +1,18 @@
 //! Definitions for [`Member`]s, the main performers in a [`Battle`](crate::battle::Battle).
 
+use alloc::vec::Vec;
+
+use crate::battle::StartupInfo;
+use crate::element::Element;
 use crate::equipment::Equipment;
+use crate::event::Event;
+use crate::loot::DropTable;
+use crate::narration::narrate;
+use crate::resource::Resource;
+use crate::stage::StatStages;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Fighting entity of a [`Team`](crate::team::Team).
 pub trait Member: core::fmt::Debug + Clone + PartialEq + Eq {
@@ -23,6 +35,17 @@ pub trait Member: core::fmt::Debug + Clone + PartialEq + Eq {
     /// Returns a reference to this [`Member`]'s equipment struct.
     fn equipment(&self) -> &Self::Equipment;
 
+    /// Returns a mutable reference to this [`Member`]'s equipment struct, or `None` if it can't change during a battle.
+    ///
+    /// # Notes
+    ///
+    /// The battle uses it to [wear](Equipment::wear) the equipment down.
+    ///
+    /// The default implementation returns `None`, so the equipment never wears down.
+    fn equipment_mut(&mut self) -> Option<&mut Self::Equipment> {
+        None
+    }
+
     /// Returns the [`Properties`] associated with this [`Member`] after *all* standard property calculations.
     ///
     /// # Notes
@@ -35,6 +58,48 @@ pub trait Member: core::fmt::Debug + Clone + PartialEq + Eq {
             .sum_properties(&self.equipment().associated_properties())
     }
 
+    /// Returns the opening effects of this [`Member`], applied once when the battle starts.
+    ///
+    /// # Notes
+    ///
+    /// This is the place for start-of-battle buffs granted by equipment, passive abilities or an ambush
+    /// described in the [`StartupInfo`]. `id` is the identifier of this member in the battle.
+    ///
+    /// The default implementation returns no effects.
+    #[allow(unused_variables)]
+    fn on_battle_start(&self, id: MemberIdentifier, startup: &StartupInfo) -> Vec<Event> {
+        Vec::new()
+    }
+
+    /// Returns the experience granted to the members of the other teams when this [`Member`] is defeated.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`BattleOutcome`](crate::outcome::BattleOutcome) to learn how experience is shared.
+    ///
+    /// The default implementation returns 0.
+    fn experience_yield(&self) -> u64 {
+        0
+    }
+
+    /// Returns the [`DropTable`] rolled when this [`Member`] is defeated, or `None` if it never drops anything.
+    ///
+    /// # Notes
+    ///
+    /// The table is rolled every time the member is defeated, even after being revived.
+    ///
+    /// The default implementation returns `None`.
+    fn drop_table(&self) -> Option<&DropTable> {
+        None
+    }
+
+    /// Returns the [`Element`]s of this [`Member`], used to calculate its affinity to elemental damage.
+    ///
+    /// The default implementation returns no elements.
+    fn elements(&self) -> &[Element] {
+        &[]
+    }
+
     // `Properties` and `Statistics` function escalation (to access them directly via `Member` with additional information).
 
     /// Returns this [`Member`]'s current health.
@@ -54,7 +119,7 @@ pub trait Member: core::fmt::Debug + Clone + PartialEq + Eq {
     fn damage(&mut self, damage: u64) {
         self.member_properties_mut().damage(damage);
 
-        log::info!(
+        narrate!(
             "Member {} takes {} damage! Health: {}/{}",
             self.name(),
             damage,
@@ -62,6 +127,83 @@ pub trait Member: core::fmt::Debug + Clone + PartialEq + Eq {
             self.statistics().reference_health(),
         );
     }
+
+    /// Returns whether this [`Member`] has been defeated, having no health left.
+    ///
+    /// # Notes
+    ///
+    /// Defeated members stay in their team, but can't be healed until they are [revived](Member::revive).
+    fn is_defeated(&self) -> bool {
+        self.health() == 0
+    }
+
+    /// Restore this [`Member`]'s health.
+    ///
+    /// # Notes
+    ///
+    /// The restored health never exceeds the [reference health](Statistics::reference_health),
+    /// unless it was already higher before healing.
+    ///
+    /// Defeated members aren't healed.
+    fn heal(&mut self, amount: u64) {
+        if self.is_defeated() {
+            narrate!("Member {} is defeated and can't be healed", self.name());
+
+            return;
+        }
+
+        let reference = self.statistics().reference_health();
+        let health = self.member_properties().health();
+
+        *self.member_properties_mut().health_mut() =
+            health.max(health.saturating_add(amount).min(reference));
+
+        narrate!(
+            "Member {} heals {} health! Health: {}/{}",
+            self.name(),
+            amount,
+            self.member_properties().health(),
+            reference,
+        );
+    }
+
+    /// Bring this defeated [`Member`] back with some health.
+    ///
+    /// # Notes
+    ///
+    /// The member is revived with at least 1 health, and never more than its [reference health](Statistics::reference_health).
+    /// Members that aren't defeated are left untouched.
+    fn revive(&mut self, health: u64) {
+        if !self.is_defeated() {
+            return;
+        }
+
+        let reference = self.statistics().reference_health();
+
+        *self.member_properties_mut().health_mut() = health.min(reference).max(1);
+
+        narrate!(
+            "Member {} is revived! Health: {}/{}",
+            self.name(),
+            self.member_properties().health(),
+            reference,
+        );
+    }
+}
+
+/// [`Member`] that grows stronger by gaining experience after its battles.
+///
+/// # Notes
+///
+/// Experience is given through [`Battle::claim_rewards()`](crate::battle::Battle::claim_rewards).
+pub trait Levelable: Member {
+    /// Returns the current level of this [`Member`].
+    fn level(&self) -> u32;
+
+    /// Gain an amount of experience, applying any level-up (like raising statistics) to this [`Member`].
+    ///
+    /// Returns the amount of levels gained.
+    fn gain_experience(&mut self, amount: u64) -> u32;
 }
 
 /// Unmutable statistics associated with a specific [`Member`].
@@ -81,6 +223,34 @@ pub trait Statistics: core::fmt::Debug + Clone + PartialEq + Eq {
     ///
     /// This attack value is supposed to be the "base" attack deeply associated with a member's statistics.
     fn base_attack(&self) -> u64;
+
+    /// Defense value associated to a [`Member`].
+    ///
+    /// The default implementation returns 0.
+    fn base_defense(&self) -> u64 {
+        0
+    }
+
+    /// Base chance of hitting foes, as a percentage.
+    ///
+    /// The default implementation returns 100.
+    fn base_accuracy(&self) -> u64 {
+        100
+    }
+
+    /// Base chance of evading attacks, as a percentage.
+    ///
+    /// The default implementation returns 0.
+    fn base_evasion(&self) -> u64 {
+        0
+    }
+
+    /// Base chance of resisting control effects, as a percentage.
+    ///
+    /// The default implementation returns 0.
+    fn base_control_resistance(&self) -> u64 {
+        0
+    }
 }
 
 /// Properties of a [`Member`] that can change during a match.
@@ -124,7 +294,122 @@ pub trait Properties: core::fmt::Debug + Clone + PartialEq + Eq {
     /// calculations are applied (like statistic's boosts).
     fn attack(&self) -> u64;
 
-    /// Auto-generate a new set of [`Properties`] from some [`Statistics`].
+    /// Defense value used to mitigate the damage received.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation returns 0, so damage isn't mitigated at all.
+    fn defense(&self) -> u64 {
+        0
+    }
+
+    /// Chance of hitting foes, as a percentage, after all needed calculations are applied.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation returns 100, so attacks always hit unless the target can evade them.
+    fn accuracy(&self) -> u64 {
+        100
+    }
+
+    /// Chance of evading attacks, as a percentage, after all needed calculations are applied.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation returns 0, so the member never evades attacks.
+    fn evasion(&self) -> u64 {
+        0
+    }
+
+    /// Speed of the member, after all needed calculations are applied.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation returns 0. Look at [`TurnMode::Simultaneous`](crate::battle::TurnMode::Simultaneous)
+    /// to learn how the speed orders the actions.
+    fn speed(&self) -> u64 {
+        0
+    }
+
+    /// Chance of resisting control effects (like stuns, pushes, taunts and charms), as a percentage,
+    /// after all needed calculations are applied.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation returns 0, so control effects are never resisted.
+    /// Look at [`Context::apply_control()`](crate::action::Context::apply_control) for more information.
+    fn control_resistance(&self) -> u64 {
+        0
+    }
+
+    /// Temporary health points absorbing damage before the health does, like a shield or an overheal.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation returns 0. Look at [`Event::ShieldGained`] to learn how shields are granted
+    /// and how long they last.
+    fn shield(&self) -> u64 {
+        0
+    }
+
+    /// Returns a mutable reference to the [shield](Properties::shield), or `None` if these properties can't hold one.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation doesn't provide any shield, so damage is always taken by the health.
+    fn shield_mut(&mut self) -> Option<&mut u64> {
+        None
+    }
+
+    /// Returns the current amount of a [`Resource`] pool, or `None` if these properties don't have such a pool.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation doesn't provide any resource pool.
+    #[allow(unused_variables)]
+    fn resource(&self, resource: Resource) -> Option<u64> {
+        None
+    }
+
+    /// Returns a mutable reference to a [`Resource`] pool, or `None` if these properties don't have such a pool.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation doesn't provide any resource pool.
+    #[allow(unused_variables)]
+    fn resource_mut(&mut self, resource: Resource) -> Option<&mut u64> {
+        None
+    }
+
+    /// Subtract an amount of a [`Resource`] from its pool.
+    ///
+    /// Returns `true` if the pool exists and holds enough of the resource, `false` otherwise (nothing is spent).
+    ///
+    /// # Notes
+    ///
+    /// This function should not be reimplemented.
+    fn spend_resource(&mut self, resource: Resource, amount: u64) -> bool {
+        match self.resource_mut(resource) {
+            Some(pool) if *pool >= amount => {
+                *pool -= amount;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Raise or lower the property values according to the stages of a buffed or debuffed member.
+    ///
+    /// # Notes
+    ///
+    /// Stages are applied on top of the [final properties](crate::state::BattleState::final_properties) of a member,
+    /// after its equipment and the auras of its team. Scale every staged value with [`StatStages::scale()`].
+    ///
+    /// The default implementation leaves the values unmodified, so stages have no effect.
+    #[allow(unused_variables)]
+    fn apply_stages(&mut self, stages: &StatStages) {}
+
+    // Auto-generate a new set of [`Properties`] from some [`Statistics`].
     // TODO: Require From<Statistics>
     /*fn from_stats(statistics: &Statistics) -> Self {
         Self {
@@ -136,17 +421,52 @@ pub trait Properties: core::fmt::Debug + Clone + PartialEq + Eq {
     ///
     /// # Notes
     ///
-    /// The health subtraction saturates to 0 if the damage exceeds the current health.
+    /// The [shield](Properties::shield) absorbs as much of the damage as it can before the health is touched.
+    /// The health subtraction saturates to 0 if the remaining damage exceeds the current health.
     ///
     /// This function should not be reimplemented.
     fn damage(&mut self, damage: u64) {
-        *self.health_mut() = self.health().saturating_sub(damage);
+        let absorbed = match self.shield_mut() {
+            Some(shield) => {
+                let absorbed = damage.min(*shield);
+                *shield -= absorbed;
+
+                absorbed
+            }
+            None => 0,
+        };
+
+        *self.health_mut() = self.health().saturating_sub(damage - absorbed);
+    }
+}
+
+/// Stable handle of a member, assigned when it enters a battle.
+///
+/// # Notes
+///
+/// Unlike [`MemberIdentifier`]s, handles never change while the battle goes on, even if other members are removed,
+/// and are never reused for other members. Resolve them into identifiers with
+/// [`BattleState::resolve()`](crate::state::BattleState::resolve).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemberHandle(u64);
+
+impl MemberHandle {
+    /// Create a handle from its raw value.
+    pub(crate) fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Returns the raw value of the handle.
+    pub fn get(&self) -> u64 {
+        self.0
     }
 }
 
 /// Identifier of a member using the team index and a "relative" member index.
 #[non_exhaustive]
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct MemberIdentifier {
     pub team_id: usize,
     pub member_id: usize,
@@ -154,15 +474,29 @@ pub struct MemberIdentifier {
 
 impl MemberIdentifier {
     /// Create a new [`MemberIdentifier`] using the member's team index and relative index.
-    pub fn new(team_id: usize, member_id: usize) -> Self {
+    pub const fn new(team_id: usize, member_id: usize) -> Self {
         Self { team_id, member_id }
     }
 
     /// Create a new [`MemberIdentifier`] that reference's to the first team's first member.
-    pub fn zeroed() -> Self {
+    pub const fn zeroed() -> Self {
         Self {
             team_id: 0,
             member_id: 0,
         }
     }
+
+    /// Returns the identifier of this member after another member has been removed from the battle.
+    ///
+    /// Members following the removed one in the same team are shifted down by one.
+    /// Returns `None` if this is the removed member.
+    pub(crate) fn after_removal(self, removed: MemberIdentifier) -> Option<Self> {
+        if self == removed {
+            None
+        } else if self.team_id == removed.team_id && self.member_id > removed.member_id {
+            Some(Self::new(self.team_id, self.member_id - 1))
+        } else {
+            Some(self)
+        }
+    }
 }
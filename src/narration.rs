@@ -0,0 +1,84 @@
+//! Switch for the narration logged while battles are played.
+//!
+//! Every hit, heal and action logs a line at the `info` level, which is useful while developing a game
+//! but dominates the profile of headless runs, like [simulations](crate::simulate).
+//! Battles built with [`Builder::set_narration(false)`](crate::battle::Builder::set_narration) skip the narration
+//! (and the formatting of its arguments) entirely, while still logging warnings and errors.
+//!
+//! # Notes
+//!
+//! Narration is switched off only on the thread playing a battle, and only while one of its turns is played.
+//! Battles played at the same time on other threads keep their own setting, while asynchronous battles
+//! resumed on a different thread than the one they were suspended on may narrate some lines anyway.
+//! Without the `std` feature, narration is switched for the whole program instead.
+
+#[cfg(feature = "std")]
+use core::cell::Cell;
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(true) };
+}
+
+#[cfg(not(feature = "std"))]
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Returns whether the narration is currently enabled on this thread.
+#[cfg(feature = "std")]
+pub fn is_enabled() -> bool {
+    ENABLED.with(Cell::get)
+}
+
+/// Returns whether the narration is currently enabled.
+#[cfg(not(feature = "std"))]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Switch the narration, returning the previous setting.
+#[cfg(feature = "std")]
+fn replace(enabled: bool) -> bool {
+    ENABLED.with(|e| e.replace(enabled))
+}
+
+/// Switch the narration, returning the previous setting.
+#[cfg(not(feature = "std"))]
+fn replace(enabled: bool) -> bool {
+    // Loading and storing separately works on targets without atomic read-modify-write operations.
+    let previous = ENABLED.load(Ordering::Relaxed);
+    ENABLED.store(enabled, Ordering::Relaxed);
+
+    previous
+}
+
+/// Guard setting the narration of the current thread, restoring the previous setting once dropped.
+pub(crate) struct NarrationGuard {
+    previous: bool,
+}
+
+impl NarrationGuard {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            previous: replace(enabled),
+        }
+    }
+}
+
+impl Drop for NarrationGuard {
+    fn drop(&mut self) {
+        replace(self.previous);
+    }
+}
+
+/// Log a line of narration at the `info` level, if the narration is enabled.
+macro_rules! narrate {
+    ($($arg:tt)+) => {
+        if $crate::narration::is_enabled() {
+            log::info!($($arg)+);
+        }
+    };
+}
+
+pub(crate) use narrate;
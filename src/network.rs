@@ -0,0 +1,191 @@
+//! Choices made by a remote peer, like the opponent of a PvP battle.
+//!
+//! A [`RemoteController`] sends a [`BattleView`] to the peer every time a choice is needed, then waits for the
+//! [`RemoteChoice`] it sends back, referring to its action by [`ActionId`]. Messages go through a [`Transport`],
+//! which can be implemented over any connection (like a WebSocket). [`JsonLines`] is a ready-made transport
+//! exchanging one JSON document per line over any stream, like a [`TcpStream`](std::net::TcpStream).
+//!
+//! # Notes
+//!
+//! The view contains the teams as shown by the [`ChoicePrompt`], which are the whole teams unless the battle has a
+//! [`FogOfWar`](crate::visibility::FogOfWar). Battles with hidden information should set one, and stream the events to
+//! the peer through a [`TeamObserver`](crate::visibility::TeamObserver), so that it can't read data its player
+//! shouldn't see.
+
+use std::io::{BufRead, BufReader, Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::action::{ActionId, ChoiceReturn, Target};
+use crate::controller::{ChoicePrompt, Controller};
+use crate::error::NetworkError;
+use crate::member::MemberIdentifier;
+use crate::registry::ActionRegistry;
+use crate::team::Team;
+
+/// State of the battle sent to the remote peer to make a choice.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BattleView<M> {
+    /// Member suggested to perform the next action, if any.
+    pub performer: Option<MemberIdentifier>,
+    pub teams: Vec<Team<M>>,
+}
+
+/// Choice sent back by the remote peer, referring to its action by [`ActionId`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteChoice {
+    pub action: ActionId,
+    pub performers: Target,
+    pub targets: Target,
+}
+
+/// Connection to a remote peer, exchanging [`BattleView`]s and [`RemoteChoice`]s.
+pub trait Transport<M> {
+    /// Send the view of the battle to the peer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`NetworkError`] if the view could not be sent.
+    fn send(&mut self, view: &BattleView<M>) -> Result<(), NetworkError>;
+
+    /// Wait for the next choice made by the peer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`NetworkError`] if no valid choice could be received.
+    fn receive(&mut self) -> Result<RemoteChoice, NetworkError>;
+}
+
+/// [`Transport`] exchanging one JSON document per line over a stream.
+///
+/// # Notes
+///
+/// Both sides of the connection use the same format, so the peer can wrap its end of the stream in a [`JsonLines`]
+/// too, and exchange messages with [`JsonLines::read()`] and [`JsonLines::write()`].
+pub struct JsonLines<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: Read + Write> JsonLines<S> {
+    /// Create a new [`JsonLines`] transport over the stream.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        self.stream.get_ref()
+    }
+
+    /// Write a message as a single line of JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NetworkError::Malformed`] if the message can't be encoded, or [`NetworkError::Io`] if writing fails.
+    pub fn write<T: Serialize>(&mut self, message: &T) -> Result<(), NetworkError> {
+        let mut line =
+            serde_json::to_vec(message).map_err(|e| NetworkError::Malformed(e.to_string()))?;
+        line.push(b'\n');
+
+        let stream = self.stream.get_mut();
+
+        stream
+            .write_all(&line)
+            .and_then(|()| stream.flush())
+            .map_err(|e| NetworkError::Io(e.to_string()))
+    }
+
+    /// Wait for the next line and decode it as a message.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NetworkError::Closed`] if the stream ended, [`NetworkError::Io`] if reading fails,
+    /// or [`NetworkError::Malformed`] if the line can't be decoded.
+    pub fn read<T: DeserializeOwned>(&mut self) -> Result<T, NetworkError> {
+        let mut line = String::new();
+
+        match self.stream.read_line(&mut line) {
+            Ok(0) => Err(NetworkError::Closed),
+            Ok(_) => {
+                serde_json::from_str(&line).map_err(|e| NetworkError::Malformed(e.to_string()))
+            }
+            Err(e) => Err(NetworkError::Io(e.to_string())),
+        }
+    }
+}
+
+impl<M: Serialize, S: Read + Write> Transport<M> for JsonLines<S> {
+    fn send(&mut self, view: &BattleView<M>) -> Result<(), NetworkError> {
+        self.write(view)
+    }
+
+    fn receive(&mut self) -> Result<RemoteChoice, NetworkError> {
+        self.read()
+    }
+}
+
+/// Controller whose choices are made by a remote peer.
+///
+/// # Notes
+///
+/// Every choice is requested by sending a [`BattleView`], then blocks until a [`RemoteChoice`] is received.
+/// Actions are created from the registry. If the connection fails or the action is not registered,
+/// the choice is made by the fallback controller instead.
+pub struct RemoteController<M> {
+    registry: ActionRegistry<M>,
+    transport: Box<dyn Transport<M>>,
+    fallback: Box<dyn Controller<M>>,
+}
+
+impl<M> RemoteController<M> {
+    /// Create a new [`RemoteController`] resolving received actions against the registry.
+    pub fn new(
+        registry: ActionRegistry<M>,
+        transport: impl Transport<M> + 'static,
+        fallback: impl Controller<M> + 'static,
+    ) -> Self {
+        Self {
+            registry,
+            transport: Box::new(transport),
+            fallback: Box::new(fallback),
+        }
+    }
+}
+
+impl<M: Clone> Controller<M> for RemoteController<M> {
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        let view = BattleView {
+            performer: prompt.performer(),
+            teams: prompt.teams().to_vec(),
+        };
+
+        let choice = self
+            .transport
+            .send(&view)
+            .and_then(|()| self.transport.receive());
+
+        let choice = match choice {
+            Ok(choice) => choice,
+            Err(e) => {
+                log::warn!("{}, using the fallback controller", e);
+
+                return prompt.delegate(self.fallback.as_mut());
+            }
+        };
+
+        match self.registry.create(&choice.action) {
+            Some(action) => (action, choice.performers, choice.targets),
+            None => {
+                log::warn!(
+                    "Received unknown action {:?}, using the fallback controller",
+                    choice.action
+                );
+
+                prompt.delegate(self.fallback.as_mut())
+            }
+        }
+    }
+}
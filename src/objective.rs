@@ -0,0 +1,112 @@
+//! Per-team goals of asymmetric battles, like an escort where the heroes win by keeping a cart intact.
+//!
+//! Every team can be given its own [`Objective`]s, independently from the battle's
+//! [`EndCondition`](crate::battle::EndCondition). Their [`ObjectiveStatus`] is reported by the
+//! [`BattleOutcome`](crate::outcome::BattleOutcome), so campaign logic can branch on partial successes too.
+
+use crate::member::Member;
+use crate::state::BattleState;
+use crate::terrain::StructureId;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Goal of a team during a battle.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Defeat every member of a team. The progress is the amount of its defeated members.
+    DefeatTeam { team_id: usize },
+    /// Keep every member of a team alive. The progress is the amount of its members still alive.
+    Survive { team_id: usize },
+    /// Destroy a [`Structure`](crate::terrain::Structure). The progress is the damage it has taken.
+    DestroyStructure(StructureId),
+    /// Keep a [`Structure`](crate::terrain::Structure) standing. The progress is its health left.
+    ProtectStructure(StructureId),
+    /// Keep the noise of the battle below the limit.
+    /// Look at [`Action::noise()`](crate::action::Action::noise) for more information.
+    StayUndetected { noise_limit: u64 },
+}
+
+/// Status of an [`Objective`] at a point of the battle.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveStatus {
+    /// The objective has been completely achieved.
+    Succeeded,
+    /// The objective can't be achieved anymore, or the battle finished without any progress towards it.
+    Failed,
+    /// The objective has been achieved only in part, or is still in progress.
+    Partial { progress: u64, goal: u64 },
+}
+
+impl ObjectiveStatus {
+    /// Returns the status of an objective with some progress towards a goal.
+    ///
+    /// # Notes
+    ///
+    /// Reaching the goal is a success, while having no progress is a failure only once the battle has finished.
+    fn from_progress(progress: u64, goal: u64, finished: bool) -> Self {
+        if progress >= goal {
+            Self::Succeeded
+        } else if progress == 0 && finished {
+            Self::Failed
+        } else {
+            Self::Partial { progress, goal }
+        }
+    }
+}
+
+impl Objective {
+    /// Returns the status of the objective in the given state of a battle.
+    ///
+    /// # Notes
+    ///
+    /// Objectives referring to teams or structures that don't exist are always failed.
+    pub fn status<M: Member>(&self, state: &BattleState<M>, finished: bool) -> ObjectiveStatus {
+        match *self {
+            Self::DefeatTeam { team_id } => {
+                let Some(team) = state.teams().get(team_id) else {
+                    return ObjectiveStatus::Failed;
+                };
+
+                let members = team.member_list();
+                let defeated = members.iter().filter(|m| m.health() == 0).count();
+
+                ObjectiveStatus::from_progress(defeated as u64, members.len() as u64, finished)
+            }
+            Self::Survive { team_id } => {
+                let Some(team) = state.teams().get(team_id) else {
+                    return ObjectiveStatus::Failed;
+                };
+
+                let members = team.member_list();
+                let alive = members.iter().filter(|m| m.health() > 0).count();
+
+                // Survival is never failed before the whole team is defeated.
+                ObjectiveStatus::from_progress(alive as u64, members.len() as u64, true)
+            }
+            Self::DestroyStructure(id) => match state.structure(id) {
+                Some(s) => ObjectiveStatus::from_progress(
+                    s.max_health() - s.health(),
+                    s.max_health(),
+                    finished,
+                ),
+                None => ObjectiveStatus::Failed,
+            },
+            Self::ProtectStructure(id) => match state.structure(id) {
+                Some(s) => ObjectiveStatus::from_progress(s.health(), s.max_health(), true),
+                None => ObjectiveStatus::Failed,
+            },
+            Self::StayUndetected { noise_limit } => {
+                if state.noise() < noise_limit {
+                    ObjectiveStatus::Succeeded
+                } else {
+                    ObjectiveStatus::Failed
+                }
+            }
+        }
+    }
+}
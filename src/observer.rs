@@ -0,0 +1,26 @@
+//! Observers notified of every change happening during a [`Battle`](crate::battle::Battle).
+//!
+//! Frontends (like terminal or graphical user interfaces) can register a [`BattleObserver`] to react to each
+//! [`Event`] as soon as it's applied, instead of comparing the state of the teams before and after every turn.
+
+use crate::event::Event;
+use crate::state::BattleState;
+
+/// Listener of the [`Event`]s applied to a battle.
+///
+/// # Notes
+///
+/// Observers are invoked synchronously, right after the event has been applied and recorded,
+/// so the given [`BattleState`] already includes its effects.
+///
+/// This trait is implemented for any closure with a matching signature.
+pub trait BattleObserver<M> {
+    /// React to an event applied to the battle.
+    fn on_event(&mut self, event: &Event, state: &BattleState<M>);
+}
+
+impl<M, F: FnMut(&Event, &BattleState<M>)> BattleObserver<M> for F {
+    fn on_event(&mut self, event: &Event, state: &BattleState<M>) {
+        self(event, state)
+    }
+}
@@ -0,0 +1,337 @@
+//! Aggregate statistics of a played [`Battle`](crate::battle::Battle).
+//!
+//! A [`BattleOutcome`] is derived from the battle's [`EventLog`](crate::event::EventLog), so it can be built at any
+//! point of the battle, as well as from replayed states. With the `export` feature, outcomes can be exported
+//! to JSON and CSV to be consumed by external tooling.
+//!
+//! Outcomes also hold the experience earned by every member, which [`Levelable`](crate::member::Levelable) members
+//! can gain with [`Battle::claim_rewards()`](crate::battle::Battle::claim_rewards).
+//! The status of every team's [`Objective`] is reported independently, beyond which teams survived,
+//! together with the loot dropped by the defeated members.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+#[cfg(feature = "export")]
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use crate::event::Event;
+use crate::loot::LootDrop;
+use crate::member::{Member, MemberIdentifier};
+use crate::objective::{Objective, ObjectiveStatus};
+use crate::state::BattleState;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Statistics of a single member over the course of a battle.
+///
+/// # Notes
+///
+/// Damage and healing are attributed to every performer of the action that caused them.
+/// Amounts are the ones recorded in the events, before any saturation to the members' health.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemberReport {
+    pub member: MemberIdentifier,
+    /// Health of the member at the time the outcome was built.
+    pub health: u64,
+    pub damage_dealt: u64,
+    pub damage_taken: u64,
+    pub healing_done: u64,
+    pub healing_received: u64,
+    /// Amount of actions performed by the member.
+    pub actions: u64,
+    /// Experience earned by the member from the defeated members of the other teams.
+    pub experience: u64,
+    /// Amount of levels gained by the member once its experience was claimed.
+    pub levels_gained: u32,
+}
+
+impl MemberReport {
+    /// Create a new, empty [`MemberReport`].
+    pub fn new(member: MemberIdentifier) -> Self {
+        Self {
+            member,
+            ..Default::default()
+        }
+    }
+}
+
+/// Result of a battle, as far as its teams are concerned.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Winner {
+    /// The battle has finished with a single team standing.
+    Team(usize),
+    /// The battle has finished without any team standing.
+    Draw,
+    /// The battle is still in progress, or has finished with more teams standing, like when a structure is destroyed.
+    ///
+    /// # Notes
+    ///
+    /// Look at the [objectives](BattleOutcome::objectives) of the teams to decide who won in such battles.
+    #[default]
+    Undecided,
+}
+
+/// Status of the [`Objective`] of a team.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectiveReport {
+    pub team_id: usize,
+    pub objective: Objective,
+    pub status: ObjectiveStatus,
+}
+
+/// Aggregate statistics of a battle.
+///
+/// # Notes
+///
+/// Every member still alive when the outcome is built earns the whole [experience yield](Member::experience_yield)
+/// of each member of the other teams defeated during the battle (and not revived since), without sharing it.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BattleOutcome {
+    /// Seed of the battle's source of randomness, to reproduce the battle.
+    pub seed: u64,
+    /// Amount of turns played.
+    pub turns: u64,
+    /// Whether the battle has come to an end.
+    pub finished: bool,
+    /// IDs of the teams with at least one member still alive.
+    pub surviving_teams: Vec<usize>,
+    /// Team that won the battle, if it has been decided.
+    pub winner: Winner,
+    /// Statistics of every member, ordered by team and member ID.
+    pub members: Vec<MemberReport>,
+    /// Status of the objectives of every team, in the order they were given.
+    pub objectives: Vec<ObjectiveReport>,
+    /// Items dropped by the defeated members, in the order they were dropped.
+    pub loot: Vec<LootDrop>,
+}
+
+impl BattleOutcome {
+    /// Build the outcome of a battle from its current state.
+    pub fn from_state<M: Member>(state: &BattleState<M>) -> Self {
+        let mut reports: BTreeMap<MemberIdentifier, MemberReport> = BTreeMap::new();
+
+        // Members that were revived don't yield any experience.
+        let mut yields: BTreeMap<usize, u64> = BTreeMap::new();
+
+        for (handle, (team_id, experience)) in &state.fallen {
+            let revived = state
+                .resolve(*handle)
+                .and_then(|id| state.teams().get(id.team_id)?.member(id.member_id))
+                .is_some_and(|m| m.health() > 0);
+
+            if !revived {
+                let total = yields.entry(*team_id).or_default();
+                *total = total.saturating_add(*experience);
+            }
+        }
+
+        let total_yield = yields.values().fold(0u64, |sum, y| sum.saturating_add(*y));
+
+        for (team_id, team) in state.teams().iter().enumerate() {
+            for (member_id, member) in team.member_list().iter().enumerate() {
+                let id = MemberIdentifier::new(team_id, member_id);
+
+                let experience = if member.health() > 0 {
+                    total_yield.saturating_sub(yields.get(&team_id).copied().unwrap_or(0))
+                } else {
+                    0
+                };
+
+                reports.insert(
+                    id,
+                    MemberReport {
+                        health: member.health(),
+                        experience,
+                        ..MemberReport::new(id)
+                    },
+                );
+            }
+        }
+
+        let mut turns = 0;
+        let mut finished = false;
+        let mut performers: Vec<MemberIdentifier> = Vec::new();
+        let mut loot = Vec::new();
+
+        for event in state.events().events() {
+            match event {
+                Event::TurnStarted { turn_number } => {
+                    turns = *turn_number;
+                    performers.clear();
+                }
+                Event::ActionPerformed {
+                    performers: current,
+                    ..
+                } => {
+                    performers.clone_from(current);
+
+                    for p in &performers {
+                        reports
+                            .entry(*p)
+                            .or_insert_with(|| MemberReport::new(*p))
+                            .actions += 1;
+                    }
+                }
+                Event::Damaged { member, amount } => {
+                    let report = reports
+                        .entry(*member)
+                        .or_insert_with(|| MemberReport::new(*member));
+                    report.damage_taken = report.damage_taken.saturating_add(*amount);
+
+                    for p in &performers {
+                        let report = reports.entry(*p).or_insert_with(|| MemberReport::new(*p));
+                        report.damage_dealt = report.damage_dealt.saturating_add(*amount);
+                    }
+                }
+                Event::Healed { member, amount } => {
+                    let report = reports
+                        .entry(*member)
+                        .or_insert_with(|| MemberReport::new(*member));
+                    report.healing_received = report.healing_received.saturating_add(*amount);
+
+                    for p in &performers {
+                        let report = reports.entry(*p).or_insert_with(|| MemberReport::new(*p));
+                        report.healing_done = report.healing_done.saturating_add(*amount);
+                    }
+                }
+                Event::LootDropped {
+                    member,
+                    item,
+                    amount,
+                } => loot.push(LootDrop {
+                    member: *member,
+                    item: item.clone(),
+                    amount: *amount,
+                }),
+                Event::BattleFinished => finished = true,
+                _ => (),
+            }
+        }
+
+        let surviving_teams: Vec<usize> = state
+            .teams()
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.member_list().iter().any(|m| m.health() > 0))
+            .map(|(team_id, _)| team_id)
+            .collect();
+
+        let winner = match surviving_teams[..] {
+            _ if !finished => Winner::Undecided,
+            [team_id] => Winner::Team(team_id),
+            [] => Winner::Draw,
+            _ => Winner::Undecided,
+        };
+
+        let objectives = state
+            .objectives()
+            .iter()
+            .map(|&(team_id, objective)| ObjectiveReport {
+                team_id,
+                objective,
+                status: objective.status(state, finished),
+            })
+            .collect();
+
+        Self {
+            seed: state.rng.seed(),
+            turns,
+            finished,
+            surviving_teams,
+            winner,
+            members: reports.into_values().collect(),
+            objectives,
+            loot,
+        }
+    }
+
+    /// Returns an iterator over the IDs of the members still alive, ordered by team and member ID.
+    pub fn surviving_members(&self) -> impl Iterator<Item = MemberIdentifier> + '_ {
+        self.members
+            .iter()
+            .filter(|r| r.health > 0)
+            .map(|r| r.member)
+    }
+
+    /// Returns an iterator over the objectives of a team, together with their status.
+    pub fn team_objectives(&self, team_id: usize) -> impl Iterator<Item = &ObjectiveReport> {
+        self.objectives.iter().filter(move |r| r.team_id == team_id)
+    }
+
+    /// Returns the report of a specific member.
+    pub fn member(&self, member: MemberIdentifier) -> Option<&MemberReport> {
+        self.members.iter().find(|r| r.member == member)
+    }
+
+    /// Export the outcome as a JSON object.
+    #[cfg(feature = "export")]
+    pub fn to_json(&self) -> String {
+        let surviving_teams = self
+            .surviving_teams
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let members = self
+            .members
+            .iter()
+            .map(|r| {
+                format!(
+                    "{{\"team_id\":{},\"member_id\":{},\"health\":{},\"damage_dealt\":{},\"damage_taken\":{},\"healing_done\":{},\"healing_received\":{},\"actions\":{},\"experience\":{},\"levels_gained\":{}}}",
+                    r.member.team_id,
+                    r.member.member_id,
+                    r.health,
+                    r.damage_dealt,
+                    r.damage_taken,
+                    r.healing_done,
+                    r.healing_received,
+                    r.actions,
+                    r.experience,
+                    r.levels_gained
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"seed\":{},\"turns\":{},\"finished\":{},\"surviving_teams\":[{}],\"members\":[{}]}}",
+            self.seed, self.turns, self.finished, surviving_teams, members
+        )
+    }
+
+    /// Export the per-member statistics as CSV, with a header line and one line per member.
+    #[cfg(feature = "export")]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "team_id,member_id,health,damage_dealt,damage_taken,healing_done,healing_received,actions,experience,levels_gained\n",
+        );
+
+        for r in &self.members {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                r.member.team_id,
+                r.member.member_id,
+                r.health,
+                r.damage_dealt,
+                r.damage_taken,
+                r.healing_done,
+                r.healing_received,
+                r.actions,
+                r.experience,
+                r.levels_gained
+            ));
+        }
+
+        csv
+    }
+}
@@ -0,0 +1,121 @@
+//! Summary of what persists once a [`Battle`](crate::battle::Battle) is over, to be merged back into save data.
+//!
+//! A [`BattleDelta`] reports the state every member is left in (like its remaining health and the tags it still
+//! holds), together with the items used up by every member and team, so that the game layer outside of battles
+//! doesn't have to diff the returned teams against copies taken before the battle.
+
+use alloc::vec::Vec;
+
+use crate::component::Tag;
+use crate::inventory::{Inventory, InventoryOwner};
+use crate::member::{Member, MemberHandle, MemberIdentifier};
+use crate::state::BattleState;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// State a single member is left in by a battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberDelta<P> {
+    pub member: MemberIdentifier,
+    /// Stable handle of the member. Starting members are given handles in team and member order.
+    pub handle: MemberHandle,
+    /// Properties of the member, without the ones granted by equipment or team auras.
+    pub properties: P,
+    /// Health left to the member.
+    pub health: u64,
+    /// Whether the member is [defeated](Member::is_defeated).
+    pub defeated: bool,
+    /// Items used up from the member's own inventory.
+    pub items_consumed: Inventory,
+    /// Items left in the member's own inventory.
+    pub inventory: Inventory,
+    /// Tags held by the member, together with their stacks, ordered by tag.
+    pub tags: Vec<(Tag, u32)>,
+}
+
+/// Items used up and left by a team.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TeamDelta {
+    pub team_id: usize,
+    /// Items used up from the team's shared inventory.
+    pub items_consumed: Inventory,
+    /// Items left in the team's shared inventory.
+    pub inventory: Inventory,
+}
+
+/// Summary of what persists once a battle is over.
+///
+/// # Notes
+///
+/// Members that left the battle aren't reported, while the ones that joined it (like summons) are.
+/// Look at [`MemberDelta::handle`] to tell them apart from the starting members.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BattleDelta<P> {
+    /// State of every member, ordered by team and member ID.
+    pub members: Vec<MemberDelta<P>>,
+    /// Items of every team, ordered by team ID.
+    pub teams: Vec<TeamDelta>,
+}
+
+impl<P: Clone> BattleDelta<P> {
+    /// Build the delta of a battle from its current state.
+    pub fn from_state<M>(state: &BattleState<M>) -> Self
+    where
+        M: Member<Properties = P>,
+    {
+        let mut members = Vec::new();
+        let mut teams = Vec::new();
+
+        for (team_id, team) in state.teams().iter().enumerate() {
+            for (member_id, member) in team.member_list().iter().enumerate() {
+                let id = MemberIdentifier::new(team_id, member_id);
+
+                let Some(handle) = state.handle(id) else {
+                    log::warn!("Could not find the handle of member {:?}", id);
+
+                    continue;
+                };
+
+                members.push(MemberDelta {
+                    member: id,
+                    handle,
+                    properties: member.member_properties().clone(),
+                    health: member.health(),
+                    defeated: member.is_defeated(),
+                    items_consumed: items(state, InventoryOwner::Member(id)),
+                    inventory: state
+                        .inventory(InventoryOwner::Member(id))
+                        .cloned()
+                        .unwrap_or_default(),
+                    tags: state
+                        .components()
+                        .tags(id)
+                        .map(|(tag, stacks)| (tag.clone(), stacks))
+                        .collect(),
+                });
+            }
+
+            teams.push(TeamDelta {
+                team_id,
+                items_consumed: items(state, InventoryOwner::Team(team_id)),
+                inventory: team.inventory().clone(),
+            });
+        }
+
+        Self { members, teams }
+    }
+
+    /// Returns the delta of a member, if it's still in the battle.
+    pub fn member(&self, handle: MemberHandle) -> Option<&MemberDelta<P>> {
+        self.members.iter().find(|d| d.handle == handle)
+    }
+}
+
+/// Returns the items used up by a member or team.
+fn items<M: Member>(state: &BattleState<M>, owner: InventoryOwner) -> Inventory {
+    state.consumed_items(owner).cloned().unwrap_or_default()
+}
@@ -0,0 +1,24 @@
+//! Commonly used types and traits, to be glob imported by crate consumers.
+//!
+//! ```
+//! use fierceful_atto::prelude::*;
+//! ```
+
+pub use crate::action::{
+    Action, ActionId, ActionKind, ActionState, AsAny, ChoiceCallback, ChoiceReturn, Context,
+    Target, TargetFilter,
+};
+pub use crate::battle::{
+    self, Battle, BattleStep, Builder, EndCondition, InvalidActionFallback, PerformerModel,
+    TurnMode, TurnPhase, TurnResult,
+};
+pub use crate::controller::{ChoicePrompt, Controller, HotSeatController};
+pub use crate::equipment::Equipment;
+pub use crate::error::{ActionError, BattleError, BuildError};
+pub use crate::event::Event;
+pub use crate::member::{Member, MemberHandle, MemberIdentifier, Properties, Statistics};
+pub use crate::resource::{Cost, Resource};
+pub use crate::rng::BattleRng;
+pub use crate::search::SuggestedPerformerCriteria;
+pub use crate::stage::{Stat, StatStages};
+pub use crate::team::Team;
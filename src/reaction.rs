@@ -0,0 +1,103 @@
+//! Reactions of defending [`Member`](crate::member::Member)s to the actions performed against them.
+//!
+//! After an action resolves, the [`TurnSystem`](crate::battle::TurnSystem) looks for every member that was
+//! [targeted](crate::event::Event::Targeted) or [damaged](crate::event::Event::Damaged) by it, and invokes the
+//! [`ReactionHandler`]s registered for them. Handlers may answer with a counter action, which is resolved right away.
+//!
+//! Members can also threaten a [`ZoneOfControl`] with an [`OpportunityHandler`]: enemies moving out of it are
+//! attacked before their movement completes.
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+
+use crate::action::ChoiceReturn;
+use crate::event::Event;
+use crate::formation::ZoneOfControl;
+use crate::member::MemberIdentifier;
+use crate::state::BattleState;
+
+/// Function type invoked when a member is affected by an action.
+///
+/// The handler receives the reacting member, the events produced by the action and the battle's state,
+/// and may return a counter action to be resolved right after the triggering one.
+///
+/// # Notes
+///
+/// Counter actions don't trigger further reactions, to avoid endless exchanges.
+pub type ReactionHandler<M> =
+    Box<dyn Fn(MemberIdentifier, &[Event], &BattleState<M>) -> Option<ChoiceReturn<M>>>;
+
+/// Function type invoked when an enemy moves out of a cell threatened by a member.
+///
+/// The handler receives the threatening member, the moving member and the battle's state,
+/// and may return an attack of opportunity, resolved before the movement completes.
+///
+/// # Notes
+///
+/// The movement is stopped if the moving member is defeated or [interrupted](crate::action::Context::interrupt)
+/// by the attack. Costs and cooldowns of the attack are checked and paid like for any other action.
+pub type OpportunityHandler<M> =
+    Box<dyn Fn(MemberIdentifier, MemberIdentifier, &BattleState<M>) -> Option<ChoiceReturn<M>>>;
+
+/// Function behind an [`OpportunityHandler`].
+type OpportunityFn<M> =
+    dyn Fn(MemberIdentifier, MemberIdentifier, &BattleState<M>) -> Option<ChoiceReturn<M>>;
+
+/// [`OpportunityHandler`] registered for a member, together with the cells it threatens.
+///
+/// # Notes
+///
+/// The handler is shared, so that registered opportunity attacks can be captured by checkpoints.
+pub(crate) struct OpportunityAttack<M> {
+    pub(crate) member: MemberIdentifier,
+    pub(crate) zone: ZoneOfControl,
+    pub(crate) handler: Rc<OpportunityFn<M>>,
+}
+
+impl<M> Clone for OpportunityAttack<M> {
+    fn clone(&self) -> Self {
+        Self {
+            member: self.member,
+            zone: self.zone,
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+/// Members a [`ReactionHandler`] is registered for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionScope {
+    /// Only the given member reacts.
+    Member(MemberIdentifier),
+    /// Every member of the given team reacts.
+    Team { team_id: usize },
+}
+
+impl ReactionScope {
+    /// Returns whether the given member is covered by this scope.
+    pub fn contains(&self, member: MemberIdentifier) -> bool {
+        match self {
+            Self::Member(m) => *m == member,
+            Self::Team { team_id } => member.team_id == *team_id,
+        }
+    }
+}
+
+/// Returns every member targeted or damaged in a list of events, in order of appearance and without duplicates.
+pub fn affected_members(events: &[Event]) -> Vec<MemberIdentifier> {
+    let mut members = Vec::new();
+
+    for event in events {
+        let member = match event {
+            Event::Targeted { member } | Event::Damaged { member, .. } => *member,
+            _ => continue,
+        };
+
+        if !members.contains(&member) {
+            members.push(member);
+        }
+    }
+
+    members
+}
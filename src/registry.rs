@@ -0,0 +1,59 @@
+//! Registry of the actions available in a game, indexed by their [`ActionId`].
+//!
+//! Data-driven content (like enemy patterns loaded from encounter files) references actions by ID,
+//! and resolves them against an [`ActionRegistry`] to create new instances when needed.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+
+use crate::action::{Action, ActionId};
+
+/// Function type used to create a new instance of an action every time it's needed.
+pub type ActionFactory<M> = Box<dyn Fn() -> Box<dyn Action<M>>>;
+
+/// Collection of [`ActionFactory`]s indexed by [`ActionId`].
+pub struct ActionRegistry<M> {
+    factories: BTreeMap<ActionId, ActionFactory<M>>,
+}
+
+impl<M> ActionRegistry<M> {
+    /// Create a new, empty [`ActionRegistry`].
+    pub fn new() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Register an action factory under an ID, replacing any previous factory with the same ID.
+    pub fn register(&mut self, id: ActionId, factory: ActionFactory<M>) {
+        self.factories.insert(id, factory);
+    }
+
+    /// Register an action factory under an ID, returning the registry.
+    pub fn with(mut self, id: ActionId, factory: ActionFactory<M>) -> Self {
+        self.register(id, factory);
+
+        self
+    }
+
+    /// Returns whether an action is registered under the ID.
+    pub fn contains(&self, id: &ActionId) -> bool {
+        self.factories.contains_key(id)
+    }
+
+    /// Create a new instance of the action registered under the ID, if any.
+    pub fn create(&self, id: &ActionId) -> Option<Box<dyn Action<M>>> {
+        self.factories.get(id).map(|factory| factory())
+    }
+
+    /// Returns an iterator over the IDs of all registered actions.
+    pub fn ids(&self) -> impl Iterator<Item = &ActionId> {
+        self.factories.keys()
+    }
+}
+
+impl<M> Default for ActionRegistry<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
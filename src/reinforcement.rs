@@ -0,0 +1,188 @@
+//! Scheduling of [`Member`]s joining a [`Battle`](crate::battle::Battle) after it has started.
+//!
+//! Reinforcements are the building blocks of wave-based encounters: once their [`Trigger`] fires,
+//! new members are spawned from a template and added to one of the battling teams.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::event::Event;
+use crate::member::Member;
+use crate::narration::narrate;
+use crate::rng::BattleRng;
+use crate::state::BattleState;
+
+/// Function type used to spawn the members of a [`Reinforcement`].
+///
+/// # Notes
+///
+/// The battle's source of randomness is given to allow random encounters that can still be replayed.
+pub type MemberTemplate<M> = Box<dyn Fn(&mut BattleRng) -> Vec<M>>;
+
+/// Function type used to check whether an [`Event`] should trigger a [`Reinforcement`].
+pub type EventTrigger<M> = Box<dyn Fn(&Event, &BattleState<M>) -> bool>;
+
+/// Condition that makes a [`Reinforcement`] join the battle.
+#[non_exhaustive]
+pub enum Trigger<M> {
+    /// Join the battle at the end of the given turn, ready to act from the next one.
+    Turn(u64),
+    /// Join the battle once every member of the given team has been defeated.
+    TeamDefeated { team_id: usize },
+    /// Join the battle once the noise of the battle reaches the threshold, like guards alerted by a fight.
+    /// Look at [`Action::noise()`](crate::action::Action::noise) for more information.
+    Noise { threshold: u64 },
+    /// Join the battle once an event satisfying the condition has been applied.
+    Event(EventTrigger<M>),
+}
+
+/// Group of members joining a team once its [`Trigger`] fires.
+pub struct Reinforcement<M> {
+    team_id: usize,
+    trigger: Trigger<M>,
+    template: MemberTemplate<M>,
+    /// Position of the reinforcement in the order it was scheduled.
+    order: usize,
+}
+
+impl<M> Reinforcement<M> {
+    /// Create a new [`Reinforcement`] spawning members from the template into the team with the given ID.
+    pub fn new(team_id: usize, trigger: Trigger<M>, template: MemberTemplate<M>) -> Self {
+        Self {
+            team_id,
+            trigger,
+            template,
+            order: 0,
+        }
+    }
+
+    /// Returns the ID of the team joined by this reinforcement.
+    pub fn team_id(&self) -> usize {
+        self.team_id
+    }
+
+    /// Returns the condition that makes this reinforcement join the battle.
+    pub fn trigger(&self) -> &Trigger<M> {
+        &self.trigger
+    }
+}
+
+/// Collection of [`Reinforcement`]s waiting to join a battle.
+///
+/// # Notes
+///
+/// Every reinforcement joins the battle at most once. Schedule multiple reinforcements to design multiple waves.
+pub struct ReinforcementScheduler<M> {
+    pending: Vec<Reinforcement<M>>,
+    /// Reinforcements that already joined the battle, kept to be rolled back.
+    joined: Vec<Reinforcement<M>>,
+    /// Amount of events of the battle's log already checked for triggers.
+    checked_events: usize,
+}
+
+impl<M> ReinforcementScheduler<M> {
+    /// Create a new, empty [`ReinforcementScheduler`].
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            joined: Vec::new(),
+            checked_events: 0,
+        }
+    }
+
+    /// Schedule a new [`Reinforcement`].
+    pub fn add(&mut self, mut reinforcement: Reinforcement<M>) {
+        reinforcement.order = self.pending.len() + self.joined.len();
+
+        self.pending.push(reinforcement);
+    }
+
+    /// Returns the reinforcements that have yet to join the battle.
+    pub fn pending(&self) -> &[Reinforcement<M>] {
+        &self.pending
+    }
+
+    /// Capture which reinforcements have yet to join the battle.
+    pub(crate) fn checkpoint(&self) -> ReinforcementCheckpoint {
+        ReinforcementCheckpoint {
+            pending: self.pending.iter().map(|r| r.order).collect(),
+            checked_events: self.checked_events,
+        }
+    }
+
+    /// Bring back the reinforcements that were still pending when a checkpoint was captured.
+    pub(crate) fn rollback(&mut self, checkpoint: ReinforcementCheckpoint) {
+        let (mut pending, joined): (Vec<_>, Vec<_>) = core::mem::take(&mut self.pending)
+            .into_iter()
+            .chain(core::mem::take(&mut self.joined))
+            .partition(|r| checkpoint.pending.contains(&r.order));
+
+        pending.sort_by_key(|r| r.order);
+
+        self.pending = pending;
+        self.joined = joined;
+        self.checked_events = checkpoint.checked_events;
+    }
+}
+
+/// Reinforcements captured by a [`Checkpoint`](crate::checkpoint::Checkpoint).
+pub(crate) struct ReinforcementCheckpoint {
+    /// Scheduling order of the reinforcements yet to join the battle.
+    pending: Vec<usize>,
+    checked_events: usize,
+}
+
+impl<M: Member> ReinforcementScheduler<M> {
+    /// Spawn every reinforcement whose trigger fired by the end of the given turn.
+    ///
+    /// Returns the amount of reinforcements that joined the battle.
+    pub(crate) fn spawn_due(&mut self, data: &mut BattleState<M>, turn_number: u64) -> usize {
+        let new_events = data.events().since(self.checked_events);
+
+        let (due, pending): (Vec<_>, Vec<_>) = core::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|r| match &r.trigger {
+                Trigger::Turn(turn) => turn_number >= *turn,
+                Trigger::TeamDefeated { team_id } => data
+                    .teams()
+                    .get(*team_id)
+                    .is_some_and(|t| t.member_list().iter().all(|m| m.health() == 0)),
+                Trigger::Noise { threshold } => data.noise() >= *threshold,
+                Trigger::Event(condition) => new_events.iter().any(|e| condition(e, data)),
+            });
+
+        self.pending = pending;
+
+        for reinforcement in &due {
+            let members = (reinforcement.template)(&mut data.rng);
+
+            narrate!(
+                "{} member(s) joined the team with id {} as reinforcements",
+                members.len(),
+                reinforcement.team_id
+            );
+
+            for member in members {
+                if data.add_member(reinforcement.team_id, member).is_none() {
+                    log::warn!(
+                        "Reinforced team with id {} was not found",
+                        reinforcement.team_id
+                    );
+                }
+            }
+        }
+
+        self.checked_events = data.events().len();
+
+        let joined = due.len();
+        self.joined.extend(due);
+
+        joined
+    }
+}
+
+impl<M> Default for ReinforcementScheduler<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
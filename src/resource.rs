@@ -0,0 +1,32 @@
+//! Resource pools (like mana or stamina) and the costs actions spend from them.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kind of resource pool a [`Member`](crate::member::Member) may own.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Resource {
+    /// Magic points, usually spent to cast spells.
+    Mana,
+    /// Physical energy, usually spent to perform special moves.
+    Stamina,
+    /// Any other game-specific resource, identified by a custom index.
+    Custom(u16),
+}
+
+/// Amount of a [`Resource`] that must be spent to perform an [`Action`](crate::action::Action).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cost {
+    pub resource: Resource,
+    pub amount: u64,
+}
+
+impl Cost {
+    /// Create a new [`Cost`] of the given amount of resource.
+    pub fn new(resource: Resource, amount: u64) -> Self {
+        Self { resource, amount }
+    }
+}
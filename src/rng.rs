@@ -0,0 +1,166 @@
+//! Seedable source of randomness shared by every participant of a [`Battle`](crate::battle::Battle).
+//!
+//! All random decisions (critical hits, target picks, AI choices...) should draw from the [`BattleRng`] owned
+//! by the battle, so that battles started with the same seed and inputs can be reproduced exactly.
+
+use core::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Small and fast pseudo-random number generator based on the SplitMix64 algorithm.
+///
+/// # Notes
+///
+/// This generator is NOT cryptographically secure. It is only meant to drive game logic.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BattleRng {
+    seed: u64,
+    state: u64,
+}
+
+impl BattleRng {
+    /// Create a new [`BattleRng`] from a specific seed.
+    pub fn from_seed(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    /// Create a new [`BattleRng`] seeded with the current system time.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Notes
+    ///
+    /// The chosen seed can be retrieved with [`BattleRng::seed()`] to reproduce the same sequence later on.
+    #[cfg(feature = "std")]
+    pub fn from_time() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+
+        Self::from_seed(seed)
+    }
+
+    /// Create a new [`BattleRng`] for a battle whose seed wasn't chosen.
+    #[cfg(feature = "std")]
+    pub(crate) fn unseeded() -> Self {
+        Self::from_time()
+    }
+
+    /// Create a new [`BattleRng`] for a battle whose seed wasn't chosen.
+    ///
+    /// Without a system clock to seed it with, every such battle starts from the same seed.
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn unseeded() -> Self {
+        Self::from_seed(0)
+    }
+
+    /// Returns the seed this generator was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Create an independent [`BattleRng`] for a subsystem, derived from this generator's seed.
+    ///
+    /// # Notes
+    ///
+    /// The derived generator only depends on the original seed and the namespace, not on how many numbers
+    /// were already drawn. Subsystems (like loot or AI) can thus consume randomness without affecting the battle's
+    /// own sequence, keeping replays deterministic. Look at [`derive_seed()`] for more information.
+    pub fn derive(&self, namespace: &str) -> Self {
+        Self::from_seed(derive_seed(self.seed, namespace))
+    }
+
+    /// Create an independent [`BattleRng`] for one of many instances of a subsystem (like each member's AI).
+    ///
+    /// # Notes
+    ///
+    /// This is equivalent to [`BattleRng::derive()`], with the index mixed into the derived seed.
+    pub fn derive_indexed(&self, namespace: &str, index: u64) -> Self {
+        Self::from_seed(mix(derive_seed(self.seed, namespace) ^ mix(index)))
+    }
+
+    /// Returns the next random `u64` of the sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed random number within the given range.
+    ///
+    /// # Panics
+    ///
+    /// The function will panic if the range is empty.
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(
+            !range.is_empty(),
+            "cannot generate a number from an empty range"
+        );
+
+        let span = range.end - range.start;
+
+        // Reject the values that would make the modulo operation biased.
+        let zone = u64::MAX - (u64::MAX % span);
+
+        loop {
+            let value = self.next_u64();
+
+            if value < zone {
+                return range.start + value % span;
+            }
+        }
+    }
+
+    /// Returns `true` with a probability of `numerator / denominator`.
+    ///
+    /// # Panics
+    ///
+    /// The function will panic if `denominator` is 0.
+    pub fn gen_ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        assert!(denominator != 0, "ratio denominator must not be 0");
+
+        self.gen_range(0..denominator) < numerator
+    }
+
+    /// Returns a reference to a random element of the slice, or `None` if it's empty.
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            return None;
+        }
+
+        let index = self.gen_range(0..slice.len() as u64) as usize;
+
+        slice.get(index)
+    }
+}
+
+/// Derive a child seed from a master seed and a namespace.
+///
+/// # Notes
+///
+/// The namespace is hashed with FNV-1a, which (unlike the standard library hasher) is stable across platforms
+/// and compiler versions, and mixed with the master seed. Different namespaces produce uncorrelated seeds.
+pub fn derive_seed(seed: u64, namespace: &str) -> u64 {
+    const FNV_OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+    let hash = namespace.bytes().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+
+    mix(seed ^ mix(hash))
+}
+
+/// SplitMix64 finalizer, spreading every bit of the input over the whole output.
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
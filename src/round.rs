@@ -0,0 +1,82 @@
+//! Rounds, in which every alive member is suggested to perform exactly once.
+//!
+//! Rounds are layered on top of turns: once enabled with [`Builder::set_round_order()`](crate::battle::Builder::set_round_order),
+//! each round lines up the alive members following a [`RoundOrder`], and every following turn is played by the next
+//! of them. A round starts with an [`Event::RoundStarted`](crate::event::Event::RoundStarted) and ends with an
+//! [`Event::RoundEnded`](crate::event::Event::RoundEnded) once all of its members have acted.
+//! Round-scoped effects can be applied by [`PhaseHook`](crate::battle::PhaseHook)s registered for the
+//! [`RoundStart`](crate::battle::TurnPhase::RoundStart) and [`RoundEnd`](crate::battle::TurnPhase::RoundEnd) phases.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::member::{Member, MemberIdentifier};
+use crate::rng::BattleRng;
+use crate::team::Team;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Order in which the alive members act during a round.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RoundOrder {
+    /// Every member of the first team acts, followed by every member of the second team and so on.
+    #[default]
+    TeamByTeam,
+    /// The first member of every team acts, followed by the second member of every team and so on.
+    Interleaved,
+    /// Members act in a random order, shuffled with the battle's source of randomness at the start of every round.
+    Random,
+}
+
+impl RoundOrder {
+    /// Line up the alive members of the given teams.
+    pub(crate) fn arrange<M: Member>(
+        &self,
+        team_list: &[Team<M>],
+        rng: &mut BattleRng,
+    ) -> VecDeque<MemberIdentifier> {
+        let alive = |team_id: usize, member_id: usize| {
+            team_list[team_id]
+                .member(member_id)
+                .is_some_and(|m| m.health() > 0)
+        };
+
+        let mut members: Vec<MemberIdentifier> = match self {
+            Self::TeamByTeam | Self::Random => team_list
+                .iter()
+                .enumerate()
+                .flat_map(|(team_id, team)| {
+                    (0..team.member_list().len()).map(move |m| MemberIdentifier::new(team_id, m))
+                })
+                .collect(),
+            Self::Interleaved => {
+                let longest = team_list
+                    .iter()
+                    .map(|t| t.member_list().len())
+                    .max()
+                    .unwrap_or(0);
+
+                (0..longest)
+                    .flat_map(|member_id| {
+                        (0..team_list.len()).map(move |t| MemberIdentifier::new(t, member_id))
+                    })
+                    .collect()
+            }
+        };
+
+        members.retain(|id| alive(id.team_id, id.member_id));
+
+        if *self == Self::Random {
+            // Fisher-Yates shuffle.
+            for i in (1..members.len()).rev() {
+                let j = rng.gen_range(0..i as u64 + 1) as usize;
+                members.swap(i, j);
+            }
+        }
+
+        members.into()
+    }
+}
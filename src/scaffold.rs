@@ -0,0 +1,465 @@
+//! Generator of minimal game skeletons, to get new projects past the trait boilerplate.
+//!
+//! A [`Scaffold`] is described with a few parameters (the name of the crate, of the member type and the teams
+//! of the first encounter) and produces the files of a binary crate ready to be built:
+//!
+//! - `Cargo.toml`, depending on this crate, `serde` and `ron`.
+//! - `src/member.rs`, with the [`Member`](crate::member::Member) implementation and its statistics, properties and equipment.
+//! - `src/controller.rs`, with a controller attacking the first alive enemy.
+//! - `src/main.rs`, loading the encounter and running the battle until its conclusion.
+//! - `encounter.ron`, with the teams of the encounter.
+//!
+//! ```no_run
+//! use fierceful_atto::scaffold::Scaffold;
+//!
+//! Scaffold::new("my-game")
+//!     .with_member_type("Hero")
+//!     .with_team("Heroes", &[("Picco", 100, 15)])
+//!     .with_team("Goblins", &[("Bacco", 60, 10), ("Tacco", 60, 10)])
+//!     .write_to("my-game")
+//!     .expect("the skeleton couldn't be written");
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CARGO_TEMPLATE: &str = r#"[package]
+name = "$crate"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+fierceful-atto = { $dependency }
+ron = "0.8"
+serde = { version = "1.0", features = ["derive"] }
+"#;
+
+const MEMBER_TEMPLATE: &str = r#"use fierceful_atto::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct $Member {
+    name: String,
+    statistics: Stats,
+    properties: Props,
+}
+
+impl $Member {
+    pub fn new(name: String, statistics: Stats) -> Self {
+        Self {
+            name,
+            statistics,
+            properties: Props::from(statistics),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub max_health: u64,
+    pub base_attack: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Props {
+    pub health: u64,
+    pub attack: u64,
+}
+
+pub struct Gear;
+
+impl Member for $Member {
+    type Statistics = Stats;
+    type Properties = Props;
+    type Equipment = Gear;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn member_properties(&self) -> &Props {
+        &self.properties
+    }
+
+    fn member_properties_mut(&mut self) -> &mut Props {
+        &mut self.properties
+    }
+
+    fn statistics(&self) -> &Stats {
+        &self.statistics
+    }
+
+    fn equipment(&self) -> &Gear {
+        &Gear
+    }
+}
+
+impl From<Stats> for Props {
+    fn from(statistics: Stats) -> Self {
+        Self {
+            health: statistics.max_health,
+            attack: statistics.base_attack,
+        }
+    }
+}
+
+impl Properties for Props {
+    fn health(&self) -> u64 {
+        self.health
+    }
+
+    fn health_mut(&mut self) -> &mut u64 {
+        &mut self.health
+    }
+
+    fn attack(&self) -> u64 {
+        self.attack
+    }
+}
+
+impl Equipment for Gear {
+    type Properties = Props;
+
+    fn associated_properties(&self) -> Props {
+        Props {
+            health: 0,
+            attack: 0,
+        }
+    }
+}
+
+impl Statistics for Stats {
+    fn reference_health(&self) -> u64 {
+        self.max_health
+    }
+
+    fn base_attack(&self) -> u64 {
+        self.base_attack
+    }
+}
+"#;
+
+const CONTROLLER_TEMPLATE: &str = r#"use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::prelude::*;
+
+use crate::member::$Member;
+
+/// Controller making every performer attack the first alive member of another team.
+pub fn attack_first_alive() -> ChoiceCallback<$Member> {
+    Box::new(|team_list, performer, _rng| {
+        let performer = performer.unwrap_or_default();
+
+        let target = team_list
+            .iter()
+            .enumerate()
+            .filter(|(team_id, _)| *team_id != performer.team_id)
+            .flat_map(|(team_id, team)| {
+                team.member_list()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, m)| m.health() > 0)
+                    .map(move |(member_id, _)| MemberIdentifier::new(team_id, member_id))
+            })
+            .next();
+
+        let target = match target {
+            Some(target) => Target::Single(target),
+            None => Target::None,
+        };
+
+        (Box::new(DirectAttack), Target::Single(performer), target)
+    })
+}
+"#;
+
+const MAIN_TEMPLATE: &str = r#"mod controller;
+mod member;
+
+use fierceful_atto::prelude::*;
+use serde::Deserialize;
+
+use member::{$Member, Stats};
+
+#[derive(Deserialize)]
+struct Encounter {
+    seed: u64,
+    teams: Vec<TeamData>,
+}
+
+#[derive(Deserialize)]
+struct TeamData {
+    name: String,
+    members: Vec<MemberData>,
+}
+
+#[derive(Deserialize)]
+struct MemberData {
+    name: String,
+    health: u64,
+    attack: u64,
+}
+
+fn main() {
+    let encounter: Encounter =
+        ron::from_str(include_str!("../encounter.ron")).expect("the encounter is invalid");
+
+    let teams = encounter
+        .teams
+        .into_iter()
+        .map(|team| {
+            let members = team
+                .members
+                .into_iter()
+                .map(|m| {
+                    let statistics = Stats {
+                        max_health: m.health,
+                        base_attack: m.attack,
+                    };
+
+                    $Member::new(m.name, statistics)
+                })
+                .collect();
+
+            Team::new(team.name, members)
+        })
+        .collect();
+
+    let battle = Builder::new(
+        teams,
+        None,
+        controller::attack_first_alive(),
+        EndCondition::LastTeamStanding,
+    )
+    .set_seed(encounter.seed)
+    .build()
+    .expect("the battle could not be built");
+
+    let (teams, outcome) = battle
+        .run_with_outcome()
+        .expect("the battle could not be played");
+
+    println!("{:?} after {} turns", outcome.winner, outcome.turns);
+    println!("{teams:#?}");
+}
+"#;
+
+/// File generated by a [`Scaffold`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaffoldFile {
+    /// Path of the file, relative to the root of the generated crate.
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// Member of a team of the generated encounter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScaffoldMember {
+    name: String,
+    health: u64,
+    attack: u64,
+}
+
+/// Team of the generated encounter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScaffoldTeam {
+    name: String,
+    members: Vec<ScaffoldMember>,
+}
+
+/// Parameters of a generated game skeleton.
+///
+/// # Notes
+///
+/// Without any team, the encounter is generated with a single hero fighting a single goblin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scaffold {
+    crate_name: String,
+    member_type: String,
+    dependency: String,
+    seed: u64,
+    teams: Vec<ScaffoldTeam>,
+}
+
+impl Scaffold {
+    /// Create a new [`Scaffold`] for a crate, with `Fighter` as member type.
+    ///
+    /// # Notes
+    ///
+    /// The generated crate depends on the current version of this crate from the registry.
+    /// Use [`Scaffold::with_dependency()`] to depend on it in another way.
+    pub fn new(crate_name: impl Into<String>) -> Self {
+        Self {
+            crate_name: crate_name.into(),
+            member_type: String::from("Fighter"),
+            dependency: format!("version = \"{}\"", env!("CARGO_PKG_VERSION")),
+            seed: 0,
+            teams: Vec::new(),
+        }
+    }
+
+    /// Set the name of the type implementing [`Member`](crate::member::Member).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name isn't a valid Rust identifier.
+    pub fn with_member_type(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+
+        let mut chars = name.chars();
+        let valid = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        assert!(valid, "\"{name}\" is not a valid type name");
+
+        self.member_type = name;
+
+        self
+    }
+
+    /// Set the specification of the dependency on this crate, as written in the generated `Cargo.toml`.
+    ///
+    /// # Notes
+    ///
+    /// The specification is the content of an inline table, like `path = "../fierceful-atto"`.
+    pub fn with_dependency(mut self, spec: impl Into<String>) -> Self {
+        self.dependency = spec.into();
+
+        self
+    }
+
+    /// Set the seed the battle of the encounter is played with.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+
+        self
+    }
+
+    /// Add a team to the encounter, with the name, health and attack of each of its members.
+    pub fn with_team(mut self, name: impl Into<String>, members: &[(&str, u64, u64)]) -> Self {
+        self.teams.push(ScaffoldTeam {
+            name: name.into(),
+            members: members
+                .iter()
+                .map(|&(name, health, attack)| ScaffoldMember {
+                    name: String::from(name),
+                    health,
+                    attack,
+                })
+                .collect(),
+        });
+
+        self
+    }
+
+    /// Returns the name of the generated crate.
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    /// Returns the name of the type implementing [`Member`](crate::member::Member).
+    pub fn member_type(&self) -> &str {
+        &self.member_type
+    }
+
+    /// Generate the files of the skeleton, without writing them.
+    pub fn files(&self) -> Vec<ScaffoldFile> {
+        let fill = |template: &str| {
+            template
+                .replace("$crate", &self.crate_name)
+                .replace("$dependency", &self.dependency)
+                .replace("$Member", &self.member_type)
+        };
+
+        vec![
+            ScaffoldFile {
+                path: PathBuf::from("Cargo.toml"),
+                contents: fill(CARGO_TEMPLATE),
+            },
+            ScaffoldFile {
+                path: PathBuf::from("src/member.rs"),
+                contents: fill(MEMBER_TEMPLATE),
+            },
+            ScaffoldFile {
+                path: PathBuf::from("src/controller.rs"),
+                contents: fill(CONTROLLER_TEMPLATE),
+            },
+            ScaffoldFile {
+                path: PathBuf::from("src/main.rs"),
+                contents: fill(MAIN_TEMPLATE),
+            },
+            ScaffoldFile {
+                path: PathBuf::from("encounter.ron"),
+                contents: self.encounter(),
+            },
+        ]
+    }
+
+    /// Generate the skeleton and write its files in a directory, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or any of the files couldn't be written.
+    /// Files already in the directory are overwritten.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+
+        for file in self.files() {
+            let path = dir.join(&file.path);
+
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(&path, file.contents)?;
+        }
+
+        log::info!(
+            "The skeleton of \"{}\" has been written in {}",
+            self.crate_name,
+            dir.display()
+        );
+
+        Ok(())
+    }
+
+    /// Returns the RON encounter file, listing the seed and the teams.
+    fn encounter(&self) -> String {
+        let default_teams;
+
+        let teams = if self.teams.is_empty() {
+            default_teams = Self::new("")
+                .with_team("Heroes", &[("Picco", 100, 15)])
+                .with_team("Goblins", &[("Bacco", 60, 10)])
+                .teams;
+
+            &default_teams
+        } else {
+            &self.teams
+        };
+
+        let mut encounter = format!("(\n    seed: {},\n    teams: [\n", self.seed);
+
+        for team in teams {
+            // Debug formatting escapes strings the same way RON expects them.
+            encounter.push_str(&format!(
+                "        (\n            name: {:?},\n            members: [\n",
+                team.name
+            ));
+
+            for m in &team.members {
+                encounter.push_str(&format!(
+                    "                (name: {:?}, health: {}, attack: {}),\n",
+                    m.name, m.health, m.attack
+                ));
+            }
+
+            encounter.push_str("            ],\n        ),\n");
+        }
+
+        encounter.push_str("    ],\n)\n");
+
+        encounter
+    }
+}
@@ -0,0 +1,279 @@
+//! Actions scheduled to be resolved during a future turn, like a meteor landing a few turns after being summoned.
+//!
+//! Scheduled actions wait in an [`ActionSchedule`] until their turn comes, when they are resolved together with the
+//! other actions of the turn (following their [priority](crate::action::Action::priority)). Until then, they can be
+//! inspected to show them to the players, and they are cancelled following their [`CancelRule`].
+
+use alloc::vec::Vec;
+
+use crate::action::{Action, ChoiceReturn, Target};
+use crate::checkpoint;
+use crate::error::BattleError;
+use crate::event::Event;
+use crate::member::{Member, MemberIdentifier};
+use crate::narration::narrate;
+use crate::state::BattleState;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Identifier of a [`ScheduledAction`], unique within its [`ActionSchedule`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScheduleId(u64);
+
+/// Rule deciding when a [`ScheduledAction`] is cancelled before being resolved.
+///
+/// # Notes
+///
+/// Rules only consider the performers chosen as a [`Target::Single`] or [`Target::DiscreteMultiple`].
+/// Regardless of the rule, an action is cancelled if one of its performers leaves the battle, and it's interrupted
+/// when resolved if its performers can't act anymore.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CancelRule {
+    /// The action is only cancelled manually, with [`ActionSchedule::cancel()`].
+    Manual,
+    /// The action is cancelled as soon as one of its performers is defeated.
+    #[default]
+    PerformerDefeated,
+    /// The action is cancelled as soon as one of its performers takes damage, is interrupted or is defeated.
+    PerformerDisrupted,
+}
+
+/// Action waiting in an [`ActionSchedule`] for its turn to come.
+pub struct ScheduledAction<M> {
+    id: ScheduleId,
+    choice: ChoiceReturn<M>,
+    turn: u64,
+    rule: CancelRule,
+}
+
+impl<M> ScheduledAction<M> {
+    /// Returns the identifier of the scheduled action.
+    pub fn id(&self) -> ScheduleId {
+        self.id
+    }
+
+    /// Returns the scheduled action.
+    pub fn action(&self) -> &dyn Action<M> {
+        &*self.choice.0
+    }
+
+    /// Returns the performers of the scheduled action.
+    pub fn performers(&self) -> &Target {
+        &self.choice.1
+    }
+
+    /// Returns the targets of the scheduled action.
+    pub fn targets(&self) -> &Target {
+        &self.choice.2
+    }
+
+    /// Returns the number of the turn during which the action will be resolved.
+    pub fn turn(&self) -> u64 {
+        self.turn
+    }
+
+    /// Returns the rule deciding when the action is cancelled.
+    pub fn cancel_rule(&self) -> CancelRule {
+        self.rule
+    }
+
+    /// Returns the performers named by the action's choice.
+    fn named_performers(&self) -> &[MemberIdentifier] {
+        match &self.choice.1 {
+            Target::Single(id) => core::slice::from_ref(id),
+            Target::DiscreteMultiple(ids) => ids,
+            _ => &[],
+        }
+    }
+}
+
+/// Collection of [`ScheduledAction`]s waiting to be resolved during a future turn.
+pub struct ActionSchedule<M> {
+    pending: Vec<ScheduledAction<M>>,
+    next_id: u64,
+    /// Amount of events of the battle's log already checked for disrupted performers.
+    checked_events: usize,
+}
+
+impl<M> ActionSchedule<M> {
+    /// Create a new, empty [`ActionSchedule`].
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            next_id: 0,
+            checked_events: 0,
+        }
+    }
+
+    /// Schedule an action to be resolved during the given turn, returning its identifier.
+    pub fn add(&mut self, choice: ChoiceReturn<M>, turn: u64, rule: CancelRule) -> ScheduleId {
+        let id = ScheduleId(self.next_id);
+        self.next_id += 1;
+
+        self.pending.push(ScheduledAction {
+            id,
+            choice,
+            turn,
+            rule,
+        });
+
+        id
+    }
+
+    /// Cancel a scheduled action, returning its choice.
+    ///
+    /// Returns `None` if no action with the given identifier is waiting to be resolved.
+    pub fn cancel(&mut self, id: ScheduleId) -> Option<ChoiceReturn<M>> {
+        let index = self.pending.iter().position(|s| s.id == id)?;
+
+        Some(self.pending.remove(index).choice)
+    }
+
+    /// Returns the actions waiting to be resolved, in the same order they were scheduled.
+    pub fn pending(&self) -> &[ScheduledAction<M>] {
+        &self.pending
+    }
+
+    /// Returns the actions that will be resolved during the given turn, in the same order they were scheduled.
+    pub fn due_at(&self, turn: u64) -> impl Iterator<Item = &ScheduledAction<M>> {
+        self.pending.iter().filter(move |s| s.turn == turn)
+    }
+
+    /// Returns the scheduled action with the given identifier, if it's still waiting to be resolved.
+    pub fn get(&self, id: ScheduleId) -> Option<&ScheduledAction<M>> {
+        self.pending.iter().find(|s| s.id == id)
+    }
+
+    /// Capture the scheduled actions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BattleError::UncopyableAction`] if a scheduled action can't be copied.
+    pub(crate) fn checkpoint(&self) -> Result<ScheduleCheckpoint<M>, BattleError> {
+        let pending = self
+            .pending
+            .iter()
+            .map(|s| {
+                Ok(ScheduledAction {
+                    choice: checkpoint::duplicate(&s.choice)?,
+                    ..*s
+                })
+            })
+            .collect::<Result<_, BattleError>>()?;
+
+        Ok(ScheduleCheckpoint {
+            pending,
+            next_id: self.next_id,
+            checked_events: self.checked_events,
+        })
+    }
+
+    /// Restore the scheduled actions captured by a checkpoint.
+    pub(crate) fn rollback(&mut self, checkpoint: ScheduleCheckpoint<M>) {
+        self.pending = checkpoint.pending;
+        self.next_id = checkpoint.next_id;
+        self.checked_events = checkpoint.checked_events;
+    }
+
+    /// Remove the actions due by the given turn, returning their choices in the same order they were scheduled.
+    pub(crate) fn take_due(&mut self, turn_number: u64) -> Vec<ChoiceReturn<M>> {
+        let (due, pending): (Vec<_>, Vec<_>) = core::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|s| s.turn <= turn_number);
+
+        self.pending = pending;
+
+        due.into_iter().map(|s| s.choice).collect()
+    }
+
+    /// Update the identifiers of the scheduled actions after a member was removed from the battle,
+    /// returning the choices of the actions it was performing.
+    pub(crate) fn after_removal(&mut self, removed: MemberIdentifier) -> Vec<ChoiceReturn<M>> {
+        let mut cancelled = Vec::new();
+
+        for scheduled in core::mem::take(&mut self.pending) {
+            let left = scheduled.named_performers().contains(&removed);
+            let (action, performers, targets) = scheduled.choice;
+
+            if left {
+                cancelled.push((action, performers, targets));
+                continue;
+            }
+
+            self.pending.push(ScheduledAction {
+                choice: (
+                    action,
+                    performers.after_removal(removed),
+                    targets.after_removal(removed),
+                ),
+                ..scheduled
+            });
+        }
+
+        cancelled
+    }
+}
+
+impl<M: Member> ActionSchedule<M> {
+    /// Cancel every action whose performers were defeated or disrupted since the last check,
+    /// following the actions' [`CancelRule`]s.
+    ///
+    /// Returns the choices of the cancelled actions.
+    pub(crate) fn cancel_disrupted(&mut self, data: &BattleState<M>) -> Vec<ChoiceReturn<M>> {
+        let new_events = data.events().since(self.checked_events);
+
+        let defeated = |id: &MemberIdentifier| {
+            data.teams()
+                .get(id.team_id)
+                .and_then(|t| t.member(id.member_id))
+                .is_none_or(|m| m.health() == 0)
+        };
+        let disrupted = |id: &MemberIdentifier| {
+            new_events.iter().any(|e| match e {
+                Event::Damaged { member, amount } => member == id && *amount > 0,
+                Event::Interrupted { member } => member == id,
+                _ => false,
+            })
+        };
+
+        let (cancelled, pending): (Vec<_>, Vec<_>) = core::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|s| match s.rule {
+                CancelRule::Manual => false,
+                CancelRule::PerformerDefeated => s.named_performers().iter().any(defeated),
+                CancelRule::PerformerDisrupted => s
+                    .named_performers()
+                    .iter()
+                    .any(|id| defeated(id) || disrupted(id)),
+            });
+
+        self.pending = pending;
+        self.checked_events = data.events().len();
+
+        for scheduled in &cancelled {
+            narrate!(
+                "The action scheduled for turn {} was cancelled",
+                scheduled.turn
+            );
+        }
+
+        cancelled.into_iter().map(|s| s.choice).collect()
+    }
+}
+
+/// Scheduled actions captured by a [`Checkpoint`](crate::checkpoint::Checkpoint).
+pub(crate) struct ScheduleCheckpoint<M> {
+    pending: Vec<ScheduledAction<M>>,
+    next_id: u64,
+    checked_events: usize,
+}
+
+impl<M> Default for ActionSchedule<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
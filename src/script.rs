@@ -0,0 +1,419 @@
+//! Actions defined by [Rhai](https://rhai.rs) scripts, loaded at runtime.
+//!
+//! A [`ScriptEngine`] compiles scripts into [`ScriptedAction`]s, which can be registered like any other action.
+//! Scripts only reach the battle through a small API:
+//!
+//! - `performers` and `targets` are arrays of members, each one a map with the `team`, `member`, `name`, `health`,
+//!   `max_health`, `attack`, `defense`, `accuracy`, `alive` and `tags` (tag name to stacks) fields.
+//!   The properties are the final ones, including the auras of the member's team and its stat stages.
+//! - `damage(member, amount)` inflicts damage through the battle's damage [`Pipeline`](crate::damage::Pipeline).
+//! - `heal(member, amount)` restores health.
+//! - `apply_tag(member, tag)` and `consume_tag(member, tag)` change the member's tags.
+//!
+//! ```rhai
+//! let attack = 0;
+//!
+//! for p in performers {
+//!     attack += p.attack;
+//! }
+//!
+//! for t in targets {
+//!     damage(t, attack * 2);
+//!     apply_tag(t, "Burning");
+//! }
+//! ```
+//!
+//! Scripts compiled with [`ScriptEngine::compile_environment()`] run at the end of every turn instead, and are given
+//! the `members` array (every member of the battle) and the `turn` number in place of `performers` and `targets`.
+//!
+//! # Notes
+//!
+//! Effects are applied in order once the script has run, so members read by the script always reflect
+//! the state from before the action. If the script fails, none of its effects are applied.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+
+use crate::action::{Action, ActionId, ActionState, Context};
+use crate::component::{ComponentStore, Tag};
+use crate::environment::{EnvironmentEffect, EnvironmentScript};
+use crate::error::ScriptError;
+use crate::event::Event;
+use crate::member::{Member, MemberIdentifier, Properties, Statistics};
+
+/// Maximum amount of operations a single script run can take, protecting the battle from endless loops.
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// Effect requested by a script, applied once the script has run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ScriptEffect {
+    Damage(MemberIdentifier, u64),
+    Heal(MemberIdentifier, u64),
+    ApplyTag(MemberIdentifier, Tag),
+    ConsumeTag(MemberIdentifier, Tag),
+}
+
+/// Sandboxed interpreter compiling and running action scripts.
+pub struct ScriptEngine {
+    engine: Rc<Engine>,
+    effects: Rc<RefCell<Vec<ScriptEffect>>>,
+}
+
+impl ScriptEngine {
+    /// Create a new [`ScriptEngine`] exposing the battle API to scripts.
+    ///
+    /// # Notes
+    ///
+    /// Scripts can't import modules, and each run is limited in the amount of operations it can take.
+    /// Printed messages are forwarded to the log.
+    pub fn new() -> Self {
+        let effects = Rc::new(RefCell::new(Vec::new()));
+
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_module_resolver(DummyModuleResolver::new())
+            .on_print(|message| log::info!("{}", message))
+            .on_debug(|message, _, _| log::debug!("{}", message));
+
+        let queue = Rc::clone(&effects);
+        engine.register_fn(
+            "damage",
+            move |member: Map, amount: i64| -> Result<(), Box<EvalAltResult>> {
+                let member = identifier(&member)?;
+                queue
+                    .borrow_mut()
+                    .push(ScriptEffect::Damage(member, amount.max(0) as u64));
+
+                Ok(())
+            },
+        );
+
+        let queue = Rc::clone(&effects);
+        engine.register_fn(
+            "heal",
+            move |member: Map, amount: i64| -> Result<(), Box<EvalAltResult>> {
+                let member = identifier(&member)?;
+                queue
+                    .borrow_mut()
+                    .push(ScriptEffect::Heal(member, amount.max(0) as u64));
+
+                Ok(())
+            },
+        );
+
+        let queue = Rc::clone(&effects);
+        engine.register_fn(
+            "apply_tag",
+            move |member: Map, tag: &str| -> Result<(), Box<EvalAltResult>> {
+                let member = identifier(&member)?;
+                queue
+                    .borrow_mut()
+                    .push(ScriptEffect::ApplyTag(member, Tag::new(tag)));
+
+                Ok(())
+            },
+        );
+
+        let queue = Rc::clone(&effects);
+        engine.register_fn(
+            "consume_tag",
+            move |member: Map, tag: &str| -> Result<(), Box<EvalAltResult>> {
+                let member = identifier(&member)?;
+                queue
+                    .borrow_mut()
+                    .push(ScriptEffect::ConsumeTag(member, Tag::new(tag)));
+
+                Ok(())
+            },
+        );
+
+        Self {
+            engine: Rc::new(engine),
+            effects,
+        }
+    }
+
+    /// Compile a script into a [`ScriptedAction`] with the given [`ActionId`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScriptError::Compile`] if the script isn't valid Rhai code.
+    pub fn compile(&self, id: ActionId, source: &str) -> Result<ScriptedAction, ScriptError> {
+        let ast = self
+            .engine
+            .compile(source)
+            .map_err(|e| ScriptError::Compile(e.to_string()))?;
+
+        Ok(ScriptedAction {
+            id,
+            ast: Rc::new(ast),
+            engine: Rc::clone(&self.engine),
+            effects: Rc::clone(&self.effects),
+        })
+    }
+
+    /// Compile a script into a [`ScriptedEnvironment`], run by the battlefield at the end of every turn.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScriptError::Compile`] if the script isn't valid Rhai code.
+    pub fn compile_environment(
+        &self,
+        name: impl Into<String>,
+        source: &str,
+    ) -> Result<ScriptedEnvironment, ScriptError> {
+        let ast = self
+            .engine
+            .compile(source)
+            .map_err(|e| ScriptError::Compile(e.to_string()))?;
+
+        Ok(ScriptedEnvironment {
+            name: name.into(),
+            ast: Rc::new(ast),
+            engine: Rc::clone(&self.engine),
+            effects: Rc::clone(&self.effects),
+        })
+    }
+}
+
+impl Default for ScriptEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [`Action`] performed by running a compiled script.
+///
+/// # Notes
+///
+/// Cloning the action is cheap, as the compiled script is shared. Register it with a factory like
+/// `Box::new(move || Box::new(action.clone()))`.
+#[derive(Clone)]
+pub struct ScriptedAction {
+    id: ActionId,
+    ast: Rc<AST>,
+    engine: Rc<Engine>,
+    effects: Rc<RefCell<Vec<ScriptEffect>>>,
+}
+
+impl ScriptedAction {
+    /// Run the script against the performers and targets of an action, returning the requested effects.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScriptError::Runtime`] if the script fails while running.
+    fn run<M: Member>(&self, context: &Context<M>) -> Result<Vec<ScriptEffect>, ScriptError> {
+        let members = |ids: Vec<MemberIdentifier>| {
+            ids.into_iter()
+                .filter_map(|id| Some((id, context.member(id)?, context.final_properties(id)?)))
+                .collect::<Vec<_>>()
+        };
+
+        let mut scope = Scope::new();
+        scope.push_constant(
+            "performers",
+            snapshot(context.components(), members(context.performer_ids())),
+        );
+        scope.push_constant(
+            "targets",
+            snapshot(context.components(), members(context.target_ids())),
+        );
+
+        run_ast(&self.engine, &self.ast, &self.effects, scope)
+    }
+}
+
+impl<M: Member> Action<M> for ScriptedAction {
+    fn act(&mut self, mut context: Context<M>) -> ActionState {
+        let effects = match self.run(&context) {
+            Ok(effects) => effects,
+            Err(e) => {
+                log::error!("Script of action {} failed: {}", self.id.name(), e);
+
+                return ActionState::Finished;
+            }
+        };
+
+        for effect in effects {
+            match effect {
+                ScriptEffect::Damage(member, amount) => {
+                    context.deal_damage(member, amount);
+                }
+                ScriptEffect::Heal(member, amount) => context.heal(member, amount),
+                ScriptEffect::ApplyTag(member, tag) => context.apply_tag(member, tag),
+                ScriptEffect::ConsumeTag(member, tag) => {
+                    context.consume_tag(member, &tag);
+                }
+            }
+        }
+
+        ActionState::Finished
+    }
+
+    fn action_id(&self) -> ActionId {
+        self.id.clone()
+    }
+}
+
+/// Script run by the battlefield at the end of every turn, compiled with [`ScriptEngine::compile_environment()`].
+///
+/// # Notes
+///
+/// Damage is applied as it is, without going through the battle's damage pipeline.
+/// Turn it into an [`EnvironmentScript`] with [`ScriptedEnvironment::into_script()`] to register it.
+#[derive(Clone)]
+pub struct ScriptedEnvironment {
+    name: String,
+    ast: Rc<AST>,
+    engine: Rc<Engine>,
+    effects: Rc<RefCell<Vec<ScriptEffect>>>,
+}
+
+impl ScriptedEnvironment {
+    /// Turn the compiled script into an [`EnvironmentScript`] with the same name.
+    ///
+    /// # Notes
+    ///
+    /// If the script fails, an error is logged and none of its effects are applied.
+    pub fn into_script<M: Member + 'static>(self) -> EnvironmentScript<M> {
+        let name = self.name.clone();
+
+        EnvironmentScript::new(
+            name,
+            Box::new(move |view| {
+                let members = view.teams().iter().enumerate().flat_map(|(team_id, team)| {
+                    team.member_list()
+                        .iter()
+                        .enumerate()
+                        .filter_map(move |(member_id, m)| {
+                            let id = MemberIdentifier::new(team_id, member_id);
+
+                            Some((id, m, view.state().final_properties(id)?))
+                        })
+                });
+
+                let mut scope = Scope::new();
+                scope.push_constant("members", snapshot(view.components(), members.collect()));
+                scope.push_constant("turn", saturate(view.turn_number()));
+
+                let effects = match run_ast(&self.engine, &self.ast, &self.effects, scope) {
+                    Ok(effects) => effects,
+                    Err(e) => {
+                        log::error!("Environment script {} failed: {}", self.name, e);
+
+                        return Vec::new();
+                    }
+                };
+
+                effects
+                    .into_iter()
+                    .filter_map(|effect| {
+                        let event = match effect {
+                            ScriptEffect::Damage(member, amount) => {
+                                Event::Damaged { member, amount }
+                            }
+                            ScriptEffect::Heal(member, amount) => Event::Healed { member, amount },
+                            ScriptEffect::ApplyTag(member, tag) => {
+                                Event::TagApplied { member, tag }
+                            }
+                            ScriptEffect::ConsumeTag(member, tag) => {
+                                if !view.components().has_tag(member, &tag) {
+                                    return None;
+                                }
+
+                                Event::TagConsumed { member, tag }
+                            }
+                        };
+
+                        Some(EnvironmentEffect::Apply(event))
+                    })
+                    .collect()
+            }),
+        )
+    }
+}
+
+/// Run a compiled script with the given scope, returning the requested effects.
+///
+/// # Errors
+///
+/// Returns [`ScriptError::Runtime`] if the script fails while running.
+fn run_ast(
+    engine: &Engine,
+    ast: &AST,
+    effects: &RefCell<Vec<ScriptEffect>>,
+    mut scope: Scope,
+) -> Result<Vec<ScriptEffect>, ScriptError> {
+    effects.borrow_mut().clear();
+
+    let result = engine.run_ast_with_scope(&mut scope, ast);
+    let effects = std::mem::take(&mut *effects.borrow_mut());
+
+    result.map_err(|e| ScriptError::Runtime(e.to_string()))?;
+
+    Ok(effects)
+}
+
+/// Describe members as script values, together with their final properties.
+fn snapshot<M: Member>(
+    components: &ComponentStore,
+    members: Vec<(MemberIdentifier, &M, M::Properties)>,
+) -> Array {
+    members
+        .into_iter()
+        .map(|(id, member, properties)| {
+            let tags: Map = components
+                .tags(id)
+                .map(|(tag, stacks)| (tag.name().into(), Dynamic::from(i64::from(stacks))))
+                .collect();
+
+            let mut map = Map::new();
+            map.insert("team".into(), Dynamic::from(id.team_id as i64));
+            map.insert("member".into(), Dynamic::from(id.member_id as i64));
+            map.insert("name".into(), Dynamic::from(member.name().to_string()));
+            map.insert("health".into(), Dynamic::from(saturate(member.health())));
+            map.insert(
+                "max_health".into(),
+                Dynamic::from(saturate(member.statistics().reference_health())),
+            );
+            map.insert(
+                "attack".into(),
+                Dynamic::from(saturate(properties.attack())),
+            );
+            map.insert(
+                "defense".into(),
+                Dynamic::from(saturate(properties.defense())),
+            );
+            map.insert(
+                "accuracy".into(),
+                Dynamic::from(saturate(properties.accuracy())),
+            );
+            map.insert("alive".into(), Dynamic::from(member.health() > 0));
+            map.insert("tags".into(), Dynamic::from(tags));
+
+            Dynamic::from(map)
+        })
+        .collect()
+}
+
+/// Convert a value into a script integer, saturating at the integer's maximum.
+fn saturate(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+/// Read the [`MemberIdentifier`] of a member described by a script value.
+fn identifier(member: &Map) -> Result<MemberIdentifier, Box<EvalAltResult>> {
+    let field = |name: &str| {
+        member
+            .get(name)
+            .and_then(|v| v.as_int().ok())
+            .and_then(|v| usize::try_from(v).ok())
+            .ok_or_else(|| format!("the member has no valid \"{name}\" field"))
+    };
+
+    Ok(MemberIdentifier::new(field("team")?, field("member")?))
+}
@@ -1,17 +1,25 @@
 //! Helper module to search for special conditions in battles and teams.
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
 use crate::member::{Member, MemberIdentifier};
+use crate::rng::BattleRng;
 use crate::team::Team;
 
 pub type FilterCriteria<M> = dyn Fn(MemberIdentifier, &M) -> bool;
 
+/// Function type returning the speed of a member, used to order the performers with
+/// [`SuggestedPerformerCriteria::FastestFirst`].
+pub type SpeedAccessor<M> = dyn Fn(MemberIdentifier, &M) -> u64;
+
 #[non_exhaustive]
 pub enum SuggestedPerformerCriteria<M> {
     /// Suggests no performer every time.
     None,
     /// Suggests the given member ID every time.
     Constant(MemberIdentifier),
-    /// If possible, chooses the next "alive" member (`health > 0`) of the currently acting member's team.
+    /// If possible, chooses the next member of the currently acting member's team that isn't [defeated](Member::is_defeated).
     ///
     /// Otherwise, cycles through the teams going forward choosing the first member that is found alive.
     CycleAlive,
@@ -23,6 +31,15 @@ pub enum SuggestedPerformerCriteria<M> {
     ///
     /// Use [`CycleAlive`] if all you need is to check whether a member is currently alive.
     CycleWith(Box<FilterCriteria<M>>),
+    /// Chooses any "alive" member, with the same chance for all of them, using the battle's [`BattleRng`].
+    Random,
+    /// Chooses the "alive" members from the fastest to the slowest, starting over from the fastest one once all have acted.
+    ///
+    /// # Notes
+    ///
+    /// The speed of every member is returned by the accessor, and is checked again at every turn.
+    /// Members with the same speed keep their team and member order.
+    FastestFirst(Box<SpeedAccessor<M>>),
 }
 
 // TODO: remove yucky code duplication
@@ -31,6 +48,7 @@ impl<M: Member> SuggestedPerformerCriteria<M> {
         &self,
         current_playing_member: Option<MemberIdentifier>,
         team_list: &[Team<M>],
+        rng: &mut BattleRng,
     ) -> Option<MemberIdentifier> {
         match self {
             Self::None => return None,
@@ -48,7 +66,7 @@ impl<M: Member> SuggestedPerformerCriteria<M> {
                     };
 
                     for (member_id, member) in team.member_list().iter().enumerate().skip(skip) {
-                        if member.health() != 0 {
+                        if !member.is_defeated() {
                             return Some(MemberIdentifier { team_id, member_id });
                         }
                     }
@@ -75,10 +93,113 @@ impl<M: Member> SuggestedPerformerCriteria<M> {
                     }
                 }
             }
+            Self::Random => return random(members(team_list), rng),
+            Self::FastestFirst(speed) => {
+                return fastest_first(speed, current_playing_member, team_list, members(team_list))
+            }
         }
 
         None
     }
+
+    /// Search the next performer among the members of a single team.
+    ///
+    /// # Notes
+    ///
+    /// `previous_team_member` is the last member of the team that was suggested to perform, if any.
+    /// Cycling criteria start over from the first member of the team once they reach its end, without moving to other teams.
+    /// [`Constant`](Self::Constant) suggests its member even if it belongs to another team.
+    pub fn search_in_team(
+        &self,
+        team_id: usize,
+        previous_team_member: Option<MemberIdentifier>,
+        team_list: &[Team<M>],
+        rng: &mut BattleRng,
+    ) -> Option<MemberIdentifier> {
+        let team = team_list.get(team_id)?;
+        let team_members = members(team_list).filter(move |(id, _)| id.team_id == team_id);
+
+        match self {
+            Self::None => None,
+            Self::Constant(member) => Some(*member),
+            Self::CycleAlive | Self::CycleWith(_) => {
+                let start = previous_team_member
+                    .filter(|previous| previous.team_id == team_id)
+                    .map_or(0, |previous| previous.member_id + 1);
+
+                cycle_from_point_enumerated(team.member_list(), start)
+                    .map(|(member_id, member)| (MemberIdentifier::new(team_id, member_id), member))
+                    .find(|&(id, member)| match self {
+                        Self::CycleWith(condition) => condition(id, member),
+                        _ => !member.is_defeated(),
+                    })
+                    .map(|(id, _)| id)
+            }
+            Self::Random => random(team_members, rng),
+            Self::FastestFirst(speed) => {
+                fastest_first(speed, previous_team_member, team_list, team_members)
+            }
+        }
+    }
+}
+
+/// Choose any alive member, with the same chance for all of them.
+fn random<'a, M: Member + 'a>(
+    candidates: impl Iterator<Item = (MemberIdentifier, &'a M)>,
+    rng: &mut BattleRng,
+) -> Option<MemberIdentifier> {
+    let alive: Vec<_> = candidates
+        .filter(|(_, member)| !member.is_defeated())
+        .map(|(id, _)| id)
+        .collect();
+
+    if alive.is_empty() {
+        return None;
+    }
+
+    let index = rng.gen_range(0..alive.len() as u64) as usize;
+
+    Some(alive[index])
+}
+
+/// Choose the alive member following the current one by speed, or the fastest one.
+fn fastest_first<'a, M: Member + 'a>(
+    speed: &dyn Fn(MemberIdentifier, &M) -> u64,
+    current_playing_member: Option<MemberIdentifier>,
+    team_list: &[Team<M>],
+    candidates: impl Iterator<Item = (MemberIdentifier, &'a M)> + Clone,
+) -> Option<MemberIdentifier> {
+    // Faster members come first, with ties broken by their position.
+    let order = |id: MemberIdentifier, member: &M| (core::cmp::Reverse(speed(id, member)), id);
+
+    let current = current_playing_member.and_then(|id| {
+        let member = team_list.get(id.team_id)?.member(id.member_id)?;
+
+        Some(order(id, member))
+    });
+
+    let alive = candidates
+        .filter(|(_, member)| !member.is_defeated())
+        .map(|(id, member)| order(id, member));
+
+    // The next member is the first one slower than the current one, or the fastest one.
+    let next = alive
+        .clone()
+        .filter(|key| current.is_some_and(|current| *key > current))
+        .min()
+        .or_else(|| alive.min());
+
+    next.map(|(_, id)| id)
+}
+
+/// Returns an iterator over every member of the teams, together with its ID.
+fn members<M>(team_list: &[Team<M>]) -> impl Iterator<Item = (MemberIdentifier, &M)> + Clone {
+    team_list.iter().enumerate().flat_map(|(team_id, team)| {
+        team.member_list()
+            .iter()
+            .enumerate()
+            .map(move |(member_id, member)| (MemberIdentifier::new(team_id, member_id), member))
+    })
 }
 
 /// Create a cyclic operator over a slice starting from a point and ending at the one before it.
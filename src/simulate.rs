@@ -0,0 +1,425 @@
+//! Headless batch simulation of battles, to balance encounters.
+//!
+//! A [`Simulation`] plays the same encounter many times, each with its own seed, and aggregates
+//! the [`BattleOutcome`]s into a [`SimulationReport`] with the win rates, the turn counts
+//! and the damage dealt by every team.
+//!
+//! # Threads
+//!
+//! A [`Battle`](crate::battle::Battle) is neither [`Send`] nor [`Sync`], since it owns boxed callbacks
+//! (like its [`Controller`], its actions and hooks) without any thread-safety bound.
+//! With the `rayon` feature, the [`ParallelSimulation`] works around this by building every battle on the
+//! worker thread playing it: only the team templates (which must be [`Sync`]) and the factories
+//! (which must be [`Send`] and [`Sync`]) are shared between threads, and only the outcomes are sent back.
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::action::ChoiceReturn;
+use crate::battle::{Builder, EndCondition};
+use crate::controller::{ChoicePrompt, Controller};
+use crate::error::BattleError;
+use crate::member::Member;
+use crate::outcome::{BattleOutcome, Winner};
+use crate::team::Team;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Function building the controller of every simulated battle.
+pub type ControllerFactory<M> = Box<dyn Fn() -> Box<dyn Controller<M>>>;
+
+/// Function customizing the [`Builder`] of every simulated battle, like to add objectives or environment scripts.
+pub type BuilderHook<M> = Box<dyn Fn(Builder<M>) -> Builder<M>>;
+
+/// Thread-safe [`ControllerFactory`], used by a [`ParallelSimulation`].
+#[cfg(feature = "rayon")]
+pub type SyncControllerFactory<M> = Box<dyn Fn() -> Box<dyn Controller<M>> + Send + Sync>;
+
+/// Thread-safe [`BuilderHook`], used by a [`ParallelSimulation`].
+#[cfg(feature = "rayon")]
+pub type SyncBuilderHook<M> = Box<dyn Fn(Builder<M>) -> Builder<M> + Send + Sync>;
+
+/// Batch of battles played from the same team templates.
+///
+/// # Notes
+///
+/// Every battle is played with its own seed, counting up from the simulation's one (`0` by default),
+/// so the whole simulation is reproducible.
+pub struct Simulation<M> {
+    teams: Vec<Team<M>>,
+    controller: ControllerFactory<M>,
+    end_condition: EndCondition,
+    iterations: u64,
+    seed: u64,
+    max_turns: u64,
+    setup: Option<BuilderHook<M>>,
+}
+
+impl<M: Member + Clone + 'static> Simulation<M> {
+    /// Create a new [`Simulation`] playing an amount of battles between copies of the given teams.
+    ///
+    /// # Notes
+    ///
+    /// Battles are stopped after 1000 turns. Look at [`Simulation::with_max_turns()`] to change the limit.
+    pub fn new(
+        teams: Vec<Team<M>>,
+        controller: ControllerFactory<M>,
+        end_condition: EndCondition,
+        iterations: u64,
+    ) -> Self {
+        Self {
+            teams,
+            controller,
+            end_condition,
+            iterations,
+            seed: 0,
+            max_turns: 1000,
+            setup: None,
+        }
+    }
+
+    /// Set the seed of the first battle. The following battles use the next seeds, in order.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+
+        self
+    }
+
+    /// Set the amount of turns after which a battle is stopped, counting it as [`Winner::Undecided`].
+    ///
+    /// # Notes
+    ///
+    /// The limit keeps encounters that can't be won by anybody (like between members unable to damage each other)
+    /// from running forever.
+    pub fn with_max_turns(mut self, max_turns: u64) -> Self {
+        self.max_turns = max_turns;
+
+        self
+    }
+
+    /// Set a hook customizing the [`Builder`] of every battle, after its seed has been set and its
+    /// [narration](Builder::set_narration) has been disabled.
+    pub fn with_setup(mut self, setup: BuilderHook<M>) -> Self {
+        self.setup = Some(setup);
+
+        self
+    }
+
+    /// Play every battle of the simulation, returning the aggregated results.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned while playing a turn, stopping the simulation.
+    pub fn run(&self) -> Result<SimulationReport, BattleError> {
+        log::info!(
+            "Simulating {} battles starting from seed {}",
+            self.iterations,
+            self.seed
+        );
+
+        let mut report = SimulationReport::new(self.teams.len());
+
+        for i in 0..self.iterations {
+            let outcome = self.play(self.seed.wrapping_add(i))?;
+
+            report.add(&outcome);
+        }
+
+        Ok(report)
+    }
+
+    /// Play a single battle with the given seed, returning its outcome.
+    fn play(&self, seed: u64) -> Result<BattleOutcome, BattleError> {
+        play_battle(
+            &self.teams,
+            (self.controller)(),
+            self.end_condition,
+            seed,
+            self.max_turns,
+            self.setup.as_deref(),
+        )
+    }
+}
+
+/// Batch of battles played from the same team templates, spread across the threads of the global rayon pool.
+///
+/// # Notes
+///
+/// Battles are seeded just like in a [`Simulation`] and their outcomes are aggregated in seed order,
+/// so the report is the same one a [`Simulation`] with the same parameters would return.
+#[cfg(feature = "rayon")]
+pub struct ParallelSimulation<M> {
+    teams: Vec<Team<M>>,
+    controller: SyncControllerFactory<M>,
+    end_condition: EndCondition,
+    iterations: u64,
+    seed: u64,
+    max_turns: u64,
+    setup: Option<SyncBuilderHook<M>>,
+}
+
+#[cfg(feature = "rayon")]
+impl<M: Member + Clone + Sync + 'static> ParallelSimulation<M> {
+    /// Create a new [`ParallelSimulation`] playing an amount of battles between copies of the given teams.
+    ///
+    /// # Notes
+    ///
+    /// Battles are stopped after 1000 turns. Look at [`ParallelSimulation::with_max_turns()`] to change the limit.
+    pub fn new(
+        teams: Vec<Team<M>>,
+        controller: SyncControllerFactory<M>,
+        end_condition: EndCondition,
+        iterations: u64,
+    ) -> Self {
+        Self {
+            teams,
+            controller,
+            end_condition,
+            iterations,
+            seed: 0,
+            max_turns: 1000,
+            setup: None,
+        }
+    }
+
+    /// Set the seed of the first battle. The following battles use the next seeds, in order.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+
+        self
+    }
+
+    /// Set the amount of turns after which a battle is stopped, counting it as [`Winner::Undecided`].
+    pub fn with_max_turns(mut self, max_turns: u64) -> Self {
+        self.max_turns = max_turns;
+
+        self
+    }
+
+    /// Set a hook customizing the [`Builder`] of every battle, after its seed has been set and its
+    /// [narration](Builder::set_narration) has been disabled.
+    pub fn with_setup(mut self, setup: SyncBuilderHook<M>) -> Self {
+        self.setup = Some(setup);
+
+        self
+    }
+
+    /// Play every battle of the simulation across threads, returning the aggregated results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error returned while playing a turn, if any. Battles still running on other threads are
+    /// stopped as soon as possible, so which error is returned isn't deterministic if more battles fail.
+    pub fn run(&self) -> Result<SimulationReport, BattleError> {
+        use rayon::prelude::*;
+
+        log::info!(
+            "Simulating {} battles in parallel starting from seed {}",
+            self.iterations,
+            self.seed
+        );
+
+        let outcomes = (0..self.iterations)
+            .into_par_iter()
+            .map(|i| {
+                play_battle(
+                    &self.teams,
+                    (self.controller)(),
+                    self.end_condition,
+                    self.seed.wrapping_add(i),
+                    self.max_turns,
+                    self.setup
+                        .as_deref()
+                        .map(|s| s as &dyn Fn(Builder<M>) -> Builder<M>),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut report = SimulationReport::new(self.teams.len());
+
+        for outcome in &outcomes {
+            report.add(outcome);
+        }
+
+        Ok(report)
+    }
+}
+
+/// Play a single battle between copies of the given teams, returning its outcome.
+fn play_battle<M: Member + Clone + 'static>(
+    teams: &[Team<M>],
+    controller: Box<dyn Controller<M>>,
+    end_condition: EndCondition,
+    seed: u64,
+    max_turns: u64,
+    setup: Option<&dyn Fn(Builder<M>) -> Builder<M>>,
+) -> Result<BattleOutcome, BattleError> {
+    let builder = Builder::new(teams.to_vec(), None, Boxed(controller), end_condition)
+        .set_seed(seed)
+        .set_narration(false);
+
+    let mut battle = match setup {
+        Some(setup) => setup(builder),
+        None => builder,
+    }
+    .build()?;
+
+    for _ in 0..max_turns {
+        if battle.is_finished() {
+            break;
+        }
+
+        battle.play_turn()?;
+    }
+
+    if !battle.is_finished() {
+        log::debug!("The battle with seed {} hit the turn limit", seed);
+    }
+
+    Ok(battle.outcome())
+}
+
+/// Controller built by a [`ControllerFactory`].
+struct Boxed<M>(Box<dyn Controller<M>>);
+
+impl<M> Controller<M> for Boxed<M> {
+    fn choose(&mut self, prompt: ChoicePrompt<M>) -> ChoiceReturn<M> {
+        self.0.choose(prompt)
+    }
+}
+
+/// Aggregated results of a [`Simulation`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SimulationReport {
+    /// Amount of battles played.
+    pub iterations: u64,
+    /// Battles won by every team, indexed by team ID.
+    pub wins: Vec<u64>,
+    /// Battles finished without any team standing.
+    pub draws: u64,
+    /// Battles without a winner, like the ones stopped by the turn limit.
+    pub undecided: u64,
+    /// Sum of the turns played in every battle.
+    pub total_turns: u64,
+    /// Damage dealt by every team in each battle, indexed by team ID.
+    pub damage: Vec<DamageDistribution>,
+}
+
+impl SimulationReport {
+    /// Create a new, empty [`SimulationReport`] for an amount of teams.
+    pub fn new(teams: usize) -> Self {
+        Self {
+            wins: vec![0; teams],
+            damage: vec![DamageDistribution::default(); teams],
+            ..Default::default()
+        }
+    }
+
+    /// Add the outcome of a battle to the report.
+    pub fn add(&mut self, outcome: &BattleOutcome) {
+        self.iterations += 1;
+        self.total_turns = self.total_turns.saturating_add(outcome.turns);
+
+        match outcome.winner {
+            Winner::Team(team_id) => {
+                if let Some(wins) = self.wins.get_mut(team_id) {
+                    *wins += 1;
+                }
+            }
+            Winner::Draw => self.draws += 1,
+            _ => self.undecided += 1,
+        }
+
+        for (team_id, distribution) in self.damage.iter_mut().enumerate() {
+            let dealt = outcome
+                .members
+                .iter()
+                .filter(|r| r.member.team_id == team_id)
+                .fold(0u64, |sum, r| sum.saturating_add(r.damage_dealt));
+
+            distribution.samples.push(dealt);
+        }
+    }
+
+    /// Returns the fraction of battles won by a team, between `0.0` and `1.0`.
+    pub fn win_rate(&self, team_id: usize) -> f64 {
+        let wins = self.wins.get(team_id).copied().unwrap_or(0);
+
+        self.rate(wins)
+    }
+
+    /// Returns the fraction of battles finished without any team standing.
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(self.draws)
+    }
+
+    /// Returns the average amount of turns played in a battle.
+    pub fn average_turns(&self) -> f64 {
+        self.rate(self.total_turns)
+    }
+
+    fn rate(&self, amount: u64) -> f64 {
+        if self.iterations == 0 {
+            0.0
+        } else {
+            amount as f64 / self.iterations as f64
+        }
+    }
+}
+
+/// Amounts of damage dealt by a team across the battles of a [`Simulation`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DamageDistribution {
+    /// Damage dealt in each battle, in the order the battles were played.
+    samples: Vec<u64>,
+}
+
+impl DamageDistribution {
+    /// Returns the damage dealt in each battle, in the order the battles were played.
+    pub fn samples(&self) -> &[u64] {
+        &self.samples
+    }
+
+    /// Returns the lowest damage dealt in a battle, or `None` if no battle was played.
+    pub fn min(&self) -> Option<u64> {
+        self.samples.iter().copied().min()
+    }
+
+    /// Returns the highest damage dealt in a battle, or `None` if no battle was played.
+    pub fn max(&self) -> Option<u64> {
+        self.samples.iter().copied().max()
+    }
+
+    /// Returns the average damage dealt in a battle, or `0.0` if no battle was played.
+    pub fn mean(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+
+        self.samples.iter().map(|s| *s as f64).sum::<f64>() / self.samples.len() as f64
+    }
+
+    /// Returns the lowest damage not exceeded in the given percentage of battles, or `None` if no battle was played.
+    ///
+    /// # Notes
+    ///
+    /// Uses the nearest-rank method, so `percentile(50)` is the median and `percentile(100)` the maximum.
+    /// Percentages above 100 are treated as 100.
+    pub fn percentile(&self, percent: u8) -> Option<u64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+
+        let percent = usize::from(percent.min(100));
+        let rank = (percent * sorted.len()).div_ceil(100).max(1);
+
+        sorted.get(rank - 1).copied()
+    }
+}
@@ -0,0 +1,56 @@
+//! Rules to spread the effects of area actions around an epicenter.
+
+use crate::member::MemberIdentifier;
+use crate::team::Team;
+
+/// Rule that decides how the damage of an area action changes the farther a member is from the epicenter.
+pub trait Spread {
+    /// Returns the damage inflicted on a member at `distance` from the epicenter.
+    ///
+    /// # Notes
+    ///
+    /// `damage` is the full amount inflicted on the epicenter, which is at distance 0.
+    fn spread(&self, damage: u64, distance: usize) -> u64;
+}
+
+/// Returns the distance between two members, if they are on the same team.
+///
+/// # Notes
+///
+/// Without any additional positional information, members are considered lined up in the same order as their team's member list.
+/// Members of different teams are never considered in reach of each other.
+pub fn distance(a: MemberIdentifier, b: MemberIdentifier) -> Option<usize> {
+    if a.team_id != b.team_id {
+        return None;
+    }
+
+    Some(a.member_id.abs_diff(b.member_id))
+}
+
+/// Returns the distance between two members, if they are on the same team, using their positions if they have any.
+///
+/// # Notes
+///
+/// If both members are placed in their team's [`Formation`](crate::formation::Formation), the distance is the one
+/// between their [positions](crate::formation::Position::distance). Otherwise, it falls back to [`distance()`].
+///
+/// Every team has its own formation, with no shared grid to measure across teams,
+/// so members of different teams are never considered in reach of each other.
+pub fn formation_distance<M>(
+    teams: &[Team<M>],
+    a: MemberIdentifier,
+    b: MemberIdentifier,
+) -> Option<usize> {
+    if a.team_id != b.team_id {
+        return None;
+    }
+
+    let positions = teams
+        .get(a.team_id)
+        .and_then(|team| Some((team.position(a.member_id)?, team.position(b.member_id)?)));
+
+    match positions {
+        Some((a, b)) => Some(a.distance(&b)),
+        None => distance(a, b),
+    }
+}
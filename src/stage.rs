@@ -0,0 +1,103 @@
+//! Temporary boosts and drops of a member's properties, counted in stages.
+//!
+//! Buffs and debuffs raise or lower a [`Stat`] of a member by some stages, clamped between [`MIN_STAGE`] and
+//! [`MAX_STAGE`], instead of permanently changing its [`Properties`](crate::member::Properties).
+//! Stages are kept in the [`ComponentStore`](crate::component::ComponentStore) for the duration of the battle, and are
+//! applied to the [final properties](crate::state::BattleState::final_properties) of members by
+//! [`Properties::apply_stages()`](crate::member::Properties::apply_stages).
+
+use alloc::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Highest stage a stat can be raised to.
+pub const MAX_STAGE: i8 = 6;
+
+/// Lowest stage a stat can be lowered to.
+pub const MIN_STAGE: i8 = -6;
+
+/// Property of a member that can be raised or lowered in stages.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stat {
+    /// Look at [`Properties::attack()`](crate::member::Properties::attack).
+    Attack,
+    /// Look at [`Properties::defense()`](crate::member::Properties::defense).
+    Defense,
+    /// Look at [`Properties::accuracy()`](crate::member::Properties::accuracy).
+    Accuracy,
+    /// Look at [`Properties::evasion()`](crate::member::Properties::evasion).
+    Evasion,
+    /// Look at [`Properties::speed()`](crate::member::Properties::speed).
+    Speed,
+}
+
+/// Stages of every [`Stat`] of a member.
+///
+/// # Notes
+///
+/// Stats that were never changed are at stage 0, which leaves their value untouched.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatStages {
+    stages: BTreeMap<Stat, i8>,
+}
+
+impl StatStages {
+    /// Create a new [`StatStages`] with every stat at stage 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current stage of a stat.
+    pub fn stage(&self, stat: Stat) -> i8 {
+        self.stages.get(&stat).copied().unwrap_or(0)
+    }
+
+    /// Raise (or lower, if negative) a stat by some stages, returning the change actually applied.
+    ///
+    /// # Notes
+    ///
+    /// The resulting stage is clamped between [`MIN_STAGE`] and [`MAX_STAGE`], so the applied change is 0 if the
+    /// stat is already at the limit.
+    pub fn change(&mut self, stat: Stat, amount: i8) -> i8 {
+        let current = self.stage(stat);
+        let stage = current.saturating_add(amount).clamp(MIN_STAGE, MAX_STAGE);
+
+        if stage == 0 {
+            self.stages.remove(&stat);
+        } else {
+            self.stages.insert(stat, stage);
+        }
+
+        stage - current
+    }
+
+    /// Returns the value of a stat scaled by its current stage.
+    ///
+    /// # Notes
+    ///
+    /// Every stage above 0 adds half of the original value, while every stage below 0 shrinks it by the same ratio,
+    /// going from a quarter of the value at [`MIN_STAGE`] to four times the value at [`MAX_STAGE`].
+    pub fn scale(&self, stat: Stat, value: u64) -> u64 {
+        let stage = u64::from(self.stage(stat).unsigned_abs());
+
+        if self.stage(stat) >= 0 {
+            value.saturating_mul(2 + stage) / 2
+        } else {
+            value.saturating_mul(2) / (2 + stage)
+        }
+    }
+
+    /// Returns whether every stat is at stage 0.
+    pub fn is_neutral(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Returns an iterator over every stat that isn't at stage 0, and its stage.
+    pub fn iter(&self) -> impl Iterator<Item = (Stat, i8)> + '_ {
+        self.stages.iter().map(|(stat, stage)| (*stat, *stage))
+    }
+}
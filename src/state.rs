@@ -0,0 +1,1182 @@
+//! Authoritative data of a running [`Battle`](crate::battle::Battle).
+//!
+//! Not to be confused with [`battle::State`](crate::battle::State), which only describes the battle's progress.
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::action::ActionId;
+use crate::aura::TeamAura;
+use crate::component::ComponentStore;
+use crate::control::{Control, ControlTracker};
+use crate::damage::{Damage, Mitigation, Pipeline};
+use crate::equipment::{Equipment, Wear};
+use crate::event::{ActionSummary, Event, EventLog, Retention};
+use crate::formation::ZoneOfControl;
+use crate::inventory::{Inventory, InventoryOwner, ItemId};
+use crate::member::{Member, MemberHandle, MemberIdentifier, Properties};
+use crate::narration::narrate;
+use crate::objective::Objective;
+use crate::observer::BattleObserver;
+use crate::reaction::{OpportunityAttack, OpportunityHandler};
+use crate::rng::BattleRng;
+use crate::statistics::CombatStatistics;
+use crate::summon::Summoning;
+use crate::team::Team;
+use crate::terrain::{Structure, StructureId};
+use crate::threat::ThreatTable;
+
+/// Collection of everything that can change while a battle is played.
+///
+/// # Notes
+///
+/// Changes to members and components should always be made by applying [`Event`]s with [`BattleState::apply()`],
+/// so that they are recorded in the [`EventLog`].
+pub struct BattleState<M> {
+    /// List of all teams involved in the battle.
+    pub(crate) team_list: Vec<Team<M>>,
+    /// Additional per-member data stored for the duration of the battle.
+    pub(crate) components: ComponentStore,
+    /// Source of randomness of the whole battle.
+    pub(crate) rng: BattleRng,
+    /// History of all changes applied to this state.
+    pub(crate) events: EventLog,
+    /// Members interrupted during the current turn.
+    pub(crate) interrupted: BTreeSet<MemberIdentifier>,
+    /// Stages every damage calculation goes through.
+    pub(crate) damage_pipeline: Pipeline<M>,
+    /// Listeners notified of every recorded event.
+    pub(crate) observers: Vec<Box<dyn BattleObserver<M>>>,
+    /// Threat generated by every member against every team.
+    pub(crate) threat: ThreatTable,
+    /// Control effects applied to every member.
+    pub(crate) controls: ControlTracker,
+    /// Attacks provoked by members moving out of threatened cells.
+    pub(crate) opportunities: Vec<OpportunityAttack<M>>,
+    /// Non-member objects of the battlefield, in the order they were built.
+    pub(crate) structures: Vec<Structure>,
+    /// Consumable items held by members, as the ones of teams are held by the teams themselves.
+    pub(crate) inventories: BTreeMap<InventoryOwner, Inventory>,
+    /// Consumable items used up by every member and team.
+    pub(crate) consumed: BTreeMap<InventoryOwner, Inventory>,
+    /// Goals of every team, together with the team's ID.
+    pub(crate) objectives: Vec<(usize, Objective)>,
+    /// Auras benefiting every team, together with the team's ID.
+    pub(crate) auras: Vec<(usize, TeamAura<M>)>,
+    /// Experience yielded by every defeated member, together with the ID of its team.
+    pub(crate) fallen: BTreeMap<MemberHandle, (usize, u64)>,
+    /// Policy deciding how many events the log keeps in memory.
+    pub(crate) retention: Retention,
+    /// Total noise made by the actions performed during the battle.
+    pub(crate) noise: u64,
+    /// Performers of the actions being resolved, with the innermost (like an attack of opportunity) last.
+    pub(crate) acting: Vec<Vec<MemberIdentifier>>,
+    /// Combat statistics of every member, if they are tracked.
+    pub(crate) statistics: Option<CombatStatistics>,
+    /// Amount of alive members of every team, updated as members are defeated, revived, added and removed.
+    alive: Vec<usize>,
+    /// Whether members were changed without events (like through [`Context::targets()`](crate::action::Context::targets)),
+    /// so that the alive counts must be recounted.
+    pub(crate) alive_stale: bool,
+    /// Stable handle of every member, laid out like the team list.
+    handles: Vec<Vec<MemberHandle>>,
+    /// Value of the next assigned handle.
+    next_handle: u64,
+}
+
+impl<M: Member> BattleState<M> {
+    /// Create a new [`BattleState`] from the starting teams.
+    ///
+    /// # Notes
+    ///
+    /// Every starting member is given a [`MemberHandle`], in team and member order.
+    pub fn new(team_list: Vec<Team<M>>, rng: BattleRng) -> Self {
+        let mut next_handle = 0;
+
+        let handles = team_list
+            .iter()
+            .map(|t| {
+                t.member_list()
+                    .iter()
+                    .map(|_| {
+                        next_handle += 1;
+                        MemberHandle::new(next_handle - 1)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let alive = team_list.iter().map(count_alive).collect();
+
+        Self {
+            team_list,
+            components: ComponentStore::new(),
+            rng,
+            events: EventLog::new(),
+            interrupted: BTreeSet::new(),
+            damage_pipeline: Pipeline::new().with_stage(Mitigation),
+            observers: Vec::new(),
+            threat: ThreatTable::new(),
+            controls: ControlTracker::new(),
+            opportunities: Vec::new(),
+            structures: Vec::new(),
+            inventories: BTreeMap::new(),
+            consumed: BTreeMap::new(),
+            objectives: Vec::new(),
+            auras: Vec::new(),
+            fallen: BTreeMap::new(),
+            retention: Retention::Full,
+            noise: 0,
+            acting: Vec::new(),
+            statistics: None,
+            alive,
+            alive_stale: false,
+            handles,
+            next_handle,
+        }
+    }
+
+    /// Rebuild the state reached by applying a list of events to the starting teams.
+    ///
+    /// # Notes
+    ///
+    /// Only changes that were applied through [`Event`]s can be replayed, and only from a [complete](EventLog::is_complete) log.
+    /// Derived events (like [`Event::MemberDefeated`], [`Event::StructureDestroyed`], [`Event::EquipmentBroken`] and the
+    /// shield ones) are recorded again while replaying the events that cause them.
+    ///
+    /// The random number generator is created from the seed recorded by [`Event::BattleStarted`], wherever it is in the log,
+    /// so it starts out like the battle's one did before the opening effects drew from it.
+    /// Random draws aren't events, so only the seed can be restored, not the generator's state at the end of the log.
+    pub fn replay<'a>(
+        team_list: Vec<Team<M>>,
+        events: impl IntoIterator<Item = &'a Event>,
+    ) -> Self {
+        let events: Vec<&Event> = events.into_iter().collect();
+
+        let seed = events.iter().find_map(|e| match e {
+            Event::BattleStarted { seed } => Some(*seed),
+            _ => None,
+        });
+
+        let mut state = Self::new(team_list, BattleRng::from_seed(seed.unwrap_or(0)));
+
+        for e in events {
+            if matches!(
+                e,
+                Event::MemberDefeated { .. }
+                    | Event::StructureDestroyed { .. }
+                    | Event::EquipmentBroken { .. }
+                    | Event::ShieldAbsorbed { .. }
+                    | Event::ShieldExpired { .. }
+                    | Event::LogTruncated { .. }
+            ) {
+                continue;
+            }
+
+            state.apply(e.clone());
+        }
+
+        state
+    }
+
+    /// Apply an [`Event`] to the state and record it.
+    ///
+    /// # Notes
+    ///
+    /// Damage that depletes a member's health also records an [`Event::MemberDefeated`] right after the damage.
+    /// The same goes for structures, with an [`Event::StructureDestroyed`].
+    /// Damage absorbed by a member's shield is recorded with an [`Event::ShieldAbsorbed`] before the defeat, while the end of
+    /// a round records an [`Event::ShieldExpired`] for every member whose shield expired.
+    ///
+    /// Damage also wears down the [`Equipment`] of the damaged member and of the performers of the current action,
+    /// recording an [`Event::EquipmentBroken`] for each member whose equipment broke.
+    pub fn apply(&mut self, event: Event) {
+        let mut defeated = None;
+        let mut destroyed = None;
+        let mut broken = Vec::new();
+        let mut expired = Vec::new();
+        let mut absorbed = None;
+        let mut shields = Vec::new();
+
+        match &event {
+            Event::Damaged { member, amount } => {
+                match self
+                    .team_list
+                    .get_mut(member.team_id)
+                    .and_then(|t| t.member_mut(member.member_id))
+                {
+                    Some(m) => {
+                        let alive = m.health() > 0;
+                        let shield = m.member_properties().shield();
+                        m.damage(*amount);
+
+                        let amount = shield.saturating_sub(m.member_properties().shield());
+                        if amount > 0 {
+                            absorbed = Some((*member, amount));
+                        }
+
+                        if alive && m.health() == 0 {
+                            defeated = Some(*member);
+                            self.alive[member.team_id] =
+                                self.alive[member.team_id].saturating_sub(1);
+                        }
+                    }
+                    None => log::warn!("Could not find damaged member at index {:?}", member),
+                }
+
+                if *amount > 0 {
+                    broken = self.wear_equipment(*member);
+                }
+            }
+            Event::Healed { member, amount } => {
+                match self
+                    .team_list
+                    .get_mut(member.team_id)
+                    .and_then(|t| t.member_mut(member.member_id))
+                {
+                    Some(m) => m.heal(*amount),
+                    None => log::warn!("Could not find healed member at index {:?}", member),
+                }
+            }
+            Event::Revived { member, health } => {
+                match self
+                    .team_list
+                    .get_mut(member.team_id)
+                    .and_then(|t| t.member_mut(member.member_id))
+                {
+                    Some(m) if m.is_defeated() => {
+                        m.revive(*health);
+                        self.alive[member.team_id] += 1;
+                    }
+                    Some(_) => {
+                        log::warn!("Member {:?} can't be revived as it isn't defeated", member)
+                    }
+                    None => log::warn!("Could not find revived member at index {:?}", member),
+                }
+            }
+            Event::ShieldGained { member, amount } => {
+                let shielded = self
+                    .team_list
+                    .get_mut(member.team_id)
+                    .and_then(|t| t.member_mut(member.member_id))
+                    .and_then(|m| m.member_properties_mut().shield_mut())
+                    .map(|shield| *shield = shield.saturating_add(*amount));
+
+                if shielded.is_none() {
+                    log::warn!("Member at index {:?} can't hold a shield", member);
+                }
+            }
+            // Shields only last until the end of the round they were granted in.
+            Event::RoundEnded { .. } => shields = self.expire_shields(),
+            Event::ResourceSpent {
+                member,
+                resource,
+                amount,
+            } => {
+                let spent = self
+                    .team_list
+                    .get_mut(member.team_id)
+                    .and_then(|t| t.member_mut(member.member_id))
+                    .is_some_and(|m| m.member_properties_mut().spend_resource(*resource, *amount));
+
+                if !spent {
+                    log::warn!(
+                        "Member at index {:?} could not spend {} {:?}",
+                        member,
+                        amount,
+                        resource
+                    );
+                }
+            }
+            Event::TagApplied { member, tag } => self.components.apply_tag(*member, tag.clone()),
+            Event::TagConsumed { member, tag } => {
+                self.components.consume_tag(*member, tag);
+            }
+            Event::StageChanged {
+                member,
+                stat,
+                change,
+            } => {
+                self.components.change_stage(*member, *stat, *change);
+            }
+            Event::CooldownStarted {
+                member,
+                action,
+                turns,
+            } => self
+                .components
+                .start_cooldown(*member, action.clone(), *turns),
+            Event::CooldownsTicked => {
+                self.components.tick_cooldowns();
+                expired = self.tick_auras();
+            }
+            Event::ActionPerformed { action, performers } => {
+                for member in performers {
+                    self.components.reveal_action(*member, action.clone());
+                }
+
+                self.acting.push(performers.clone());
+            }
+            Event::ActionResolved { .. } => {
+                self.acting.pop();
+            }
+            Event::Interrupted { member } => {
+                self.interrupted.insert(*member);
+            }
+            Event::Summoned {
+                member,
+                summoner,
+                lifetime,
+            } => self.components.add_summon(
+                *member,
+                Summoning {
+                    summoner: Some(*summoner),
+                    lifetime: *lifetime,
+                },
+            ),
+            Event::Moved { member, to, .. } => {
+                match self
+                    .team_list
+                    .get_mut(member.team_id)
+                    .and_then(|t| t.formation_mut())
+                {
+                    Some(f) => f.set_position(member.member_id, *to),
+                    None => log::warn!("Could not find the formation of moved member {:?}", member),
+                }
+            }
+            Event::StructureBuilt { name, health } => {
+                self.structures.push(Structure::new(name.clone(), *health));
+            }
+            Event::StructureDamaged { structure, amount } => {
+                match self.structures.get_mut(structure.0) {
+                    Some(s) => {
+                        let standing = !s.is_destroyed();
+                        s.damage(*amount);
+
+                        if standing && s.is_destroyed() {
+                            destroyed = Some(*structure);
+                        }
+                    }
+                    None => log::warn!("Could not find damaged structure {:?}", structure),
+                }
+            }
+            Event::ItemsStocked {
+                owner,
+                item,
+                amount,
+            } => match self.inventory_mut(*owner) {
+                Some(inventory) => inventory.add(item.clone(), *amount),
+                None => log::warn!("Could not find the inventory of {:?}", owner),
+            },
+            Event::ItemConsumed { owner, item } => {
+                let taken = self.inventory_mut(*owner).is_some_and(|i| i.take(item));
+
+                if taken {
+                    self.consumed
+                        .entry(*owner)
+                        .or_default()
+                        .add(item.clone(), 1);
+                } else {
+                    log::warn!("Could not find item {:?} owned by {:?}", item, owner);
+                }
+            }
+            Event::NoiseMade { amount, .. } => self.noise = self.noise.saturating_add(*amount),
+            Event::SummonExpired { member } => {
+                self.components.remove_summon(*member);
+            }
+            Event::MemberLeft { member } => {
+                if self.detach(*member).is_none() {
+                    log::warn!("Could not find removed member at index {:?}", member);
+                }
+            }
+            // Interruptions only last for the turn they happened in, while summons count the turns they spend in battle.
+            Event::TurnStarted { .. } => {
+                self.interrupted.clear();
+                self.acting.clear();
+                self.components.tick_summons();
+            }
+            // Events that only concern the turn system don't change any data stored here.
+            // The member is inserted by `BattleState::add_member()`, this event only records it.
+            Event::BattleStarted { .. }
+            | Event::MemberJoined { .. }
+            | Event::MemberDefeated { .. }
+            | Event::EquipmentBroken { .. }
+            | Event::StructureDestroyed { .. }
+            | Event::Targeted { .. }
+            | Event::CriticalHit { .. }
+            | Event::Missed { .. }
+            | Event::ActionInterrupted { .. }
+            | Event::EnvironmentTriggered { .. }
+            | Event::LootDropped { .. }
+            | Event::LogTruncated { .. }
+            | Event::WaveCleared { .. }
+            | Event::RoundStarted { .. }
+            | Event::ShieldAbsorbed { .. }
+            | Event::ShieldExpired { .. }
+            | Event::TurnForfeited { .. }
+            | Event::ThreatGenerated { .. }
+            | Event::ControlApplied { .. }
+            | Event::ControlResisted { .. }
+            | Event::MoveBlocked { .. }
+            | Event::ChoiceTimedOut { .. }
+            | Event::ControlTakenOver { .. }
+            | Event::ControlRestored { .. }
+            | Event::RevivalCountdown { .. }
+            | Event::MemberRevived { .. }
+            | Event::PerformerSuggested { .. }
+            | Event::AuraApplied { .. }
+            | Event::AuraExpired { .. }
+            | Event::BattlePaused
+            | Event::BattleResumed
+            | Event::BattleFinished => {}
+        }
+
+        self.record(event);
+
+        if let Some((member, amount)) = absorbed {
+            narrate!(
+                "The shield of member {:?} absorbs {} damage",
+                member,
+                amount
+            );
+
+            self.record(Event::ShieldAbsorbed { member, amount });
+        }
+
+        if let Some(member) = defeated {
+            narrate!("Member {:?} was defeated", member);
+
+            let experience = self
+                .team_list
+                .get(member.team_id)
+                .and_then(|t| t.member(member.member_id))
+                .map(Member::experience_yield);
+
+            if let (Some(handle), Some(experience)) = (self.handle(member), experience) {
+                self.fallen.insert(handle, (member.team_id, experience));
+            }
+
+            self.record(Event::MemberDefeated { member });
+        }
+
+        for member in broken {
+            narrate!("The equipment of member {:?} broke", member);
+
+            self.record(Event::EquipmentBroken { member });
+        }
+
+        for (member, amount) in shields {
+            narrate!("The shield of member {:?} expired", member);
+
+            self.record(Event::ShieldExpired { member, amount });
+        }
+
+        for (team_id, aura) in expired {
+            narrate!("The aura {} of team {} wore off", aura, team_id);
+
+            self.record(Event::AuraExpired { team_id, aura });
+        }
+
+        if let Some(structure) = destroyed {
+            narrate!("Structure {:?} was destroyed", structure);
+
+            self.record(Event::StructureDestroyed { structure });
+        }
+    }
+
+    /// Build a new [`Structure`] on the battlefield, returning its [`StructureId`].
+    pub fn add_structure(&mut self, name: String, health: u64) -> StructureId {
+        let id = StructureId(self.structures.len());
+
+        self.apply(Event::StructureBuilt { name, health });
+
+        id
+    }
+
+    /// Add some consumable items to the inventory of a member or team.
+    pub fn add_items(&mut self, owner: InventoryOwner, item: ItemId, amount: u32) {
+        self.apply(Event::ItemsStocked {
+            owner,
+            item,
+            amount,
+        });
+    }
+
+    /// Returns the inventory of a member or team.
+    ///
+    /// Returns `None` if the member was never given any item, or if the team doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// Team inventories are owned by the [`Team`]s themselves, so they are kept once the battle is over.
+    pub fn inventory(&self, owner: InventoryOwner) -> Option<&Inventory> {
+        match owner {
+            InventoryOwner::Member(_) => self.inventories.get(&owner),
+            InventoryOwner::Team(team_id) => self.team_list.get(team_id).map(Team::inventory),
+        }
+    }
+
+    /// Returns a mutable reference to the inventory of a member or team, creating the member's one if needed.
+    ///
+    /// Returns `None` if the team doesn't exist.
+    fn inventory_mut(&mut self, owner: InventoryOwner) -> Option<&mut Inventory> {
+        match owner {
+            InventoryOwner::Member(_) => Some(self.inventories.entry(owner).or_default()),
+            InventoryOwner::Team(team_id) => {
+                self.team_list.get_mut(team_id).map(Team::inventory_mut)
+            }
+        }
+    }
+
+    /// Returns the items used up by a member or team during the battle, or `None` if it never used any.
+    pub fn consumed_items(&self, owner: InventoryOwner) -> Option<&Inventory> {
+        self.consumed.get(&owner)
+    }
+
+    /// Returns the total noise made by the actions performed during the battle.
+    ///
+    /// # Notes
+    ///
+    /// Noise is never lowered, so it can be compared against thresholds like with [`Trigger::Noise`](crate::reinforcement::Trigger::Noise).
+    pub fn noise(&self) -> u64 {
+        self.noise
+    }
+
+    /// Returns every structure of the battlefield, in the order they were built.
+    pub fn structures(&self) -> &[Structure] {
+        &self.structures
+    }
+
+    /// Returns a structure of the battlefield.
+    ///
+    /// Returns `None` if the structure doesn't exist.
+    pub fn structure(&self, id: StructureId) -> Option<&Structure> {
+        self.structures.get(id.0)
+    }
+
+    /// Returns the amount of alive members of every team.
+    ///
+    /// # Notes
+    ///
+    /// The amounts are kept up to date as events are applied, so no member is looked at,
+    /// unless an action changed members directly through its [`Context`](crate::action::Context).
+    pub fn alive_summary(&self) -> AliveSummary {
+        if self.alive_stale {
+            return AliveSummary {
+                teams: self.team_list.iter().map(count_alive).collect(),
+            };
+        }
+
+        AliveSummary {
+            teams: self.alive.clone(),
+        }
+    }
+
+    /// Count the alive members again, if an action changed members directly.
+    pub(crate) fn refresh_alive(&mut self) {
+        if self.alive_stale {
+            self.alive = self.team_list.iter().map(count_alive).collect();
+            self.alive_stale = false;
+        }
+    }
+
+    /// Add a new member to a team mid-battle, returning its [`MemberIdentifier`].
+    ///
+    /// Returns `None` if the team doesn't exist.
+    pub fn add_member(&mut self, team_id: usize, member: M) -> Option<MemberIdentifier> {
+        let alive = member.health() > 0;
+        let member_id = self.team_list.get_mut(team_id)?.add_member(member);
+        let member = MemberIdentifier::new(team_id, member_id);
+
+        if alive {
+            self.alive[team_id] += 1;
+        }
+
+        let handle = MemberHandle::new(self.next_handle);
+        self.next_handle += 1;
+        self.handles[team_id].push(handle);
+
+        self.apply(Event::MemberJoined { member });
+
+        Some(member)
+    }
+
+    /// Remove a member from the battle, returning it.
+    ///
+    /// Returns `None` if the member doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// The IDs of the members following the removed one in the same team are shifted down by one,
+    /// together with their components.
+    pub fn remove_member(&mut self, member: MemberIdentifier) -> Option<M> {
+        let removed = self.detach(member)?;
+
+        // The member was already detached, the event is only recorded to be replayed.
+        self.record(Event::MemberLeft { member });
+
+        Some(removed)
+    }
+
+    /// Record the [`Event::ActionResolved`] of the first action performed since the given amount of events.
+    pub(crate) fn resolve_action(&mut self, action: ActionId, since: usize) {
+        let events = self.events.since(since);
+
+        let Some(performed) = events
+            .iter()
+            .position(|e| matches!(e, Event::ActionPerformed { .. }))
+        else {
+            log::warn!("Could not find the performed action {:?}", action);
+
+            return;
+        };
+
+        let summary = ActionSummary::from_events(&events[performed + 1..]);
+
+        self.apply(Event::ActionResolved { action, summary });
+    }
+
+    /// Capture everything that changes while the battle is played.
+    pub(crate) fn checkpoint(&self) -> StateCheckpoint<M>
+    where
+        M: Clone,
+    {
+        StateCheckpoint {
+            team_list: self.team_list.clone(),
+            components: self.components.clone(),
+            rng: self.rng.clone(),
+            events: self.events.clone(),
+            interrupted: self.interrupted.clone(),
+            threat: self.threat.clone(),
+            controls: self.controls.clone(),
+            structures: self.structures.clone(),
+            inventories: self.inventories.clone(),
+            consumed: self.consumed.clone(),
+            objectives: self.objectives.clone(),
+            auras: self.auras.clone(),
+            opportunities: self.opportunities.clone(),
+            fallen: self.fallen.clone(),
+            noise: self.noise,
+            statistics: self.statistics.clone(),
+            alive: self.alive.clone(),
+            alive_stale: self.alive_stale,
+            handles: self.handles.clone(),
+            next_handle: self.next_handle,
+        }
+    }
+
+    /// Restore everything captured by a checkpoint.
+    pub(crate) fn rollback(&mut self, checkpoint: StateCheckpoint<M>) {
+        self.team_list = checkpoint.team_list;
+        self.components = checkpoint.components;
+        self.rng = checkpoint.rng;
+        self.events = checkpoint.events;
+        self.interrupted = checkpoint.interrupted;
+        self.threat = checkpoint.threat;
+        self.controls = checkpoint.controls;
+        self.structures = checkpoint.structures;
+        self.inventories = checkpoint.inventories;
+        self.consumed = checkpoint.consumed;
+        self.objectives = checkpoint.objectives;
+        self.auras = checkpoint.auras;
+        self.opportunities = checkpoint.opportunities;
+        self.fallen = checkpoint.fallen;
+        self.noise = checkpoint.noise;
+        self.statistics = checkpoint.statistics;
+        self.alive = checkpoint.alive;
+        self.alive_stale = checkpoint.alive_stale;
+        self.handles = checkpoint.handles;
+        self.next_handle = checkpoint.next_handle;
+    }
+
+    /// Set the policy deciding how many events the log keeps in memory.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`Retention`] to learn what is lost when the log is truncated.
+    pub fn set_retention(&mut self, retention: Retention) {
+        self.retention = retention;
+    }
+
+    /// Give an [`Objective`] to a team, reported by the [`BattleOutcome`](crate::outcome::BattleOutcome).
+    pub fn add_objective(&mut self, team_id: usize, objective: Objective) {
+        self.objectives.push((team_id, objective));
+    }
+
+    /// Let a [`TeamAura`] benefit every member of a team, applying an [`Event::AuraApplied`].
+    ///
+    /// # Notes
+    ///
+    /// Auras are kept by the state, so they aren't brought back when rebuilding it from its events with
+    /// [`BattleState::replay()`].
+    pub fn add_aura(&mut self, team_id: usize, aura: TeamAura<M>) {
+        let name = String::from(aura.name());
+
+        narrate!("Team {} is surrounded by the aura {}", team_id, name);
+
+        self.auras.push((team_id, aura));
+        self.apply(Event::AuraApplied {
+            team_id,
+            aura: name,
+        });
+    }
+
+    /// Returns the auras currently benefiting a team, in the order they were added.
+    pub fn auras(&self, team_id: usize) -> impl Iterator<Item = &TeamAura<M>> {
+        self.auras
+            .iter()
+            .filter(move |(id, _)| *id == team_id)
+            .map(|(_, aura)| aura)
+    }
+
+    /// Returns the final properties of a member, including the auras of its team and its stat stages.
+    ///
+    /// Returns `None` if the member doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`Member::final_properties()`] for the properties of a member without the auras of its team,
+    /// and at [`Properties::apply_stages()`] to learn how stages are applied.
+    pub fn final_properties(&self, member: MemberIdentifier) -> Option<M::Properties> {
+        final_properties(&self.team_list, &self.auras, &self.components, member)
+    }
+
+    /// Returns a copy of a member with the auras of its team summed into its properties.
+    pub(crate) fn with_auras(&self, id: MemberIdentifier) -> Option<M> {
+        with_auras(&self.team_list, &self.auras, id)
+    }
+
+    /// Let one turn pass for every aura, returning the names of the ones that wore off, together with their team's ID.
+    fn tick_auras(&mut self) -> Vec<(usize, String)> {
+        let mut expired = Vec::new();
+
+        self.auras.retain_mut(|(team_id, aura)| {
+            if aura.tick() {
+                expired.push((*team_id, String::from(aura.name())));
+
+                false
+            } else {
+                true
+            }
+        });
+
+        expired
+    }
+
+    /// Remove the shield of every member, returning the amount each shielded member lost.
+    fn expire_shields(&mut self) -> Vec<(MemberIdentifier, u64)> {
+        let mut expired = Vec::new();
+
+        for (team_id, team) in self.team_list.iter_mut().enumerate() {
+            for (member_id, member) in team.member_list_mut().iter_mut().enumerate() {
+                if let Some(shield) = member.member_properties_mut().shield_mut() {
+                    if *shield > 0 {
+                        expired.push((MemberIdentifier::new(team_id, member_id), *shield));
+                        *shield = 0;
+                    }
+                }
+            }
+        }
+
+        expired
+    }
+
+    /// Returns the objectives of every team, together with the team's ID, in the order they were added.
+    pub fn objectives(&self) -> &[(usize, Objective)] {
+        &self.objectives
+    }
+
+    /// Enable or disable the tracking of [`CombatStatistics`].
+    ///
+    /// # Notes
+    ///
+    /// Enabling the tracking starts from empty statistics, while disabling it throws the accumulated ones away.
+    /// Tracking is left untouched if it's already in the requested state.
+    pub fn set_statistics_tracking(&mut self, enabled: bool) {
+        if enabled != self.statistics.is_some() {
+            self.statistics = enabled.then(CombatStatistics::new);
+        }
+    }
+
+    /// Returns the [`CombatStatistics`] accumulated since their tracking was enabled, or `None` if they aren't tracked.
+    pub fn statistics(&self) -> Option<&CombatStatistics> {
+        self.statistics.as_ref()
+    }
+
+    /// Register a [`BattleObserver`], notified of every event recorded from now on.
+    pub fn add_observer(&mut self, observer: Box<dyn BattleObserver<M>>) {
+        self.observers.push(observer);
+    }
+
+    /// Let a member threaten a [`ZoneOfControl`], attacking enemies that move out of it.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`OpportunityHandler`] for more information. Handlers are invoked in the same order they are added.
+    pub fn add_opportunity_attack(
+        &mut self,
+        member: MemberIdentifier,
+        zone: ZoneOfControl,
+        handler: OpportunityHandler<M>,
+    ) {
+        self.opportunities.push(OpportunityAttack {
+            member,
+            zone,
+            handler: Rc::from(handler),
+        });
+    }
+
+    /// Returns the [`ThreatTable`] of the battle.
+    pub fn threat(&self) -> &ThreatTable {
+        &self.threat
+    }
+
+    /// Returns the alive member of a team other than `team_id` with the most threat against it.
+    ///
+    /// # Notes
+    ///
+    /// Ties are broken in favour of the member with the lowest team and member ID.
+    pub fn most_threatening(&self, team_id: usize) -> Option<MemberIdentifier> {
+        let mut most: Option<(MemberIdentifier, u64)> = None;
+
+        for (t, team) in self.team_list.iter().enumerate() {
+            if t == team_id {
+                continue;
+            }
+
+            for (m, member) in team.member_list().iter().enumerate() {
+                let id = MemberIdentifier::new(t, m);
+                let threat = self.threat.threat(id, team_id);
+
+                if member.health() > 0 && most.is_none_or(|(_, max)| threat > max) {
+                    most = Some((id, threat));
+                }
+            }
+        }
+
+        most.map(|(id, _)| id)
+    }
+
+    /// Record an already applied event and notify every observer.
+    ///
+    /// # Notes
+    ///
+    /// The log is truncated following the [`Retention`] policy, around the new event.
+    fn record(&mut self, event: Event) {
+        if self.retention == Retention::CurrentTurn && matches!(event, Event::TurnStarted { .. }) {
+            self.truncate_log(self.events.events().len());
+        }
+
+        self.threat.observe(&event, self.team_list.len());
+        self.controls.observe(&event);
+
+        if let Some(statistics) = &mut self.statistics {
+            let performers = self.acting.last().map_or(&[][..], Vec::as_slice);
+            statistics.observe(&event, performers);
+        }
+
+        let truncation = matches!(event, Event::LogTruncated { .. });
+        self.events.push(event);
+
+        // Observers are taken out of the state to let them look at it while being notified.
+        let mut observers = core::mem::take(&mut self.observers);
+        let event = self
+            .events
+            .events()
+            .last()
+            .expect("an event was just recorded");
+
+        for observer in &mut observers {
+            observer.on_event(event, self);
+        }
+
+        self.observers = observers;
+
+        if let Retention::MaxEvents(max) = self.retention {
+            let kept = self.events.events().len();
+
+            // The truncation event itself never triggers another truncation, even with tiny limits.
+            if kept > max && !truncation {
+                self.truncate_log(kept - max / 2);
+            }
+        }
+    }
+
+    /// Drop the oldest events of the log, recording the truncation.
+    fn truncate_log(&mut self, amount: usize) {
+        let dropped = self.events.drop_oldest(amount);
+
+        if dropped > 0 {
+            log::debug!("Dropped the {} oldest events of the log", dropped);
+
+            self.record(Event::LogTruncated { dropped });
+        }
+    }
+
+    /// Wear down the equipment of a damaged member and of the performers damaging it,
+    /// returning the members whose equipment broke.
+    fn wear_equipment(&mut self, damaged: MemberIdentifier) -> Vec<MemberIdentifier> {
+        let attackers = self.acting.last().cloned().unwrap_or_default();
+
+        let worn = attackers
+            .into_iter()
+            .filter(|p| *p != damaged)
+            .map(|p| (p, Wear::Attacking))
+            .chain([(damaged, Wear::Hit)]);
+
+        worn.filter(|(id, wear)| {
+            self.team_list
+                .get_mut(id.team_id)
+                .and_then(|t| t.member_mut(id.member_id))
+                .and_then(|m| m.equipment_mut())
+                .is_some_and(|e| e.wear(*wear))
+        })
+        .map(|(id, _)| id)
+        .collect()
+    }
+
+    /// Take a member out of its team, shifting every data referencing the following members.
+    fn detach(&mut self, member: MemberIdentifier) -> Option<M> {
+        let removed = self
+            .team_list
+            .get_mut(member.team_id)?
+            .remove_member(member.member_id)?;
+
+        if removed.health() > 0 {
+            self.alive[member.team_id] = self.alive[member.team_id].saturating_sub(1);
+        }
+
+        self.handles[member.team_id].remove(member.member_id);
+        self.components.remove_member(member);
+        self.threat.remove_member(member);
+        self.controls.remove_member(member);
+
+        if let Some(statistics) = &mut self.statistics {
+            statistics.remove_member(member);
+        }
+
+        self.inventories = owners_after_removal(core::mem::take(&mut self.inventories), member);
+        self.consumed = owners_after_removal(core::mem::take(&mut self.consumed), member);
+        self.acting = core::mem::take(&mut self.acting)
+            .into_iter()
+            .map(|performers| {
+                performers
+                    .into_iter()
+                    .filter_map(|id| id.after_removal(member))
+                    .collect()
+            })
+            .collect();
+        self.interrupted = core::mem::take(&mut self.interrupted)
+            .into_iter()
+            .filter_map(|id| id.after_removal(member))
+            .collect();
+        self.opportunities = core::mem::take(&mut self.opportunities)
+            .into_iter()
+            .filter_map(|o| {
+                Some(OpportunityAttack {
+                    member: o.member.after_removal(member)?,
+                    ..o
+                })
+            })
+            .collect();
+
+        Some(removed)
+    }
+
+    /// Returns the current [`MemberIdentifier`] of the member with the given handle.
+    ///
+    /// Returns `None` if the member has left the battle.
+    pub fn resolve(&self, handle: MemberHandle) -> Option<MemberIdentifier> {
+        self.handles.iter().enumerate().find_map(|(team_id, t)| {
+            t.iter()
+                .position(|h| *h == handle)
+                .map(|member_id| MemberIdentifier::new(team_id, member_id))
+        })
+    }
+
+    /// Returns the stable [`MemberHandle`] of a member.
+    ///
+    /// Returns `None` if the member doesn't exist.
+    pub fn handle(&self, member: MemberIdentifier) -> Option<MemberHandle> {
+        self.handles
+            .get(member.team_id)?
+            .get(member.member_id)
+            .copied()
+    }
+
+    /// Run a damage calculation through the battle's damage [`Pipeline`].
+    ///
+    /// Returns `None` if the damaged member doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// The damage is only calculated, not applied. Apply it with an [`Event::Damaged`].
+    /// The target's final defense and evasion are filled in before the calculation,
+    /// look at [`BattleState::final_properties()`].
+    pub fn calculate_damage(&mut self, mut damage: Damage) -> Option<Damage> {
+        let member = self.with_auras(damage.target)?;
+        let properties = self.final_properties(damage.target)?;
+
+        damage.defense = properties.defense();
+        damage.evasion = properties.evasion();
+
+        Some(
+            self.damage_pipeline
+                .calculate(damage, &member, &mut self.rng),
+        )
+    }
+
+    /// Returns the damage [`Pipeline`] of the battle.
+    pub fn damage_pipeline(&self) -> &Pipeline<M> {
+        &self.damage_pipeline
+    }
+
+    /// Returns whether a member has been interrupted during the current turn.
+    ///
+    /// # Notes
+    ///
+    /// Stunned members are interrupted for as long as the stun lasts.
+    pub fn is_interrupted(&self, member: MemberIdentifier) -> bool {
+        self.interrupted.contains(&member) || self.controls.is_controlled(member, Control::Stun)
+    }
+
+    /// Returns the control effects applied to every member.
+    pub fn controls(&self) -> &ControlTracker {
+        &self.controls
+    }
+
+    /// Returns a reference to the battling teams.
+    pub fn teams(&self) -> &[Team<M>] {
+        &self.team_list
+    }
+
+    /// Returns a reference to the [`ComponentStore`] of the battle.
+    pub fn components(&self) -> &ComponentStore {
+        &self.components
+    }
+
+    /// Returns the battle's source of randomness.
+    pub fn rng(&mut self) -> &mut BattleRng {
+        &mut self.rng
+    }
+
+    /// Returns the history of all events applied to this state.
+    pub fn events(&self) -> &EventLog {
+        &self.events
+    }
+
+    /// Unwrap the state and return the battling teams.
+    pub fn take_teams(self) -> Vec<Team<M>> {
+        self.team_list
+    }
+}
+
+/// Amount of alive members of every team of a battle.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AliveSummary {
+    teams: Vec<usize>,
+}
+
+impl AliveSummary {
+    /// Returns the amount of alive members of a team, or `0` if the team doesn't exist.
+    pub fn members(&self, team_id: usize) -> usize {
+        self.teams.get(team_id).copied().unwrap_or(0)
+    }
+
+    /// Returns the amount of alive members of every team, indexed by team ID.
+    pub fn teams(&self) -> &[usize] {
+        &self.teams
+    }
+
+    /// Returns the total amount of alive members.
+    pub fn total(&self) -> usize {
+        self.teams.iter().sum()
+    }
+
+    /// Returns the amount of teams with at least one alive member.
+    pub fn teams_alive(&self) -> usize {
+        self.teams.iter().filter(|a| **a > 0).count()
+    }
+}
+
+/// Data of a [`BattleState`] captured by a [`Checkpoint`](crate::checkpoint::Checkpoint).
+pub(crate) struct StateCheckpoint<M> {
+    pub(crate) team_list: Vec<Team<M>>,
+    components: ComponentStore,
+    rng: BattleRng,
+    pub(crate) events: EventLog,
+    interrupted: BTreeSet<MemberIdentifier>,
+    threat: ThreatTable,
+    controls: ControlTracker,
+    structures: Vec<Structure>,
+    inventories: BTreeMap<InventoryOwner, Inventory>,
+    consumed: BTreeMap<InventoryOwner, Inventory>,
+    objectives: Vec<(usize, Objective)>,
+    auras: Vec<(usize, TeamAura<M>)>,
+    opportunities: Vec<OpportunityAttack<M>>,
+    fallen: BTreeMap<MemberHandle, (usize, u64)>,
+    noise: u64,
+    statistics: Option<CombatStatistics>,
+    alive: Vec<usize>,
+    alive_stale: bool,
+    handles: Vec<Vec<MemberHandle>>,
+    next_handle: u64,
+}
+
+/// Returns the final properties of a member of some teams, including the auras of its team and its stat stages.
+///
+/// Look at [`BattleState::final_properties()`] for more information.
+pub(crate) fn final_properties<M: Member>(
+    team_list: &[Team<M>],
+    auras: &[(usize, TeamAura<M>)],
+    components: &ComponentStore,
+    member: MemberIdentifier,
+) -> Option<M::Properties> {
+    let mut properties = with_auras(team_list, auras, member)?.final_properties();
+
+    if let Some(stages) = components.stages(member) {
+        properties.apply_stages(stages);
+    }
+
+    Some(properties)
+}
+
+/// Returns a copy of a member of some teams with the auras of its team summed into its properties.
+fn with_auras<M: Member>(
+    team_list: &[Team<M>],
+    auras: &[(usize, TeamAura<M>)],
+    id: MemberIdentifier,
+) -> Option<M> {
+    let mut member = team_list.get(id.team_id)?.member(id.member_id)?.clone();
+
+    for (_, aura) in auras.iter().filter(|(team_id, _)| *team_id == id.team_id) {
+        aura.apply(&mut member);
+    }
+
+    Some(member)
+}
+
+/// Returns the amount of alive members of a team.
+fn count_alive<M: Member>(team: &Team<M>) -> usize {
+    team.member_list().iter().filter(|m| m.health() > 0).count()
+}
+
+/// Update the owners of some inventories after a member has been removed, dropping the removed member's one.
+fn owners_after_removal(
+    inventories: BTreeMap<InventoryOwner, Inventory>,
+    removed: MemberIdentifier,
+) -> BTreeMap<InventoryOwner, Inventory> {
+    inventories
+        .into_iter()
+        .filter_map(|(owner, inventory)| match owner {
+            InventoryOwner::Member(id) => Some((
+                InventoryOwner::Member(id.after_removal(removed)?),
+                inventory,
+            )),
+            InventoryOwner::Team(_) => Some((owner, inventory)),
+        })
+        .collect()
+}
@@ -0,0 +1,151 @@
+//! Combat statistics of every member, accumulated while a battle is played.
+//!
+//! Tracking is optional and enabled with [`Builder::set_statistics_tracking()`](crate::battle::Builder::set_statistics_tracking).
+//! Unlike the [`BattleOutcome`](crate::outcome::BattleOutcome), which is built from the event log, the
+//! [`CombatStatistics`] are fed by every event as soon as it's recorded, so they still cover the whole battle
+//! once the log has been truncated by its [`Retention`](crate::event::Retention) policy.
+
+use alloc::collections::BTreeMap;
+
+use crate::event::Event;
+use crate::member::MemberIdentifier;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Statistics of a single member.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemberStatistics {
+    pub damage_dealt: u64,
+    pub damage_taken: u64,
+    pub healing_done: u64,
+    pub healing_received: u64,
+    /// Amount of members of other teams defeated by the member's actions.
+    pub kills: u64,
+    /// Amount of actions performed by the member.
+    pub actions: u64,
+}
+
+impl MemberStatistics {
+    /// Returns the sum of these statistics and another member's ones.
+    pub fn sum(&self, rhs: &Self) -> Self {
+        Self {
+            damage_dealt: self.damage_dealt.saturating_add(rhs.damage_dealt),
+            damage_taken: self.damage_taken.saturating_add(rhs.damage_taken),
+            healing_done: self.healing_done.saturating_add(rhs.healing_done),
+            healing_received: self.healing_received.saturating_add(rhs.healing_received),
+            kills: self.kills.saturating_add(rhs.kills),
+            actions: self.actions.saturating_add(rhs.actions),
+        }
+    }
+}
+
+/// Statistics of every member of a battle.
+///
+/// # Notes
+///
+/// Damage, healing and kills are attributed to the performers of the action being resolved.
+/// Changes happening outside of any action (like the ones of
+/// [`EnvironmentScript`](crate::environment::EnvironmentScript)s) only count as taken or received.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CombatStatistics {
+    members: BTreeMap<MemberIdentifier, MemberStatistics>,
+}
+
+impl CombatStatistics {
+    /// Create a new, empty [`CombatStatistics`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the statistics of a member.
+    ///
+    /// # Notes
+    ///
+    /// Members without any recorded activity have empty statistics.
+    pub fn member(&self, member: MemberIdentifier) -> MemberStatistics {
+        self.members.get(&member).copied().unwrap_or_default()
+    }
+
+    /// Returns an iterator over the statistics of every member with some recorded activity, ordered by team and member ID.
+    pub fn members(&self) -> impl Iterator<Item = (MemberIdentifier, &MemberStatistics)> {
+        self.members.iter().map(|(id, s)| (*id, s))
+    }
+
+    /// Returns the sum of the statistics of every member of a team.
+    pub fn team(&self, team_id: usize) -> MemberStatistics {
+        self.members
+            .iter()
+            .filter(|(id, _)| id.team_id == team_id)
+            .fold(MemberStatistics::default(), |sum, (_, s)| sum.sum(s))
+    }
+
+    /// Returns the most valuable member of a team, if any member of it has recorded some activity.
+    ///
+    /// # Notes
+    ///
+    /// Members are ranked by the sum of their damage dealt and healing done, with ties broken by kills.
+    /// Members ranking the same are won by the one with the lowest ID.
+    pub fn most_valuable(&self, team_id: usize) -> Option<MemberIdentifier> {
+        self.members
+            .iter()
+            .filter(|(id, _)| id.team_id == team_id)
+            .max_by_key(|(id, s)| {
+                (
+                    s.damage_dealt.saturating_add(s.healing_done),
+                    s.kills,
+                    core::cmp::Reverse(**id),
+                )
+            })
+            .map(|(id, _)| *id)
+    }
+
+    /// Update the statistics with a newly recorded event, given the performers of the action being resolved.
+    pub(crate) fn observe(&mut self, event: &Event, performers: &[MemberIdentifier]) {
+        match event {
+            Event::ActionPerformed { performers, .. } => {
+                for p in performers {
+                    self.entry(*p).actions += 1;
+                }
+            }
+            Event::Damaged { member, amount } => {
+                let taken = &mut self.entry(*member).damage_taken;
+                *taken = taken.saturating_add(*amount);
+
+                for p in performers {
+                    let dealt = &mut self.entry(*p).damage_dealt;
+                    *dealt = dealt.saturating_add(*amount);
+                }
+            }
+            Event::Healed { member, amount } => {
+                let received = &mut self.entry(*member).healing_received;
+                *received = received.saturating_add(*amount);
+
+                for p in performers {
+                    let done = &mut self.entry(*p).healing_done;
+                    *done = done.saturating_add(*amount);
+                }
+            }
+            Event::MemberDefeated { member } => {
+                for p in performers.iter().filter(|p| p.team_id != member.team_id) {
+                    self.entry(*p).kills += 1;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Forget the statistics of a member removed from the battle, shifting the following members of its team.
+    pub(crate) fn remove_member(&mut self, removed: MemberIdentifier) {
+        self.members = core::mem::take(&mut self.members)
+            .into_iter()
+            .filter_map(|(id, s)| Some((id.after_removal(removed)?, s)))
+            .collect();
+    }
+
+    fn entry(&mut self, member: MemberIdentifier) -> &mut MemberStatistics {
+        self.members.entry(member).or_default()
+    }
+}
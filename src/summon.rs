@@ -0,0 +1,51 @@
+//! Members spawned mid-battle by the action of another member.
+//!
+//! Summoned members join the team of their summoner through [`Context::summon()`](crate::action::Context::summon),
+//! and leave the fight according to their [`Lifetime`]. Expired summons are defeated at the end of the turn.
+
+use crate::member::MemberIdentifier;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Policy deciding how long a summoned member stays in the battle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifetime {
+    /// The summoned member stays until defeated.
+    Permanent,
+    /// The summoned member stays for the given amount of turns after the one it was summoned in.
+    Turns(u32),
+    /// The summoned member is defeated together with its summoner.
+    WithSummoner,
+}
+
+/// Record of a summoned member, stored in the [`ComponentStore`](crate::component::ComponentStore).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summoning {
+    /// Member that summoned this one, or `None` if it has left the battle.
+    pub summoner: Option<MemberIdentifier>,
+    /// Remaining lifetime of the summoned member.
+    pub lifetime: Lifetime,
+}
+
+impl Summoning {
+    /// Count one more turn spent in the battle.
+    pub(crate) fn tick(&mut self) {
+        if let Lifetime::Turns(turns) = &mut self.lifetime {
+            *turns = turns.saturating_sub(1);
+        }
+    }
+
+    /// Returns whether the summoned member must leave the battle.
+    ///
+    /// `summoner_alive` tells whether the summoner is still in the battle with some health left.
+    pub fn is_expired(&self, summoner_alive: bool) -> bool {
+        match self.lifetime {
+            Lifetime::Permanent => false,
+            Lifetime::Turns(turns) => turns == 0,
+            Lifetime::WithSummoner => self.summoner.is_none() || !summoner_alive,
+        }
+    }
+}
@@ -1,19 +1,27 @@
-//! Definitions for [`Team`], groups of [`Member`]s that fight in a [`Battle`](crate::battle::Battle).
+//! Definitions for [`Team`], groups of [`Member`](crate::member::Member)s that fight in a [`Battle`](crate::battle::Battle).
 
-use crate::member::Member;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::formation::{Formation, Position, Row};
+use crate::inventory::Inventory;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-/// Coalition made up of multiple fighting [`Member`]s.
+/// Coalition made up of multiple fighting [`Member`](crate::member::Member)s.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Team<M> {
     name: String,
     member_list: Vec<M>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    formation: Option<Formation>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    inventory: Inventory,
 }
 
-impl<M: Member> Team<M> {
+impl<M> Team<M> {
     /// Create a new [`Team`] object using a list of members associated to it.
     pub fn new(name: String, member_list: Vec<M>) -> Self {
         log::debug!(
@@ -21,7 +29,74 @@ impl<M: Member> Team<M> {
             member_list.len()
         );
 
-        Self { name, member_list }
+        Self {
+            name,
+            member_list,
+            formation: None,
+            inventory: Inventory::new(),
+        }
+    }
+
+    /// Place the team's members according to a [`Formation`].
+    pub fn with_formation(mut self, formation: Formation) -> Self {
+        self.formation = Some(formation);
+
+        self
+    }
+
+    /// Give the team an [`Inventory`] shared by all of its members.
+    pub fn with_inventory(mut self, inventory: Inventory) -> Self {
+        self.inventory = inventory;
+
+        self
+    }
+
+    /// Returns the [`Inventory`] shared by all of the team's members.
+    ///
+    /// # Notes
+    ///
+    /// The inventory is kept by the team after the battle, so items used during the battle are gone for good.
+    pub fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    /// Returns a mutable reference to the [`Inventory`] shared by all of the team's members.
+    pub fn inventory_mut(&mut self) -> &mut Inventory {
+        &mut self.inventory
+    }
+
+    /// Returns the team's [`Formation`], if its members are positioned.
+    pub fn formation(&self) -> Option<&Formation> {
+        self.formation.as_ref()
+    }
+
+    /// Returns a mutable reference to the team's [`Formation`], if its members are positioned.
+    pub fn formation_mut(&mut self) -> Option<&mut Formation> {
+        self.formation.as_mut()
+    }
+
+    /// Returns the position of one associated member, if the team has a formation.
+    pub fn position(&self, member_id: usize) -> Option<Position> {
+        self.formation.as_ref()?.position(member_id)
+    }
+
+    /// Returns a mutable iterator over the members placed in a row.
+    ///
+    /// # Notes
+    ///
+    /// The iterator is empty if the team has no formation.
+    pub fn row_members_mut(&mut self, row: Row) -> impl Iterator<Item = &mut M> {
+        let formation = self.formation.as_ref();
+
+        self.member_list
+            .iter_mut()
+            .enumerate()
+            .filter(move |(id, _)| {
+                formation
+                    .and_then(|f| f.position(*id))
+                    .is_some_and(|p| p.row == row)
+            })
+            .map(|(_, m)| m)
     }
 
     /// Returns this team's name.
@@ -39,6 +114,36 @@ impl<M: Member> Team<M> {
         &mut self.member_list
     }
 
+    /// Remove a member from the team, returning it.
+    ///
+    /// Returns `None` if the member doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// The IDs of the members following the removed one are shifted down by one.
+    pub fn remove_member(&mut self, member_id: usize) -> Option<M> {
+        if member_id >= self.member_list.len() {
+            return None;
+        }
+
+        if let Some(formation) = &mut self.formation {
+            formation.remove(member_id);
+        }
+
+        Some(self.member_list.remove(member_id))
+    }
+
+    /// Add a new member at the end of the member list, returning its ID within the team.
+    ///
+    /// # Notes
+    ///
+    /// If the team has a formation, the new member has no position until one is set.
+    pub fn add_member(&mut self, member: M) -> usize {
+        self.member_list.push(member);
+
+        self.member_list.len() - 1
+    }
+
     /// Returns a reference to one associated member.
     pub fn member(&self, member_id: usize) -> Option<&M> {
         self.member_list.get(member_id)
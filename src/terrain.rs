@@ -0,0 +1,64 @@
+//! Battlefield objects with health that never act, like barricades or crystals.
+//!
+//! [`Structure`]s are not members of any team: they are never suggested as performers and are ignored by
+//! team-based targets and end conditions. Actions reach them through [`Target::Structure`](crate::action::Target::Structure),
+//! and battles can be won by destroying one with [`EndCondition::StructureDestroyed`](crate::battle::EndCondition::StructureDestroyed).
+
+use alloc::string::String;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Index of a [`Structure`] in the battle, in the order structures were built.
+///
+/// # Notes
+///
+/// Structures are never removed from the battle, so their IDs never change.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StructureId(pub usize);
+
+/// Non-member object of the battlefield that can be damaged until it is destroyed.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Structure {
+    name: String,
+    health: u64,
+    max_health: u64,
+}
+
+impl Structure {
+    /// Create a new, undamaged [`Structure`].
+    pub fn new(name: String, max_health: u64) -> Self {
+        Self {
+            name,
+            health: max_health,
+            max_health,
+        }
+    }
+
+    /// Returns the name of the structure.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the current health of the structure.
+    pub fn health(&self) -> u64 {
+        self.health
+    }
+
+    /// Returns the health the structure was built with.
+    pub fn max_health(&self) -> u64 {
+        self.max_health
+    }
+
+    /// Returns whether the structure has no health left.
+    pub fn is_destroyed(&self) -> bool {
+        self.health == 0
+    }
+
+    /// Lower the health of the structure, down to 0.
+    pub(crate) fn damage(&mut self, amount: u64) {
+        self.health = self.health.saturating_sub(amount);
+    }
+}
@@ -0,0 +1,99 @@
+//! Threat (or "aggro") generated by members against the teams they fight.
+//!
+//! The [`ThreatTable`] of a battle is fed by its events: every performer gains threat against a team by damaging
+//! its members, and against every other team by healing. Taunts and similar effects can add threat directly with
+//! [`Event::ThreatGenerated`]. Actions can then aim at the most threatening enemy with
+//! [`Target::MostThreatening`](crate::action::Target::MostThreatening).
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::event::Event;
+use crate::member::MemberIdentifier;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Threat generated by every member against every team.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ThreatTable {
+    /// Threat of each member, against each team ID.
+    threat: BTreeMap<MemberIdentifier, BTreeMap<usize, u64>>,
+    /// Performers of the action being resolved, which the threat of damage and healing is attributed to.
+    performers: Vec<MemberIdentifier>,
+}
+
+impl ThreatTable {
+    /// Create a new, empty [`ThreatTable`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the threat generated by a member against a team.
+    pub fn threat(&self, member: MemberIdentifier, team_id: usize) -> u64 {
+        self.threat
+            .get(&member)
+            .and_then(|t| t.get(&team_id))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Update the table with a newly applied event.
+    ///
+    /// # Notes
+    ///
+    /// Damage generates its whole amount as threat against the damaged member's team.
+    /// Healing generates half of its amount as threat against every team other than the healed member's one.
+    /// Damage and healing within the performers' own team don't generate any threat.
+    pub(crate) fn observe(&mut self, event: &Event, teams: usize) {
+        match event {
+            Event::TurnStarted { .. } => self.performers.clear(),
+            Event::ActionPerformed { performers, .. } => self.performers.clone_from(performers),
+            Event::Damaged { member, amount } => {
+                for p in self.performers.clone() {
+                    if p.team_id != member.team_id {
+                        self.add(p, member.team_id, *amount);
+                    }
+                }
+            }
+            Event::Healed { member, amount } => {
+                for p in self.performers.clone() {
+                    for team_id in (0..teams).filter(|t| *t != member.team_id && *t != p.team_id) {
+                        self.add(p, team_id, amount / 2);
+                    }
+                }
+            }
+            Event::ThreatGenerated {
+                member,
+                team_id,
+                amount,
+            } => self.add(*member, *team_id, *amount),
+            _ => (),
+        }
+    }
+
+    fn add(&mut self, member: MemberIdentifier, team_id: usize, amount: u64) {
+        let threat = self
+            .threat
+            .entry(member)
+            .or_default()
+            .entry(team_id)
+            .or_default();
+
+        *threat = threat.saturating_add(amount);
+    }
+
+    /// Forget the threat of a member removed from the battle, shifting the following members of its team.
+    pub(crate) fn remove_member(&mut self, removed: MemberIdentifier) {
+        self.threat = core::mem::take(&mut self.threat)
+            .into_iter()
+            .filter_map(|(id, t)| Some((id.after_removal(removed)?, t)))
+            .collect();
+
+        self.performers = core::mem::take(&mut self.performers)
+            .into_iter()
+            .filter_map(|id| id.after_removal(removed))
+            .collect();
+    }
+}
@@ -0,0 +1,294 @@
+//! Reusable [ratatui](https://ratatui.rs) widgets displaying the state of a battle.
+//!
+//! - [`HealthBars`] shows the health of every member of a [`Team`].
+//! - [`TurnOrder`] shows the members known to perform the next turns, as tracked by the [`TurnSystem`].
+//! - [`ActionMenu`] lists actions together with their metadata, letting the user pick one of them.
+//!
+//! Every widget borrows the data it displays, so they are meant to be built again on every frame.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, LineGauge, List, ListItem, ListState, Paragraph, StatefulWidget, Widget,
+};
+
+use crate::action::Action;
+use crate::battle::TurnSystem;
+use crate::member::{Member, Statistics};
+use crate::registry::ActionRegistry;
+use crate::team::Team;
+
+/// List of health bars, one for every member of a [`Team`].
+///
+/// # Notes
+///
+/// Bars are filled according to the [reference health](Statistics::reference_health) of each member.
+/// Members that don't fit in the rendered area aren't shown.
+pub struct HealthBars<'a, M> {
+    team: &'a Team<M>,
+    block: Option<Block<'a>>,
+    gauge_style: Style,
+}
+
+impl<'a, M> HealthBars<'a, M> {
+    /// Create a new [`HealthBars`] widget for the members of the team.
+    pub fn new(team: &'a Team<M>) -> Self {
+        Self {
+            team,
+            block: None,
+            gauge_style: Style::default(),
+        }
+    }
+
+    /// Surround the health bars with a block.
+    pub fn with_block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+
+        self
+    }
+
+    /// Set the style of the filled part of the health bars.
+    pub fn with_gauge_style(mut self, style: Style) -> Self {
+        self.gauge_style = style;
+
+        self
+    }
+}
+
+impl<M: Member> Widget for HealthBars<'_, M> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = render_block(self.block, area, buf);
+
+        for (row, member) in (area.top()..area.bottom()).zip(self.team.member_list()) {
+            let health = member.health();
+            let reference_health = member.statistics().reference_health();
+
+            let ratio = if reference_health == 0 {
+                0.0
+            } else {
+                (health as f64 / reference_health as f64).min(1.0)
+            };
+
+            LineGauge::default()
+                .ratio(ratio)
+                .label(format!("{} {health}/{reference_health}", member.name()))
+                .filled_style(self.gauge_style)
+                .render(
+                    Rect {
+                        y: row,
+                        height: 1,
+                        ..area
+                    },
+                    buf,
+                );
+        }
+    }
+}
+
+/// Single-line strip showing the members known to perform the next turns.
+///
+/// # Notes
+///
+/// Look at [`TurnSystem::upcoming_performers()`] for more information about which members are shown.
+/// The first member, which performs the next turn, is highlighted.
+pub struct TurnOrder<'a> {
+    names: Vec<&'a str>,
+    block: Option<Block<'a>>,
+    highlight_style: Style,
+    separator: &'a str,
+}
+
+impl<'a> TurnOrder<'a> {
+    /// Create a new [`TurnOrder`] widget for the turn system of a battle with the given teams.
+    ///
+    /// # Notes
+    ///
+    /// Members that aren't part of the teams anymore are skipped.
+    pub fn new<M: Member>(turn_system: &TurnSystem<M>, teams: &'a [Team<M>]) -> Self {
+        let names = turn_system
+            .upcoming_performers()
+            .into_iter()
+            .filter_map(|id| teams.get(id.team_id)?.member(id.member_id))
+            .map(Member::name)
+            .collect();
+
+        Self {
+            names,
+            block: None,
+            highlight_style: Style::default().add_modifier(Modifier::BOLD),
+            separator: " > ",
+        }
+    }
+
+    /// Surround the strip with a block.
+    pub fn with_block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+
+        self
+    }
+
+    /// Set the style of the member performing the next turn.
+    pub fn with_highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+
+        self
+    }
+
+    /// Set the text shown between two members.
+    pub fn with_separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+
+        self
+    }
+}
+
+impl Widget for TurnOrder<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = render_block(self.block, area, buf);
+
+        let mut spans = Vec::with_capacity(self.names.len() * 2);
+
+        for (i, name) in self.names.into_iter().enumerate() {
+            if i == 0 {
+                spans.push(Span::styled(name, self.highlight_style));
+            } else {
+                spans.push(Span::raw(self.separator));
+                spans.push(Span::raw(name));
+            }
+        }
+
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+}
+
+/// Selectable list of actions, showing the metadata of each one.
+///
+/// # Notes
+///
+/// Every entry shows the name of the [`ActionId`](crate::action::ActionId) (without its module path),
+/// followed by the element, the costs and the cooldown of the action, when it has any.
+/// The selection is kept in a [`ListState`], with the entries in the order they were given.
+pub struct ActionMenu<'a> {
+    entries: Vec<Line<'a>>,
+    block: Option<Block<'a>>,
+    highlight_style: Style,
+    highlight_symbol: &'a str,
+}
+
+impl<'a> ActionMenu<'a> {
+    /// Create a new [`ActionMenu`] listing the given actions.
+    pub fn new<'b, M: 'b>(actions: impl IntoIterator<Item = &'b dyn Action<M>>) -> Self {
+        Self {
+            entries: actions.into_iter().map(entry).collect(),
+            block: None,
+            highlight_style: Style::default().add_modifier(Modifier::REVERSED),
+            highlight_symbol: "> ",
+        }
+    }
+
+    /// Create a new [`ActionMenu`] listing every action of the registry, ordered by ID.
+    pub fn from_registry<M>(registry: &ActionRegistry<M>) -> Self {
+        let actions: Vec<_> = registry
+            .ids()
+            .filter_map(|id| registry.create(id))
+            .collect();
+
+        Self::new(actions.iter().map(|a| a.as_ref()))
+    }
+
+    /// Surround the menu with a block.
+    pub fn with_block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+
+        self
+    }
+
+    /// Set the style of the selected entry.
+    pub fn with_highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+
+        self
+    }
+
+    /// Set the text shown before the selected entry.
+    pub fn with_highlight_symbol(mut self, symbol: &'a str) -> Self {
+        self.highlight_symbol = symbol;
+
+        self
+    }
+
+    /// Returns the amount of entries in the menu.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the menu has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl StatefulWidget for ActionMenu<'_> {
+    type State = ListState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut ListState) {
+        let mut list = List::new(self.entries.into_iter().map(ListItem::new))
+            .highlight_style(self.highlight_style)
+            .highlight_symbol(self.highlight_symbol);
+
+        if let Some(block) = self.block {
+            list = list.block(block);
+        }
+
+        StatefulWidget::render(list, area, buf, state);
+    }
+}
+
+/// Build the menu entry of an action.
+fn entry<'a, M>(action: &dyn Action<M>) -> Line<'a> {
+    let id = action.action_id();
+    let name = id.name().rsplit("::").next().unwrap_or_default();
+
+    let mut details = Vec::new();
+
+    if let Some(element) = action.element() {
+        details.push(element.name().to_string());
+    }
+
+    for cost in action.cost() {
+        details.push(format!("{} {:?}", cost.amount, cost.resource));
+    }
+
+    match action.cooldown_turns() {
+        0 => (),
+        1 => details.push(String::from("1 turn cooldown")),
+        turns => details.push(format!("{turns} turns cooldown")),
+    }
+
+    if details.is_empty() {
+        Line::from(name.to_string())
+    } else {
+        Line::from(vec![
+            Span::raw(name.to_string()),
+            Span::styled(
+                format!(" ({})", details.join(", ")),
+                Style::default().add_modifier(Modifier::DIM),
+            ),
+        ])
+    }
+}
+
+/// Render the block, if any, returning the area left inside of it.
+fn render_block(block: Option<Block<'_>>, area: Rect, buf: &mut Buffer) -> Rect {
+    match block {
+        Some(block) => {
+            let inner = block.inner(area);
+            block.render(area, buf);
+
+            inner
+        }
+        None => area,
+    }
+}
@@ -0,0 +1,214 @@
+//! Hidden information of a battle, like the exact health of the opposing members.
+//!
+//! A [`FogOfWar`] decides what every team can't see about the members of the other teams.
+//! Once set with [`Builder::set_fog_of_war()`](crate::battle::Builder::set_fog_of_war), every
+//! [`ChoicePrompt`](crate::controller::ChoicePrompt) only shows the teams as seen by the team making the choice,
+//! while a [`TeamObserver`] streams the events of the battle as seen by a single team
+//! (and [`Battle::events_seen_by()`](crate::battle::Battle::events_seen_by) lists the past ones).
+//!
+//! Actions are hidden until they are observed: the only actions of a member known to the other teams are the ones it
+//! has already performed, listed by [`ComponentStore::revealed_actions()`](crate::component::ComponentStore::revealed_actions).
+
+use alloc::vec::Vec;
+
+use crate::event::Event;
+use crate::member::{Member, MemberIdentifier, Properties, Statistics};
+use crate::observer::BattleObserver;
+use crate::state::BattleState;
+use crate::team::Team;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Policy deciding what a team can't see about the members of the other teams.
+///
+/// # Notes
+///
+/// A team always sees everything about its own members.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FogOfWar {
+    hide_health: bool,
+    hide_actions: bool,
+}
+
+impl FogOfWar {
+    /// Create a new [`FogOfWar`] hiding both the exact health and the unobserved actions of the opposing members.
+    pub fn new() -> Self {
+        Self {
+            hide_health: true,
+            hide_actions: true,
+        }
+    }
+
+    /// Let every team see the exact health of the opposing members.
+    pub fn with_visible_health(mut self) -> Self {
+        self.hide_health = false;
+
+        self
+    }
+
+    /// Let every team see the actions of the opposing members that were interrupted before being performed.
+    pub fn with_visible_actions(mut self) -> Self {
+        self.hide_actions = false;
+
+        self
+    }
+
+    /// Returns whether the exact health of the opposing members is hidden.
+    pub fn hides_health(&self) -> bool {
+        self.hide_health
+    }
+
+    /// Returns whether the actions of the opposing members are hidden until they are performed.
+    pub fn hides_actions(&self) -> bool {
+        self.hide_actions
+    }
+
+    /// Returns a copy of the teams as seen by a team, or by nobody in particular if `viewer` is `None`.
+    ///
+    /// # Notes
+    ///
+    /// Opposing members that aren't defeated are shown with their [reference health](Statistics::reference_health)
+    /// and without any [shield](Properties::shield), so only their defeat can be told apart.
+    pub fn redact_teams<M: Member>(
+        &self,
+        teams: &[Team<M>],
+        viewer: Option<usize>,
+    ) -> Vec<Team<M>> {
+        let mut teams = teams.to_vec();
+
+        if !self.hide_health {
+            return teams;
+        }
+
+        for (_, team) in teams
+            .iter_mut()
+            .enumerate()
+            .filter(|(team_id, _)| Some(*team_id) != viewer)
+        {
+            for member in team.member_list_mut() {
+                if member.is_defeated() {
+                    continue;
+                }
+
+                let reference = member.statistics().reference_health();
+                let properties = member.member_properties_mut();
+
+                *properties.health_mut() = reference;
+
+                if let Some(shield) = properties.shield_mut() {
+                    *shield = 0;
+                }
+            }
+        }
+
+        teams
+    }
+
+    /// Returns an event as seen by a team, or by nobody in particular if `viewer` is `None`.
+    ///
+    /// Returns `None` if the event is hidden from the team.
+    ///
+    /// # Notes
+    ///
+    /// Health amounts (like damage, healing and shields) concerning opposing members are replaced by 0,
+    /// while action summaries hide their total damage and healing altogether.
+    /// Actions interrupted before being performed are hidden, as they were never observed.
+    pub fn redact_event(&self, event: &Event, viewer: Option<usize>) -> Option<Event> {
+        let hidden = |member: &MemberIdentifier| self.hide_health && Some(member.team_id) != viewer;
+
+        let event = match event {
+            Event::ActionInterrupted { .. } if self.hide_actions => return None,
+            Event::Damaged { member, .. } if hidden(member) => Event::Damaged {
+                member: *member,
+                amount: 0,
+            },
+            Event::Healed { member, .. } if hidden(member) => Event::Healed {
+                member: *member,
+                amount: 0,
+            },
+            Event::Revived { member, .. } if hidden(member) => Event::Revived {
+                member: *member,
+                health: 0,
+            },
+            Event::ShieldGained { member, .. } if hidden(member) => Event::ShieldGained {
+                member: *member,
+                amount: 0,
+            },
+            Event::ShieldAbsorbed { member, .. } if hidden(member) => Event::ShieldAbsorbed {
+                member: *member,
+                amount: 0,
+            },
+            Event::ShieldExpired { member, .. } if hidden(member) => Event::ShieldExpired {
+                member: *member,
+                amount: 0,
+            },
+            Event::ActionResolved { action, summary } if self.hide_health => {
+                let mut summary = summary.clone();
+                summary.damage = 0;
+                summary.healing = 0;
+
+                Event::ActionResolved {
+                    action: action.clone(),
+                    summary,
+                }
+            }
+            event => event.clone(),
+        };
+
+        Some(event)
+    }
+
+    /// Returns a list of events as seen by a team, or by nobody in particular if `viewer` is `None`.
+    ///
+    /// # Notes
+    ///
+    /// Look at [`FogOfWar::redact_event()`] to learn how events are redacted.
+    pub fn redact_events<'a>(
+        &'a self,
+        events: &'a [Event],
+        viewer: Option<usize>,
+    ) -> impl Iterator<Item = Event> + 'a {
+        events
+            .iter()
+            .filter_map(move |event| self.redact_event(event, viewer))
+    }
+}
+
+impl Default for FogOfWar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [`BattleObserver`] streaming the events of a battle as seen by a single team, like a networked player.
+///
+/// # Notes
+///
+/// Unlike other observers, the listener isn't given the [`BattleState`], which holds everything the team can't see.
+/// Look at [`FogOfWar::redact_event()`] to learn how events are redacted.
+pub struct TeamObserver<F> {
+    team_id: usize,
+    fog: FogOfWar,
+    listener: F,
+}
+
+impl<F: FnMut(&Event)> TeamObserver<F> {
+    /// Create a new [`TeamObserver`] calling the listener with every event seen by the team.
+    pub fn new(team_id: usize, fog: FogOfWar, listener: F) -> Self {
+        Self {
+            team_id,
+            fog,
+            listener,
+        }
+    }
+}
+
+impl<M, F: FnMut(&Event)> BattleObserver<M> for TeamObserver<F> {
+    fn on_event(&mut self, event: &Event, _: &BattleState<M>) {
+        if let Some(event) = self.fog.redact_event(event, Some(self.team_id)) {
+            (self.listener)(&event);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Wave-based battles, where defeating every enemy brings the next group of enemies into the fight.
+//!
+//! A [`Waves`] encounter is played inside a single [`Battle`](crate::battle::Battle): the members the enemy team
+//! starts with form the first wave, and each following wave is spawned into the same team once the previous one
+//! has been defeated. Hooks can be run between waves to heal or reward the surviving members.
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::event::Event;
+use crate::member::Member;
+use crate::narration::narrate;
+use crate::reinforcement::MemberTemplate;
+use crate::state::BattleState;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Function type run once a wave has been cleared, before the next one is spawned.
+///
+/// # Notes
+///
+/// Effects (like healing the survivors) should be applied as [`Event`]s using [`BattleState::apply()`].
+/// The hook is also run after the last wave, right before the battle ends.
+pub type WaveHook<M> = Box<dyn Fn(&mut BattleState<M>, &WaveStatistics)>;
+
+/// Statistics gathered while a single wave was fought.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WaveStatistics {
+    /// Index of the wave, starting from 0 for the members the enemy team starts with.
+    pub wave: usize,
+    /// Amount of turns it took to clear the wave.
+    pub turns: u64,
+    /// Total damage inflicted on the members of the wave's team.
+    pub damage_dealt: u64,
+    /// Total damage inflicted on the members of every other team.
+    pub damage_taken: u64,
+}
+
+/// Sequence of enemy groups fought one after the other by the same team.
+pub struct Waves<M> {
+    team_id: usize,
+    pending: VecDeque<MemberTemplate<M>>,
+    /// Templates of the waves already spawned, from the first to the last, kept to be rolled back.
+    spawned: Vec<MemberTemplate<M>>,
+    hooks: Vec<WaveHook<M>>,
+    statistics: Vec<WaveStatistics>,
+    /// Turn number at which the current wave started.
+    started_turn: u64,
+    /// Length of the event log when the current wave started.
+    started_event: usize,
+    /// Whether the last wave has been cleared.
+    finished: bool,
+}
+
+impl<M> Waves<M> {
+    /// Create a new [`Waves`] encounter for the team with the given ID.
+    ///
+    /// # Notes
+    ///
+    /// The members the team starts the battle with form the first wave.
+    pub fn new(team_id: usize) -> Self {
+        Self {
+            team_id,
+            pending: VecDeque::new(),
+            spawned: Vec::new(),
+            hooks: Vec::new(),
+            statistics: Vec::new(),
+            started_turn: 0,
+            started_event: 0,
+            finished: false,
+        }
+    }
+
+    /// Add a wave spawned from the template once all previous ones have been cleared.
+    pub fn with_wave(mut self, template: MemberTemplate<M>) -> Self {
+        self.pending.push_back(template);
+
+        self
+    }
+
+    /// Add a hook run every time a wave is cleared.
+    ///
+    /// # Notes
+    ///
+    /// Hooks are run in the same order they are added.
+    pub fn with_hook(mut self, hook: WaveHook<M>) -> Self {
+        self.hooks.push(hook);
+
+        self
+    }
+
+    /// Returns the ID of the team the waves are spawned into.
+    pub fn team_id(&self) -> usize {
+        self.team_id
+    }
+
+    /// Returns the amount of waves that have yet to be spawned.
+    pub fn remaining(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns the statistics of every wave cleared so far, in order.
+    pub fn statistics(&self) -> &[WaveStatistics] {
+        &self.statistics
+    }
+
+    /// Returns the index of the wave currently being fought, starting from 0.
+    ///
+    /// # Notes
+    ///
+    /// Once the last wave has been cleared, this is the amount of waves of the encounter.
+    pub fn current(&self) -> usize {
+        self.statistics.len()
+    }
+
+    /// Returns whether the last wave has been cleared.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Capture the progress of the encounter.
+    pub(crate) fn checkpoint(&self) -> WavesCheckpoint {
+        WavesCheckpoint {
+            remaining: self.pending.len(),
+            statistics: self.statistics.clone(),
+            started_turn: self.started_turn,
+            started_event: self.started_event,
+            finished: self.finished,
+        }
+    }
+
+    /// Bring the encounter back to the progress captured by a checkpoint.
+    pub(crate) fn rollback(&mut self, checkpoint: WavesCheckpoint) {
+        while self.pending.len() < checkpoint.remaining {
+            let Some(template) = self.spawned.pop() else {
+                break;
+            };
+
+            self.pending.push_front(template);
+        }
+
+        self.statistics = checkpoint.statistics;
+        self.started_turn = checkpoint.started_turn;
+        self.started_event = checkpoint.started_event;
+        self.finished = checkpoint.finished;
+    }
+}
+
+/// Progress of a [`Waves`] encounter captured by a [`Checkpoint`](crate::checkpoint::Checkpoint).
+pub(crate) struct WavesCheckpoint {
+    remaining: usize,
+    statistics: Vec<WaveStatistics>,
+    started_turn: u64,
+    started_event: usize,
+    finished: bool,
+}
+
+impl<M: Member> Waves<M> {
+    /// Check whether the current wave has been cleared, spawning the next one if any.
+    ///
+    /// Returns `true` if the current wave was cleared during this call.
+    pub(crate) fn advance(&mut self, data: &mut BattleState<M>, turn_number: u64) -> bool {
+        let cleared = data
+            .teams()
+            .get(self.team_id)
+            .is_some_and(|t| t.member_list().iter().all(|m| m.health() == 0));
+
+        if self.finished || !cleared {
+            return false;
+        }
+
+        let wave = self.statistics.len();
+        let mut statistics = WaveStatistics {
+            wave,
+            turns: turn_number - self.started_turn,
+            ..Default::default()
+        };
+
+        for event in data.events().since(self.started_event) {
+            if let Event::Damaged { member, amount } = event {
+                if member.team_id == self.team_id {
+                    statistics.damage_dealt = statistics.damage_dealt.saturating_add(*amount);
+                } else {
+                    statistics.damage_taken = statistics.damage_taken.saturating_add(*amount);
+                }
+            }
+        }
+
+        narrate!("Wave {} was cleared after {} turns", wave, statistics.turns);
+
+        data.apply(Event::WaveCleared { wave });
+
+        for hook in &self.hooks {
+            hook(data, &statistics);
+        }
+
+        self.statistics.push(statistics);
+
+        let Some(template) = self.pending.pop_front() else {
+            narrate!("The last wave was cleared");
+            self.finished = true;
+
+            return true;
+        };
+
+        let members = template(&mut data.rng);
+        self.spawned.push(template);
+
+        narrate!(
+            "Wave {} joins the battle with {} member(s)",
+            wave + 1,
+            members.len()
+        );
+
+        for member in members {
+            if data.add_member(self.team_id, member).is_none() {
+                log::warn!("Wave team with id {} was not found", self.team_id);
+            }
+        }
+
+        self.started_turn = turn_number;
+        self.started_event = data.events().len();
+
+        true
+    }
+}
@@ -0,0 +1,222 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, Context, Target};
+use fierceful_atto::aura::TeamAura;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::catalogue::ai::{
+    Aggressive, AiController, Defensive, HealerSupport, PersonalityController, Random,
+};
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter, Props};
+
+const HEALER: MemberIdentifier = MemberIdentifier::new(0, 0);
+const KNIGHT: MemberIdentifier = MemberIdentifier::new(0, 1);
+
+/// Heals every target by 20.
+struct Mend;
+
+impl Action<Fighter> for Mend {
+    fn act(&mut self, mut context: Context<'_, Fighter>) -> ActionState {
+        for id in context.target_ids() {
+            context.heal(id, 20);
+        }
+
+        ActionState::Finished
+    }
+}
+
+/// Heroes facing a strong slime, a weak slime and a defeated one.
+fn teams() -> Vec<Team<Fighter>> {
+    let mut teams = vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![
+                Fighter::new("Picco", 100, 5),
+                Fighter::new("Nocco", 100, 10),
+            ],
+        ),
+        Team::new(
+            String::from("Slimes"),
+            vec![
+                Fighter::new("Bacco", 100, 8),
+                Fighter::new("Lillo", 100, 2),
+                Fighter::new("Tacco", 100, 20),
+            ],
+        ),
+    ];
+
+    teams[1]
+        .member_mut(1)
+        .unwrap()
+        .member_properties_mut()
+        .health = 30;
+    teams[1]
+        .member_mut(2)
+        .unwrap()
+        .member_properties_mut()
+        .health = 0;
+
+    teams
+}
+
+fn wound(teams: &mut [Team<Fighter>], member: MemberIdentifier, health: u64) {
+    teams[member.team_id]
+        .member_mut(member.member_id)
+        .unwrap()
+        .member_properties_mut()
+        .health = health;
+}
+
+/// Returns whether the chosen action is a `Mend`, together with its targets.
+fn choice(
+    controller: &dyn AiController<Fighter>,
+    teams: &[Team<Fighter>],
+    performer: MemberIdentifier,
+) -> (bool, Target) {
+    let (action, performers, targets) =
+        controller.choose(teams, Some(performer), &mut BattleRng::from_seed(0));
+
+    assert_eq!(performers, Target::Single(performer));
+
+    (action.is::<Mend>(), targets)
+}
+
+#[test]
+fn aggressive_strikes_the_weakest_enemy() {
+    let controller = Aggressive {
+        attack: Box::new(|| Box::new(DirectAttack)),
+    };
+
+    assert_eq!(
+        choice(&controller, &teams(), KNIGHT),
+        (false, Target::Single(MemberIdentifier::new(1, 1)))
+    );
+}
+
+#[test]
+fn defensive_guards_itself_when_in_danger() {
+    let controller = Defensive {
+        attack: Box::new(|| Box::new(DirectAttack)),
+        defend: Box::new(|| Box::new(Mend)),
+        threshold: 50,
+    };
+    let mut teams = teams();
+
+    // The strongest alive enemy is targeted.
+    assert_eq!(
+        choice(&controller, &teams, KNIGHT),
+        (false, Target::Single(MemberIdentifier::new(1, 0)))
+    );
+
+    wound(&mut teams, KNIGHT, 40);
+    assert_eq!(
+        choice(&controller, &teams, KNIGHT),
+        (true, Target::Single(KNIGHT))
+    );
+}
+
+#[test]
+fn defensive_counts_the_auras_of_enemies() {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 5)]),
+        Team::new(String::from("Slimes"), vec![Fighter::new("Bacco", 100, 8)]),
+        Team::new(String::from("Bats"), vec![Fighter::new("Lillo", 100, 2)]),
+    ];
+    let rage = TeamAura::new(
+        "Rage",
+        Props {
+            health: 0,
+            attack: 10,
+            control_resistance: 0,
+            speed: 0,
+            shield: 0,
+        },
+    );
+    let controller = Defensive {
+        attack: Box::new(|| Box::new(DirectAttack)),
+        defend: Box::new(|| Box::new(Mend)),
+        threshold: 50,
+    };
+
+    let mut battle = last_team_standing(teams, controller.into_controller())
+        .add_aura(2, rage)
+        .build()
+        .unwrap();
+    battle.play_turn().unwrap();
+
+    // The aura makes Lillo hit harder than Bacco, so it's the one being attacked.
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+    assert_eq!(health(battle.teams(), 2, 0), 95);
+}
+
+#[test]
+fn healers_tend_to_the_most_wounded_ally() {
+    let controller = HealerSupport {
+        attack: Box::new(|| Box::new(DirectAttack)),
+        heal: Box::new(|| Box::new(Mend)),
+        threshold: 50,
+    };
+    let mut teams = teams();
+
+    assert_eq!(
+        choice(&controller, &teams, HEALER),
+        (false, Target::Single(MemberIdentifier::new(1, 1)))
+    );
+
+    wound(&mut teams, HEALER, 45);
+    wound(&mut teams, KNIGHT, 20);
+    assert_eq!(
+        choice(&controller, &teams, HEALER),
+        (true, Target::Single(KNIGHT))
+    );
+}
+
+#[test]
+fn random_only_picks_alive_enemies() {
+    let controller = Random {
+        actions: vec![
+            Box::new(|| Box::new(DirectAttack)),
+            Box::new(|| Box::new(Mend)),
+        ],
+    };
+    let teams = teams();
+    let mut rng = BattleRng::from_seed(2535);
+
+    for _ in 0..32 {
+        let (_, _, target) = controller.choose(&teams, Some(KNIGHT), &mut rng);
+
+        assert!(matches!(target, Target::Single(id) if id.team_id == 1 && id.member_id < 2));
+    }
+}
+
+#[test]
+fn random_without_actions_stays_idle() {
+    let controller = Random::<Fighter> {
+        actions: Vec::new(),
+    };
+    let (_, performers, targets) =
+        controller.choose(&teams(), Some(KNIGHT), &mut BattleRng::from_seed(2535));
+
+    assert_eq!(performers, Target::None);
+    assert_eq!(targets, Target::None);
+}
+
+#[test]
+fn controllers_become_callbacks() {
+    let callbacks = [
+        Aggressive {
+            attack: Box::new(|| Box::new(DirectAttack)),
+        }
+        .into_callback(),
+        PersonalityController::new(Box::new(|| Box::new(DirectAttack))).into_callback(),
+    ];
+
+    for callback in callbacks {
+        let (_, performers, _) = callback(&teams(), Some(KNIGHT), &mut BattleRng::from_seed(0));
+
+        assert_eq!(performers, Target::Single(KNIGHT));
+    }
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, Context, Target};
+use fierceful_atto::battle::Builder;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::member::{Member, MemberIdentifier, Properties};
+use fierceful_atto::team::Team;
+
+use common::{last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn teams() -> Vec<Team<Fighter>> {
+    vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 60)]),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 50, 0), Fighter::new("Tacco", 50, 0)],
+        ),
+    ]
+}
+
+/// Picco always attacks Bacco, whoever is suggested to perform.
+fn builder() -> Builder<Fighter> {
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        )
+    });
+
+    last_team_standing(teams(), controller).set_seed(0)
+}
+
+/// Sets the health of every target to zero, without applying any event.
+struct Erase;
+
+impl Action<Fighter> for Erase {
+    fn act(&mut self, mut context: Context<'_, Fighter>) -> ActionState {
+        for target in context.targets() {
+            *target.member_properties_mut().health_mut() = 0;
+        }
+
+        ActionState::Finished
+    }
+}
+
+#[test]
+fn alive_counts_follow_defeats_and_new_members() {
+    let mut battle = builder().build().unwrap();
+
+    assert_eq!(battle.alive_summary().teams(), &[1, 2]);
+
+    battle.play_turn().unwrap();
+
+    let alive = battle.alive_summary();
+    assert_eq!(alive.members(1), 1);
+    assert_eq!(alive.total(), 2);
+    assert_eq!(alive.teams_alive(), 2);
+    assert!(!battle.is_finished());
+
+    battle.add_member(1, Fighter::new("Zacco", 50, 0)).unwrap();
+    assert_eq!(battle.alive_summary().members(1), 2);
+
+    battle.remove_member(MemberIdentifier::new(1, 1)).unwrap();
+    battle.remove_member(MemberIdentifier::new(1, 1)).unwrap();
+
+    assert_eq!(battle.alive_summary().members(1), 0);
+    assert_eq!(battle.alive_summary().teams_alive(), 1);
+    assert!(battle.is_finished());
+}
+
+#[test]
+fn members_changed_by_actions_are_counted_again() {
+    let mut battle = builder()
+        .queue_action((
+            Box::new(Erase),
+            Target::Single(PICCO),
+            Target::FullTeam { team_id: 1 },
+        ))
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(battle.alive_summary().members(1), 0);
+    assert!(battle.is_finished());
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use fierceful_atto::action::{ChoiceCallback, ChoiceFuture, ChoiceReturn, Target};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::team::Team;
+
+use common::{duel, health, last_team_standing, Fighter};
+
+/// Poll a future to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn attack_other_team(performer: Option<MemberIdentifier>) -> ChoiceReturn<Fighter> {
+    let performer = performer.unwrap_or_default();
+
+    (
+        Box::new(DirectAttack),
+        Target::Single(performer),
+        Target::Single(MemberIdentifier::new(1 - performer.team_id, 0)),
+    )
+}
+
+fn attack_sync() -> ChoiceCallback<Fighter> {
+    Box::new(|_, performer, _| attack_other_team(performer))
+}
+
+/// Synchronous callback for battles that should only use the asynchronous one.
+fn unused_sync() -> ChoiceCallback<Fighter> {
+    Box::new(|_, _, _| unreachable!("the asynchronous callback should be used"))
+}
+
+fn attack_async<'a>(
+    _: &'a [Team<Fighter>],
+    performer: Option<MemberIdentifier>,
+    _: &'a mut BattleRng,
+) -> ChoiceFuture<'a, Fighter> {
+    Box::pin(async move { attack_other_team(performer) })
+}
+
+#[test]
+fn async_callback_chooses_the_action() {
+    let mut battle = last_team_standing(duel(), unused_sync())
+        .set_async_action_choice_callback(Box::new(attack_async))
+        .build()
+        .unwrap();
+
+    block_on(battle.play_turn_async()).unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+/// Asynchronous callback only expecting to choose for the second team.
+fn attack_async_for_team_one<'a>(
+    _: &'a [Team<Fighter>],
+    performer: Option<MemberIdentifier>,
+    _: &'a mut BattleRng,
+) -> ChoiceFuture<'a, Fighter> {
+    assert_eq!(performer.map(|p| p.team_id), Some(1));
+
+    Box::pin(async move { attack_other_team(performer) })
+}
+
+#[test]
+fn team_controllers_win_over_the_async_callback() {
+    let mut battle = last_team_standing(duel(), unused_sync())
+        .set_async_action_choice_callback(Box::new(attack_async_for_team_one))
+        .set_team_controller(0, attack_sync())
+        .build()
+        .unwrap();
+
+    // Picco is prompted through its team's controller, while Bacco's harmless attacks are chosen asynchronously.
+    for _ in 0..3 {
+        block_on(battle.play_turn_async()).unwrap();
+    }
+
+    assert_eq!(health(battle.teams(), 1, 0), 80);
+}
+
+#[test]
+fn falls_back_to_the_sync_callback() {
+    let mut battle = last_team_standing(duel(), attack_sync()).build().unwrap();
+
+    block_on(battle.play_turn_async()).unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn run_async_plays_until_a_team_is_defeated() {
+    let teams = block_on(
+        last_team_standing(duel(), unused_sync())
+            .set_async_action_choice_callback(Box::new(attack_async))
+            .build()
+            .unwrap()
+            .run_async(),
+    )
+    .unwrap();
+
+    assert_eq!(health(&teams, 0, 0), 100);
+    assert_eq!(health(&teams, 1, 0), 0);
+}
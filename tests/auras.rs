@@ -0,0 +1,67 @@
+mod common;
+
+use fierceful_atto::aura::TeamAura;
+use fierceful_atto::battle::Battle;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier, Properties};
+
+use common::{attack_first, duel, health, last_team_standing, Fighter, Props};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+
+fn rally() -> TeamAura<Fighter> {
+    TeamAura::new(
+        "Rally",
+        Props {
+            health: 0,
+            attack: 15,
+            control_resistance: 0,
+            speed: 0,
+            shield: 0,
+        },
+    )
+}
+
+fn battle(aura: TeamAura<Fighter>) -> Battle<Fighter> {
+    last_team_standing(duel(), attack_first)
+        .add_aura(0, aura)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn auras_are_summed_into_the_final_properties() {
+    let mut battle = battle(rally());
+
+    let state = battle.battle_state();
+    assert_eq!(state.final_properties(PICCO).unwrap().attack(), 25);
+    assert_eq!(
+        state.teams()[0]
+            .member(0)
+            .unwrap()
+            .final_properties()
+            .attack(),
+        10
+    );
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 75);
+}
+
+#[test]
+fn auras_wear_off_after_their_duration() {
+    let mut battle = battle(rally().with_duration(2));
+
+    // Picco attacks with the aura, then Bacco's turn makes it wear off.
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 65);
+    assert_eq!(battle.battle_state().auras(0).count(), 0);
+    assert!(battle.events().events().contains(&Event::AuraExpired {
+        team_id: 0,
+        aura: String::from("Rally"),
+    }));
+}
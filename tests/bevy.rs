@@ -0,0 +1,98 @@
+#![cfg(feature = "bevy")]
+
+mod common;
+
+use bevy_app::App;
+use bevy_ecs::event::Events;
+
+use fierceful_atto::bevy::{ActiveBattle, BattleEvent, BattlePlugin, MemberState};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::MemberIdentifier;
+
+use common::{attack_first, duel, last_team_standing, Fighter, Props};
+
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn app() -> App {
+    let battle = last_team_standing(duel(), attack_first)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    let mut app = App::new();
+    app.add_plugins(BattlePlugin::<Fighter>::default())
+        .insert_non_send_resource(ActiveBattle::new(battle));
+
+    app
+}
+
+/// Returns the mirrored states, sorted by member.
+fn states(app: &mut App) -> Vec<MemberState<Props>> {
+    let world = app.world_mut();
+    let mut states: Vec<_> = world
+        .query::<&MemberState<Props>>()
+        .iter(world)
+        .cloned()
+        .collect();
+
+    states.sort_by_key(|s| s.id);
+
+    states
+}
+
+fn events(app: &App) -> Vec<Event> {
+    app.world()
+        .resource::<Events<BattleEvent>>()
+        .iter_current_update_events()
+        .map(|e| e.0.clone())
+        .collect()
+}
+
+#[test]
+fn turns_are_played_by_the_schedule() {
+    let mut app = app();
+
+    app.update();
+
+    let events = events(&app);
+    assert!(events.contains(&Event::BattleStarted { seed: 0 }));
+    assert!(events.contains(&Event::Damaged {
+        member: BACCO,
+        amount: 10
+    }));
+
+    let states = states(&mut app);
+    assert_eq!(states.len(), 2);
+    assert_eq!(states[0].health, 100);
+    assert_eq!(states[1].health, 90);
+    assert_eq!(states[1].properties.health, 90);
+}
+
+#[test]
+fn paused_battles_are_not_played() {
+    let mut app = app();
+
+    app.world_mut()
+        .non_send_resource_mut::<ActiveBattle<Fighter>>()
+        .set_paused(true);
+    app.update();
+
+    assert!(events(&app).is_empty());
+    assert_eq!(states(&mut app)[1].health, 100);
+}
+
+#[test]
+fn members_leaving_are_despawned() {
+    let mut app = app();
+
+    app.update();
+    app.world_mut()
+        .non_send_resource_mut::<ActiveBattle<Fighter>>()
+        .battle_mut()
+        .remove_member(BACCO);
+    app.update();
+
+    let states = states(&mut app);
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0].id, MemberIdentifier::new(0, 0));
+}
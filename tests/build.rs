@@ -0,0 +1,92 @@
+mod common;
+
+use fierceful_atto::aura::TeamAura;
+use fierceful_atto::battle::{Builder, EndCondition, StartupInfo};
+use fierceful_atto::error::BuildError;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::search::SuggestedPerformerCriteria;
+use fierceful_atto::team::Team;
+
+use common::{attack_first, duel, last_team_standing, Fighter, Props};
+
+fn build(teams: Vec<Team<Fighter>>, startup: Option<StartupInfo>) -> Result<(), BuildError> {
+    Builder::new(teams, startup, attack_first, EndCondition::LastTeamStanding)
+        .build()
+        .map(|_| ())
+}
+
+#[test]
+fn battles_need_members() {
+    assert_eq!(build(Vec::new(), None), Err(BuildError::NoTeams));
+
+    let mut teams = duel();
+    teams.push(Team::new(String::from("Ghosts"), Vec::new()));
+
+    assert_eq!(
+        build(teams, None),
+        Err(BuildError::EmptyTeam { team_id: 2 })
+    );
+    assert_eq!(build(duel(), None), Ok(()));
+}
+
+#[test]
+fn startup_info_must_reference_existing_members() {
+    let missing = MemberIdentifier::new(1, 3);
+
+    assert_eq!(
+        build(
+            duel(),
+            Some(StartupInfo::new().with_first_performer(missing))
+        ),
+        Err(BuildError::UnknownMember(missing))
+    );
+    assert_eq!(
+        build(
+            duel(),
+            Some(StartupInfo::new().with_initiative(vec![missing]))
+        ),
+        Err(BuildError::UnknownMember(missing))
+    );
+    assert_eq!(
+        build(duel(), Some(StartupInfo::new().with_ambushing_team(2))),
+        Err(BuildError::UnknownTeam { team_id: 2 })
+    );
+}
+
+#[test]
+fn team_controllers_must_reference_existing_teams() {
+    let result = last_team_standing(duel(), attack_first)
+        .set_team_controller(5, attack_first)
+        .build();
+
+    assert_eq!(result.err(), Some(BuildError::UnknownTeam { team_id: 5 }));
+}
+
+#[test]
+fn team_criteria_must_reference_existing_teams() {
+    let result = last_team_standing(duel(), attack_first)
+        .set_team_performer_criteria(3, SuggestedPerformerCriteria::CycleAlive)
+        .build();
+
+    assert_eq!(result.err(), Some(BuildError::UnknownTeam { team_id: 3 }));
+}
+
+#[test]
+fn auras_must_reference_existing_teams() {
+    let aura = TeamAura::new(
+        "Rally",
+        Props {
+            health: 0,
+            attack: 5,
+            control_resistance: 0,
+            speed: 0,
+            shield: 0,
+        },
+    );
+
+    let result = last_team_standing(duel(), attack_first)
+        .add_aura(2, aura)
+        .build();
+
+    assert_eq!(result.err(), Some(BuildError::UnknownTeam { team_id: 2 }));
+}
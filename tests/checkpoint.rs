@@ -0,0 +1,132 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, Context, Target};
+use fierceful_atto::battle::{Battle, Builder};
+use fierceful_atto::catalogue::actions::{Charged, DirectAttack};
+use fierceful_atto::error::BattleError;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::reinforcement::{Reinforcement, Trigger};
+use fierceful_atto::schedule::CancelRule;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Action doing nothing, which can't be copied.
+struct Wait;
+
+impl<M: Member> Action<M> for Wait {
+    fn act(&mut self, _: Context<M>) -> ActionState {
+        ActionState::Finished
+    }
+}
+
+/// Every performer randomly attacks the first member of the other team, or waits.
+fn builder() -> Builder<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(String::from("Slimes"), vec![Fighter::new("Bacco", 100, 7)]),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, performer, rng| {
+        let performer = performer.unwrap_or_default();
+        let target = Target::Single(MemberIdentifier::new(1 - performer.team_id, 0));
+
+        let action: Box<dyn Action<Fighter>> = match rng.gen_range(0..2) {
+            0 => Box::new(DirectAttack),
+            _ => Box::new(Wait),
+        };
+
+        (action, Target::Single(performer), target)
+    });
+
+    last_team_standing(teams, controller).set_seed(7)
+}
+
+fn play(battle: &mut Battle<Fighter>, turns: usize) {
+    for _ in 0..turns {
+        battle.play_turn().unwrap();
+    }
+}
+
+#[test]
+fn rolled_back_battles_replay_the_same_turns() {
+    let mut battle = builder().build().unwrap();
+    play(&mut battle, 2);
+
+    let checkpoint = battle.checkpoint().unwrap();
+    let events = battle.events().len();
+    let teams = battle.teams().to_vec();
+
+    assert_eq!(checkpoint.turn_number(), 2);
+    assert_eq!(checkpoint.events(), events);
+
+    play(&mut battle, 6);
+    let first_run = battle.events().events()[events..].to_vec();
+
+    battle.rollback(checkpoint);
+
+    assert_eq!(battle.events().len(), events);
+    assert_eq!(battle.teams(), teams.as_slice());
+
+    play(&mut battle, 6);
+
+    assert_eq!(battle.events().events()[events..], first_run[..]);
+}
+
+#[test]
+fn pending_actions_are_rolled_back() {
+    let mut battle = builder()
+        .schedule_action(
+            (
+                Box::new(DirectAttack),
+                Target::Single(PICCO),
+                Target::Single(BACCO),
+            ),
+            3,
+            CancelRule::Manual,
+        )
+        .queue_action((
+            Box::new(Charged::new(DirectAttack, 1)),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        ))
+        .add_reinforcement(Reinforcement::new(
+            1,
+            Trigger::Turn(2),
+            Box::new(|_| vec![Fighter::new("Lillo", 100, 1)]),
+        ))
+        .build()
+        .unwrap();
+
+    let checkpoint = battle.checkpoint().unwrap();
+    play(&mut battle, 3);
+
+    assert!(battle.scheduled_actions().is_empty());
+    assert_eq!(battle.teams()[1].member_list().len(), 2);
+
+    battle.rollback(checkpoint);
+
+    assert_eq!(battle.scheduled_actions().len(), 1);
+    assert_eq!(battle.teams()[1].member_list().len(), 1);
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+
+    play(&mut battle, 3);
+
+    assert!(battle.scheduled_actions().is_empty());
+    assert_eq!(battle.teams()[1].member_list().len(), 2);
+}
+
+#[test]
+fn uncopyable_actions_prevent_checkpoints() {
+    let mut battle = builder().build().unwrap();
+
+    battle.queue_action((Box::new(Wait), Target::Single(PICCO), Target::None));
+
+    assert!(matches!(
+        battle.checkpoint(),
+        Err(BattleError::UncopyableAction(_))
+    ));
+}
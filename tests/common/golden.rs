@@ -0,0 +1,49 @@
+//! Golden files, recording the canonical result of a test to detect any later drift.
+//!
+//! Golden files are stored in `tests/golden`. A test compares its actual output with the recorded one and fails
+//! if they differ, so that balance changes (like a new damage pipeline stage) never go unnoticed.
+//! Run the tests with the `UPDATE_GOLDEN` environment variable set to accept the new results.
+
+use std::path::Path;
+
+/// Compare the output of a test with its golden file, recording it if missing or if updates are requested.
+///
+/// # Panics
+///
+/// Panics if the golden file exists and differs from `actual`.
+pub fn assert_golden(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name)
+        .with_extension("txt");
+
+    let expected = std::fs::read_to_string(&path).ok();
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() || expected.is_none() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual)
+            .unwrap_or_else(|e| panic!("could not record {}: {}", path.display(), e));
+
+        eprintln!("Recorded golden file {}", path.display());
+
+        return;
+    }
+
+    let expected = expected.unwrap();
+
+    if expected != actual {
+        let line = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(e, a)| e != a)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+
+        panic!(
+            "{} drifted from its golden file at line {}:\n  expected: {}\n  actual:   {}\nRun the tests with UPDATE_GOLDEN=1 to accept the new results.",
+            name,
+            line + 1,
+            expected.lines().nth(line).unwrap_or("<end of file>"),
+            actual.lines().nth(line).unwrap_or("<end of file>"),
+        );
+    }
+}
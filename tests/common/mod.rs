@@ -0,0 +1,267 @@
+//! Shared fixtures for the integration tests.
+
+#![allow(dead_code)]
+
+pub mod golden;
+pub mod scenario;
+
+use fierceful_atto::action::{ChoiceReturn, Target};
+use fierceful_atto::battle::{Builder, EndCondition};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::controller::Controller;
+use fierceful_atto::equipment::Equipment;
+use fierceful_atto::loot::DropTable;
+use fierceful_atto::member::{Levelable, Member, MemberIdentifier, Properties, Statistics};
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::stage::{Stat, StatStages};
+use fierceful_atto::team::Team;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fighter {
+    name: String,
+    statistics: Stats,
+    properties: Props,
+    level: u32,
+    experience: u64,
+    experience_yield: u64,
+    drop_table: Option<DropTable>,
+}
+
+impl Fighter {
+    pub fn new(name: &str, max_health: u64, attack: u64) -> Self {
+        let statistics = Stats {
+            max_health,
+            base_attack: attack,
+        };
+
+        Self {
+            name: String::from(name),
+            statistics,
+            properties: Props {
+                health: max_health,
+                attack,
+                control_resistance: 0,
+                speed: 0,
+                shield: 0,
+            },
+            level: 1,
+            experience: 0,
+            experience_yield: 0,
+            drop_table: None,
+        }
+    }
+
+    /// Set the chance of resisting control effects, as a percentage.
+    pub fn with_control_resistance(mut self, resistance: u64) -> Self {
+        self.properties.control_resistance = resistance;
+
+        self
+    }
+
+    /// Set the speed deciding the order of simultaneous actions.
+    pub fn with_speed(mut self, speed: u64) -> Self {
+        self.properties.speed = speed;
+
+        self
+    }
+
+    /// Set the experience granted to the other teams when defeated.
+    pub fn with_experience_yield(mut self, experience: u64) -> Self {
+        self.experience_yield = experience;
+
+        self
+    }
+
+    /// Set the table rolled when defeated.
+    pub fn with_drop_table(mut self, table: DropTable) -> Self {
+        self.drop_table = Some(table);
+
+        self
+    }
+
+    /// Returns the experience gained towards the next level.
+    pub fn experience(&self) -> u64 {
+        self.experience
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub max_health: u64,
+    pub base_attack: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Props {
+    pub health: u64,
+    pub attack: u64,
+    pub control_resistance: u64,
+    pub speed: u64,
+    pub shield: u64,
+}
+
+pub struct Gear;
+
+impl Member for Fighter {
+    type Statistics = Stats;
+    type Properties = Props;
+    type Equipment = Gear;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn statistics(&self) -> &Stats {
+        &self.statistics
+    }
+
+    fn member_properties(&self) -> &Props {
+        &self.properties
+    }
+
+    fn member_properties_mut(&mut self) -> &mut Props {
+        &mut self.properties
+    }
+
+    fn equipment(&self) -> &Gear {
+        &Gear
+    }
+
+    fn experience_yield(&self) -> u64 {
+        self.experience_yield
+    }
+
+    fn drop_table(&self) -> Option<&DropTable> {
+        self.drop_table.as_ref()
+    }
+}
+
+/// Fighters level up every 100 experience, gaining 10 max health and 2 attack.
+impl Levelable for Fighter {
+    fn level(&self) -> u32 {
+        self.level
+    }
+
+    fn gain_experience(&mut self, amount: u64) -> u32 {
+        self.experience += amount;
+
+        let levels = (self.experience / 100) as u32;
+        self.experience %= 100;
+        self.level += levels;
+
+        self.statistics.max_health += 10 * u64::from(levels);
+        self.statistics.base_attack += 2 * u64::from(levels);
+        self.properties.attack += 2 * u64::from(levels);
+
+        levels
+    }
+}
+
+impl Statistics for Stats {
+    fn reference_health(&self) -> u64 {
+        self.max_health
+    }
+
+    fn base_attack(&self) -> u64 {
+        self.base_attack
+    }
+}
+
+/// Summing properties adds up everything but the health and the shield.
+impl Properties for Props {
+    fn sum_properties(&self, rhs: &Self) -> Self {
+        Self {
+            health: self.health,
+            attack: self.attack + rhs.attack,
+            control_resistance: self.control_resistance + rhs.control_resistance,
+            speed: self.speed + rhs.speed,
+            shield: self.shield,
+        }
+    }
+
+    fn health(&self) -> u64 {
+        self.health
+    }
+
+    fn health_mut(&mut self) -> &mut u64 {
+        &mut self.health
+    }
+
+    fn attack(&self) -> u64 {
+        self.attack
+    }
+
+    fn control_resistance(&self) -> u64 {
+        self.control_resistance
+    }
+
+    fn speed(&self) -> u64 {
+        self.speed
+    }
+
+    fn shield(&self) -> u64 {
+        self.shield
+    }
+
+    fn shield_mut(&mut self) -> Option<&mut u64> {
+        Some(&mut self.shield)
+    }
+
+    fn apply_stages(&mut self, stages: &StatStages) {
+        self.attack = stages.scale(Stat::Attack, self.attack);
+        self.speed = stages.scale(Stat::Speed, self.speed);
+    }
+}
+
+impl Equipment for Gear {
+    type Properties = Props;
+
+    fn associated_properties(&self) -> Props {
+        Props {
+            health: 0,
+            attack: 0,
+            control_resistance: 0,
+            speed: 0,
+            shield: 0,
+        }
+    }
+}
+
+/// Two teams of one member each: an attacker with 10 attack and a dummy with 100 health.
+pub fn duel() -> Vec<Team<Fighter>> {
+    vec![
+        Team::new(
+            String::from("Attackers"),
+            vec![Fighter::new("Picco", 100, 10)],
+        ),
+        Team::new(String::from("Dummies"), vec![Fighter::new("Bacco", 100, 0)]),
+    ]
+}
+
+/// Battle between the given teams, lasting until only one of them is standing.
+pub fn last_team_standing<M: Member>(
+    teams: Vec<Team<M>>,
+    controller: impl Controller<M> + 'static,
+) -> Builder<M> {
+    Builder::new(teams, None, controller, EndCondition::LastTeamStanding)
+}
+
+/// The suggested performer attacks the first member of the other team.
+pub fn attack_first(
+    _: &[Team<Fighter>],
+    performer: Option<MemberIdentifier>,
+    _: &mut BattleRng,
+) -> ChoiceReturn<Fighter> {
+    let performer = performer.unwrap_or_default();
+
+    (
+        Box::new(DirectAttack),
+        Target::Single(performer),
+        Target::Single(MemberIdentifier::new(1 - performer.team_id, 0)),
+    )
+}
+
+/// Returns the current health of a member.
+pub fn health(teams: &[Team<Fighter>], team_id: usize, member_id: usize) -> u64 {
+    teams[team_id].member(member_id).unwrap().health()
+}
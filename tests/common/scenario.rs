@@ -0,0 +1,218 @@
+//! Deterministic battles described by small encounter fixtures.
+//!
+//! Fixtures are RON files stored in `tests/fixtures`. Each one lists the participating teams, how every member
+//! chooses its targets and what the battle is expected to look like once it ends. Running a scenario plays
+//! the battle with a fixed seed and checks every expectation, so fixtures double as documentation of the
+//! intended end-condition and targeting semantics.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::{Battle, Builder, EndCondition};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::team::Team;
+
+use super::Fighter;
+
+/// Mirror of [`EndCondition`] that can be read from fixtures.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum End {
+    LastMemberStanding,
+    LastTeamStanding,
+}
+
+impl From<End> for EndCondition {
+    fn from(end: End) -> Self {
+        match end {
+            End::LastMemberStanding => EndCondition::LastMemberStanding,
+            End::LastTeamStanding => EndCondition::LastTeamStanding,
+        }
+    }
+}
+
+/// Members targeted by every attack of a scenario.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum Targeting {
+    /// The first alive member of the first team (other than the performer's) with any alive member.
+    FirstAlive,
+    /// The whole team of the first alive enemy.
+    FullTeam,
+    /// Every member of the battle, allies and performer included.
+    All,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MemberFixture {
+    pub name: String,
+    pub health: u64,
+    pub attack: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeamFixture {
+    pub name: String,
+    pub members: Vec<MemberFixture>,
+}
+
+/// What the battle should look like once it ends.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Expectations {
+    /// Exact amount of turns played.
+    pub turns: Option<u64>,
+    /// Teams with at least one alive member.
+    pub surviving_teams: Option<Vec<usize>>,
+    /// Final health of specific members, as `((team_id, member_id), health)`.
+    pub health: Vec<((usize, usize), u64)>,
+    /// Events (in their `Debug` representation) that must appear in the event log, in this order.
+    pub trace: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub seed: u64,
+    pub end_condition: End,
+    pub targeting: Targeting,
+    /// Turns after which the battle is considered stuck.
+    pub max_turns: u64,
+    pub teams: Vec<TeamFixture>,
+    pub expect: Expectations,
+}
+
+impl Scenario {
+    /// Load a scenario from `tests/fixtures`.
+    pub fn load(name: &str) -> Self {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+            .with_extension("ron");
+
+        let text = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not read {}: {}", path.display(), e));
+
+        ron::from_str(&text).unwrap_or_else(|e| panic!("invalid fixture {}: {}", path.display(), e))
+    }
+
+    /// Build the battle described by the scenario.
+    pub fn battle(&self) -> Battle<Fighter> {
+        self.builder().build().unwrap()
+    }
+
+    /// Prepare the battle described by the scenario, allowing further customization.
+    pub fn builder(&self) -> Builder<Fighter> {
+        let teams = self
+            .teams
+            .iter()
+            .map(|t| {
+                Team::new(
+                    t.name.clone(),
+                    t.members
+                        .iter()
+                        .map(|m| Fighter::new(&m.name, m.health, m.attack))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        Builder::new(
+            teams,
+            None,
+            controller(self.targeting),
+            self.end_condition.into(),
+        )
+        .set_seed(self.seed)
+    }
+
+    /// Play the scenario until the battle ends and check every expectation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the battle doesn't end within `max_turns` or if any expectation isn't met.
+    pub fn run(&self) -> Battle<Fighter> {
+        let mut battle = self.battle();
+
+        while !battle.is_finished() {
+            let outcome = battle.outcome();
+            assert!(
+                outcome.turns < self.max_turns,
+                "the battle didn't end within {} turns",
+                self.max_turns
+            );
+
+            battle.play_turn().expect("the turn couldn't be played");
+        }
+
+        self.check(&battle);
+
+        battle
+    }
+
+    fn check(&self, battle: &Battle<Fighter>) {
+        let outcome = battle.outcome();
+        let expect = &self.expect;
+
+        if let Some(turns) = expect.turns {
+            assert_eq!(outcome.turns, turns, "unexpected amount of turns");
+        }
+
+        if let Some(surviving_teams) = &expect.surviving_teams {
+            assert_eq!(
+                &outcome.surviving_teams, surviving_teams,
+                "unexpected surviving teams"
+            );
+        }
+
+        for ((team_id, member_id), health) in &expect.health {
+            let member = battle.teams()[*team_id].member(*member_id).unwrap();
+
+            assert_eq!(
+                member.health(),
+                *health,
+                "unexpected health of {}",
+                member.name()
+            );
+        }
+
+        let mut events = battle.events().events().iter().map(|e| format!("{:?}", e));
+
+        for expected in &expect.trace {
+            assert!(
+                events.any(|e| &e == expected),
+                "event `{}` is missing from the trace (or out of order)",
+                expected
+            );
+        }
+    }
+}
+
+/// Controller making every performer attack according to the scenario's targeting.
+fn controller(targeting: Targeting) -> ChoiceCallback<Fighter> {
+    Box::new(move |team_list, performer, _rng| {
+        let performer = performer.unwrap_or_default();
+
+        let enemy = team_list
+            .iter()
+            .enumerate()
+            .filter(|(team_id, _)| *team_id != performer.team_id)
+            .find_map(|(team_id, t)| {
+                t.member_list()
+                    .iter()
+                    .position(|m| m.health() > 0)
+                    .map(|member_id| MemberIdentifier::new(team_id, member_id))
+            });
+
+        let target = match (targeting, enemy) {
+            (_, None) => Target::None,
+            (Targeting::FirstAlive, Some(enemy)) => Target::Single(enemy),
+            (Targeting::FullTeam, Some(enemy)) => Target::FullTeam {
+                team_id: enemy.team_id,
+            },
+            (Targeting::All, Some(_)) => Target::All,
+        };
+
+        (Box::new(DirectAttack), Target::Single(performer), target)
+    })
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use fierceful_atto::action::{Action, Context, Target};
+use fierceful_atto::catalogue::actions::{Push, Stun, Taunt};
+use fierceful_atto::control::{self, Control};
+use fierceful_atto::event::Event;
+use fierceful_atto::formation::{Formation, Position};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::state::BattleState;
+use fierceful_atto::team::Team;
+
+use common::{attack_first, duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn perform(state: &mut BattleState<Fighter>, mut action: impl Action<Fighter>) {
+    action.act(Context::new(
+        state,
+        Target::Single(PICCO),
+        Target::Single(BACCO),
+    ));
+}
+
+/// Returns the magnitudes of all control effects applied so far.
+fn magnitudes(state: &BattleState<Fighter>) -> Vec<u64> {
+    state
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::ControlApplied { magnitude, .. } => Some(*magnitude),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn repeated_control_gets_shorter() {
+    assert_eq!(control::diminish(4, 0), 4);
+    assert_eq!(control::diminish(4, 1), 2);
+    assert_eq!(control::diminish(3, 1), 2);
+    assert_eq!(control::diminish(4, 3), 1);
+    assert_eq!(control::diminish(4, 100), 1);
+    assert_eq!(control::diminish(0, 0), 0);
+
+    let mut state = BattleState::new(duel(), BattleRng::from_seed(0));
+
+    for _ in 0..3 {
+        perform(&mut state, Stun { turns: 4 });
+    }
+    perform(&mut state, Taunt { threat: 8 });
+
+    // Every kind of control has its own diminishing returns.
+    assert_eq!(magnitudes(&state), [4, 2, 1, 8]);
+    assert_eq!(state.controls().applications(BACCO, Control::Stun), 3);
+    assert_eq!(state.controls().applications(BACCO, Control::Taunt), 1);
+}
+
+#[test]
+fn stuns_last_the_given_turns() {
+    let mut state = BattleState::new(duel(), BattleRng::from_seed(0));
+    perform(&mut state, Stun { turns: 2 });
+
+    assert!(state.is_interrupted(BACCO));
+
+    for turn_number in 1..=2 {
+        state.apply(Event::TurnStarted { turn_number });
+
+        assert!(state.is_interrupted(BACCO));
+        assert_eq!(
+            state.controls().remaining(BACCO, Control::Stun),
+            2 - turn_number
+        );
+    }
+
+    state.apply(Event::TurnStarted { turn_number: 3 });
+
+    assert!(!state.is_interrupted(BACCO));
+    assert!(!state.controls().is_controlled(BACCO, Control::Stun));
+}
+
+#[test]
+fn resistant_members_shrug_off_control() {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(
+            String::from("Golems"),
+            vec![Fighter::new("Bacco", 100, 5).with_control_resistance(100)],
+        )
+        .with_formation(Formation::new(vec![Position::front(0)])),
+    ];
+
+    let mut state = BattleState::new(teams, BattleRng::from_seed(0));
+    perform(&mut state, Stun { turns: 3 });
+    perform(&mut state, Push);
+    perform(&mut state, Taunt { threat: 50 });
+
+    assert!(!state.is_interrupted(BACCO));
+    assert_eq!(state.threat().threat(PICCO, 1), 0);
+    assert_eq!(
+        state.teams()[1].formation().unwrap().position(0),
+        Some(Position::front(0))
+    );
+    assert!(magnitudes(&state).is_empty());
+    assert_eq!(
+        state
+            .events()
+            .events()
+            .iter()
+            .filter(|e| matches!(e, Event::ControlResisted { member: BACCO, .. }))
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn stunned_members_lose_their_turns() {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(String::from("Slimes"), vec![Fighter::new("Bacco", 100, 5)]),
+    ];
+
+    let mut battle = last_team_standing(teams, attack_first)
+        .set_seed(0)
+        .queue_action((
+            Box::new(Stun { turns: 1 }),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        ))
+        .build()
+        .unwrap();
+
+    for _ in 0..4 {
+        battle.play_turn().unwrap();
+    }
+
+    // Bacco's first turn comes while the stun lasts, so only its second one goes through.
+    let interruptions = battle
+        .events()
+        .events()
+        .iter()
+        .filter(|e| matches!(e, Event::ActionInterrupted { .. }))
+        .count();
+
+    assert_eq!(interruptions, 1);
+    assert_eq!(health(battle.teams(), 0, 0), 95);
+}
@@ -0,0 +1,239 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+
+use fierceful_atto::action::{Action, ActionId, ChoiceReturn, Target};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::catalogue::ai::{Aggressive, AiController};
+use fierceful_atto::controller::{
+    ChannelChoice, ChannelController, ChoicePrompt, ChoiceRequest, Controller, HotSeatController,
+    ScriptedController,
+};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::registry::ActionRegistry;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::team::Team;
+
+use common::{attack_first, duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Counts the choices it made, without targeting anybody.
+struct Counter {
+    choices: Rc<Cell<u32>>,
+}
+
+impl Controller<Fighter> for Counter {
+    fn choose(&mut self, prompt: ChoicePrompt<Fighter>) -> ChoiceReturn<Fighter> {
+        self.choices.set(self.choices.get() + 1);
+
+        (
+            Box::new(DirectAttack),
+            Target::Single(prompt.performer().unwrap_or_default()),
+            Target::None,
+        )
+    }
+}
+
+#[test]
+fn functions_are_controllers() {
+    let mut battle = last_team_standing(duel(), attack_first)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn scripted_choices_come_first() {
+    let controller = ScriptedController::new(attack_first)
+        .with_choice((
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            Target::Single(PICCO),
+        ))
+        .with_choice((Box::new(DirectAttack), Target::Single(BACCO), Target::None));
+    assert_eq!(controller.remaining(), 2);
+
+    let mut battle = last_team_standing(duel(), controller)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    // Picco hits itself, then Bacco does nothing.
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 0, 0), 90);
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+
+    // Once the script is over, the fallback takes control.
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn channels_receive_choices_from_other_threads() {
+    let (request_sender, requests) = mpsc::channel::<ChoiceRequest>();
+    let (choices, choice_receiver) = mpsc::channel::<ChannelChoice>();
+
+    let registry =
+        ActionRegistry::new().with(ActionId::new("Attack"), Box::new(|| Box::new(DirectAttack)));
+    let controller =
+        ChannelController::new(registry, request_sender, choice_receiver, attack_first);
+
+    let remote = thread::spawn(move || {
+        let mut performers = Vec::new();
+
+        for request in requests.iter().take(2) {
+            let performer = request.performer.unwrap();
+            performers.push(performer);
+
+            let action = match performer.team_id {
+                0 => "Attack",
+                _ => "Unknown",
+            };
+
+            choices
+                .send(ChannelChoice {
+                    action: ActionId::new(action),
+                    performers: Target::Single(performer),
+                    targets: Target::Single(performer),
+                })
+                .unwrap();
+        }
+
+        performers
+    });
+
+    let mut battle = last_team_standing(duel(), controller)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    // Picco hits itself as requested.
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 0, 0), 90);
+
+    // The unknown action is replaced by the fallback's attack, which has no effect coming from Bacco.
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+
+    assert_eq!(remote.join().unwrap(), vec![PICCO, BACCO]);
+
+    // With the other end gone, the fallback makes every choice.
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn ai_controllers_are_adapted() {
+    let ai = Aggressive::<Fighter> {
+        attack: Box::new(|| Box::new(DirectAttack)),
+    };
+
+    let mut battle = last_team_standing(duel(), ai.into_controller())
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn controllers_keep_their_state() {
+    let choices = Rc::new(Cell::new(0));
+    let controller = Counter {
+        choices: Rc::clone(&choices),
+    };
+
+    let mut battle = last_team_standing(duel(), controller)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(choices.get(), 3);
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
+
+/// Records the team of every prompt it receives, without targeting anybody.
+fn seat(log: &Rc<RefCell<Vec<usize>>>) -> impl Controller<Fighter> {
+    let log = Rc::clone(log);
+
+    move |_: &[Team<Fighter>], performer: Option<MemberIdentifier>, _: &mut BattleRng| {
+        let performer = performer.unwrap_or_default();
+        log.borrow_mut().push(performer.team_id);
+
+        (
+            Box::new(DirectAttack) as Box<dyn Action<Fighter>>,
+            Target::Single(performer),
+            Target::None,
+        )
+    }
+}
+
+#[test]
+fn hot_seats_gate_every_hand_over() {
+    let teams = vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![
+                Fighter::new("Picco", 100, 10),
+                Fighter::new("Nocco", 100, 10),
+            ],
+        ),
+        Team::new(String::from("Rivals"), vec![Fighter::new("Lillo", 100, 10)]),
+        Team::new(String::from("Slimes"), vec![Fighter::new("Bacco", 100, 10)]),
+    ];
+
+    let prompts = Rc::new(RefCell::new(Vec::new()));
+    let hand_overs = Rc::new(RefCell::new(Vec::new()));
+    let gate_log = Rc::clone(&hand_overs);
+
+    let controller = HotSeatController::new(seat(&Rc::new(RefCell::new(Vec::new()))))
+        .with_seat(0, seat(&prompts))
+        .with_seat(1, seat(&prompts))
+        .with_gate(Box::new(move |team_id, teams| {
+            gate_log
+                .borrow_mut()
+                .push((team_id, teams[team_id].name().to_string()));
+        }));
+    assert_eq!(controller.current_team(), None);
+
+    let mut battle = last_team_standing(teams, controller)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    // Picco, Nocco, Lillo, Bacco and Picco again.
+    for _ in 0..5 {
+        battle.play_turn().unwrap();
+    }
+
+    // The slimes have no seat, so they never reach the seated controllers.
+    assert_eq!(*prompts.borrow(), vec![0, 0, 1, 0]);
+
+    // Nocco keeps the device from Picco, and the slimes don't take it from the rivals.
+    assert_eq!(
+        *hand_overs.borrow(),
+        vec![
+            (0, String::from("Heroes")),
+            (1, String::from("Rivals")),
+            (0, String::from("Heroes")),
+        ]
+    );
+}
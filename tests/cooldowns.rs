@@ -0,0 +1,76 @@
+mod common;
+
+use fierceful_atto::action::{Action, ChoiceCallback, Target};
+use fierceful_atto::battle::{Battle, InvalidActionFallback};
+use fierceful_atto::catalogue::actions::{DirectAttack, WithCooldown};
+use fierceful_atto::error::{ActionError, BattleError};
+use fierceful_atto::member::MemberIdentifier;
+
+use common::{duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+
+/// Battle where every member attacks the other team with an action on a cooldown of 2 turns.
+fn battle(fallback: InvalidActionFallback) -> Battle<Fighter> {
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, performer, _| {
+        let performer = performer.unwrap_or_default();
+
+        (
+            Box::new(WithCooldown {
+                action: DirectAttack,
+                turns: 2,
+            }),
+            Target::Single(performer),
+            Target::Single(MemberIdentifier::new(1 - performer.team_id, 0)),
+        )
+    });
+
+    last_team_standing(duel(), controller)
+        .set_invalid_action_fallback(fallback)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn performing_starts_the_cooldown() {
+    let mut battle = battle(InvalidActionFallback::Fail);
+    let attack = Action::<Fighter>::action_id(&DirectAttack);
+
+    battle.play_turn().unwrap();
+    assert_eq!(battle.components().cooldown(PICCO, &attack), 2);
+    assert!(!battle.components().is_available(PICCO, &attack));
+
+    battle.play_turn().unwrap();
+    assert_eq!(battle.components().cooldown(PICCO, &attack), 1);
+}
+
+#[test]
+fn actions_on_cooldown_are_rejected() {
+    let mut battle = battle(InvalidActionFallback::Fail);
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    assert_eq!(
+        battle.play_turn(),
+        Err(BattleError::InvalidAction(ActionError::OnCooldown {
+            member: PICCO,
+            action: Action::<Fighter>::action_id(&DirectAttack),
+            remaining: 1,
+        }))
+    );
+}
+
+#[test]
+fn cooldown_expires_after_its_turns() {
+    let mut battle = battle(InvalidActionFallback::SkipTurn);
+    let mut seen = Vec::new();
+
+    for _ in 0..5 {
+        battle.play_turn().unwrap();
+        seen.push(health(battle.teams(), 1, 0));
+    }
+
+    // Picco acts every other turn, but has to skip the turn falling within the cooldown.
+    assert_eq!(seen, [90, 90, 90, 90, 80]);
+}
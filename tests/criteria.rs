@@ -0,0 +1,130 @@
+mod common;
+
+use fierceful_atto::battle::{Battle, StartupInfo};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::search::SuggestedPerformerCriteria;
+use fierceful_atto::team::Team;
+
+use common::{attack_first, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const POCCO: MemberIdentifier = MemberIdentifier::new(0, 1);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+const BECCO: MemberIdentifier = MemberIdentifier::new(1, 1);
+
+fn teams(becco_health: u64) -> Vec<Team<Fighter>> {
+    vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![
+                Fighter::new("Picco", 100, 0).with_speed(1),
+                Fighter::new("Pocco", 100, 0).with_speed(5),
+            ],
+        ),
+        Team::new(
+            String::from("Villains"),
+            vec![
+                Fighter::new("Bacco", 100, 0).with_speed(3),
+                Fighter::new("Becco", becco_health, 0).with_speed(3),
+            ],
+        ),
+    ]
+}
+
+fn battle(
+    becco_health: u64,
+    criteria: SuggestedPerformerCriteria<Fighter>,
+    seed: u64,
+) -> Battle<Fighter> {
+    last_team_standing(teams(becco_health), attack_first)
+        .set_suggested_performer_criteria(criteria)
+        .set_startup_info(StartupInfo::new().with_first_performer(POCCO))
+        .set_seed(seed)
+        .build()
+        .unwrap()
+}
+
+/// Play some turns, returning who performed them.
+fn performers(battle: &mut Battle<Fighter>, turns: usize) -> Vec<MemberIdentifier> {
+    for _ in 0..turns {
+        battle.play_turn().unwrap();
+    }
+
+    battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::ActionPerformed { performers, .. } => Some(performers[0]),
+            _ => None,
+        })
+        .collect()
+}
+
+fn by_speed() -> SuggestedPerformerCriteria<Fighter> {
+    SuggestedPerformerCriteria::FastestFirst(Box::new(|_, member: &Fighter| {
+        member.member_properties().speed
+    }))
+}
+
+#[test]
+fn fastest_members_act_first() {
+    let mut battle = battle(100, by_speed(), 0);
+
+    assert_eq!(
+        performers(&mut battle, 5),
+        [POCCO, BACCO, BECCO, PICCO, POCCO]
+    );
+}
+
+#[test]
+fn defeated_members_are_skipped_by_speed() {
+    let mut battle = battle(0, by_speed(), 0);
+
+    assert_eq!(performers(&mut battle, 4), [POCCO, BACCO, PICCO, POCCO]);
+}
+
+#[test]
+fn random_performers_are_alive_and_reproducible() {
+    let mut first = battle(0, SuggestedPerformerCriteria::Random, 7);
+    let played = performers(&mut first, 30);
+
+    assert!(!played.contains(&BECCO));
+    for member in [PICCO, POCCO, BACCO] {
+        assert!(played.contains(&member));
+    }
+
+    let mut replayed = battle(0, SuggestedPerformerCriteria::Random, 7);
+
+    assert_eq!(performers(&mut replayed, 30), played);
+}
+
+#[test]
+fn team_criteria_picks_the_performing_member() {
+    let mut battle = last_team_standing(teams(100), attack_first)
+        .set_team_performer_criteria(1, SuggestedPerformerCriteria::Constant(BECCO))
+        .set_startup_info(StartupInfo::new().with_first_performer(POCCO))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        performers(&mut battle, 5),
+        [POCCO, BECCO, PICCO, POCCO, BECCO]
+    );
+}
+
+#[test]
+fn team_criteria_cycles_within_the_team() {
+    let mut battle = last_team_standing(teams(100), attack_first)
+        .set_suggested_performer_criteria(SuggestedPerformerCriteria::Constant(BACCO))
+        .set_team_performer_criteria(1, SuggestedPerformerCriteria::CycleAlive)
+        .set_startup_info(StartupInfo::new().with_first_performer(POCCO))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        performers(&mut battle, 5),
+        [POCCO, BACCO, BECCO, BACCO, BECCO]
+    );
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::diff::BattleDiff;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::team::Team;
+
+use common::{last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Picco attacks Bacco, who can be defeated in 2 hits.
+fn battle() -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(String::from("Slimes"), vec![Fighter::new("Bacco", 20, 0)]),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        )
+    });
+
+    last_team_standing(teams, controller).build().unwrap()
+}
+
+#[test]
+fn unchanged_teams_have_no_differences() {
+    let battle = battle();
+
+    assert!(BattleDiff::new(battle.teams(), battle.teams()).is_empty());
+}
+
+#[test]
+fn damaged_members_are_reported() {
+    let mut battle = battle();
+    let before = battle.teams().to_vec();
+
+    battle.play_turn().unwrap();
+
+    let diff = BattleDiff::new(&before, battle.teams());
+    let bacco = diff.member(BACCO).unwrap();
+
+    assert_eq!(diff.members.len(), 1);
+    assert_eq!(bacco.health, Some((20, 10)));
+    assert_eq!(bacco.properties.as_ref().unwrap().1.health, 10);
+    assert!(!bacco.defeated);
+    assert!(diff.member(PICCO).is_none());
+}
+
+#[test]
+fn defeated_and_joined_members_are_reported() {
+    let mut battle = battle();
+
+    battle.play_turn().unwrap();
+    let before = battle.teams().to_vec();
+
+    battle.add_member(0, Fighter::new("Pacco", 100, 5)).unwrap();
+    battle.play_turn().unwrap();
+
+    let diff = BattleDiff::new(&before, battle.teams());
+
+    assert_eq!(diff.defeated().collect::<Vec<_>>(), [BACCO]);
+    assert_eq!(diff.joined, [MemberIdentifier::new(0, 1)]);
+    assert!(diff.left.is_empty());
+}
+
+#[test]
+fn checkpoints_can_be_diffed_against_the_current_teams() {
+    let mut battle = battle();
+    let checkpoint = battle.checkpoint().unwrap();
+
+    battle.play_turn().unwrap();
+
+    let diff = BattleDiff::new(checkpoint.teams(), battle.teams());
+
+    assert_eq!(diff.member(BACCO).unwrap().health, Some((20, 10)));
+}
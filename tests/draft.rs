@@ -0,0 +1,129 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fierceful_atto::draft::{Draft, DraftCallback, DraftPrompt, DraftResult, DraftStep};
+use fierceful_atto::error::DraftError;
+
+fn pool() -> Vec<&'static str> {
+    vec!["Knight", "Archer", "Mage", "Cleric", "Rogue"]
+}
+
+/// Controller always choosing the first available entry.
+fn first() -> DraftCallback<&'static str> {
+    Box::new(|_| 0)
+}
+
+/// Controller always choosing the last available entry.
+fn last() -> DraftCallback<&'static str> {
+    Box::new(|prompt| prompt.available.len() - 1)
+}
+
+#[test]
+fn teams_alternate_bans_and_picks() {
+    let result = Draft::new(pool(), vec![first(), last()])
+        .with_rounds(DraftStep::Ban, 1)
+        .with_rounds(DraftStep::Pick, 1)
+        .with_turn(0, DraftStep::Pick)
+        .run()
+        .unwrap();
+
+    assert_eq!(
+        result,
+        DraftResult {
+            picks: vec![vec!["Archer", "Mage"], vec!["Cleric"]],
+            bans: vec!["Knight", "Rogue"],
+            leftover: vec![],
+        }
+    );
+
+    let teams = result.into_teams(&["Blue"]);
+
+    assert_eq!(teams[0].name(), "Blue");
+    assert_eq!(teams[1].name(), "Team 1");
+    assert_eq!(teams[1].member_list(), ["Cleric"]);
+}
+
+#[test]
+fn prompts_show_the_draft_so_far() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let record = seen.clone();
+
+    let spy: DraftCallback<&'static str> = Box::new(move |prompt: &DraftPrompt<_>| {
+        record.borrow_mut().push((
+            prompt.step,
+            prompt.available.len(),
+            prompt.picks[0].clone(),
+            prompt.bans.to_vec(),
+        ));
+
+        0
+    });
+
+    Draft::new(pool(), vec![first(), spy])
+        .with_turn(0, DraftStep::Pick)
+        .with_turn(1, DraftStep::Ban)
+        .run()
+        .unwrap();
+
+    assert_eq!(
+        *seen.borrow(),
+        [(DraftStep::Ban, 4, vec!["Knight"], vec![])]
+    );
+}
+
+#[test]
+fn rejected_choices_are_prompted_again() {
+    let result = Draft::new(pool(), vec![Box::new(|prompt| prompt.attempt as usize)])
+        .with_turn(0, DraftStep::Pick)
+        .with_validator(Box::new(|_, entry| match *entry {
+            "Mage" => Ok(()),
+            _ => Err(String::from("only mages allowed")),
+        }))
+        .with_attempts(3)
+        .run()
+        .unwrap();
+
+    assert_eq!(result.picks, [vec!["Mage"]]);
+}
+
+#[test]
+fn fails_after_running_out_of_attempts() {
+    let error = Draft::new(pool(), vec![first()])
+        .with_turn(0, DraftStep::Pick)
+        .with_validator(Box::new(|_, _| Err(String::from("nothing allowed"))))
+        .with_attempts(2)
+        .run()
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        DraftError::Rejected {
+            team_id: 0,
+            reason: String::from("nothing allowed"),
+        }
+    );
+}
+
+#[test]
+fn invalid_turns_and_choices_fail() {
+    let unknown = Draft::new(pool(), vec![first()])
+        .with_turn(1, DraftStep::Pick)
+        .run();
+    assert_eq!(unknown, Err(DraftError::UnknownTeam { team_id: 1 }));
+
+    let empty = Draft::new(vec!["Knight"], vec![first()])
+        .with_rounds(DraftStep::Pick, 2)
+        .run();
+    assert_eq!(empty, Err(DraftError::EmptyPool));
+
+    let out_of_pool = Draft::new(pool(), vec![Box::new(|_| 10)])
+        .with_turn(0, DraftStep::Ban)
+        .run();
+    assert_eq!(
+        out_of_pool,
+        Err(DraftError::InvalidIndex {
+            team_id: 0,
+            index: 10
+        })
+    );
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::ElementalAttack;
+use fierceful_atto::damage::Pipeline;
+use fierceful_atto::element::{Affinity, EffectivenessChart, Element, ElementalAffinity};
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::team::Team;
+
+use common::{last_team_standing, Fighter, Gear, Props, Stats};
+
+/// Fighter with some elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Elemental {
+    fighter: Fighter,
+    elements: Vec<Element>,
+}
+
+impl Member for Elemental {
+    type Statistics = Stats;
+    type Properties = Props;
+    type Equipment = Gear;
+
+    fn name(&self) -> &str {
+        self.fighter.name()
+    }
+
+    fn statistics(&self) -> &Stats {
+        self.fighter.statistics()
+    }
+
+    fn member_properties(&self) -> &Props {
+        self.fighter.member_properties()
+    }
+
+    fn member_properties_mut(&mut self) -> &mut Props {
+        self.fighter.member_properties_mut()
+    }
+
+    fn equipment(&self) -> &Gear {
+        self.fighter.equipment()
+    }
+
+    fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+}
+
+fn fire() -> Element {
+    Element::new("Fire")
+}
+
+fn water() -> Element {
+    Element::new("Water")
+}
+
+fn grass() -> Element {
+    Element::new("Grass")
+}
+
+fn chart() -> EffectivenessChart {
+    EffectivenessChart::new()
+        .with(water(), fire(), Affinity::Weak)
+        .with(fire(), water(), Affinity::Resist)
+        .with(fire(), fire(), Affinity::Immune)
+}
+
+/// Battle where an attacker with 10 attack hits a member of the given elements with an elemental attack.
+fn battle(
+    pipeline: Pipeline<Elemental>,
+    attack: Element,
+    defending: Vec<Element>,
+) -> Battle<Elemental> {
+    let member = |name, attack, elements| Elemental {
+        fighter: Fighter::new(name, 100, attack),
+        elements,
+    };
+
+    let teams = vec![
+        Team::new(String::from("Attackers"), vec![member("Picco", 10, vec![])]),
+        Team::new(String::from("Dummies"), vec![member("Bacco", 0, defending)]),
+    ];
+
+    let controller: ChoiceCallback<Elemental> = Box::new(move |_, _, _| {
+        (
+            Box::new(ElementalAttack {
+                element: attack.clone(),
+            }),
+            Target::Single(MemberIdentifier::new(0, 0)),
+            Target::Single(MemberIdentifier::new(1, 0)),
+        )
+    });
+
+    last_team_standing(teams, controller)
+        .set_damage_pipeline(pipeline)
+        .build()
+        .unwrap()
+}
+
+fn damage_dealt(mut battle: Battle<Elemental>) -> u64 {
+    battle.play_turn().unwrap();
+
+    100 - battle.teams()[1].member(0).unwrap().health()
+}
+
+fn affinity_stage() -> Pipeline<Elemental> {
+    Pipeline::new().with_stage(ElementalAffinity { chart: chart() })
+}
+
+#[test]
+fn chart_defaults_to_neutral() {
+    let chart = chart();
+
+    assert_eq!(chart.affinity(&water(), &fire()), Affinity::Weak);
+    assert_eq!(chart.affinity(&fire(), &water()), Affinity::Resist);
+    assert_eq!(chart.affinity(&grass(), &fire()), Affinity::Neutral);
+}
+
+#[test]
+fn multipliers_of_every_defending_element_are_combined() {
+    let chart = chart().with(water(), grass(), Affinity::Weak);
+
+    assert_eq!(chart.multiplier(&water(), &[]), 100);
+    assert_eq!(chart.multiplier(&water(), &[fire()]), 200);
+    assert_eq!(chart.multiplier(&water(), &[fire(), grass()]), 400);
+    assert_eq!(chart.multiplier(&fire(), &[water(), fire()]), 0);
+}
+
+#[test]
+fn affinity_stage_scales_elemental_damage() {
+    assert_eq!(
+        damage_dealt(battle(affinity_stage(), water(), vec![fire()])),
+        20
+    );
+    assert_eq!(
+        damage_dealt(battle(affinity_stage(), fire(), vec![water()])),
+        5
+    );
+    assert_eq!(
+        damage_dealt(battle(affinity_stage(), fire(), vec![fire()])),
+        0
+    );
+    assert_eq!(
+        damage_dealt(battle(affinity_stage(), grass(), vec![fire()])),
+        10
+    );
+}
+
+#[test]
+fn elements_are_ignored_without_the_affinity_stage() {
+    assert_eq!(
+        damage_dealt(battle(Pipeline::new(), water(), vec![fire()])),
+        10
+    );
+}
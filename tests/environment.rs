@@ -0,0 +1,91 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, Context, Target};
+use fierceful_atto::battle::Builder;
+use fierceful_atto::environment::{EnvironmentEffect, EnvironmentScript};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+
+use common::{attack_first, duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+
+/// Every performer attacks the member of the other team, so only Picco deals damage.
+fn builder() -> Builder<Fighter> {
+    last_team_standing(duel(), attack_first).set_seed(0)
+}
+
+/// Falling rocks damaging every target, without any performer.
+struct Rockfall;
+
+impl Action<Fighter> for Rockfall {
+    fn act(&mut self, mut context: Context<'_, Fighter>) -> ActionState {
+        for id in context.target_ids() {
+            context.damage(id, 20);
+        }
+
+        ActionState::Finished
+    }
+}
+
+#[test]
+fn scripts_only_run_when_their_condition_holds() {
+    let lava = EnvironmentScript::<Fighter>::new(
+        "Lava",
+        Box::new(|view| {
+            view.teams()
+                .iter()
+                .enumerate()
+                .flat_map(|(team_id, team)| {
+                    team.member_list()
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, m)| m.health() > 0)
+                        .map(move |(member_id, _)| MemberIdentifier::new(team_id, member_id))
+                })
+                .map(|member| EnvironmentEffect::Apply(Event::Damaged { member, amount: 5 }))
+                .collect()
+        }),
+    )
+    .with_condition(Box::new(|view| view.turn_number() % 2 == 0));
+
+    let mut battle = builder().add_environment_script(lava).build().unwrap();
+
+    for _ in 0..4 {
+        battle.play_turn().unwrap();
+    }
+
+    // The lava erupts at the end of the 2nd and 4th turns.
+    let eruptions = battle
+        .events()
+        .events()
+        .iter()
+        .filter(|e| matches!(e, Event::EnvironmentTriggered { script } if script == "Lava"))
+        .count();
+
+    assert_eq!(eruptions, 2);
+    assert_eq!(health(battle.teams(), 0, 0), 90);
+}
+
+#[test]
+fn scripts_queue_actions_for_the_next_turn() {
+    let collapse = EnvironmentScript::new(
+        "Collapse",
+        Box::new(|_| {
+            vec![EnvironmentEffect::QueueAction((
+                Box::new(Rockfall),
+                Target::None,
+                Target::Single(PICCO),
+            ))]
+        }),
+    )
+    .with_condition(Box::new(|view| view.turn_number() == 1));
+
+    let mut battle = builder().add_environment_script(collapse).build().unwrap();
+
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 0, 0), 80);
+}
@@ -0,0 +1,299 @@
+mod common;
+
+use fierceful_atto::action::{Action, Target};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::catalogue::members::{SimpleProps, SimpleStats};
+use fierceful_atto::equipment::{Durability, Equipment, EquipmentSet, Item, Slot, Wear};
+use fierceful_atto::error::EquipError;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::state::BattleState;
+use fierceful_atto::team::Team;
+
+use common::last_team_standing;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Gear {
+    name: &'static str,
+    slot: Slot,
+    attack: u64,
+    defense: u64,
+    blocks: &'static [Slot],
+    durability: Option<Durability>,
+}
+
+impl Gear {
+    fn new(name: &'static str, slot: Slot, attack: u64, defense: u64) -> Self {
+        Self {
+            name,
+            slot,
+            attack,
+            defense,
+            blocks: &[],
+            durability: None,
+        }
+    }
+
+    fn with_durability(mut self, durability: u64) -> Self {
+        self.durability = Some(Durability::new(durability));
+
+        self
+    }
+}
+
+impl Item for Gear {
+    type Properties = SimpleProps;
+
+    fn slot(&self) -> Slot {
+        self.slot
+    }
+
+    fn properties(&self) -> SimpleProps {
+        SimpleProps {
+            health: 0,
+            attack: self.attack,
+            defense: self.defense,
+        }
+    }
+
+    fn blocked_slots(&self) -> &[Slot] {
+        self.blocks
+    }
+
+    fn durability(&self) -> Option<&Durability> {
+        self.durability.as_ref()
+    }
+
+    fn durability_mut(&mut self) -> Option<&mut Durability> {
+        self.durability.as_mut()
+    }
+}
+
+/// Member whose equipment wears down during battles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Knight {
+    statistics: SimpleStats,
+    properties: SimpleProps,
+    equipment: EquipmentSet<Gear>,
+}
+
+impl Knight {
+    fn new(max_health: u64, equipment: EquipmentSet<Gear>) -> Self {
+        let statistics = SimpleStats {
+            max_health,
+            attack: 0,
+            defense: 0,
+        };
+
+        Self {
+            statistics,
+            properties: SimpleProps::from(statistics),
+            equipment,
+        }
+    }
+}
+
+impl Member for Knight {
+    type Statistics = SimpleStats;
+    type Properties = SimpleProps;
+    type Equipment = EquipmentSet<Gear>;
+
+    fn name(&self) -> &str {
+        "Knight"
+    }
+
+    fn statistics(&self) -> &SimpleStats {
+        &self.statistics
+    }
+
+    fn member_properties(&self) -> &SimpleProps {
+        &self.properties
+    }
+
+    fn member_properties_mut(&mut self) -> &mut SimpleProps {
+        &mut self.properties
+    }
+
+    fn equipment(&self) -> &EquipmentSet<Gear> {
+        &self.equipment
+    }
+
+    fn equipment_mut(&mut self) -> Option<&mut EquipmentSet<Gear>> {
+        Some(&mut self.equipment)
+    }
+}
+
+fn greatsword() -> Gear {
+    Gear {
+        blocks: &[Slot::OffHand],
+        ..Gear::new("Greatsword", Slot::Weapon, 20, 0)
+    }
+}
+
+#[test]
+fn worn_items_add_up_their_properties() {
+    let mut set = EquipmentSet::new();
+
+    assert_eq!(set.associated_properties(), SimpleProps::default());
+
+    set.equip(Gear::new("Sword", Slot::Weapon, 10, 0)).unwrap();
+    set.equip(Gear::new("Shield", Slot::OffHand, 0, 5)).unwrap();
+    set.equip(Gear::new("Ring", Slot::Accessory, 2, 2)).unwrap();
+
+    assert_eq!(
+        set.associated_properties(),
+        SimpleProps {
+            health: 0,
+            attack: 12,
+            defense: 7,
+        }
+    );
+
+    let sword = set.equip(Gear::new("Axe", Slot::Weapon, 15, 0)).unwrap();
+
+    assert_eq!(sword.unwrap().name, "Sword");
+    assert_eq!(set.associated_properties().attack, 17);
+    assert_eq!(set.items().count(), 3);
+}
+
+#[test]
+fn items_only_fit_available_slots() {
+    let mut set = EquipmentSet::with_slots([Slot::Weapon, Slot::Custom(0)]);
+
+    assert_eq!(
+        set.equip(Gear::new("Robe", Slot::Armor, 0, 3)),
+        Err(EquipError::UnavailableSlot(Slot::Armor))
+    );
+    assert!(set.equip(Gear::new("Tail", Slot::Custom(0), 1, 0)).is_ok());
+    assert_eq!(set.slots().count(), 2);
+}
+
+#[test]
+fn blocked_slots_conflict_both_ways() {
+    let mut set = EquipmentSet::new();
+    set.equip(Gear::new("Shield", Slot::OffHand, 0, 5)).unwrap();
+
+    assert_eq!(
+        set.equip(greatsword()),
+        Err(EquipError::Blocked {
+            slot: Slot::OffHand,
+            by: Slot::Weapon,
+        })
+    );
+
+    let shield = set.unequip(Slot::OffHand).unwrap();
+    set.equip(greatsword()).unwrap();
+
+    assert_eq!(
+        set.can_equip(&shield),
+        Err(EquipError::Blocked {
+            slot: Slot::OffHand,
+            by: Slot::Weapon,
+        })
+    );
+    assert!(set.item(Slot::OffHand).is_none());
+    assert_eq!(set.item(Slot::Weapon).unwrap().name, "Greatsword");
+}
+
+#[test]
+fn broken_items_stop_granting_properties() {
+    let mut set = EquipmentSet::new();
+    set.equip(Gear::new("Sword", Slot::Weapon, 10, 0).with_durability(2))
+        .unwrap();
+    set.equip(Gear::new("Ring", Slot::Accessory, 2, 0)).unwrap();
+
+    assert!(!set.wear(Wear::Hit));
+    assert!(!set.wear(Wear::Attacking));
+    assert_eq!(set.associated_properties().attack, 12);
+
+    assert!(set.wear(Wear::Attacking));
+    assert!(!set.wear(Wear::Attacking));
+    assert_eq!(set.associated_properties().attack, 2);
+    assert!(set.item(Slot::Weapon).unwrap().is_broken());
+
+    let mut sword = set.unequip(Slot::Weapon).unwrap();
+    sword.durability.as_mut().unwrap().repair(5);
+    set.equip(sword).unwrap();
+
+    assert_eq!(set.associated_properties().attack, 12);
+    assert_eq!(
+        set.item(Slot::Weapon)
+            .unwrap()
+            .durability()
+            .unwrap()
+            .current(),
+        2
+    );
+}
+
+#[test]
+fn equipment_wears_down_during_battles() {
+    let armored = |durability| {
+        let mut set = EquipmentSet::new();
+        set.equip(Gear::new("Sword", Slot::Weapon, 10, 0).with_durability(durability))
+            .unwrap();
+        set.equip(Gear::new("Mail", Slot::Armor, 0, 4).with_durability(durability))
+            .unwrap();
+
+        Knight::new(100, set)
+    };
+
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![armored(2)]),
+        Team::new(String::from("Villains"), vec![armored(1)]),
+    ];
+
+    let controller =
+        |_: &[Team<Knight>], performer: Option<MemberIdentifier>, _: &mut BattleRng| {
+            let performer = performer.unwrap_or_default();
+
+            (
+                Box::new(DirectAttack) as Box<dyn Action<Knight>>,
+                Target::Single(performer),
+                Target::Single(MemberIdentifier::new(1 - performer.team_id, 0)),
+            )
+        };
+
+    let mut battle = last_team_standing(teams, controller)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    // The hero's sword deals 10 - 4 damage, which breaks the villain's mail.
+    battle.play_turn().unwrap();
+
+    let villain = battle.teams()[1].member(0).unwrap();
+    assert_eq!(villain.health(), 94);
+    assert_eq!(villain.final_properties().defense, 0);
+    assert!(battle.events().events().contains(&Event::EquipmentBroken {
+        member: MemberIdentifier::new(1, 0)
+    }));
+
+    // The villain hits back, breaking its own sword. The hero's equipment still has some durability left.
+    battle.play_turn().unwrap();
+
+    let hero = battle.teams()[0].member(0).unwrap();
+    assert_eq!(hero.health(), 94);
+    assert_eq!(hero.final_properties().defense, 4);
+    assert_eq!(
+        battle.teams()[1]
+            .member(0)
+            .unwrap()
+            .final_properties()
+            .attack,
+        0
+    );
+
+    // Breakage is replayed from the damage alone.
+    let replayed = BattleState::replay(
+        vec![
+            Team::new(String::from("Heroes"), vec![armored(2)]),
+            Team::new(String::from("Villains"), vec![armored(1)]),
+        ],
+        battle.events().events(),
+    );
+
+    assert_eq!(replayed.teams(), battle.teams());
+    assert_eq!(replayed.events(), battle.events());
+}
@@ -0,0 +1,209 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionId, ActionState, Context, Target};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::{ActionSummary, Event};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::state::BattleState;
+use fierceful_atto::team::Team;
+
+use common::scenario::Scenario;
+use common::{attack_first, duel, last_team_standing, Fighter};
+
+#[test]
+fn drained_events_are_only_returned_once() {
+    let mut battle = Scenario::load("duel").battle();
+
+    battle.play_turn().unwrap();
+    let first = battle.drain_events().to_vec();
+    assert!(first.contains(&Event::BattleStarted { seed: 0 }));
+
+    battle.play_turn().unwrap();
+    let second = battle.drain_events().to_vec();
+    assert!(!second.contains(&Event::BattleStarted { seed: 0 }));
+    assert!(second.contains(&Event::TurnStarted { turn_number: 2 }));
+
+    assert!(battle.drain_events().is_empty());
+
+    // Draining doesn't remove anything from the battle's history.
+    assert_eq!(battle.events().len(), first.len() + second.len());
+}
+
+#[test]
+fn replays_are_seeded_from_the_log() {
+    let mut battle = last_team_standing(duel(), attack_first)
+        .set_seed(42)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    let mut replayed = BattleState::replay(duel(), battle.events().events());
+
+    assert_eq!(replayed.rng().seed(), 42);
+    assert_eq!(replayed.events(), battle.events());
+}
+
+#[test]
+fn replays_start_from_the_seed_of_the_opening_draws() {
+    let mut battle = last_team_standing(duel(), attack_first)
+        .set_seed(7)
+        .add_battle_start_hook(Box::new(|state, _| {
+            let amount = state.rng().gen_range(1..50);
+
+            state.apply(Event::Damaged {
+                member: MemberIdentifier::new(1, 0),
+                amount,
+            });
+        }))
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    // The hook's damage is recorded before the battle starts, together with the seed it was drawn with.
+    let events = battle.events().events();
+    let drawn = match events[0] {
+        Event::Damaged { amount, .. } => amount,
+        ref e => panic!("unexpected first event {:?}", e),
+    };
+
+    let mut replayed = BattleState::replay(duel(), events);
+
+    assert_eq!(replayed.teams(), battle.teams());
+    assert_eq!(replayed.rng().gen_range(1..50), drawn);
+}
+
+#[test]
+fn defeat_follows_lethal_damage() {
+    let dummy = MemberIdentifier::new(1, 0);
+    let mut state = BattleState::new(duel(), BattleRng::from_seed(0));
+
+    state.apply(Event::Damaged {
+        member: dummy,
+        amount: 150,
+    });
+    state.apply(Event::Damaged {
+        member: dummy,
+        amount: 10,
+    });
+
+    assert_eq!(
+        state.events().events(),
+        &[
+            Event::Damaged {
+                member: dummy,
+                amount: 150,
+            },
+            Event::MemberDefeated { member: dummy },
+            Event::Damaged {
+                member: dummy,
+                amount: 10,
+            },
+        ]
+    );
+
+    let replayed = BattleState::replay(duel(), state.events().events());
+    assert_eq!(replayed.events(), state.events());
+}
+
+/// Damages the first target by 30 and lands a critical hit on it, while every other target evades.
+struct Flurry;
+
+impl Action<Fighter> for Flurry {
+    fn act(&mut self, mut context: Context<'_, Fighter>) -> ActionState {
+        let targets = context.target_ids();
+
+        for (i, id) in targets.into_iter().enumerate() {
+            if i == 0 {
+                context.apply(Event::CriticalHit { member: id });
+                context.damage(id, 30);
+            } else {
+                context.apply(Event::Missed { member: id });
+            }
+        }
+
+        ActionState::Finished
+    }
+
+    fn action_id(&self) -> ActionId {
+        ActionId::new("Flurry")
+    }
+}
+
+#[test]
+fn actions_are_summed_up_once_resolved() {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 40)]),
+        Team::new(
+            String::from("Slimes"),
+            vec![
+                Fighter::new("Bacco", 30, 1),
+                Fighter::new("Lillo", 100, 1),
+                Fighter::new("Tacco", 40, 1),
+            ],
+        ),
+    ];
+
+    let mut battle = last_team_standing(teams, |_: &[Team<Fighter>], _, _: &mut BattleRng| {
+        (
+            Box::new(Flurry) as Box<dyn Action<Fighter>>,
+            Target::Single(MemberIdentifier::new(0, 0)),
+            Target::FullTeam { team_id: 1 },
+        )
+    })
+    .set_seed(0)
+    .queue_action((
+        Box::new(DirectAttack),
+        Target::Single(MemberIdentifier::new(0, 0)),
+        Target::DiscreteMultiple(vec![
+            MemberIdentifier::new(1, 1),
+            MemberIdentifier::new(1, 2),
+        ]),
+    ))
+    .build()
+    .unwrap();
+
+    battle.play_turn().unwrap();
+
+    let summaries: Vec<_> = battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::ActionResolved { action, summary } => Some((action.name(), summary.clone())),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(summaries.len(), 2);
+
+    // The queued attack is resolved before the chosen action.
+    assert_eq!(summaries[0].1.damage, 80);
+    assert_eq!(summaries[0].1.defeated, vec![MemberIdentifier::new(1, 2)]);
+    assert_eq!(summaries[0].1.kills(), 1);
+
+    assert_eq!(summaries[1].0, "Flurry");
+    assert_eq!(
+        summaries[1].1,
+        ActionSummary {
+            damage: 30,
+            healing: 0,
+            defeated: vec![MemberIdentifier::new(1, 0)],
+            misses: 2,
+            critical_hits: 1,
+        }
+    );
+
+    // The summary always comes right after the effects of its action.
+    let last = battle
+        .events()
+        .events()
+        .iter()
+        .rposition(|e| matches!(e, Event::ActionResolved { .. }));
+    assert!(matches!(
+        battle.events().events()[last.unwrap() - 1],
+        Event::Missed { .. }
+    ));
+}
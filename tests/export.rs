@@ -0,0 +1,46 @@
+#![cfg(feature = "export")]
+
+mod common;
+
+use fierceful_atto::battle::Battle;
+
+use common::{attack_first, duel, last_team_standing, Fighter};
+
+/// Battle seeded with 3 where Picco and Bacco took one turn each to attack the other.
+fn battle() -> Battle<Fighter> {
+    let mut battle = last_team_standing(duel(), attack_first)
+        .set_seed(3)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    battle
+}
+
+#[test]
+fn outcomes_are_exported_to_json() {
+    assert_eq!(
+        battle().outcome().to_json(),
+        concat!(
+            r#"{"seed":3,"turns":2,"finished":false,"surviving_teams":[0,1],"members":["#,
+            r#"{"team_id":0,"member_id":0,"health":100,"damage_dealt":10,"damage_taken":0,"#,
+            r#""healing_done":0,"healing_received":0,"actions":1,"experience":0,"levels_gained":0},"#,
+            r#"{"team_id":1,"member_id":0,"health":90,"damage_dealt":0,"damage_taken":10,"#,
+            r#""healing_done":0,"healing_received":0,"actions":1,"experience":0,"levels_gained":0}]}"#,
+        )
+    );
+}
+
+#[test]
+fn outcomes_are_exported_to_csv() {
+    assert_eq!(
+        battle().outcome().to_csv(),
+        concat!(
+            "team_id,member_id,health,damage_dealt,damage_taken,healing_done,healing_received,actions,experience,levels_gained\n",
+            "0,0,100,10,0,0,0,1,0,0\n",
+            "1,0,90,0,10,0,0,1,0,0\n",
+        )
+    );
+}
@@ -0,0 +1,204 @@
+//! Golden replays of every catalogue action, played in the same fixed scenario.
+
+mod common;
+
+use std::fmt::Write;
+
+use fierceful_atto::action::{Action, Target};
+use fierceful_atto::catalogue::actions::{
+    AreaAttack, Charged, ConsumingAttack, CriticalAttack, DirectAttack, ElementalAttack,
+    MeleeAttack, TaggingAttack, WithCooldown,
+};
+use fierceful_atto::component::Tag;
+use fierceful_atto::damage::{Clamp, Critical, HitCheck, Pipeline};
+use fierceful_atto::element::Element;
+use fierceful_atto::formation::{Formation, Position};
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::registry::ActionFactory;
+use fierceful_atto::spread::Spread;
+use fierceful_atto::team::Team;
+
+use common::Fighter;
+use common::{golden::assert_golden, last_team_standing};
+
+const SEED: u64 = 2526;
+const TURNS: u64 = 8;
+
+/// Spread halving the damage for every step away from the epicenter.
+struct Halving;
+
+impl Spread for Halving {
+    fn spread(&self, damage: u64, distance: usize) -> u64 {
+        damage >> distance.min(63)
+    }
+}
+
+/// Play the fixed scenario, with the attackers using the tested action against the whole defending team,
+/// and record every event and the final health of all members.
+fn replay(action: ActionFactory<Fighter>) -> String {
+    let teams = vec![
+        Team::new(
+            String::from("Attackers"),
+            vec![
+                Fighter::new("Picco", 100, 12),
+                Fighter::new("Lillo", 100, 8),
+            ],
+        ),
+        Team::new(
+            String::from("Defenders"),
+            vec![
+                Fighter::new("Bacco", 90, 5),
+                Fighter::new("Tacco", 70, 5),
+                Fighter::new("Nocco", 50, 5),
+            ],
+        )
+        .with_formation(Formation::new(vec![
+            Position::front(0),
+            Position::front(1),
+            Position::back(0),
+        ])),
+    ];
+
+    let callback = Box::new(
+        move |_: &[Team<Fighter>], performer: Option<MemberIdentifier>, _: &mut _| {
+            let performer = performer.unwrap_or_default();
+
+            let (action, target): (Box<dyn Action<Fighter>>, Target) = if performer.team_id == 0 {
+                (action(), Target::FullTeam { team_id: 1 })
+            } else {
+                (
+                    Box::new(DirectAttack),
+                    Target::Single(MemberIdentifier::new(0, 0)),
+                )
+            };
+
+            (action, Target::Single(performer), target)
+        },
+    );
+
+    let mut battle = last_team_standing(teams, callback)
+        .set_seed(SEED)
+        .set_damage_pipeline(
+            Pipeline::new()
+                .with_stage(HitCheck)
+                .with_stage(Clamp { min: 0, max: 60 }),
+        )
+        .build()
+        .unwrap();
+
+    for _ in 0..TURNS {
+        battle.play_turn().unwrap();
+    }
+
+    let mut record = format!("seed: {}\n", SEED);
+
+    for event in battle.events().events() {
+        writeln!(record, "{:?}", event).unwrap();
+    }
+
+    for team in battle.teams() {
+        for member in team.member_list() {
+            writeln!(record, "{}: {}", member.name(), member.health()).unwrap();
+        }
+    }
+
+    record
+}
+
+#[test]
+fn direct_attack() {
+    assert_golden(
+        "direct_attack",
+        &replay(Box::new(|| Box::new(DirectAttack))),
+    );
+}
+
+#[test]
+fn melee_attack() {
+    let action = || {
+        Box::new(MeleeAttack {
+            back_row_reduction: 50,
+        }) as Box<dyn Action<Fighter>>
+    };
+
+    assert_golden("melee_attack", &replay(Box::new(action)));
+}
+
+#[test]
+fn critical_attack() {
+    let action = || {
+        Box::new(CriticalAttack {
+            critical: Critical {
+                chance: (1, 3),
+                multiplier: 200,
+            },
+        }) as Box<dyn Action<Fighter>>
+    };
+
+    assert_golden("critical_attack", &replay(Box::new(action)));
+}
+
+#[test]
+fn elemental_attack() {
+    let action = || {
+        Box::new(ElementalAttack {
+            element: Element::new("Fire"),
+        }) as Box<dyn Action<Fighter>>
+    };
+
+    assert_golden("elemental_attack", &replay(Box::new(action)));
+}
+
+#[test]
+fn tagging_attack() {
+    let action = || {
+        Box::new(TaggingAttack {
+            tag: Tag::new("Wet"),
+        }) as Box<dyn Action<Fighter>>
+    };
+
+    assert_golden("tagging_attack", &replay(Box::new(action)));
+}
+
+#[test]
+fn consuming_attack() {
+    let action = || {
+        Box::new(ConsumingAttack {
+            tag: Tag::new("Wet"),
+            bonus_damage: 25,
+        }) as Box<dyn Action<Fighter>>
+    };
+
+    assert_golden("consuming_attack", &replay(Box::new(action)));
+}
+
+#[test]
+fn area_attack() {
+    let action = || {
+        Box::new(AreaAttack {
+            epicenter: MemberIdentifier::new(1, 1),
+            spread: Some(Box::new(Halving)),
+        }) as Box<dyn Action<Fighter>>
+    };
+
+    assert_golden("area_attack", &replay(Box::new(action)));
+}
+
+#[test]
+fn with_cooldown() {
+    let action = || {
+        Box::new(WithCooldown {
+            action: DirectAttack,
+            turns: 2,
+        }) as Box<dyn Action<Fighter>>
+    };
+
+    assert_golden("with_cooldown", &replay(Box::new(action)));
+}
+
+#[test]
+fn charged() {
+    let action = || Box::new(Charged::new(DirectAttack, 1)) as Box<dyn Action<Fighter>>;
+
+    assert_golden("charged", &replay(Box::new(action)));
+}
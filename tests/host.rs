@@ -0,0 +1,126 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use fierceful_atto::action::Target;
+use fierceful_atto::battle::TurnMode;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::command::BattleCommand;
+use fierceful_atto::error::{ActionError, HostError};
+use fierceful_atto::event::Event;
+use fierceful_atto::host::BattleHost;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::team::Team;
+
+use common::{attack_first, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn attack(performer: MemberIdentifier, target: MemberIdentifier) -> BattleCommand<Fighter> {
+    BattleCommand::new(
+        Box::new(DirectAttack),
+        Target::Single(performer),
+        Target::Single(target),
+    )
+}
+
+fn host(mode: TurnMode) -> BattleHost<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(
+            String::from("Villains"),
+            vec![Fighter::new("Bacco", 100, 10)],
+        ),
+    ];
+
+    let battle = last_team_standing(teams, attack_first)
+        .set_seed(0)
+        .set_turn_mode(mode)
+        .build()
+        .unwrap();
+
+    BattleHost::new(battle, attack_first)
+}
+
+#[test]
+fn clients_submit_for_their_team() {
+    let mut host = host(TurnMode::Sequential).with_client(0).with_client(1);
+    let events = host.subscribe();
+
+    assert_eq!(host.clients(), [0, 1]);
+    assert_eq!(host.awaiting(), [0]);
+    assert_eq!(
+        host.submit(1, attack(BACCO, PICCO)).unwrap_err(),
+        HostError::NotAwaited { team_id: 1 }
+    );
+
+    let played = host.submit(0, attack(PICCO, BACCO)).unwrap();
+
+    assert!(played.contains(&Event::Damaged {
+        member: BACCO,
+        amount: 10
+    }));
+    assert_eq!(events.try_iter().collect::<Vec<_>>(), played);
+    assert_eq!(health(host.battle().teams(), 1, 0), 90);
+    assert_eq!(host.awaiting(), [1]);
+}
+
+#[test]
+fn clients_only_control_their_members() {
+    let mut host = host(TurnMode::Sequential).with_client(0).with_client(1);
+
+    assert_eq!(
+        host.submit(0, attack(BACCO, PICCO)).unwrap_err(),
+        HostError::NotControlled {
+            team_id: 0,
+            performers: Target::Single(BACCO)
+        }
+    );
+    assert_eq!(
+        host.submit(0, attack(PICCO, MemberIdentifier::new(5, 0)))
+            .unwrap_err(),
+        HostError::Rejected(ActionError::MissingMembers(Target::Single(
+            MemberIdentifier::new(5, 0)
+        )))
+    );
+
+    // Nothing was played.
+    assert_eq!(host.awaiting(), [0]);
+    assert!(host.battle().events().is_empty());
+}
+
+#[test]
+fn teams_without_clients_are_played_by_the_battle() {
+    let mut host = host(TurnMode::Sequential).with_client(0);
+
+    assert_eq!(
+        host.submit(1, attack(BACCO, PICCO)).unwrap_err(),
+        HostError::UnknownClient { team_id: 1 }
+    );
+
+    // Bacco's turn is played right after Picco's one.
+    host.submit(0, attack(PICCO, BACCO)).unwrap();
+
+    assert_eq!(health(host.battle().teams(), 0, 0), 90);
+    assert_eq!(health(host.battle().teams(), 1, 0), 90);
+    assert_eq!(host.awaiting(), [0]);
+}
+
+#[test]
+fn simultaneous_turns_wait_for_every_client() {
+    let mut host = host(TurnMode::Simultaneous).with_client(0).with_client(1);
+
+    assert_eq!(host.awaiting(), [0, 1]);
+
+    let played = host.submit(1, attack(BACCO, PICCO)).unwrap();
+
+    assert!(played.is_empty());
+    assert_eq!(host.awaiting(), [0]);
+
+    host.submit(0, attack(PICCO, BACCO)).unwrap();
+
+    assert_eq!(health(host.battle().teams(), 0, 0), 90);
+    assert_eq!(health(host.battle().teams(), 1, 0), 90);
+    assert_eq!(host.awaiting(), [0, 1]);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::MemberIdentifier;
+
+use common::{attack_first, duel, health, last_team_standing, Fighter};
+
+const AFK: MemberIdentifier = MemberIdentifier::new(0, 0);
+const DUMMY: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// The attacker never chooses any performer, while the dummy keeps attacking it.
+fn away_from_keyboard() -> ChoiceCallback<Fighter> {
+    Box::new(|_, performer, _| {
+        let performers = match performer {
+            Some(p) if p == DUMMY => Target::Single(p),
+            _ => Target::None,
+        };
+
+        (Box::new(DirectAttack), performers, Target::Single(AFK))
+    })
+}
+
+fn forfeits(battle: &Battle<Fighter>) -> Vec<u32> {
+    battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::TurnForfeited { member, idle_turns } if *member == AFK => Some(*idle_turns),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn forfeited_turns_are_counted() {
+    let mut battle = last_team_standing(duel(), away_from_keyboard())
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    for _ in 0..6 {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(forfeits(&battle), vec![1, 2, 3]);
+    assert!(!battle.is_taken_over(AFK));
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
+
+#[test]
+fn idle_members_are_taken_over() {
+    let mut battle = last_team_standing(duel(), away_from_keyboard())
+        .set_seed(0)
+        .set_idle_takeover(2, attack_first)
+        .build()
+        .unwrap();
+
+    for _ in 0..4 {
+        battle.play_turn().unwrap();
+    }
+
+    assert!(battle.is_taken_over(AFK));
+    assert!(battle
+        .events()
+        .events()
+        .contains(&Event::ControlTakenOver { member: AFK }));
+
+    // The takeover controller plays in place of the idle member.
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+    assert_eq!(forfeits(&battle), vec![1, 2]);
+
+    // Once control is restored, the member is idle again, starting from scratch.
+    assert!(battle.restore_control(AFK));
+    assert!(!battle.restore_control(AFK));
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    assert!(!battle.is_taken_over(AFK));
+    assert_eq!(forfeits(&battle), vec![1, 2, 1]);
+}
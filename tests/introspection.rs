@@ -0,0 +1,40 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionKind, AsAny};
+use fierceful_atto::catalogue::actions::{DirectAttack, MeleeAttack, WithCooldown};
+
+use common::Fighter;
+
+#[test]
+fn boxed_actions_can_be_downcast() {
+    let action: Box<dyn Action<Fighter>> = Box::new(MeleeAttack {
+        back_row_reduction: 30,
+    });
+
+    assert!(action.is::<MeleeAttack>());
+    assert!(!action.is::<DirectAttack>());
+    assert_eq!(
+        action
+            .downcast_ref::<MeleeAttack>()
+            .map(|a| a.back_row_reduction),
+        Some(30)
+    );
+    assert!(action.as_any().downcast_ref::<DirectAttack>().is_none());
+}
+
+#[test]
+fn kind_identifies_concrete_type() {
+    let direct: Box<dyn Action<Fighter>> = Box::new(DirectAttack);
+    let wrapped: Box<dyn Action<Fighter>> = Box::new(WithCooldown {
+        action: DirectAttack,
+        turns: 2,
+    });
+
+    assert_eq!(direct.kind(), ActionKind::of::<DirectAttack>());
+    assert!(direct.kind().is::<DirectAttack>());
+
+    // Wrappers share the identifier of the wrapped action, but not its kind.
+    assert_eq!(direct.action_id(), wrapped.action_id());
+    assert_ne!(direct.kind(), wrapped.kind());
+    assert!(wrapped.kind().is::<WithCooldown<DirectAttack>>());
+}
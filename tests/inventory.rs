@@ -0,0 +1,193 @@
+mod common;
+
+use fierceful_atto::action::{Action, ChoiceCallback, Context, Target};
+use fierceful_atto::battle::{Battle, Builder, EndCondition, StartupInfo};
+use fierceful_atto::catalogue::actions::{DirectAttack, UseItem};
+use fierceful_atto::catalogue::items::Potion;
+use fierceful_atto::error::ActionError;
+use fierceful_atto::event::Event;
+use fierceful_atto::inventory::{Inventory, InventoryOwner, ItemId};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::state::BattleState;
+use fierceful_atto::team::Team;
+
+use common::{health, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const NOCCO: MemberIdentifier = MemberIdentifier::new(0, 1);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn teams() -> Vec<Team<Fighter>> {
+    vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![
+                Fighter::new("Picco", 100, 10),
+                Fighter::new("Nocco", 100, 10),
+            ],
+        ),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 1000, 30)],
+        ),
+    ]
+}
+
+/// Bacco hits Picco every turn, while the heroes act through queued actions only.
+fn builder(teams: Vec<Team<Fighter>>) -> Builder<Fighter> {
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        )
+    });
+
+    Builder::new(
+        teams,
+        Some(StartupInfo::new().with_first_performer(BACCO)),
+        controller,
+        EndCondition::LastTeamStanding,
+    )
+    .set_seed(0)
+}
+
+fn battle() -> Battle<Fighter> {
+    builder(teams())
+        .add_items(InventoryOwner::Member(PICCO), ItemId::new("Potion"), 1)
+        .add_items(InventoryOwner::Team(0), ItemId::new("Potion"), 1)
+        .build()
+        .unwrap()
+}
+
+/// Battle in which the heroes share a bag with the given amount of potions.
+fn shared_bag(potions: u32) -> Battle<Fighter> {
+    let mut bag = Inventory::new();
+    bag.add(ItemId::new("Potion"), potions);
+
+    let mut teams = teams();
+    teams[0] = teams[0].clone().with_inventory(bag);
+
+    builder(teams).build().unwrap()
+}
+
+fn drink(battle: &mut Battle<Fighter>, performer: MemberIdentifier) {
+    battle.queue_action((
+        Box::new(UseItem {
+            item: Box::new(Potion::new(25)),
+        }),
+        Target::Single(performer),
+        Target::Single(PICCO),
+    ));
+}
+
+#[test]
+fn items_are_taken_from_the_member_before_the_team() {
+    let mut battle = battle();
+    let potion = ItemId::new("Potion");
+
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 0, 0), 70);
+
+    drink(&mut battle, PICCO);
+    battle.play_turn().unwrap();
+
+    // The potion is drunk before Bacco attacks again.
+    assert_eq!(health(battle.teams(), 0, 0), 65);
+    assert!(battle
+        .inventory(InventoryOwner::Member(PICCO))
+        .unwrap()
+        .items()
+        .next()
+        .is_none());
+    assert_eq!(
+        battle
+            .inventory(InventoryOwner::Team(0))
+            .unwrap()
+            .count(&potion),
+        1
+    );
+
+    // Nocco has no potions of its own, so it uses the team's one.
+    drink(&mut battle, NOCCO);
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 0, 0), 60);
+    assert!(!battle
+        .inventory(InventoryOwner::Team(0))
+        .unwrap()
+        .contains(&potion));
+
+    let replayed = BattleState::replay(teams(), battle.events().events());
+    assert_eq!(
+        replayed.inventory(InventoryOwner::Team(0)),
+        battle.inventory(InventoryOwner::Team(0))
+    );
+}
+
+#[test]
+fn items_cannot_be_used_once_finished() {
+    let mut state = BattleState::new(teams(), BattleRng::from_seed(0));
+    state.add_items(InventoryOwner::Team(1), ItemId::new("Potion"), 1);
+
+    let mut drink = UseItem {
+        item: Box::new(Potion::new(25)),
+    };
+
+    // Items of other teams are out of reach.
+    let context = Context::new(&mut state, Target::Single(NOCCO), Target::Single(PICCO));
+    assert_eq!(
+        drink.validate(&context),
+        Err(ActionError::MissingItem {
+            member: NOCCO,
+            item: ItemId::new("Potion"),
+        })
+    );
+
+    let context = Context::new(&mut state, Target::Single(BACCO), Target::Single(BACCO));
+    assert_eq!(drink.validate(&context), Ok(()));
+    drink.act(context);
+
+    let context = Context::new(&mut state, Target::Single(BACCO), Target::Single(BACCO));
+    assert!(drink.validate(&context).is_err());
+}
+
+#[test]
+fn team_inventories_are_kept_by_the_teams() {
+    let mut battle = shared_bag(2);
+    let potion = ItemId::new("Potion");
+
+    battle.play_turn().unwrap();
+    drink(&mut battle, NOCCO);
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 0, 0), 65);
+    assert_eq!(battle.teams()[0].inventory().count(&potion), 1);
+    assert_eq!(
+        battle.inventory(InventoryOwner::Team(0)),
+        Some(battle.teams()[0].inventory())
+    );
+}
+
+#[test]
+fn the_last_shared_item_is_used_once() {
+    let mut battle = shared_bag(1);
+
+    battle.play_turn().unwrap();
+    drink(&mut battle, PICCO);
+    drink(&mut battle, NOCCO);
+    battle.play_turn().unwrap();
+
+    // Only the first potion is drunk, as the second action finds the bag empty.
+    assert_eq!(health(battle.teams(), 0, 0), 65);
+    assert!(battle.teams()[0].inventory().items().next().is_none());
+
+    let consumed = battle
+        .events()
+        .events()
+        .iter()
+        .filter(|e| matches!(e, Event::ItemConsumed { .. }))
+        .count();
+    assert_eq!(consumed, 1);
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::inventory::ItemId;
+use fierceful_atto::loot::{DropTable, LootDrop};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::state::BattleState;
+use fierceful_atto::team::Team;
+
+use common::{last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn jelly() -> ItemId {
+    ItemId::new("Jelly")
+}
+
+fn teams() -> Vec<Team<Fighter>> {
+    vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 50)]),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 100, 0)
+                .with_drop_table(DropTable::new().with_entry(jelly(), 2, 1).with_rolls(3))],
+        ),
+    ]
+}
+
+fn battle() -> Battle<Fighter> {
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, performer, _| match performer {
+        Some(BACCO) => (
+            Box::new(DirectAttack),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        ),
+        _ => (
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        ),
+    });
+
+    last_team_standing(teams(), controller)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn weighted_entries_are_rolled() {
+    let table = DropTable::new()
+        .with_entry(jelly(), 1, 3)
+        .with_nothing(1)
+        .with_rolls(1000);
+    let drops = table.roll(&mut BattleRng::from_seed(0));
+
+    // About three rolls out of four drop some jelly.
+    assert!((700..800).contains(&drops.len()));
+    assert!(drops
+        .iter()
+        .all(|(item, amount)| *item == jelly() && *amount == 1));
+
+    assert!(DropTable::new()
+        .with_nothing(1)
+        .roll(&mut BattleRng::from_seed(0))
+        .is_empty());
+    assert!(DropTable::new()
+        .with_entry(jelly(), 1, 0)
+        .roll(&mut BattleRng::from_seed(0))
+        .is_empty());
+}
+
+#[test]
+fn defeated_members_drop_loot() {
+    let mut battle = battle();
+    let starting_teams = teams();
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    let drop = LootDrop {
+        member: BACCO,
+        item: jelly(),
+        amount: 2,
+    };
+    assert_eq!(battle.outcome().loot, [drop.clone(), drop.clone(), drop]);
+
+    // Drops are recorded as events, so they are replayed without rolling again.
+    let replayed = BattleState::replay(starting_teams, battle.events().events());
+    let dropped = replayed
+        .events()
+        .events()
+        .iter()
+        .filter(|e| matches!(e, Event::LootDropped { .. }))
+        .count();
+
+    assert_eq!(dropped, 3);
+}
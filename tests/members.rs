@@ -0,0 +1,96 @@
+mod common;
+
+use fierceful_atto::action::{Action, ChoiceCallback, Context, Target};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::catalogue::members::{SimpleMember, SimpleProps, SimpleStats};
+use fierceful_atto::damage::{Defense, Pipeline};
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::state::BattleState;
+use fierceful_atto::team::Team;
+
+use common::last_team_standing;
+
+#[test]
+fn simple_members_start_at_full_health() {
+    let member = SimpleMember::new("Picco", 80, 12).with_defense(3);
+
+    assert_eq!(
+        member.statistics(),
+        &SimpleStats {
+            max_health: 80,
+            attack: 12,
+            defense: 3,
+        }
+    );
+    assert_eq!(
+        member.final_properties(),
+        SimpleProps {
+            health: 80,
+            attack: 12,
+            defense: 3,
+        }
+    );
+}
+
+#[test]
+fn simple_members_mitigate_damage_with_defense() {
+    let teams = vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![SimpleMember::new("Picco", 100, 20)],
+        ),
+        Team::new(
+            String::from("Slimes"),
+            vec![SimpleMember::new("Bacco", 100, 5).with_defense(8)],
+        ),
+    ];
+
+    let mut state = BattleState::new(teams, BattleRng::from_seed(0));
+    DirectAttack.act(Context::new(
+        &mut state,
+        Target::Single(MemberIdentifier::new(0, 0)),
+        Target::Single(MemberIdentifier::new(1, 0)),
+    ));
+
+    assert_eq!(state.teams()[1].member(0).unwrap().health(), 88);
+}
+
+#[test]
+fn custom_pipelines_replace_the_default_mitigation() {
+    let health_after_attack = |pipeline: Pipeline<SimpleMember>| {
+        let teams = vec![
+            Team::new(
+                String::from("Heroes"),
+                vec![SimpleMember::new("Picco", 100, 20)],
+            ),
+            Team::new(
+                String::from("Slimes"),
+                vec![SimpleMember::new("Bacco", 100, 5).with_defense(8)],
+            ),
+        ];
+        let controller: ChoiceCallback<SimpleMember> = Box::new(|_, _, _| {
+            (
+                Box::new(DirectAttack),
+                Target::Single(MemberIdentifier::new(0, 0)),
+                Target::Single(MemberIdentifier::new(1, 0)),
+            )
+        });
+
+        let mut battle = last_team_standing(teams, controller)
+            .set_damage_pipeline(pipeline)
+            .build()
+            .unwrap();
+        battle.play_turn().unwrap();
+
+        battle.teams()[1].member(0).unwrap().health()
+    };
+
+    // Defense is only subtracted by the pipeline, so replacing it never mitigates the damage twice.
+    let defense = Defense {
+        defense: |m: &SimpleMember| m.final_properties().defense,
+    };
+
+    assert_eq!(health_after_attack(Pipeline::new()), 80);
+    assert_eq!(health_after_attack(Pipeline::new().with_stage(defense)), 88);
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use fierceful_atto::action::Target;
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::component::Tag;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::state::BattleState;
+use fierceful_atto::team::Team;
+
+use common::{attack_first, health, last_team_standing, Fighter};
+
+fn skirmish() -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(
+            String::from("Slimes"),
+            vec![
+                Fighter::new("Bacco", 100, 1),
+                Fighter::new("Lillo", 100, 1),
+                Fighter::new("Tacco", 100, 1),
+            ],
+        ),
+    ];
+
+    last_team_standing(teams, attack_first)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn removal_shifts_following_members() {
+    let mut battle = skirmish();
+    battle.play_turn().unwrap();
+
+    let removed = battle.remove_member(MemberIdentifier::new(1, 0)).unwrap();
+    assert_eq!(removed.name(), "Bacco");
+    assert_eq!(battle.teams()[1].member(0).unwrap().name(), "Lillo");
+    assert_eq!(battle.teams()[1].member(1).unwrap().name(), "Tacco");
+
+    // The suggested performer (Bacco) left, so its followers take their turns.
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 0, 0), 98);
+
+    // The member that took Bacco's place is now the one targeted by the heroes.
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+    assert!(!battle.is_finished());
+}
+
+#[test]
+fn removal_checks_end_condition() {
+    let mut battle = skirmish();
+    battle.play_turn().unwrap();
+
+    battle.remove_member(MemberIdentifier::new(0, 0)).unwrap();
+
+    assert!(battle.is_finished());
+    assert_eq!(
+        battle.events().events().last(),
+        Some(&Event::BattleFinished)
+    );
+}
+
+#[test]
+fn removal_is_replayed() {
+    let mut state = BattleState::new(skirmish().take_teams(), BattleRng::from_seed(0));
+    let starting_teams = skirmish().take_teams();
+
+    state.apply(Event::TagApplied {
+        member: MemberIdentifier::new(1, 2),
+        tag: Tag::new("Wet"),
+    });
+    state.remove_member(MemberIdentifier::new(1, 1)).unwrap();
+
+    // Components follow the shifted members.
+    assert!(state
+        .components()
+        .has_tag(MemberIdentifier::new(1, 1), &Tag::new("Wet")));
+
+    let replayed = BattleState::replay(starting_teams, state.events().events());
+    assert_eq!(
+        replayed.teams()[1].member_list(),
+        state.teams()[1].member_list()
+    );
+    assert_eq!(replayed.components(), state.components());
+}
+
+#[test]
+fn joining_members_are_targetable() {
+    let mut battle = skirmish();
+
+    let id = battle.add_member(0, Fighter::new("Nocco", 50, 10)).unwrap();
+    assert_eq!(id, MemberIdentifier::new(0, 1));
+
+    battle.queue_action((
+        Box::new(DirectAttack),
+        Target::Single(MemberIdentifier::new(1, 0)),
+        Target::Single(id),
+    ));
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 0, 1), 49);
+}
+
+#[test]
+fn handles_survive_removals() {
+    let mut battle = skirmish();
+
+    let tacco = battle.handle(MemberIdentifier::new(1, 2)).unwrap();
+    let bacco = battle.handle(MemberIdentifier::new(1, 0)).unwrap();
+    assert_ne!(tacco, bacco);
+
+    battle.remove_member(MemberIdentifier::new(1, 0)).unwrap();
+
+    assert_eq!(battle.resolve(tacco), Some(MemberIdentifier::new(1, 1)));
+    assert_eq!(battle.resolve(bacco), None);
+
+    // Handles of joining members are never reused.
+    let id = battle.add_member(1, Fighter::new("Nocco", 50, 10)).unwrap();
+    let nocco = battle.handle(id).unwrap();
+    assert_ne!(nocco, bacco);
+    assert_ne!(nocco, tacco);
+    assert_eq!(battle.resolve(nocco), Some(id));
+}
@@ -0,0 +1,257 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, Context, Target};
+use fierceful_atto::battle::{Battle, Builder};
+use fierceful_atto::catalogue::actions::{DirectAttack, Move, Pull, Push};
+use fierceful_atto::component::Tag;
+use fierceful_atto::event::Event;
+use fierceful_atto::formation::{Formation, Hazard, Position, Shape, Step, ZoneOfControl};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::reaction::OpportunityHandler;
+use fierceful_atto::state::BattleState;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+const LILLO: MemberIdentifier = MemberIdentifier::new(1, 1);
+const TACCO: MemberIdentifier = MemberIdentifier::new(1, 2);
+
+/// Slimes placed on a grid, with spikes behind Bacco:
+///
+/// ```text
+/// front: Bacco  Lillo
+/// back:  ^^^^^  Tacco
+/// ```
+fn builder() -> Builder<Fighter> {
+    let slimes = Team::new(
+        String::from("Slimes"),
+        vec![
+            Fighter::new("Bacco", 100, 1),
+            Fighter::new("Lillo", 100, 1),
+            Fighter::new("Tacco", 100, 1),
+        ],
+    )
+    .with_formation(
+        Formation::new(vec![
+            Position::front(0),
+            Position::front(1),
+            Position::back(1),
+        ])
+        .with_hazard(
+            Position::back(0),
+            Hazard::new(15).with_tag(Tag::new("Bleeding")),
+        ),
+    );
+
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        slimes,
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(Move {
+                step: Step::Left,
+                distance: 1,
+            }),
+            Target::None,
+            Target::None,
+        )
+    });
+
+    last_team_standing(teams, controller).set_seed(0)
+}
+
+fn battle() -> Battle<Fighter> {
+    builder().build().unwrap()
+}
+
+fn position(battle: &Battle<Fighter>, member: MemberIdentifier) -> Option<Position> {
+    battle.teams()[member.team_id].position(member.member_id)
+}
+
+#[test]
+fn pushed_members_step_into_hazards() {
+    let mut battle = battle();
+
+    battle.queue_action((
+        Box::new(Push),
+        Target::Single(PICCO),
+        Target::FullTeam { team_id: 1 },
+    ));
+    battle.play_turn().unwrap();
+
+    assert_eq!(position(&battle, BACCO), Some(Position::back(0)));
+    assert_eq!(health(battle.teams(), 1, 0), 85);
+    assert!(battle.components().has_tag(BACCO, &Tag::new("Bleeding")));
+
+    // Lillo is blocked by Tacco, and Tacco can't be pushed any further.
+    assert_eq!(position(&battle, LILLO), Some(Position::front(1)));
+    assert_eq!(position(&battle, TACCO), Some(Position::back(1)));
+
+    let events = battle.events().events();
+    assert!(events.contains(&Event::MoveBlocked {
+        member: LILLO,
+        blocker: Some(TACCO),
+    }));
+    assert!(events.contains(&Event::MoveBlocked {
+        member: TACCO,
+        blocker: None,
+    }));
+}
+
+#[test]
+fn members_move_until_blocked() {
+    let mut battle = battle();
+
+    battle.queue_action((Box::new(Pull), Target::Single(PICCO), Target::Single(TACCO)));
+    battle.queue_action((
+        Box::new(Move {
+            step: Step::Right,
+            distance: 3,
+        }),
+        Target::Single(LILLO),
+        Target::None,
+    ));
+    battle.play_turn().unwrap();
+
+    // Tacco is blocked by Lillo, who then walks away.
+    assert_eq!(position(&battle, TACCO), Some(Position::back(1)));
+    assert_eq!(position(&battle, LILLO), Some(Position::front(4)));
+}
+
+#[test]
+fn movement_is_replayed() {
+    let mut battle = battle();
+    let starting_teams = self::battle().take_teams();
+
+    battle.queue_action((Box::new(Push), Target::Single(PICCO), Target::Single(BACCO)));
+    battle.play_turn().unwrap();
+
+    let replayed = BattleState::replay(starting_teams, battle.events().events());
+    assert_eq!(replayed.teams(), battle.teams());
+}
+
+/// Interrupts every target, stopping it in its tracks.
+struct Trip;
+
+impl Action<Fighter> for Trip {
+    fn act(&mut self, mut context: Context<'_, Fighter>) -> ActionState {
+        for id in context.target_ids() {
+            context.interrupt(id);
+        }
+
+        ActionState::Finished
+    }
+}
+
+/// Picco threatens Bacco's cell and the ones next to it.
+fn threatening_picco(handler: OpportunityHandler<Fighter>) -> Battle<Fighter> {
+    builder()
+        .add_opportunity_attack(
+            PICCO,
+            ZoneOfControl::new(1, Position::front(0), Shape::Cross),
+            handler,
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn leaving_threatened_cells_provokes_attacks() {
+    let mut battle = threatening_picco(Box::new(|attacker, mover, _| {
+        Some((
+            Box::new(DirectAttack),
+            Target::Single(attacker),
+            Target::Single(mover),
+        ))
+    }));
+
+    battle.queue_action((Box::new(Push), Target::Single(PICCO), Target::Single(BACCO)));
+    battle.queue_action((
+        Box::new(Move {
+            step: Step::Right,
+            distance: 1,
+        }),
+        Target::Single(TACCO),
+        Target::None,
+    ));
+    battle.play_turn().unwrap();
+
+    // Bacco is hit before stepping into the spikes, while Tacco's cell isn't threatened.
+    assert_eq!(position(&battle, BACCO), Some(Position::back(0)));
+    assert_eq!(health(battle.teams(), 1, 0), 75);
+    assert_eq!(position(&battle, TACCO), Some(Position::back(2)));
+    assert_eq!(health(battle.teams(), 1, 2), 100);
+}
+
+#[test]
+fn attacks_of_opportunity_may_stop_the_movement() {
+    let mut battle = threatening_picco(Box::new(|attacker, mover, _| {
+        Some((
+            Box::new(Trip),
+            Target::Single(attacker),
+            Target::Single(mover),
+        ))
+    }));
+
+    battle.queue_action((Box::new(Push), Target::Single(PICCO), Target::Single(BACCO)));
+    battle.play_turn().unwrap();
+
+    assert_eq!(position(&battle, BACCO), Some(Position::front(0)));
+    assert!(battle.events().events().contains(&Event::MoveBlocked {
+        member: BACCO,
+        blocker: Some(PICCO),
+    }));
+}
+
+#[test]
+fn attacks_of_opportunity_are_summed_up_apart() {
+    let mut battle = threatening_picco(Box::new(|attacker, mover, _| {
+        Some((
+            Box::new(DirectAttack),
+            Target::Single(attacker),
+            Target::Single(mover),
+        ))
+    }));
+
+    battle.queue_action((Box::new(Push), Target::Single(PICCO), Target::Single(BACCO)));
+    battle.play_turn().unwrap();
+
+    let summaries: Vec<_> = battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::ActionResolved { summary, .. } => Some(summary.damage),
+            _ => None,
+        })
+        .collect();
+
+    // The attack of opportunity resolves first, while the push only counts the damage of the spikes.
+    assert_eq!(summaries[..2], [10, 15]);
+}
+
+#[test]
+fn attacks_of_opportunity_are_rolled_back() {
+    let mut battle = threatening_picco(Box::new(|attacker, mover, _| {
+        Some((
+            Box::new(DirectAttack),
+            Target::Single(attacker),
+            Target::Single(mover),
+        ))
+    }));
+
+    // Removing Picco drops its attack of opportunity, which comes back with the checkpoint.
+    let checkpoint = battle.checkpoint().unwrap();
+    battle.remove_member(PICCO);
+    battle.rollback(checkpoint);
+
+    battle.queue_action((Box::new(Push), Target::Single(PICCO), Target::Single(BACCO)));
+    battle.play_turn().unwrap();
+
+    assert_eq!(position(&battle, BACCO), Some(Position::back(0)));
+    assert_eq!(health(battle.teams(), 1, 0), 75);
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use std::cell::Cell;
+use std::sync::Once;
+
+use fierceful_atto::battle::Battle;
+use fierceful_atto::narration;
+
+use common::{attack_first, duel, last_team_standing, Fighter};
+
+thread_local! {
+    static NARRATED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Logger counting the lines narrated by the current thread.
+struct Counter;
+
+impl log::Log for Counter {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Info
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.level() == log::Level::Info {
+            NARRATED.with(|n| n.set(n.get() + 1));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn install_logger() {
+    static INIT: Once = Once::new();
+
+    INIT.call_once(|| {
+        log::set_logger(&Counter).unwrap();
+        log::set_max_level(log::LevelFilter::Info);
+    });
+
+    NARRATED.with(|n| n.set(0));
+}
+
+fn battle(narration: bool) -> Battle<Fighter> {
+    last_team_standing(duel(), attack_first)
+        .set_seed(0)
+        .set_narration(narration)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn turns_are_narrated_by_default() {
+    install_logger();
+
+    let mut battle = battle(true);
+    battle.play_turn().unwrap();
+
+    assert!(NARRATED.with(Cell::get) > 0);
+}
+
+#[test]
+fn silent_battles_narrate_nothing() {
+    install_logger();
+
+    let mut battle = battle(false);
+
+    for _ in 0..5 {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(NARRATED.with(Cell::get), 0);
+
+    // The narration is restored once the turn has been played.
+    assert!(narration::is_enabled());
+}
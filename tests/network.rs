@@ -0,0 +1,167 @@
+#![cfg(feature = "network")]
+
+mod common;
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::net::{TcpListener, TcpStream};
+use std::rc::Rc;
+use std::thread;
+
+use fierceful_atto::action::{ActionId, ChoiceReturn, Target};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::error::NetworkError;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::network::{BattleView, JsonLines, RemoteChoice, RemoteController, Transport};
+use fierceful_atto::registry::ActionRegistry;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::team::Team;
+
+use common::{duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Transport answering with scripted choices, remembering the performers it was asked about.
+struct Loopback {
+    choices: VecDeque<Result<RemoteChoice, NetworkError>>,
+    asked: Rc<RefCell<Vec<Option<MemberIdentifier>>>>,
+}
+
+impl Transport<Fighter> for Loopback {
+    fn send(&mut self, view: &BattleView<Fighter>) -> Result<(), NetworkError> {
+        self.asked.borrow_mut().push(view.performer);
+
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<RemoteChoice, NetworkError> {
+        self.choices
+            .pop_front()
+            .unwrap_or(Err(NetworkError::Closed))
+    }
+}
+
+/// The suggested performer does nothing.
+fn idle(
+    _: &[Team<Fighter>],
+    performer: Option<MemberIdentifier>,
+    _: &mut BattleRng,
+) -> ChoiceReturn<Fighter> {
+    (
+        Box::new(DirectAttack),
+        Target::Single(performer.unwrap_or_default()),
+        Target::None,
+    )
+}
+
+fn registry() -> ActionRegistry<Fighter> {
+    ActionRegistry::new().with(ActionId::new("Attack"), Box::new(|| Box::new(DirectAttack)))
+}
+
+fn attack(action: &str, performer: MemberIdentifier, target: MemberIdentifier) -> RemoteChoice {
+    RemoteChoice {
+        action: ActionId::new(action),
+        performers: Target::Single(performer),
+        targets: Target::Single(target),
+    }
+}
+
+#[test]
+fn remote_choices_are_resolved() {
+    let asked = Rc::new(RefCell::new(Vec::new()));
+    let transport = Loopback {
+        choices: VecDeque::from([Ok(attack("Attack", PICCO, BACCO))]),
+        asked: asked.clone(),
+    };
+    let controller = RemoteController::new(registry(), transport, idle);
+
+    let mut battle = last_team_standing(duel(), controller)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(*asked.borrow(), [Some(PICCO)]);
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn unknown_actions_use_the_fallback() {
+    let transport = Loopback {
+        choices: VecDeque::from([Ok(attack("Fireball", PICCO, BACCO))]),
+        asked: Rc::default(),
+    };
+    let controller = RemoteController::new(registry(), transport, idle);
+
+    let mut battle = last_team_standing(duel(), controller)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
+
+#[test]
+fn closed_connections_use_the_fallback() {
+    let asked = Rc::new(RefCell::new(Vec::new()));
+    let transport = Loopback {
+        choices: VecDeque::new(),
+        asked: asked.clone(),
+    };
+    let controller = RemoteController::new(registry(), transport, idle);
+
+    let mut battle = last_team_standing(duel(), controller)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    // The controller keeps asking the peer, in case the connection comes back.
+    assert_eq!(asked.borrow().len(), 2);
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
+
+#[test]
+fn json_lines_travel_over_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let peer = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut peer = JsonLines::new(stream);
+
+        let view: BattleView<String> = peer.read().unwrap();
+        let performer = view.performer.unwrap();
+        peer.write(&attack("Attack", performer, BACCO)).unwrap();
+
+        view
+    });
+
+    let mut transport = JsonLines::new(TcpStream::connect(address).unwrap());
+    let view = BattleView {
+        performer: Some(PICCO),
+        teams: vec![
+            Team::new(String::from("Heroes"), vec![String::from("Picco")]),
+            Team::new(String::from("Villains"), vec![String::from("Bacco")]),
+        ],
+    };
+
+    transport.send(&view).unwrap();
+    let choice = Transport::<String>::receive(&mut transport).unwrap();
+
+    assert_eq!(choice, attack("Attack", PICCO, BACCO));
+    assert_eq!(peer.join().unwrap(), view);
+
+    // Once the peer hangs up, the connection is reported as closed.
+    assert!(matches!(
+        Transport::<String>::receive(&mut transport),
+        Err(NetworkError::Closed)
+    ));
+}
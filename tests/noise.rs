@@ -0,0 +1,97 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::{Battle, Builder, EndCondition};
+use fierceful_atto::catalogue::actions::{DirectAttack, WithNoise};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::reinforcement::{Reinforcement, Trigger};
+use fierceful_atto::team::Team;
+
+use common::{health, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Picco stabs Bacco making some noise, while Bacco silently fights back.
+fn builder(end_condition: EndCondition) -> Builder<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(String::from("Guards"), vec![Fighter::new("Bacco", 100, 1)]),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, performer, _| match performer {
+        Some(PICCO) => (
+            Box::new(WithNoise {
+                action: DirectAttack,
+                noise: 5,
+            }),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        ),
+        _ => (
+            Box::new(DirectAttack),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        ),
+    });
+
+    Builder::new(teams, None, controller, end_condition).set_seed(0)
+}
+
+fn noisy_turns(battle: &Battle<Fighter>) -> usize {
+    battle
+        .events()
+        .events()
+        .iter()
+        .filter(|e| matches!(e, Event::NoiseMade { amount: 5, .. }))
+        .count()
+}
+
+#[test]
+fn noisy_actions_raise_the_noise_of_the_battle() {
+    let mut battle = builder(EndCondition::LastTeamStanding).build().unwrap();
+
+    for _ in 0..4 {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(battle.noise(), 5 * noisy_turns(&battle) as u64);
+    assert!(battle.noise() > 0);
+}
+
+#[test]
+fn noise_calls_for_reinforcements() {
+    let mut battle = builder(EndCondition::LastTeamStanding)
+        .add_reinforcement(Reinforcement::new(
+            1,
+            Trigger::Noise { threshold: 10 },
+            Box::new(|_| vec![Fighter::new("Lillo", 100, 1)]),
+        ))
+        .build()
+        .unwrap();
+
+    while battle.noise() < 10 {
+        assert_eq!(battle.teams()[1].member_list().len(), 1);
+
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(battle.teams()[1].member_list().len(), 2);
+}
+
+#[test]
+fn raising_the_alarm_ends_undetected_battles() {
+    let mut battle = builder(EndCondition::Undetected { noise_limit: 15 })
+        .build()
+        .unwrap();
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    // Bacco is still standing, but the noise was enough to end the battle.
+    assert_eq!(noisy_turns(&battle), 3);
+    assert_eq!(battle.noise(), 15);
+    assert!(health(battle.teams(), 1, 0) > 0);
+}
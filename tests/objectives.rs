@@ -0,0 +1,134 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::objective::{Objective, ObjectiveStatus};
+use fierceful_atto::outcome::BattleOutcome;
+use fierceful_atto::team::Team;
+use fierceful_atto::terrain::StructureId;
+
+use common::{health, last_team_standing, Fighter};
+
+const CART: StructureId = StructureId(0);
+
+/// Picco escorts a damaged cart, while two harmless slimes try to destroy it.
+fn escort() -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 10, 0), Fighter::new("Tacco", 30, 0)],
+        ),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|teams, performer, _| {
+        let performer = performer.unwrap_or_default();
+        let enemies = 1 - performer.team_id;
+        let target = teams[enemies]
+            .member_list()
+            .iter()
+            .position(|m| m.health() > 0)
+            .unwrap_or_default();
+
+        (
+            Box::new(DirectAttack),
+            Target::Single(performer),
+            Target::Single(MemberIdentifier::new(enemies, target)),
+        )
+    });
+
+    last_team_standing(teams, controller)
+        .set_seed(0)
+        .add_structure(String::from("Cart"), 50)
+        .add_battle_start_hook(Box::new(|state, _| {
+            state.apply(Event::StructureDamaged {
+                structure: CART,
+                amount: 20,
+            })
+        }))
+        .add_objective(0, Objective::DefeatTeam { team_id: 1 })
+        .add_objective(0, Objective::ProtectStructure(CART))
+        .add_objective(1, Objective::DestroyStructure(CART))
+        .add_objective(1, Objective::Survive { team_id: 1 })
+        .build()
+        .unwrap()
+}
+
+fn statuses(outcome: &BattleOutcome, team_id: usize) -> Vec<ObjectiveStatus> {
+    outcome.team_objectives(team_id).map(|r| r.status).collect()
+}
+
+#[test]
+fn objectives_report_their_progress() {
+    let mut battle = escort();
+
+    while health(battle.teams(), 1, 0) > 0 {
+        battle.play_turn().unwrap();
+    }
+
+    let outcome = battle.outcome();
+
+    assert_eq!(
+        statuses(&outcome, 0),
+        [
+            ObjectiveStatus::Partial {
+                progress: 1,
+                goal: 2
+            },
+            ObjectiveStatus::Partial {
+                progress: 30,
+                goal: 50
+            },
+        ]
+    );
+    assert_eq!(
+        statuses(&outcome, 1),
+        [
+            ObjectiveStatus::Partial {
+                progress: 20,
+                goal: 50
+            },
+            ObjectiveStatus::Partial {
+                progress: 1,
+                goal: 2
+            },
+        ]
+    );
+}
+
+#[test]
+fn objectives_are_reported_per_team() {
+    let mut battle = escort();
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    let outcome = battle.outcome();
+
+    // The heroes won the fight, but the slimes still managed to damage the cart.
+    assert_eq!(outcome.surviving_teams, [0]);
+    assert_eq!(
+        statuses(&outcome, 0),
+        [
+            ObjectiveStatus::Succeeded,
+            ObjectiveStatus::Partial {
+                progress: 30,
+                goal: 50
+            },
+        ]
+    );
+    assert_eq!(
+        statuses(&outcome, 1),
+        [
+            ObjectiveStatus::Partial {
+                progress: 20,
+                goal: 50
+            },
+            ObjectiveStatus::Failed,
+        ]
+    );
+}
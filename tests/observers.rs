@@ -0,0 +1,59 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::state::BattleState;
+
+use common::scenario::Scenario;
+use common::Fighter;
+
+#[test]
+fn observers_see_every_event_in_order() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let observed = Rc::clone(&seen);
+
+    let mut battle = Scenario::load("duel")
+        .builder()
+        .add_observer(Box::new(move |event: &Event, _: &BattleState<Fighter>| {
+            observed.borrow_mut().push(event.clone());
+        }))
+        .build()
+        .unwrap();
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(seen.borrow().as_slice(), battle.events().events());
+}
+
+#[test]
+fn observers_see_applied_effects() {
+    let dummy = MemberIdentifier::new(1, 0);
+    let healths = Rc::new(RefCell::new(Vec::new()));
+    let observed = Rc::clone(&healths);
+
+    let mut battle = Scenario::load("duel")
+        .builder()
+        .add_observer(Box::new(
+            move |event: &Event, state: &BattleState<Fighter>| {
+                if let Event::Damaged { member, .. } = event {
+                    if *member == dummy {
+                        let health = state.teams()[1].member(0).unwrap().health();
+                        observed.borrow_mut().push(health);
+                    }
+                }
+            },
+        ))
+        .build()
+        .unwrap();
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(healths.borrow().as_slice(), &[20, 10, 0]);
+}
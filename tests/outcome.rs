@@ -0,0 +1,68 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::outcome::Winner;
+use fierceful_atto::team::Team;
+
+use common::{duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Picco keeps attacking Bacco, whoever performs.
+fn attack_bacco() -> ChoiceCallback<Fighter> {
+    Box::new(|_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        )
+    })
+}
+
+#[test]
+fn the_last_team_standing_wins() {
+    let mut battle = last_team_standing(duel(), attack_bacco())
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+    assert_eq!(battle.outcome().winner, Winner::Undecided);
+
+    let (teams, outcome) = battle.run_with_outcome().unwrap();
+
+    assert_eq!(outcome.winner, Winner::Team(0));
+    assert_eq!(outcome.turns, 10);
+    assert_eq!(outcome.surviving_members().collect::<Vec<_>>(), [PICCO]);
+    assert_eq!(health(&teams, 1, 0), 0);
+}
+
+#[test]
+fn battles_without_survivors_are_draws() {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 10, 10)]),
+        Team::new(String::from("Slimes"), vec![Fighter::new("Bacco", 10, 0)]),
+    ];
+
+    // Picco takes Bacco down with a self-destructing attack.
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            Target::DiscreteMultiple(vec![PICCO, BACCO]),
+        )
+    });
+
+    let battle = last_team_standing(teams, controller)
+        .set_seed(0)
+        .build()
+        .unwrap();
+    let (_, outcome) = battle.run_with_outcome().unwrap();
+
+    assert_eq!(outcome.winner, Winner::Draw);
+    assert!(outcome.surviving_teams.is_empty());
+    assert_eq!(outcome.surviving_members().count(), 0);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use fierceful_atto::battle::{Battle, BattleStep};
+use fierceful_atto::error::BattleError;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::MemberIdentifier;
+
+use common::{attack_first, duel, health, last_team_standing, Fighter};
+
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn battle() -> Battle<Fighter> {
+    last_team_standing(duel(), attack_first)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn paused_battles_refuse_to_advance() {
+    let mut battle = battle();
+
+    battle.play_turn().unwrap();
+
+    assert!(battle.pause());
+    assert!(!battle.pause());
+    assert!(battle.is_paused());
+    assert_eq!(battle.play_turn().unwrap_err(), BattleError::Paused);
+    assert_eq!(battle.step().unwrap(), BattleStep::Paused);
+    assert!(battle.choosing_teams().is_empty());
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+
+    assert!(battle.resume());
+    assert!(!battle.resume());
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 80);
+
+    let events = battle.events().events();
+    let paused = events.iter().position(|e| *e == Event::BattlePaused);
+    let resumed = events.iter().position(|e| *e == Event::BattleResumed);
+
+    assert!(paused.is_some() && paused < resumed);
+}
+
+#[test]
+fn battles_can_be_paused_before_starting() {
+    let mut battle = battle();
+
+    battle.pause();
+    assert!(battle.play_turn().is_err());
+    assert!(!battle
+        .events()
+        .events()
+        .contains(&Event::BattleStarted { seed: 0 }));
+
+    battle.resume();
+    battle.play_turn().unwrap();
+
+    assert!(battle
+        .events()
+        .events()
+        .contains(&Event::BattleStarted { seed: 0 }));
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn paused_battles_still_finish() {
+    let mut battle = battle();
+
+    battle.play_turn().unwrap();
+    battle.pause();
+    battle.remove_member(BACCO);
+
+    assert!(battle.is_finished());
+    assert!(!battle.resume());
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::{Battle, Builder, EndCondition, StartupInfo};
+use fierceful_atto::catalogue::actions::{TaggingAttack, UseItem};
+use fierceful_atto::catalogue::items::Potion;
+use fierceful_atto::component::Tag;
+use fierceful_atto::inventory::{Inventory, InventoryOwner, ItemId};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::team::Team;
+
+use common::Fighter;
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const NOCCO: MemberIdentifier = MemberIdentifier::new(0, 1);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Bacco poisons Picco every turn, while the heroes share a bag with two potions.
+fn battle() -> Battle<Fighter> {
+    let mut bag = Inventory::new();
+    bag.add(ItemId::new("Potion"), 2);
+
+    let teams = vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![
+                Fighter::new("Picco", 100, 10),
+                Fighter::new("Nocco", 100, 10),
+            ],
+        )
+        .with_inventory(bag),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 1000, 30)],
+        ),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(TaggingAttack {
+                tag: Tag::new("Poisoned"),
+            }),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        )
+    });
+
+    Builder::new(
+        teams,
+        Some(StartupInfo::new().with_first_performer(BACCO)),
+        controller,
+        EndCondition::LastTeamStanding,
+    )
+    .set_seed(0)
+    .add_items(InventoryOwner::Member(PICCO), ItemId::new("Potion"), 1)
+    .build()
+    .unwrap()
+}
+
+fn drink(battle: &mut Battle<Fighter>, performer: MemberIdentifier) {
+    battle.queue_action((
+        Box::new(UseItem {
+            item: Box::new(Potion::new(25)),
+        }),
+        Target::Single(performer),
+        Target::Single(PICCO),
+    ));
+}
+
+#[test]
+fn deltas_report_what_persists() {
+    let mut battle = battle();
+    let potion = ItemId::new("Potion");
+
+    battle.play_turn().unwrap();
+    drink(&mut battle, PICCO);
+    drink(&mut battle, NOCCO);
+    battle.play_turn().unwrap();
+
+    let delta = battle.delta();
+    let picco = delta.member(battle.handle(PICCO).unwrap()).unwrap();
+
+    assert_eq!(picco.health, 70);
+    assert!(!picco.defeated);
+    assert_eq!(picco.properties.health, 70);
+    assert_eq!(picco.tags, [(Tag::new("Poisoned"), 2)]);
+    assert_eq!(picco.items_consumed.count(&potion), 1);
+    assert!(!picco.inventory.contains(&potion));
+
+    let nocco = delta.member(battle.handle(NOCCO).unwrap()).unwrap();
+    assert!(nocco.tags.is_empty());
+    assert_eq!(nocco.items_consumed.count(&potion), 0);
+
+    assert_eq!(delta.teams[0].items_consumed.count(&potion), 1);
+    assert_eq!(delta.teams[0].inventory.count(&potion), 1);
+    assert_eq!(delta.teams[1].items_consumed.items().count(), 0);
+}
+
+#[test]
+fn departed_members_are_not_reported() {
+    let mut battle = battle();
+
+    battle.play_turn().unwrap();
+    drink(&mut battle, PICCO);
+    battle.play_turn().unwrap();
+
+    let nocco = battle.handle(NOCCO).unwrap();
+    let picco = battle.handle(PICCO).unwrap();
+    battle.remove_member(PICCO).unwrap();
+
+    let delta = battle.delta();
+
+    assert_eq!(delta.members.len(), 2);
+    assert!(delta.member(picco).is_none());
+    assert_eq!(delta.member(nocco).unwrap().member, PICCO);
+    assert_eq!(
+        battle
+            .battle_state()
+            .consumed_items(InventoryOwner::Member(PICCO)),
+        None
+    );
+}
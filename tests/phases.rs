@@ -0,0 +1,91 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::{Builder, TurnPhase};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::MemberIdentifier;
+
+use common::{duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Picco always attacks Bacco, recording when the choice is made.
+fn builder(log: Rc<RefCell<Vec<(TurnPhase, u64)>>>) -> Builder<Fighter> {
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        )
+    });
+
+    let mut builder = last_team_standing(duel(), controller).set_seed(0);
+
+    for phase in [
+        TurnPhase::EndOfTurn,
+        TurnPhase::ChooseAction,
+        TurnPhase::UpkeepStart,
+        TurnPhase::ResolveAction,
+    ] {
+        let log = log.clone();
+
+        builder = builder.add_phase_hook(
+            phase,
+            Box::new(move |_, turn_number| log.borrow_mut().push((phase, turn_number))),
+        );
+    }
+
+    builder
+}
+
+#[test]
+fn phases_run_in_order_every_turn() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut battle = builder(log.clone()).build().unwrap();
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    let expected: Vec<_> = [1, 2]
+        .into_iter()
+        .flat_map(|turn| {
+            [
+                (TurnPhase::UpkeepStart, turn),
+                (TurnPhase::ChooseAction, turn),
+                (TurnPhase::ResolveAction, turn),
+                (TurnPhase::EndOfTurn, turn),
+            ]
+        })
+        .collect();
+
+    assert_eq!(*log.borrow(), expected);
+}
+
+#[test]
+fn upkeep_hooks_apply_events_before_actions() {
+    // Bacco regenerates at the start of every turn, but never above its maximum health.
+    let mut battle = builder(Rc::default())
+        .add_phase_hook(
+            TurnPhase::UpkeepStart,
+            Box::new(|state, _| {
+                state.apply(Event::Healed {
+                    member: BACCO,
+                    amount: 5,
+                })
+            }),
+        )
+        .build()
+        .unwrap();
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    // Every hit deals 10 damage, while the first regeneration happens at full health.
+    assert_eq!(health(battle.teams(), 1, 0), 80);
+}
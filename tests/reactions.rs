@@ -0,0 +1,139 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fierceful_atto::action::{Action, ChoiceCallback, ChoiceReturn, Target};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::{ActionSummary, Event};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::reaction::{self, ReactionHandler, ReactionScope};
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Two teams of one member each, both able to hit back.
+fn teams() -> Vec<Team<Fighter>> {
+    vec![
+        Team::new(String::from("Blue"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(String::from("Red"), vec![Fighter::new("Bacco", 100, 5)]),
+    ]
+}
+
+fn attack(performer: MemberIdentifier, target: MemberIdentifier) -> ChoiceReturn<Fighter> {
+    (
+        Box::new(DirectAttack),
+        Target::Single(performer),
+        Target::Single(target),
+    )
+}
+
+fn picco_attacks_bacco() -> ChoiceCallback<Fighter> {
+    Box::new(|_, _, _| attack(PICCO, BACCO))
+}
+
+/// Reaction hitting back the member of the other team.
+fn counter() -> ReactionHandler<Fighter> {
+    Box::new(|member, _, _| Some(attack(member, MemberIdentifier::new(1 - member.team_id, 0))))
+}
+
+#[test]
+fn counter_actions_resolve_right_away() {
+    let mut battle = last_team_standing(teams(), picco_attacks_bacco())
+        .add_reaction(ReactionScope::Team { team_id: 1 }, counter())
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+    assert_eq!(health(battle.teams(), 0, 0), 95);
+}
+
+#[test]
+fn counter_actions_trigger_no_reactions() {
+    let mut battle = last_team_standing(teams(), picco_attacks_bacco())
+        .add_reaction(ReactionScope::Team { team_id: 0 }, counter())
+        .add_reaction(ReactionScope::Member(BACCO), counter())
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+    assert_eq!(health(battle.teams(), 0, 0), 95);
+}
+
+#[test]
+fn handlers_receive_the_events_of_the_action() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let record = seen.clone();
+
+    let mut battle = last_team_standing(teams(), picco_attacks_bacco())
+        .add_reaction(
+            ReactionScope::Member(BACCO),
+            Box::new(move |member, events, _| {
+                record.borrow_mut().push((member, events.to_vec()));
+
+                None
+            }),
+        )
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(
+        *seen.borrow(),
+        [(
+            BACCO,
+            vec![
+                Event::ActionPerformed {
+                    action: Action::<Fighter>::action_id(&DirectAttack),
+                    performers: vec![PICCO],
+                },
+                Event::Targeted { member: BACCO },
+                Event::Damaged {
+                    member: BACCO,
+                    amount: 10
+                },
+                Event::ActionResolved {
+                    action: Action::<Fighter>::action_id(&DirectAttack),
+                    summary: ActionSummary {
+                        damage: 10,
+                        ..Default::default()
+                    },
+                },
+            ]
+        )]
+    );
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+}
+
+#[test]
+fn scopes_cover_members_or_teams() {
+    assert!(ReactionScope::Member(BACCO).contains(BACCO));
+    assert!(!ReactionScope::Member(BACCO).contains(PICCO));
+    assert!(ReactionScope::Team { team_id: 0 }.contains(PICCO));
+    assert!(!ReactionScope::Team { team_id: 0 }.contains(BACCO));
+}
+
+#[test]
+fn affected_members_are_listed_once() {
+    let events = [
+        Event::Targeted { member: BACCO },
+        Event::Damaged {
+            member: BACCO,
+            amount: 10,
+        },
+        Event::Damaged {
+            member: PICCO,
+            amount: 5,
+        },
+    ];
+
+    assert_eq!(reaction::affected_members(&events), [BACCO, PICCO]);
+}
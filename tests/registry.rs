@@ -0,0 +1,124 @@
+mod common;
+
+use fierceful_atto::action::{ActionId, Target};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::catalogue::pattern::{
+    Pattern, PatternCondition, PatternController, PatternStep, PatternTarget,
+};
+use fierceful_atto::error::PatternError;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::registry::ActionRegistry;
+use fierceful_atto::rng::BattleRng;
+
+use common::{duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn attack() -> ActionId {
+    ActionId::new("attack")
+}
+
+fn registry() -> ActionRegistry<Fighter> {
+    ActionRegistry::new().with(attack(), Box::new(|| Box::new(DirectAttack)))
+}
+
+/// Controller making Picco follow a pattern, while every other member does nothing.
+fn controller(pattern: Pattern) -> PatternController<Fighter> {
+    PatternController::new(
+        registry(),
+        Box::new(|_, performer, _| {
+            (
+                Box::new(DirectAttack),
+                Target::None,
+                Target::Single(performer.unwrap_or_default()),
+            )
+        }),
+    )
+    .with_pattern(PICCO, pattern)
+    .unwrap()
+}
+
+#[test]
+fn registry_creates_registered_actions() {
+    let registry = registry();
+
+    assert!(registry.contains(&attack()));
+    assert!(registry.create(&attack()).is_some());
+    assert!(registry.create(&ActionId::new("missing")).is_none());
+    assert_eq!(registry.ids().collect::<Vec<_>>(), [&attack()]);
+}
+
+#[test]
+fn patterns_are_validated_against_the_registry() {
+    let unknown = Pattern::new(vec![PatternStep::use_action(
+        ActionId::new("missing"),
+        PatternTarget::Performer,
+    )]);
+
+    assert_eq!(
+        Pattern::default().validate(&registry()),
+        Err(PatternError::Empty)
+    );
+    assert_eq!(
+        unknown.validate(&registry()),
+        Err(PatternError::UnknownAction(ActionId::new("missing")))
+    );
+    assert!(
+        PatternController::new(registry(), Box::new(|_, _, _| unreachable!()))
+            .with_pattern(PICCO, unknown)
+            .is_err()
+    );
+}
+
+#[test]
+fn members_follow_their_pattern_in_a_loop() {
+    let pattern = Pattern::new(vec![
+        PatternStep::use_action(attack(), PatternTarget::WeakestEnemy),
+        PatternStep::use_action(attack(), PatternTarget::Performer),
+    ]);
+
+    let mut battle = last_team_standing(duel(), controller(pattern).into_callback())
+        .build()
+        .unwrap();
+
+    let mut seen = Vec::new();
+
+    for _ in 0..6 {
+        battle.play_turn().unwrap();
+        seen.push((health(battle.teams(), 0, 0), health(battle.teams(), 1, 0)));
+    }
+
+    // Picco alternates between hitting Bacco and itself, while Bacco is left to the fallback.
+    assert_eq!(
+        seen,
+        [(100, 90), (100, 90), (90, 90), (90, 90), (90, 80), (90, 80)]
+    );
+}
+
+#[test]
+fn branches_follow_the_performer_health() {
+    let pattern = Pattern::new(vec![PatternStep::branch(
+        PatternCondition::HealthBelow(50),
+        PatternStep::use_action(attack(), PatternTarget::Performer),
+        PatternStep::use_action(attack(), PatternTarget::EnemyTeam),
+    )]);
+
+    let controller = controller(pattern);
+    let mut rng = BattleRng::from_seed(0);
+    let mut teams = duel();
+
+    let (_, performers, targets) = controller.choose(&teams, Some(PICCO), &mut rng);
+    assert_eq!(performers, Target::Single(PICCO));
+    assert_eq!(targets, Target::FullTeam { team_id: 1 });
+
+    teams[0].member_mut(0).unwrap().damage(60);
+
+    let (_, _, targets) = controller.choose(&teams, Some(PICCO), &mut rng);
+    assert_eq!(targets, Target::Single(PICCO));
+
+    // Members without a pattern are left to the fallback callback.
+    let (_, performers, targets) = controller.choose(&teams, Some(BACCO), &mut rng);
+    assert_eq!(performers, Target::None);
+    assert_eq!(targets, Target::Single(BACCO));
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, ChoiceReturn, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::{DirectAttack, WithCost};
+use fierceful_atto::equipment::Equipment;
+use fierceful_atto::error::{ActionError, BattleError};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier, Properties, Statistics};
+use fierceful_atto::resource::{Cost, Resource};
+use fierceful_atto::team::Team;
+
+use common::last_team_standing;
+
+const CASTER: MemberIdentifier = MemberIdentifier::new(0, 0);
+const DUMMY: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Member owning a mana pool, but no stamina.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Mage {
+    stats: MageStats,
+    props: MageProps,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MageStats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MageProps {
+    health: u64,
+    attack: u64,
+    mana: u64,
+}
+
+struct NoGear;
+
+impl Mage {
+    fn new(attack: u64, mana: u64) -> Self {
+        Self {
+            stats: MageStats,
+            props: MageProps {
+                health: 100,
+                attack,
+                mana,
+            },
+        }
+    }
+}
+
+impl Member for Mage {
+    type Statistics = MageStats;
+    type Properties = MageProps;
+    type Equipment = NoGear;
+
+    fn name(&self) -> &str {
+        "Mage"
+    }
+
+    fn statistics(&self) -> &MageStats {
+        &self.stats
+    }
+
+    fn member_properties(&self) -> &MageProps {
+        &self.props
+    }
+
+    fn member_properties_mut(&mut self) -> &mut MageProps {
+        &mut self.props
+    }
+
+    fn equipment(&self) -> &NoGear {
+        &NoGear
+    }
+}
+
+impl Statistics for MageStats {
+    fn reference_health(&self) -> u64 {
+        100
+    }
+
+    fn base_attack(&self) -> u64 {
+        0
+    }
+}
+
+impl Properties for MageProps {
+    fn health(&self) -> u64 {
+        self.health
+    }
+
+    fn health_mut(&mut self) -> &mut u64 {
+        &mut self.health
+    }
+
+    fn attack(&self) -> u64 {
+        self.attack
+    }
+
+    fn resource(&self, resource: Resource) -> Option<u64> {
+        match resource {
+            Resource::Mana => Some(self.mana),
+            _ => None,
+        }
+    }
+
+    fn resource_mut(&mut self, resource: Resource) -> Option<&mut u64> {
+        match resource {
+            Resource::Mana => Some(&mut self.mana),
+            _ => None,
+        }
+    }
+}
+
+impl Equipment for NoGear {
+    type Properties = MageProps;
+
+    fn associated_properties(&self) -> MageProps {
+        MageProps {
+            health: 0,
+            attack: 0,
+            mana: 0,
+        }
+    }
+}
+
+fn battle(mana: u64, cost: Cost) -> Battle<Mage> {
+    let teams = vec![
+        Team::new(String::from("Casters"), vec![Mage::new(10, mana)]),
+        Team::new(String::from("Dummies"), vec![Mage::new(0, 0)]),
+    ];
+
+    let controller: ChoiceCallback<Mage> = Box::new(move |_, _, _| -> ChoiceReturn<Mage> {
+        (
+            Box::new(WithCost {
+                action: DirectAttack,
+                costs: vec![cost],
+            }),
+            Target::Single(CASTER),
+            Target::Single(DUMMY),
+        )
+    });
+
+    last_team_standing(teams, controller).build().unwrap()
+}
+
+fn mana(battle: &Battle<Mage>) -> u64 {
+    battle.teams()[0]
+        .member(0)
+        .unwrap()
+        .member_properties()
+        .mana
+}
+
+#[test]
+fn cost_is_spent_before_acting() {
+    let mut battle = battle(12, Cost::new(Resource::Mana, 5));
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(mana(&battle), 7);
+    assert_eq!(battle.teams()[1].member(0).unwrap().health(), 90);
+    assert!(battle.events().events().contains(&Event::ResourceSpent {
+        member: CASTER,
+        resource: Resource::Mana,
+        amount: 5,
+    }));
+}
+
+#[test]
+fn unaffordable_actions_are_rejected() {
+    let mut battle = battle(4, Cost::new(Resource::Mana, 5));
+
+    assert_eq!(
+        battle.play_turn(),
+        Err(BattleError::InvalidAction(ActionError::Unaffordable {
+            member: CASTER,
+            cost: Cost::new(Resource::Mana, 5),
+        }))
+    );
+    assert_eq!(mana(&battle), 4);
+    assert_eq!(battle.teams()[1].member(0).unwrap().health(), 100);
+}
+
+#[test]
+fn missing_pools_cannot_pay() {
+    let mut battle = battle(100, Cost::new(Resource::Stamina, 1));
+
+    assert!(matches!(
+        battle.play_turn(),
+        Err(BattleError::InvalidAction(ActionError::Unaffordable { .. }))
+    ));
+}
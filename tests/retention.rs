@@ -0,0 +1,89 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::{Event, Retention};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Picco slowly chips away at Bacco, for a hundred turns.
+fn long_battle(retention: Retention) -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 1)]),
+        Team::new(String::from("Slimes"), vec![Fighter::new("Bacco", 100, 0)]),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        )
+    });
+
+    last_team_standing(teams, controller)
+        .set_seed(0)
+        .set_retention(retention)
+        .build()
+        .unwrap()
+}
+
+fn play_to_the_end(battle: &mut Battle<Fighter>) {
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+}
+
+#[test]
+fn logs_are_complete_by_default() {
+    let mut battle = long_battle(Retention::Full);
+    play_to_the_end(&mut battle);
+
+    assert!(battle.events().is_complete());
+    assert_eq!(battle.events().events().len(), battle.events().len());
+}
+
+#[test]
+fn logs_are_truncated_past_the_limit() {
+    let mut battle = long_battle(Retention::MaxEvents(40));
+    play_to_the_end(&mut battle);
+
+    let log = battle.events();
+    assert!(log.events().len() <= 40);
+    assert_eq!(log.dropped() + log.events().len(), log.len());
+    assert!(!log.is_complete());
+    assert!(log
+        .events()
+        .iter()
+        .any(|e| matches!(e, Event::LogTruncated { .. })));
+
+    // The battle plays out the same, and the outcome still knows how many turns were played.
+    assert_eq!(health(battle.teams(), 1, 0), 0);
+    assert_eq!(battle.outcome().turns, 100);
+}
+
+#[test]
+fn only_the_current_turn_can_be_kept() {
+    let mut battle = long_battle(Retention::CurrentTurn);
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    let events = battle.events().events();
+    assert!(matches!(events[0], Event::LogTruncated { .. }));
+    assert_eq!(events[1], Event::TurnStarted { turn_number: 3 });
+    assert_eq!(
+        events
+            .iter()
+            .filter(|e| matches!(e, Event::TurnStarted { .. }))
+            .count(),
+        1
+    );
+}
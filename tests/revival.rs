@@ -0,0 +1,176 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, Context, Target};
+use fierceful_atto::battle::{Battle, Builder};
+use fierceful_atto::catalogue::actions::{DirectAttack, Revive};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::round::RoundOrder;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+const LILLO: MemberIdentifier = MemberIdentifier::new(1, 1);
+
+/// Heals every target by 20.
+struct Mend;
+
+impl Action<Fighter> for Mend {
+    fn act(&mut self, mut context: Context<'_, Fighter>) -> ActionState {
+        for id in context.target_ids() {
+            context.heal(id, 20);
+        }
+
+        ActionState::Finished
+    }
+}
+
+/// Nobody acts unless an action is queued, and Bacco is defeated during the first turn.
+fn builder() -> Builder<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 50)]),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 50, 1), Fighter::new("Lillo", 100, 1)],
+        ),
+    ];
+
+    let controller: ChoiceCallback<Fighter> =
+        Box::new(|_, _, _| (Box::new(DirectAttack), Target::None, Target::None));
+
+    last_team_standing(teams, controller)
+        .set_seed(0)
+        .set_revival_window(2)
+        .queue_action((
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        ))
+}
+
+fn battle() -> Battle<Fighter> {
+    builder().build().unwrap()
+}
+
+fn countdowns(battle: &Battle<Fighter>) -> Vec<u32> {
+    battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::RevivalCountdown { remaining, .. } => Some(*remaining),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn defeated_members_leave_once_the_window_is_over() {
+    let mut battle = battle();
+
+    battle.play_turn().unwrap();
+    assert_eq!(battle.revival_countdown(BACCO), Some(2));
+
+    battle.play_turn().unwrap();
+    assert_eq!(battle.revival_countdown(BACCO), Some(1));
+
+    battle.play_turn().unwrap();
+    assert_eq!(countdowns(&battle), [2, 1, 0]);
+
+    // Lillo takes the place of Bacco, who is gone for good.
+    assert_eq!(battle.teams()[1].member_list().len(), 1);
+    assert_eq!(battle.revival_countdown(BACCO), None);
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+    assert!(battle
+        .events()
+        .events()
+        .contains(&Event::MemberLeft { member: BACCO }));
+}
+
+#[test]
+fn members_revived_in_time_stay() {
+    let mut battle = battle();
+
+    battle.play_turn().unwrap();
+    battle.queue_action((
+        Box::new(Revive { percent: 40 }),
+        Target::Single(LILLO),
+        Target::Single(BACCO),
+    ));
+    battle.play_turn().unwrap();
+
+    assert_eq!(battle.revival_countdown(BACCO), None);
+    assert!(battle
+        .events()
+        .events()
+        .contains(&Event::MemberRevived { member: BACCO }));
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(countdowns(&battle), [2]);
+    assert_eq!(health(battle.teams(), 1, 0), 20);
+}
+
+#[test]
+fn defeated_members_cannot_be_healed() {
+    let mut battle = battle();
+
+    battle.play_turn().unwrap();
+    battle.queue_action((Box::new(Mend), Target::Single(LILLO), Target::Single(BACCO)));
+    battle.play_turn().unwrap();
+
+    assert!(battle.teams()[1].member(0).unwrap().is_defeated());
+    assert_eq!(battle.revival_countdown(BACCO), Some(1));
+}
+
+#[test]
+fn reviving_needs_a_defeated_target() {
+    let mut battle = battle();
+
+    battle.play_turn().unwrap();
+    battle.queue_action((
+        Box::new(Revive { percent: 40 }),
+        Target::Single(BACCO),
+        Target::Single(LILLO),
+    ));
+    battle.play_turn().unwrap();
+
+    // The action is rejected before being performed.
+    let revive = Action::<Fighter>::action_id(&Revive { percent: 40 });
+
+    assert_eq!(health(battle.teams(), 1, 1), 100);
+    assert!(!battle.events().events().iter().any(|e| matches!(
+        e,
+        Event::ActionPerformed { action, .. } if *action == revive
+    )));
+}
+
+#[test]
+fn rounds_count_down_the_window() {
+    let mut battle = builder()
+        .set_round_order(RoundOrder::TeamByTeam)
+        .build()
+        .unwrap();
+
+    // The queued attack opens the battle, and the first round only ends with the second turn.
+    battle.play_turn().unwrap();
+    assert_eq!(battle.revival_countdown(BACCO), None);
+
+    battle.play_turn().unwrap();
+    assert_eq!(battle.revival_countdown(BACCO), Some(2));
+
+    // Picco and Lillo take two turns to end every round.
+    battle.play_turn().unwrap();
+    assert_eq!(battle.revival_countdown(BACCO), Some(2));
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(countdowns(&battle), [2, 1, 0]);
+    assert_eq!(battle.teams()[1].member_list().len(), 1);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::member::{Levelable, Member, MemberIdentifier};
+use fierceful_atto::team::Team;
+
+use common::{last_team_standing, Fighter};
+
+const LILLO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 1);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Every performer attacks the first alive member of the other team, so Lillo falls first.
+fn battle() -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![
+                Fighter::new("Lillo", 1, 1).with_experience_yield(1000),
+                Fighter::new("Picco", 100, 50),
+            ],
+        ),
+        Team::new(
+            String::from("Slimes"),
+            vec![
+                Fighter::new("Bacco", 150, 1).with_experience_yield(150),
+                Fighter::new("Tacco", 50, 1).with_experience_yield(30),
+            ],
+        ),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|teams, performer, _| {
+        let performer = performer.unwrap_or_default();
+        let enemies = 1 - performer.team_id;
+        let target = teams[enemies]
+            .member_list()
+            .iter()
+            .position(|m| m.health() > 0)
+            .unwrap_or_default();
+
+        (
+            Box::new(DirectAttack),
+            Target::Single(performer),
+            Target::Single(MemberIdentifier::new(enemies, target)),
+        )
+    });
+
+    last_team_standing(teams, controller)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+fn finished_battle() -> Battle<Fighter> {
+    let mut battle = battle();
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    battle
+}
+
+#[test]
+fn survivors_earn_the_experience_of_defeated_enemies() {
+    let outcome = finished_battle().outcome();
+
+    assert_eq!(outcome.surviving_teams, [0]);
+    assert_eq!(outcome.member(PICCO).unwrap().experience, 180);
+
+    // Defeated members don't earn anything, and their yield doesn't go to their own team.
+    assert_eq!(outcome.member(LILLO).unwrap().experience, 0);
+    assert_eq!(outcome.member(BACCO).unwrap().experience, 0);
+    assert_eq!(outcome.member(PICCO).unwrap().levels_gained, 0);
+}
+
+#[test]
+fn claimed_experience_levels_members_up() {
+    let (teams, outcome) = finished_battle().claim_rewards();
+
+    let picco = teams[0].member(1).unwrap();
+    assert_eq!(picco.level(), 2);
+    assert_eq!(picco.experience(), 80);
+    assert_eq!(picco.statistics().max_health, 110);
+    assert_eq!(outcome.member(PICCO).unwrap().levels_gained, 1);
+
+    let lillo = teams[0].member(0).unwrap();
+    assert_eq!(lillo.level(), 1);
+    assert_eq!(outcome.member(LILLO).unwrap().levels_gained, 0);
+}
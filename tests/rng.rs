@@ -0,0 +1,52 @@
+mod common;
+
+use fierceful_atto::action::ChoiceCallback;
+use fierceful_atto::rng::{derive_seed, BattleRng};
+
+use common::{duel, last_team_standing, Fighter};
+
+fn sequence(rng: &mut BattleRng) -> Vec<u64> {
+    (0..4).map(|_| rng.next_u64()).collect()
+}
+
+#[test]
+fn derived_generators_ignore_previous_draws() {
+    let mut rng = BattleRng::from_seed(42);
+    let fresh = sequence(&mut rng.derive("loot"));
+
+    sequence(&mut rng);
+
+    assert_eq!(sequence(&mut rng.derive("loot")), fresh);
+    assert_eq!(rng.derive("loot").seed(), derive_seed(42, "loot"));
+}
+
+#[test]
+fn namespaces_and_indices_produce_different_seeds() {
+    let rng = BattleRng::from_seed(42);
+
+    assert_ne!(rng.derive("loot").seed(), rng.derive("ai").seed());
+    assert_ne!(
+        rng.derive_indexed("ai", 0).seed(),
+        rng.derive_indexed("ai", 1).seed()
+    );
+    assert_eq!(
+        rng.derive_indexed("ai", 1).seed(),
+        BattleRng::from_seed(42).derive_indexed("ai", 1).seed()
+    );
+    assert_ne!(derive_seed(42, "loot"), derive_seed(43, "loot"));
+}
+
+#[test]
+fn battles_derive_from_their_seed() {
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| unreachable!("no turn is played"));
+
+    let battle = last_team_standing(duel(), controller)
+        .set_seed(7)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        battle.derive_rng("loot").seed(),
+        BattleRng::from_seed(7).derive("loot").seed()
+    );
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::{Builder, PerformerModel, TurnPhase};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::round::RoundOrder;
+use fierceful_atto::team::Team;
+
+use common::{last_team_standing, Fighter};
+
+fn teams() -> Vec<Team<Fighter>> {
+    vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![Fighter::new("Picco", 100, 5), Fighter::new("Pacco", 100, 5)],
+        ),
+        Team::new(
+            String::from("Slimes"),
+            vec![
+                Fighter::new("Bacco", 100, 5),
+                Fighter::new("Tacco", 100, 5),
+                Fighter::new("Zacco", 100, 5),
+            ],
+        ),
+    ]
+}
+
+/// Every performer attacks the first alive member of the other team.
+fn builder(order: RoundOrder) -> Builder<Fighter> {
+    let controller: ChoiceCallback<Fighter> = Box::new(|teams, performer, _| {
+        let performer = performer.unwrap_or_default();
+        let enemy_team = 1 - performer.team_id;
+
+        let target = teams[enemy_team]
+            .member_list()
+            .iter()
+            .position(|m| m.health() > 0)
+            .map_or(Target::None, |member_id| {
+                Target::Single(MemberIdentifier::new(enemy_team, member_id))
+            });
+
+        (Box::new(DirectAttack), Target::None, target)
+    });
+
+    last_team_standing(teams(), controller)
+        .set_seed(0)
+        .set_performer_model(PerformerModel::Solo)
+        .set_round_order(order)
+}
+
+/// Returns the performers of every action, in the order they acted.
+fn performers(events: &[Event]) -> Vec<MemberIdentifier> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            Event::ActionPerformed { performers, .. } => performers.first().copied(),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn every_member_acts_once_per_round() {
+    let mut battle = builder(RoundOrder::Interleaved).build().unwrap();
+
+    for _ in 0..10 {
+        battle.play_turn().unwrap();
+    }
+
+    let id = MemberIdentifier::new;
+    let round = [id(0, 0), id(1, 0), id(0, 1), id(1, 1), id(1, 2)];
+
+    assert_eq!(
+        performers(battle.events().events()),
+        [round, round].concat()
+    );
+    assert_eq!(battle.round_number(), 3);
+
+    let rounds: Vec<_> = battle
+        .events()
+        .events()
+        .iter()
+        .filter(|e| matches!(e, Event::RoundStarted { .. } | Event::RoundEnded { .. }))
+        .cloned()
+        .collect();
+
+    assert_eq!(
+        rounds,
+        [
+            Event::RoundStarted { round: 1 },
+            Event::RoundEnded { round: 1 },
+            Event::RoundStarted { round: 2 },
+            Event::RoundEnded { round: 2 },
+            Event::RoundStarted { round: 3 },
+        ]
+    );
+}
+
+#[test]
+fn random_rounds_shuffle_every_member_once() {
+    let mut battle = builder(RoundOrder::Random).build().unwrap();
+
+    for _ in 0..5 {
+        battle.play_turn().unwrap();
+    }
+
+    let mut acted = performers(battle.events().events());
+    acted.sort();
+
+    let id = MemberIdentifier::new;
+    assert_eq!(acted, [id(0, 0), id(0, 1), id(1, 0), id(1, 1), id(1, 2)]);
+}
+
+#[test]
+fn round_hooks_run_at_round_boundaries() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let start_log = log.clone();
+    let end_log = log.clone();
+
+    let mut battle = builder(RoundOrder::TeamByTeam)
+        .add_phase_hook(
+            TurnPhase::RoundStart,
+            Box::new(move |_, turn| start_log.borrow_mut().push(("start", turn))),
+        )
+        .add_phase_hook(
+            TurnPhase::RoundEnd,
+            Box::new(move |_, turn| end_log.borrow_mut().push(("end", turn))),
+        )
+        .build()
+        .unwrap();
+
+    for _ in 0..6 {
+        battle.play_turn().unwrap();
+    }
+
+    // The first round starts with the battle, and the second one once the first has ended.
+    assert_eq!(*log.borrow(), [("start", 0), ("end", 5), ("start", 5)]);
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "scaffold")]
+
+use std::path::Path;
+
+use fierceful_atto::scaffold::Scaffold;
+
+fn scaffold() -> Scaffold {
+    Scaffold::new("goblin-raid")
+        .with_member_type("Hero")
+        .with_dependency("path = \"../fierceful-atto\"")
+        .with_seed(42)
+        .with_team("Heroes", &[("Picco", 100, 15)])
+        .with_team(
+            "Goblins",
+            &[("Bacco", 60, 10), ("Tacco \"the Tall\"", 70, 8)],
+        )
+}
+
+#[test]
+fn skeletons_are_filled_with_the_parameters() {
+    let files = scaffold().files();
+
+    let contents = |path: &str| {
+        &files
+            .iter()
+            .find(|f| f.path == Path::new(path))
+            .unwrap()
+            .contents
+    };
+
+    assert_eq!(files.len(), 5);
+    assert!(contents("Cargo.toml").contains("name = \"goblin-raid\""));
+    assert!(contents("Cargo.toml").contains("fierceful-atto = { path = \"../fierceful-atto\" }"));
+    assert!(contents("src/member.rs").contains("impl Member for Hero {"));
+    assert!(contents("src/controller.rs").contains("ChoiceCallback<Hero>"));
+
+    let encounter = contents("encounter.ron");
+
+    assert!(encounter.contains("seed: 42"));
+    assert!(encounter.contains("(name: \"Tacco \\\"the Tall\\\"\", health: 70, attack: 8)"));
+
+    // No placeholder is left behind.
+    assert!(files.iter().all(|f| !f.contents.contains('$')));
+}
+
+#[test]
+fn encounters_without_teams_have_a_default_duel() {
+    let files = Scaffold::new("duel").files();
+    let encounter = &files.last().unwrap().contents;
+
+    assert!(encounter.contains("name: \"Heroes\""));
+    assert!(encounter.contains("name: \"Goblins\""));
+}
+
+#[test]
+fn skeletons_are_written_to_disk() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("goblin-raid");
+
+    scaffold().write_to(&dir).unwrap();
+
+    for file in scaffold().files() {
+        assert_eq!(
+            std::fs::read_to_string(dir.join(&file.path)).unwrap(),
+            file.contents
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn member_types_must_be_identifiers() {
+    let _ = Scaffold::new("broken").with_member_type("Not A Type");
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use common::scenario::Scenario;
+
+#[test]
+fn duel() {
+    Scenario::load("duel").run();
+}
+
+#[test]
+fn free_for_all() {
+    Scenario::load("free_for_all").run();
+}
+
+#[test]
+fn full_team() {
+    Scenario::load("full_team").run();
+}
+
+#[test]
+fn reckless() {
+    Scenario::load("reckless").run();
+}
+
+#[test]
+fn scenarios_are_deterministic() {
+    let scenario = Scenario::load("free_for_all");
+
+    let first = scenario.run();
+    let second = scenario.run();
+
+    assert_eq!(first.events(), second.events());
+}
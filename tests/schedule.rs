@@ -0,0 +1,152 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, Context, Target};
+use fierceful_atto::battle::Builder;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::schedule::CancelRule;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Action doing nothing, chosen by every performer.
+struct Wait;
+
+impl<M: Member> Action<M> for Wait {
+    fn act(&mut self, _: Context<M>) -> ActionState {
+        ActionState::Finished
+    }
+}
+
+fn builder(bacco_attack: u64) -> Builder<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 100, bacco_attack)],
+        ),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, performer, _| {
+        (
+            Box::new(Wait),
+            Target::Single(performer.unwrap_or_default()),
+            Target::None,
+        )
+    });
+
+    last_team_standing(teams, controller).set_seed(0)
+}
+
+/// Picco attacks Bacco.
+fn meteor() -> (Box<dyn Action<Fighter>>, Target, Target) {
+    (
+        Box::new(DirectAttack),
+        Target::Single(PICCO),
+        Target::Single(BACCO),
+    )
+}
+
+/// Returns the amount of interrupted attacks.
+fn interruptions(events: &[Event]) -> usize {
+    let attack = Action::<Fighter>::action_id(&DirectAttack);
+
+    events
+        .iter()
+        .filter(|e| matches!(e, Event::ActionInterrupted { action } if *action == attack))
+        .count()
+}
+
+#[test]
+fn scheduled_actions_resolve_after_their_delay() {
+    let mut battle = builder(0)
+        .schedule_action(meteor(), 3, CancelRule::default())
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+    assert_eq!(battle.scheduled_actions().len(), 1);
+    assert_eq!(battle.scheduled_actions()[0].turn(), 3);
+    assert_eq!(
+        battle.scheduled_actions()[0].targets(),
+        &Target::Single(BACCO)
+    );
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+    assert!(battle.scheduled_actions().is_empty());
+}
+
+#[test]
+fn defeated_performers_cancel_their_actions() {
+    let mut battle = builder(100)
+        .schedule_action(meteor(), 2, CancelRule::PerformerDefeated)
+        .queue_action((
+            Box::new(DirectAttack),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        ))
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert!(battle.scheduled_actions().is_empty());
+    assert_eq!(interruptions(battle.events().events()), 1);
+}
+
+#[test]
+fn disrupted_performers_only_cancel_disruptable_actions() {
+    let mut battle = builder(7)
+        .schedule_action(meteor(), 2, CancelRule::PerformerDisrupted)
+        .schedule_action(meteor(), 2, CancelRule::Manual)
+        .queue_action((
+            Box::new(DirectAttack),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        ))
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    let pending = battle.scheduled_actions();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].cancel_rule(), CancelRule::Manual);
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn scheduled_actions_can_be_cancelled() {
+    let mut battle = builder(0).build().unwrap();
+
+    let meteor = battle.schedule_action(meteor(), 1, CancelRule::Manual);
+    battle.schedule_action(
+        (
+            Box::new(DirectAttack),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        ),
+        1,
+        CancelRule::Manual,
+    );
+
+    assert!(battle.cancel_scheduled(meteor));
+    assert!(!battle.cancel_scheduled(meteor));
+
+    battle.remove_member(BACCO).unwrap();
+
+    assert!(battle.scheduled_actions().is_empty());
+    assert_eq!(interruptions(battle.events().events()), 2);
+}
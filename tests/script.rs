@@ -0,0 +1,192 @@
+#![cfg(feature = "scripting")]
+
+mod common;
+
+use fierceful_atto::action::{Action, ActionId, ChoiceCallback, Context, Target};
+use fierceful_atto::aura::TeamAura;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::component::Tag;
+use fierceful_atto::error::ScriptError;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::script::{ScriptEngine, ScriptedAction};
+use fierceful_atto::state::BattleState;
+
+use common::{duel, health, last_team_standing, Fighter, Props};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn perform(action: &mut ScriptedAction, state: &mut BattleState<Fighter>) {
+    Action::<Fighter>::act(
+        action,
+        Context::new(state, Target::Single(PICCO), Target::Single(BACCO)),
+    );
+}
+
+#[test]
+fn scripts_deal_damage_and_apply_tags() {
+    let engine = ScriptEngine::new();
+    let mut action = engine
+        .compile(
+            ActionId::new("Fireball"),
+            r#"
+                let attack = 0;
+
+                for p in performers {
+                    attack += p.attack;
+                }
+
+                for t in targets {
+                    damage(t, attack * 2);
+                    apply_tag(t, "Burning");
+                }
+            "#,
+        )
+        .unwrap();
+
+    let mut state = BattleState::new(duel(), BattleRng::from_seed(0));
+    perform(&mut action, &mut state);
+
+    assert_eq!(health(state.teams(), 1, 0), 80);
+    assert!(state.components().has_tag(BACCO, &Tag::new("Burning")));
+    assert_eq!(
+        Action::<Fighter>::action_id(&action),
+        ActionId::new("Fireball")
+    );
+}
+
+#[test]
+fn scripts_see_the_auras() {
+    let engine = ScriptEngine::new();
+    let mut action = engine
+        .compile(
+            ActionId::new("Strike"),
+            "damage(targets[0], performers[0].attack);",
+        )
+        .unwrap();
+
+    let mut state = BattleState::new(duel(), BattleRng::from_seed(0));
+    state.add_aura(
+        0,
+        TeamAura::new(
+            "Rally",
+            Props {
+                health: 0,
+                attack: 15,
+                control_resistance: 0,
+                speed: 0,
+                shield: 0,
+            },
+        ),
+    );
+    perform(&mut action, &mut state);
+
+    assert_eq!(health(state.teams(), 1, 0), 75);
+}
+
+#[test]
+fn scripts_read_the_state_before_the_action() {
+    let engine = ScriptEngine::new();
+    let mut action = engine
+        .compile(
+            ActionId::new("Drain"),
+            r#"
+                let target = targets[0];
+
+                damage(target, 30);
+
+                // Healing by the missing health of the target, which wasn't damaged yet.
+                heal(performers[0], target.max_health - target.health);
+
+                if "Burning" in target.tags {
+                    consume_tag(target, "Burning");
+                }
+            "#,
+        )
+        .unwrap();
+
+    let mut state = BattleState::new(duel(), BattleRng::from_seed(0));
+    state.apply(Event::Damaged {
+        member: PICCO,
+        amount: 50,
+    });
+    state.apply(Event::Damaged {
+        member: BACCO,
+        amount: 10,
+    });
+    state.apply(Event::TagApplied {
+        member: BACCO,
+        tag: Tag::new("Burning"),
+    });
+
+    perform(&mut action, &mut state);
+
+    assert_eq!(health(state.teams(), 1, 0), 60);
+    assert_eq!(health(state.teams(), 0, 0), 60);
+    assert!(!state.components().has_tag(BACCO, &Tag::new("Burning")));
+}
+
+#[test]
+fn failing_scripts_have_no_effect() {
+    let engine = ScriptEngine::new();
+
+    assert!(matches!(
+        engine.compile(ActionId::new("Broken"), "let = ;"),
+        Err(ScriptError::Compile(_))
+    ));
+
+    let mut endless = engine
+        .compile(ActionId::new("Endless"), "damage(targets[0], 10); loop {}")
+        .unwrap();
+    let mut invalid = engine
+        .compile(ActionId::new("Invalid"), "damage(#{ team: -1 }, 10);")
+        .unwrap();
+
+    let mut state = BattleState::new(duel(), BattleRng::from_seed(0));
+    perform(&mut endless, &mut state);
+    perform(&mut invalid, &mut state);
+
+    assert_eq!(health(state.teams(), 1, 0), 100);
+    assert!(state.events().is_empty());
+}
+
+#[test]
+fn environment_scripts_run_at_the_end_of_turns() {
+    let engine = ScriptEngine::new();
+    let storm = engine
+        .compile_environment(
+            "Storm",
+            r#"
+                for m in members {
+                    if m.alive && turn >= 2 {
+                        damage(m, 7);
+                        apply_tag(m, "Soaked");
+                    }
+                }
+            "#,
+        )
+        .unwrap();
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        )
+    });
+
+    let mut battle = last_team_standing(duel(), controller)
+        .add_environment_script(storm.into_script())
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 0, 0), 93);
+    assert_eq!(health(battle.teams(), 1, 0), 93);
+    assert!(battle.components().has_tag(PICCO, &Tag::new("Soaked")));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use fierceful_atto::action::Target;
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::Shield;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier, Properties};
+use fierceful_atto::round::RoundOrder;
+
+use common::{attack_first, duel, health, last_team_standing, Fighter, Props};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Picco shields Bacco before attacking it.
+fn battle(rounds: bool) -> Battle<Fighter> {
+    let mut builder = last_team_standing(duel(), attack_first);
+
+    if rounds {
+        builder = builder.set_round_order(RoundOrder::TeamByTeam);
+    }
+
+    let mut battle = builder.build().unwrap();
+
+    battle.queue_action((
+        Box::new(Shield { amount: 15 }),
+        Target::Single(PICCO),
+        Target::Single(BACCO),
+    ));
+
+    battle
+}
+
+fn shield(battle: &Battle<Fighter>) -> u64 {
+    battle.teams()[1]
+        .member(0)
+        .unwrap()
+        .member_properties()
+        .shield()
+}
+
+#[test]
+fn shields_absorb_damage_before_health() {
+    let mut properties = Props {
+        health: 100,
+        attack: 0,
+        control_resistance: 0,
+        speed: 0,
+        shield: 15,
+    };
+
+    properties.damage(20);
+
+    assert_eq!(properties.shield, 0);
+    assert_eq!(properties.health, 95);
+
+    let mut battle = battle(false);
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+    assert_eq!(shield(&battle), 5);
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 95);
+    assert_eq!(shield(&battle), 0);
+
+    let absorbed: Vec<_> = battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::ShieldAbsorbed { member, amount } => Some((*member, *amount)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(absorbed, [(BACCO, 10), (BACCO, 5)]);
+}
+
+#[test]
+fn shields_expire_at_the_end_of_the_round() {
+    let mut battle = battle(true);
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    assert_eq!(shield(&battle), 0);
+    assert!(battle.events().events().contains(&Event::ShieldExpired {
+        member: BACCO,
+        amount: 5,
+    }));
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
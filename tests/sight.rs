@@ -0,0 +1,176 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, Context, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::{Move, WithCost};
+use fierceful_atto::error::{ActionError, BattleError};
+use fierceful_atto::event::Event;
+use fierceful_atto::formation::{Formation, Position, Step};
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const NOCCO: MemberIdentifier = MemberIdentifier::new(0, 1);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+const LILLO: MemberIdentifier = MemberIdentifier::new(1, 1);
+const TACCO: MemberIdentifier = MemberIdentifier::new(1, 2);
+
+/// Ranged attack dealing 10 damage to every target in sight.
+struct Shot;
+
+impl Action<Fighter> for Shot {
+    fn act(&mut self, mut context: Context<'_, Fighter>) -> ActionState {
+        for id in context.target_ids() {
+            context.damage(id, 10);
+        }
+
+        ActionState::Finished
+    }
+
+    fn requires_line_of_sight(&self) -> bool {
+        true
+    }
+}
+
+/// Slimes hiding behind each other and behind a wall:
+///
+/// ```text
+/// front: Bacco  ####
+/// back:  Lillo  Tacco
+/// ```
+fn teams() -> Vec<Team<Fighter>> {
+    let heroes = Team::new(
+        String::from("Heroes"),
+        vec![
+            Fighter::new("Picco", 100, 10),
+            Fighter::new("Nocco", 100, 10),
+        ],
+    )
+    .with_formation(Formation::new(vec![Position::front(0), Position::back(0)]));
+
+    let slimes = Team::new(
+        String::from("Slimes"),
+        vec![
+            Fighter::new("Bacco", 100, 1),
+            Fighter::new("Lillo", 100, 1),
+            Fighter::new("Tacco", 100, 1),
+        ],
+    )
+    .with_formation(
+        Formation::new(vec![
+            Position::front(0),
+            Position::back(0),
+            Position::back(1),
+        ])
+        .with_obstacle(Position::front(1)),
+    );
+
+    vec![heroes, slimes]
+}
+
+/// Picco keeps shooting the given target.
+fn battle_with(teams: Vec<Team<Fighter>>, target: MemberIdentifier) -> Battle<Fighter> {
+    let controller: ChoiceCallback<Fighter> = Box::new(move |_, _, _| {
+        (
+            Box::new(Shot),
+            Target::Single(PICCO),
+            Target::Single(target),
+        )
+    });
+
+    last_team_standing(teams, controller)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+fn battle(target: MemberIdentifier) -> Battle<Fighter> {
+    battle_with(teams(), target)
+}
+
+#[test]
+fn front_row_members_are_in_sight() {
+    let mut battle = battle(BACCO);
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn members_and_obstacles_hide_the_back_row() {
+    for hidden in [LILLO, TACCO] {
+        let mut battle = battle(hidden);
+
+        assert_eq!(
+            battle.play_turn(),
+            Err(BattleError::InvalidAction(ActionError::Obstructed {
+                target: hidden
+            }))
+        );
+    }
+}
+
+#[test]
+fn defeated_members_no_longer_hide_the_back_row() {
+    let mut teams = teams();
+    teams[1]
+        .member_mut(0)
+        .unwrap()
+        .member_properties_mut()
+        .health = 0;
+
+    let mut battle = battle_with(teams, LILLO);
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 1), 90);
+}
+
+#[test]
+fn allies_are_always_in_sight() {
+    let mut battle = battle(NOCCO);
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 0, 1), 90);
+}
+
+#[test]
+fn wrappers_keep_the_requirement() {
+    let mut battle = battle(BACCO);
+
+    battle.queue_action((
+        Box::new(WithCost {
+            action: Shot,
+            costs: Vec::new(),
+        }),
+        Target::Single(PICCO),
+        Target::Single(TACCO),
+    ));
+    battle.play_turn().unwrap();
+
+    // The queued shot is dropped, while the chosen one hits.
+    assert_eq!(health(battle.teams(), 1, 2), 100);
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn obstacles_block_movement() {
+    let mut battle = battle(BACCO);
+
+    battle.queue_action((
+        Box::new(Move {
+            step: Step::Right,
+            distance: 1,
+        }),
+        Target::Single(BACCO),
+        Target::None,
+    ));
+    battle.play_turn().unwrap();
+
+    assert_eq!(battle.teams()[1].position(0), Some(Position::front(0)));
+    assert!(battle.events().events().contains(&Event::MoveBlocked {
+        member: BACCO,
+        blocker: None,
+    }));
+}
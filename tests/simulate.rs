@@ -0,0 +1,150 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::EndCondition;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::controller::Controller;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::outcome::{BattleOutcome, MemberReport};
+use fierceful_atto::simulate::{Simulation, SimulationReport};
+use fierceful_atto::team::Team;
+
+use common::{duel, Fighter};
+
+/// Every performer attacks the first alive member of the other team.
+fn controller() -> Box<dyn Controller<Fighter>> {
+    let callback: ChoiceCallback<Fighter> = Box::new(|teams, performer, _| {
+        let performer = performer.unwrap_or_default();
+        let enemy_team = 1 - performer.team_id;
+
+        let target = teams[enemy_team]
+            .member_list()
+            .iter()
+            .position(|m| m.health() > 0)
+            .map_or(Target::None, |member_id| {
+                Target::Single(MemberIdentifier::new(enemy_team, member_id))
+            });
+
+        (Box::new(DirectAttack), Target::Single(performer), target)
+    });
+
+    Box::new(callback)
+}
+
+#[test]
+fn simulations_aggregate_every_battle() {
+    let report = Simulation::new(
+        duel(),
+        Box::new(controller),
+        EndCondition::LastTeamStanding,
+        10,
+    )
+    .run()
+    .unwrap();
+
+    assert_eq!(report.iterations, 10);
+    assert_eq!(report.wins.iter().sum::<u64>() + report.draws, 10);
+    assert_eq!(report.undecided, 0);
+    assert!(report.average_turns() > 0.0);
+    assert_eq!(report.damage.len(), 2);
+    assert_eq!(report.damage[0].samples().len(), 10);
+    assert!(report.damage[0].mean() > 0.0);
+}
+
+#[test]
+fn simulations_are_reproducible() {
+    let run = |seed| {
+        Simulation::new(
+            duel(),
+            Box::new(controller),
+            EndCondition::LastTeamStanding,
+            5,
+        )
+        .with_seed(seed)
+        .run()
+        .unwrap()
+    };
+
+    assert_eq!(run(7), run(7));
+}
+
+#[test]
+fn stalemates_stop_at_the_turn_limit() {
+    let teams = vec![
+        Team::new(
+            String::from("Pacifists"),
+            vec![Fighter::new("Picco", 100, 0)],
+        ),
+        Team::new(
+            String::from("Pacifists"),
+            vec![Fighter::new("Bacco", 100, 0)],
+        ),
+    ];
+
+    let report = Simulation::new(
+        teams,
+        Box::new(controller),
+        EndCondition::LastTeamStanding,
+        3,
+    )
+    .with_max_turns(20)
+    .run()
+    .unwrap();
+
+    assert_eq!(report.undecided, 3);
+    assert_eq!(report.win_rate(0), 0.0);
+    assert_eq!(report.average_turns(), 20.0);
+}
+
+#[test]
+fn damage_percentiles_use_the_nearest_rank() {
+    let mut report = SimulationReport::new(1);
+
+    for damage_dealt in [40, 10, 30, 20] {
+        let mut outcome = BattleOutcome::default();
+        outcome.members.push(MemberReport {
+            damage_dealt,
+            ..MemberReport::new(MemberIdentifier::new(0, 0))
+        });
+
+        report.add(&outcome);
+    }
+
+    let distribution = &report.damage[0];
+
+    assert_eq!(distribution.min(), Some(10));
+    assert_eq!(distribution.max(), Some(40));
+    assert_eq!(distribution.mean(), 25.0);
+    assert_eq!(distribution.percentile(50), Some(20));
+    assert_eq!(distribution.percentile(75), Some(30));
+    assert_eq!(distribution.percentile(0), Some(10));
+    assert_eq!(report.undecided, 4);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_simulations_match_sequential_ones() {
+    use fierceful_atto::simulate::ParallelSimulation;
+
+    let sequential = Simulation::new(
+        duel(),
+        Box::new(controller),
+        EndCondition::LastTeamStanding,
+        64,
+    )
+    .with_seed(3)
+    .run()
+    .unwrap();
+
+    let parallel = ParallelSimulation::new(
+        duel(),
+        Box::new(controller),
+        EndCondition::LastTeamStanding,
+        64,
+    )
+    .with_seed(3)
+    .run()
+    .unwrap();
+
+    assert_eq!(parallel, sequential);
+}
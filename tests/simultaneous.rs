@@ -0,0 +1,123 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, Context, Target};
+use fierceful_atto::battle::{Builder, TurnMode};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+/// Attack resolved before any action with the default priority.
+struct QuickAttack;
+
+impl<M: Member> Action<M> for QuickAttack {
+    fn act(&mut self, context: Context<M>) -> ActionState {
+        DirectAttack.act(context)
+    }
+
+    fn priority(&self) -> i32 {
+        1
+    }
+}
+
+fn teams(left: Fighter, right: Fighter) -> Vec<Team<Fighter>> {
+    vec![
+        Team::new(String::from("Heroes"), vec![left]),
+        Team::new(String::from("Slimes"), vec![right]),
+    ]
+}
+
+/// Every performer attacks the first member of the other team, quickly if it's a member of the given team.
+fn builder(teams: Vec<Team<Fighter>>, quick_team: Option<usize>) -> Builder<Fighter> {
+    let controller: ChoiceCallback<Fighter> = Box::new(move |_, performer, _| {
+        let performer = performer.unwrap_or_default();
+        let target = Target::Single(MemberIdentifier::new(1 - performer.team_id, 0));
+
+        let action: Box<dyn Action<Fighter>> = if quick_team == Some(performer.team_id) {
+            Box::new(QuickAttack)
+        } else {
+            Box::new(DirectAttack)
+        };
+
+        (action, Target::Single(performer), target)
+    });
+
+    last_team_standing(teams, controller)
+        .set_seed(0)
+        .set_turn_mode(TurnMode::Simultaneous)
+}
+
+/// Returns the performers of every action, in the order they acted.
+fn performers(events: &[Event]) -> Vec<MemberIdentifier> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            Event::ActionPerformed { performers, .. } => performers.first().copied(),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn every_team_acts_during_the_same_turn() {
+    let teams = teams(
+        Fighter::new("Picco", 100, 10),
+        Fighter::new("Bacco", 100, 7),
+    );
+    let mut battle = builder(teams, None).build().unwrap();
+
+    battle.play_turn().unwrap();
+
+    let turns = battle
+        .events()
+        .events()
+        .iter()
+        .filter(|e| matches!(e, Event::TurnStarted { .. }))
+        .count();
+
+    assert_eq!(turns, 1);
+    assert_eq!(health(battle.teams(), 0, 0), 93);
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn faster_members_act_first() {
+    let teams = teams(
+        Fighter::new("Picco", 100, 10),
+        Fighter::new("Bacco", 100, 7).with_speed(5),
+    );
+    let mut battle = builder(teams, None).build().unwrap();
+
+    battle.play_turn().unwrap();
+
+    let id = MemberIdentifier::new;
+    assert_eq!(performers(battle.events().events()), [id(1, 0), id(0, 0)]);
+}
+
+#[test]
+fn priority_comes_before_speed() {
+    let teams = teams(
+        Fighter::new("Picco", 100, 10),
+        Fighter::new("Bacco", 100, 7).with_speed(5),
+    );
+    let mut battle = builder(teams, Some(0)).build().unwrap();
+
+    battle.play_turn().unwrap();
+
+    let id = MemberIdentifier::new;
+    assert_eq!(performers(battle.events().events()), [id(0, 0), id(1, 0)]);
+}
+
+#[test]
+fn defeated_members_lose_their_action() {
+    let teams = teams(Fighter::new("Picco", 100, 10), Fighter::new("Bacco", 10, 7));
+    let mut battle = builder(teams, Some(0)).build().unwrap();
+
+    battle.play_turn().unwrap();
+
+    let id = MemberIdentifier::new;
+    assert_eq!(performers(battle.events().events()), [id(0, 0)]);
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+}
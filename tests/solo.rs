@@ -0,0 +1,65 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, Context, Solo, SoloAction, SoloContext, Target};
+use fierceful_atto::battle::PerformerModel;
+use fierceful_atto::error::ActionError;
+use fierceful_atto::member::{Member, MemberIdentifier, Properties};
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::state::BattleState;
+
+use common::{duel, health, last_team_standing, Fighter};
+
+const ATTACKER: MemberIdentifier = MemberIdentifier::new(0, 0);
+const DUMMY: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Damages every target by the performer's attack.
+struct Strike;
+
+impl SoloAction<Fighter> for Strike {
+    fn act(&mut self, mut context: SoloContext<'_, Fighter>) -> ActionState {
+        let attack = context.performer().final_properties().attack();
+
+        for id in context.target_ids() {
+            context.damage(id, attack);
+        }
+
+        ActionState::Finished
+    }
+}
+
+#[test]
+fn engine_appoints_suggested_performer() {
+    // The callback wrongly names the dummy as performer, but the engine only lets the suggested member act.
+    let callback = Box::new(|_: &[_], _, _: &mut BattleRng| {
+        (
+            Box::new(Solo(Strike)) as Box<dyn Action<Fighter>>,
+            Target::Single(DUMMY),
+            Target::Single(DUMMY),
+        )
+    });
+
+    let mut battle = last_team_standing(duel(), callback)
+        .set_seed(0)
+        .set_performer_model(PerformerModel::Solo)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn solo_actions_need_one_performer() {
+    let mut state = BattleState::new(duel(), BattleRng::from_seed(0));
+    let action = Solo(Strike);
+
+    let context = Context::new(&mut state, Target::All, Target::Single(DUMMY));
+    assert_eq!(
+        Action::validate(&action, &context),
+        Err(ActionError::NotSolo { performers: 2 })
+    );
+
+    let context = Context::new(&mut state, Target::Single(ATTACKER), Target::Single(DUMMY));
+    assert_eq!(Action::validate(&action, &context), Ok(()));
+}
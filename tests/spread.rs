@@ -0,0 +1,90 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::AreaAttack;
+use fierceful_atto::catalogue::spread::HalvingFalloff;
+use fierceful_atto::formation::{Formation, Position};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::spread;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const EPICENTER: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn teams(formation: Option<Formation>) -> Vec<Team<Fighter>> {
+    let slimes = Team::new(
+        String::from("Slimes"),
+        vec![
+            Fighter::new("Bacco", 100, 0),
+            Fighter::new("Tacco", 100, 0),
+            Fighter::new("Nocco", 100, 0),
+        ],
+    );
+
+    vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 40)]),
+        match formation {
+            Some(formation) => slimes.with_formation(formation),
+            None => slimes,
+        },
+    ]
+}
+
+/// Battle where Picco hits the targets with an area attack centered on Bacco, halving the damage at every step.
+fn battle(teams: Vec<Team<Fighter>>, targets: Target) -> Battle<Fighter> {
+    let controller: ChoiceCallback<Fighter> = Box::new(move |_, _, _| {
+        (
+            Box::new(AreaAttack {
+                epicenter: EPICENTER,
+                spread: Some(Box::new(HalvingFalloff)),
+            }),
+            Target::Single(PICCO),
+            targets.clone(),
+        )
+    });
+
+    last_team_standing(teams, controller).build().unwrap()
+}
+
+#[test]
+fn members_without_positions_are_lined_up() {
+    let mut battle = battle(teams(None), Target::FullTeam { team_id: 1 });
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 60);
+    assert_eq!(health(battle.teams(), 1, 1), 80);
+    assert_eq!(health(battle.teams(), 1, 2), 90);
+}
+
+#[test]
+fn falloff_follows_the_formation() {
+    let formation = Formation::new(vec![
+        Position::front(0),
+        Position::front(3),
+        Position::back(0),
+    ]);
+
+    let mut battle = battle(teams(Some(formation)), Target::FullTeam { team_id: 1 });
+    battle.play_turn().unwrap();
+
+    // Tacco is three columns away from Bacco, while Nocco stands right behind it.
+    assert_eq!(health(battle.teams(), 1, 0), 60);
+    assert_eq!(health(battle.teams(), 1, 1), 95);
+    assert_eq!(health(battle.teams(), 1, 2), 80);
+}
+
+#[test]
+fn other_teams_are_out_of_reach() {
+    let teams = teams(Some(Formation::new(vec![Position::front(0)])));
+
+    assert_eq!(spread::formation_distance(&teams, EPICENTER, PICCO), None);
+
+    let mut battle = battle(teams, Target::All);
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+    assert_eq!(health(battle.teams(), 1, 0), 60);
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use fierceful_atto::action::Target;
+use fierceful_atto::battle::{Battle, StartupInfo};
+use fierceful_atto::catalogue::actions::{Buff, Debuff};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier, Properties};
+use fierceful_atto::stage::{Stat, StatStages, MAX_STAGE, MIN_STAGE};
+use fierceful_atto::team::Team;
+
+use common::{attack_first, duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn battle() -> Battle<Fighter> {
+    last_team_standing(duel(), attack_first).build().unwrap()
+}
+
+#[test]
+fn stages_scale_values() {
+    let mut stages = StatStages::new();
+
+    assert_eq!(stages.scale(Stat::Attack, 10), 10);
+
+    assert_eq!(stages.change(Stat::Attack, 2), 2);
+    assert_eq!(stages.scale(Stat::Attack, 10), 20);
+    assert_eq!(stages.scale(Stat::Speed, 10), 10);
+
+    assert_eq!(stages.change(Stat::Attack, -4), -4);
+    assert_eq!(stages.scale(Stat::Attack, 10), 5);
+
+    assert_eq!(stages.change(Stat::Attack, 20), 8);
+    assert_eq!(stages.stage(Stat::Attack), MAX_STAGE);
+    assert_eq!(stages.scale(Stat::Attack, 10), 40);
+
+    assert_eq!(stages.change(Stat::Attack, i8::MIN), MIN_STAGE - MAX_STAGE);
+    assert_eq!(stages.scale(Stat::Attack, 10), 2);
+
+    assert_eq!(stages.change(Stat::Attack, 6), 6);
+    assert!(stages.is_neutral());
+}
+
+#[test]
+fn buffs_change_final_properties_only() {
+    let mut battle = battle();
+
+    battle.queue_action((
+        Box::new(Buff {
+            stat: Stat::Attack,
+            stages: 2,
+        }),
+        Target::Single(PICCO),
+        Target::Single(PICCO),
+    ));
+    battle.play_turn().unwrap();
+
+    let state = battle.battle_state();
+    assert_eq!(state.final_properties(PICCO).unwrap().attack(), 20);
+    assert_eq!(
+        battle.teams()[0]
+            .member(0)
+            .unwrap()
+            .member_properties()
+            .attack,
+        10
+    );
+
+    assert_eq!(health(battle.teams(), 1, 0), 80);
+
+    // Bacco doesn't hurt, then Picco attacks again with the boosted attack.
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 60);
+}
+
+#[test]
+fn debuffs_stop_at_the_lowest_stage() {
+    let mut battle = battle();
+
+    for _ in 0..2 {
+        battle.queue_action((
+            Box::new(Debuff {
+                stat: Stat::Attack,
+                stages: 4,
+            }),
+            Target::Single(BACCO),
+            Target::Single(PICCO),
+        ));
+    }
+    battle.queue_action((
+        Box::new(Debuff {
+            stat: Stat::Attack,
+            stages: 1,
+        }),
+        Target::Single(BACCO),
+        Target::Single(PICCO),
+    ));
+    battle.play_turn().unwrap();
+
+    let changes: Vec<_> = battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::StageChanged { change, .. } => Some(*change),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(changes, [-4, -2]);
+    assert_eq!(
+        battle
+            .battle_state()
+            .components()
+            .stages(PICCO)
+            .unwrap()
+            .stage(Stat::Attack),
+        MIN_STAGE
+    );
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 1, 0), 98);
+}
+
+#[test]
+fn speed_stages_change_the_initiative() {
+    let teams = vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![Fighter::new("Picco", 100, 10).with_speed(2)],
+        ),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 100, 0).with_speed(3)],
+        ),
+    ];
+
+    let mut battle = last_team_standing(teams, attack_first)
+        .set_startup_info(StartupInfo::new().with_initiative_by_speed())
+        .add_battle_start_hook(Box::new(|state, _| {
+            state.apply(Event::StageChanged {
+                member: PICCO,
+                stat: Stat::Speed,
+                change: 2,
+            });
+        }))
+        .build()
+        .unwrap();
+    battle.play_turn().unwrap();
+
+    // Picco doubles its speed, outrunning Bacco.
+    assert_eq!(battle.startup_info().initiative, [PICCO, BACCO]);
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
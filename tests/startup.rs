@@ -0,0 +1,262 @@
+mod common;
+
+use std::cell::Cell;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::aura::TeamAura;
+use fierceful_atto::battle::{Battle, StartupInfo};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::catalogue::members::{NoEquipment, SimpleMember, SimpleProps, SimpleStats};
+use fierceful_atto::component::Tag;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::team::Team;
+
+use common::{attack_first, last_team_standing, Fighter, Props};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const POCCO: MemberIdentifier = MemberIdentifier::new(0, 1);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+const BECCO: MemberIdentifier = MemberIdentifier::new(1, 1);
+
+fn teams() -> Vec<Team<Fighter>> {
+    vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![
+                Fighter::new("Picco", 100, 0).with_speed(1),
+                Fighter::new("Pocco", 100, 0).with_speed(5),
+            ],
+        ),
+        Team::new(
+            String::from("Villains"),
+            vec![
+                Fighter::new("Bacco", 100, 0).with_speed(3),
+                Fighter::new("Becco", 100, 0).with_speed(0),
+            ],
+        ),
+    ]
+}
+
+/// Play some turns, returning who performed them.
+fn performers(mut battle: Battle<Fighter>, turns: usize) -> Vec<MemberIdentifier> {
+    for _ in 0..turns {
+        battle.play_turn().unwrap();
+    }
+
+    battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::ActionPerformed { performers, .. } => Some(performers[0]),
+            _ => None,
+        })
+        .collect()
+}
+
+fn battle(startup: StartupInfo) -> Battle<Fighter> {
+    last_team_standing(teams(), attack_first)
+        .set_startup_info(startup)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn initiative_is_rolled_by_speed() {
+    let mut battle = battle(StartupInfo::new().with_initiative_by_speed());
+    battle.play_turn().unwrap();
+
+    assert_eq!(
+        battle.startup_info().initiative,
+        [POCCO, BACCO, PICCO, BECCO]
+    );
+    assert_eq!(performers(battle, 3), [POCCO, BACCO, PICCO, BECCO]);
+}
+
+#[test]
+fn initiative_counts_the_auras() {
+    let haste = TeamAura::new(
+        "Haste",
+        Props {
+            health: 0,
+            attack: 0,
+            control_resistance: 0,
+            speed: 4,
+            shield: 0,
+        },
+    );
+    let battle = last_team_standing(teams(), attack_first)
+        .set_startup_info(StartupInfo::new().with_initiative_by_speed())
+        .add_aura(1, haste)
+        .build()
+        .unwrap();
+
+    // Bacco outruns Pocco, and even Becco outruns Picco.
+    assert_eq!(performers(battle, 4), [BACCO, POCCO, BECCO, PICCO]);
+}
+
+#[test]
+fn ambushing_teams_act_first() {
+    let startup = StartupInfo::new()
+        .with_ambushing_team(1)
+        .with_surprise_round();
+
+    assert_eq!(performers(battle(startup), 2), [BACCO, BECCO]);
+
+    // Without a surprise round, the ambush doesn't change the turn order.
+    let startup = StartupInfo::new().with_ambushing_team(1);
+
+    assert_eq!(performers(battle(startup), 2), [PICCO, POCCO]);
+}
+
+#[test]
+fn first_performer_opens_the_battle() {
+    let startup = StartupInfo::new()
+        .with_first_performer(BECCO)
+        .with_ambushing_team(0)
+        .with_surprise_round()
+        .with_initiative(vec![BACCO]);
+
+    let battle = battle(startup.clone());
+
+    assert_eq!(battle.startup_info(), &startup);
+    assert_eq!(performers(battle, 4), [BECCO, PICCO, POCCO, BACCO]);
+}
+
+#[test]
+fn starting_tags_are_applied_before_the_first_turn() {
+    let asleep = Tag::new("Asleep");
+    let mut battle = battle(StartupInfo::new().with_starting_tag(BACCO, asleep.clone()));
+    battle.play_turn().unwrap();
+
+    let tagged = position(&battle, |e| {
+        *e == Event::TagApplied {
+            member: BACCO,
+            tag: asleep.clone(),
+        }
+    });
+
+    assert!(tagged < position(&battle, |e| matches!(e, Event::TurnStarted { .. })));
+    assert!(battle.components().has_tag(BACCO, &asleep));
+    assert!(!battle.components().has_tag(PICCO, &asleep));
+}
+
+#[test]
+fn surprise_rounds_skip_defeated_ambushers() {
+    let battle = last_team_standing(teams(), attack_first)
+        .set_startup_info(
+            StartupInfo::new()
+                .with_ambushing_team(1)
+                .with_surprise_round(),
+        )
+        .add_battle_start_hook(Box::new(|state, _| {
+            state.apply(Event::Damaged {
+                member: BACCO,
+                amount: 100,
+            });
+        }))
+        .build()
+        .unwrap();
+
+    // Bacco falls to the opening effects, so Becco is the only one springing the ambush.
+    assert_eq!(performers(battle, 2), [BECCO, PICCO]);
+}
+
+/// Member hiding when its team ambushes the other one, counting how many times the battle started for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Scout {
+    member: SimpleMember,
+    openings: Cell<u32>,
+}
+
+impl Scout {
+    fn new(name: &str) -> Self {
+        Self {
+            member: SimpleMember::new(name, 100, 0),
+            openings: Cell::new(0),
+        }
+    }
+}
+
+impl Member for Scout {
+    type Statistics = SimpleStats;
+    type Properties = SimpleProps;
+    type Equipment = NoEquipment;
+
+    fn name(&self) -> &str {
+        self.member.name()
+    }
+
+    fn statistics(&self) -> &SimpleStats {
+        self.member.statistics()
+    }
+
+    fn member_properties(&self) -> &SimpleProps {
+        self.member.member_properties()
+    }
+
+    fn member_properties_mut(&mut self) -> &mut SimpleProps {
+        self.member.member_properties_mut()
+    }
+
+    fn equipment(&self) -> &NoEquipment {
+        self.member.equipment()
+    }
+
+    fn on_battle_start(&self, id: MemberIdentifier, startup: &StartupInfo) -> Vec<Event> {
+        self.openings.set(self.openings.get() + 1);
+
+        if startup.ambushing_team == Some(id.team_id) {
+            vec![Event::TagApplied {
+                member: id,
+                tag: Tag::new("Hidden"),
+            }]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Index of the first event matching `predicate`.
+fn position(battle: &Battle<impl Member>, predicate: impl Fn(&Event) -> bool) -> usize {
+    battle.events().events().iter().position(predicate).unwrap()
+}
+
+#[test]
+fn members_open_the_battle_once() {
+    let teams = vec![
+        Team::new(String::from("Scouts"), vec![Scout::new("Picco")]),
+        Team::new(String::from("Guards"), vec![Scout::new("Bacco")]),
+    ];
+    let controller: ChoiceCallback<Scout> = Box::new(|_, performer, _| {
+        let performer = performer.unwrap_or_default();
+
+        (
+            Box::new(DirectAttack),
+            Target::Single(performer),
+            Target::Single(MemberIdentifier::new(1 - performer.team_id, 0)),
+        )
+    });
+    let mut battle = last_team_standing(teams, controller)
+        .set_startup_info(StartupInfo::new().with_ambushing_team(0))
+        .build()
+        .unwrap();
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    for team in battle.teams() {
+        assert_eq!(team.member_list()[0].openings.get(), 1);
+    }
+
+    // Only the ambushers hide, before the first turn starts.
+    let hidden = Tag::new("Hidden");
+    let tagged = position(&battle, |e| matches!(e, Event::TagApplied { .. }));
+
+    assert!(tagged < position(&battle, |e| matches!(e, Event::TurnStarted { .. })));
+    assert!(battle.components().has_tag(PICCO, &hidden));
+    assert!(!battle.components().has_tag(BACCO, &hidden));
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::{Battle, Builder};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Retention;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::team::Team;
+
+use common::{last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+const TACCO: MemberIdentifier = MemberIdentifier::new(1, 1);
+
+/// Picco fights two harmless slimes, while every performer attacks the first alive enemy.
+fn builder() -> Builder<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 25)]),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 50, 0), Fighter::new("Tacco", 50, 0)],
+        ),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|teams, performer, _| {
+        let performer = performer.unwrap_or_default();
+        let enemy_team = 1 - performer.team_id;
+
+        let target = teams[enemy_team]
+            .member_list()
+            .iter()
+            .position(|m| m.health() > 0)
+            .map_or(Target::None, |member_id| {
+                Target::Single(MemberIdentifier::new(enemy_team, member_id))
+            });
+
+        (Box::new(DirectAttack), Target::Single(performer), target)
+    });
+
+    last_team_standing(teams, controller).set_seed(0)
+}
+
+fn play_to_the_end(battle: &mut Battle<Fighter>) {
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+}
+
+#[test]
+fn statistics_are_not_tracked_by_default() {
+    let mut battle = builder().build().unwrap();
+    play_to_the_end(&mut battle);
+
+    assert!(battle.statistics().is_none());
+}
+
+#[test]
+fn statistics_accumulate_damage_kills_and_actions() {
+    let mut battle = builder().set_statistics_tracking(true).build().unwrap();
+    play_to_the_end(&mut battle);
+
+    let statistics = battle.statistics().unwrap();
+    let picco = statistics.member(PICCO);
+
+    assert_eq!(picco.damage_dealt, 100);
+    assert_eq!(picco.kills, 2);
+    assert_eq!(picco.actions, 4);
+    assert_eq!(statistics.member(BACCO).damage_taken, 50);
+    assert_eq!(statistics.member(TACCO).damage_taken, 50);
+    assert_eq!(statistics.team(1).damage_taken, 100);
+    assert_eq!(statistics.team(1).kills, 0);
+    assert_eq!(statistics.most_valuable(0), Some(PICCO));
+}
+
+#[test]
+fn statistics_outlive_truncated_logs() {
+    let mut full = builder().set_statistics_tracking(true).build().unwrap();
+    play_to_the_end(&mut full);
+
+    let mut truncated = builder()
+        .set_statistics_tracking(true)
+        .set_retention(Retention::MaxEvents(4))
+        .build()
+        .unwrap();
+    play_to_the_end(&mut truncated);
+
+    assert!(!truncated.events().is_complete());
+    assert_eq!(truncated.statistics(), full.statistics());
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceReturn, Context, Target};
+use fierceful_atto::battle::{Battle, BattleStep};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Keeps its performer busy for a few more turns.
+struct Meditate {
+    turns: u32,
+}
+
+impl<M: Member> Action<M> for Meditate {
+    fn act(&mut self, _: Context<M>) -> ActionState {
+        if self.turns == 0 {
+            return ActionState::Finished;
+        }
+
+        self.turns -= 1;
+
+        ActionState::Continuing
+    }
+}
+
+/// Every choice is given from the outside, so the battle's controller must never be asked.
+fn unreachable(
+    _: &[Team<Fighter>],
+    _: Option<MemberIdentifier>,
+    _: &mut BattleRng,
+) -> ChoiceReturn<Fighter> {
+    panic!("the battle asked its controller for a choice")
+}
+
+fn battle(bacco_health: u64) -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", bacco_health, 10)],
+        ),
+    ];
+
+    last_team_standing(teams, unreachable)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+fn attack(performer: MemberIdentifier, target: MemberIdentifier) -> ChoiceReturn<Fighter> {
+    (
+        Box::new(DirectAttack),
+        Target::Single(performer),
+        Target::Single(target),
+    )
+}
+
+#[test]
+fn steps_wait_for_the_next_choice() {
+    let mut battle = battle(100);
+
+    assert_eq!(
+        battle.step().unwrap(),
+        BattleStep::AwaitingChoice {
+            performer: Some(PICCO)
+        }
+    );
+    // Stepping again doesn't play anything.
+    assert_eq!(
+        battle.step().unwrap(),
+        BattleStep::AwaitingChoice {
+            performer: Some(PICCO)
+        }
+    );
+
+    let step = battle.resume_with_choice(attack(PICCO, BACCO)).unwrap();
+
+    assert_eq!(
+        step,
+        BattleStep::AwaitingChoice {
+            performer: Some(BACCO)
+        }
+    );
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+
+    battle.resume_with_choice(attack(BACCO, PICCO)).unwrap();
+
+    assert_eq!(health(battle.teams(), 0, 0), 90);
+}
+
+#[test]
+fn channeling_turns_are_played_right_away() {
+    let mut battle = battle(100);
+
+    battle
+        .resume_with_choice((
+            Box::new(Meditate { turns: 1 }),
+            Target::Single(PICCO),
+            Target::None,
+        ))
+        .unwrap();
+
+    // Picco keeps meditating during its next turn, so Bacco is asked twice in a row.
+    assert_eq!(
+        battle.resume_with_choice(attack(BACCO, BACCO)).unwrap(),
+        BattleStep::AwaitingChoice {
+            performer: Some(BACCO)
+        }
+    );
+}
+
+#[test]
+fn finished_battles_ignore_choices() {
+    let mut battle = battle(10);
+
+    assert_eq!(
+        battle.resume_with_choice(attack(PICCO, BACCO)).unwrap(),
+        BattleStep::Finished
+    );
+    assert_eq!(
+        battle.resume_with_choice(attack(BACCO, PICCO)).unwrap(),
+        BattleStep::Finished
+    );
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+    assert!(battle.is_finished());
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::{DirectAttack, Summon};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::summon::Lifetime;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const SUMMONER: MemberIdentifier = MemberIdentifier::new(0, 0);
+const SUMMONED: MemberIdentifier = MemberIdentifier::new(0, 1);
+const ENEMY: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// The summoner summons once, every other member attacks the other team.
+fn controller(lifetime: Lifetime) -> ChoiceCallback<Fighter> {
+    Box::new(move |team_list, performer, _| {
+        let performer = performer.unwrap_or_default();
+
+        if performer == SUMMONER && team_list[0].member_list().len() == 1 {
+            return (
+                Box::new(Summon {
+                    template: Box::new(|_| Fighter::new("Nocco", 50, 5)),
+                    lifetime,
+                }),
+                Target::Single(performer),
+                Target::None,
+            );
+        }
+
+        let target = if performer.team_id == 0 {
+            ENEMY
+        } else {
+            SUMMONER
+        };
+
+        (
+            Box::new(DirectAttack),
+            Target::Single(performer),
+            Target::Single(target),
+        )
+    })
+}
+
+fn battle(summoner_health: u64, enemy_attack: u64, lifetime: Lifetime) -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(
+            String::from("Summoners"),
+            vec![Fighter::new("Picco", summoner_health, 10)],
+        ),
+        Team::new(
+            String::from("Enemies"),
+            vec![Fighter::new("Bacco", 100, enemy_attack)],
+        ),
+    ];
+
+    last_team_standing(teams, controller(lifetime))
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn summoned_member_joins_summoner_team() {
+    let mut battle = battle(100, 0, Lifetime::Permanent);
+    battle.play_turn().unwrap();
+
+    assert_eq!(battle.teams()[0].member_list().len(), 2);
+    assert_eq!(
+        battle
+            .components()
+            .summoning(SUMMONED)
+            .map(|s| (s.summoner, s.lifetime)),
+        Some((Some(SUMMONER), Lifetime::Permanent))
+    );
+
+    // The summoned member acts during the next turn.
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 1, 0), 95);
+}
+
+#[test]
+fn summon_expires_after_its_turns() {
+    let mut battle = battle(100, 0, Lifetime::Turns(1));
+
+    battle.play_turn().unwrap();
+    battle.play_turn().unwrap();
+
+    // The summoned member could act once before leaving.
+    assert_eq!(health(battle.teams(), 1, 0), 95);
+    assert_eq!(health(battle.teams(), 0, 1), 0);
+    assert!(battle
+        .events()
+        .events()
+        .contains(&Event::SummonExpired { member: SUMMONED }));
+    assert!(battle.components().summoning(SUMMONED).is_none());
+}
+
+#[test]
+fn permanent_summon_outlives_summoner() {
+    let mut battle = battle(10, 20, Lifetime::Permanent);
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(health(battle.teams(), 0, 0), 0);
+    assert_eq!(health(battle.teams(), 0, 1), 50);
+    assert!(!battle.is_finished());
+}
+
+#[test]
+fn summon_dies_with_summoner() {
+    let mut battle = battle(10, 20, Lifetime::WithSummoner);
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    assert_eq!(health(battle.teams(), 0, 0), 0);
+    assert_eq!(health(battle.teams(), 0, 1), 0);
+    assert!(battle.is_finished());
+}
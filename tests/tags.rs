@@ -0,0 +1,91 @@
+mod common;
+
+use fierceful_atto::action::{Action, Context, Target};
+use fierceful_atto::catalogue::actions::{ConsumingAttack, TaggingAttack};
+use fierceful_atto::component::Tag;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::state::BattleState;
+
+use common::{duel, health, Fighter};
+
+const ATTACKER: MemberIdentifier = MemberIdentifier::new(0, 0);
+const DUMMY: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn wet() -> TaggingAttack {
+    TaggingAttack {
+        tag: Tag::new("Wet"),
+    }
+}
+
+fn shock() -> ConsumingAttack {
+    ConsumingAttack {
+        tag: Tag::new("Wet"),
+        bonus_damage: 25,
+    }
+}
+
+fn act(action: &mut dyn Action<Fighter>, state: &mut BattleState<Fighter>) {
+    action.act(Context::new(
+        state,
+        Target::Single(ATTACKER),
+        Target::Single(DUMMY),
+    ));
+}
+
+fn state() -> BattleState<Fighter> {
+    BattleState::new(duel(), BattleRng::from_seed(0))
+}
+
+#[test]
+fn consumes_tag_applied_by_previous_action() {
+    let mut state = state();
+
+    act(&mut wet(), &mut state);
+    assert!(state.components().has_tag(DUMMY, &Tag::new("Wet")));
+    assert_eq!(health(state.teams(), 1, 0), 90);
+
+    act(&mut shock(), &mut state);
+    assert!(!state.components().has_tag(DUMMY, &Tag::new("Wet")));
+    assert_eq!(health(state.teams(), 1, 0), 55);
+}
+
+#[test]
+fn tag_is_consumed_only_once() {
+    let mut state = state();
+
+    act(&mut wet(), &mut state);
+    act(&mut shock(), &mut state);
+    act(&mut shock(), &mut state);
+
+    // 10 + (10 + 25) + 10
+    assert_eq!(health(state.teams(), 1, 0), 45);
+}
+
+#[test]
+fn consuming_before_tagging_has_no_bonus() {
+    let mut state = state();
+
+    act(&mut shock(), &mut state);
+    assert_eq!(health(state.teams(), 1, 0), 90);
+
+    act(&mut wet(), &mut state);
+    assert_eq!(state.components().tag_stacks(DUMMY, &Tag::new("Wet")), 1);
+    assert_eq!(health(state.teams(), 1, 0), 80);
+}
+
+#[test]
+fn stacks_are_consumed_one_at_a_time() {
+    let mut state = state();
+
+    act(&mut wet(), &mut state);
+    act(&mut wet(), &mut state);
+    assert_eq!(state.components().tag_stacks(DUMMY, &Tag::new("Wet")), 2);
+
+    act(&mut shock(), &mut state);
+    assert_eq!(state.components().tag_stacks(DUMMY, &Tag::new("Wet")), 1);
+
+    act(&mut shock(), &mut state);
+    assert_eq!(state.components().tag_stacks(DUMMY, &Tag::new("Wet")), 0);
+    assert_eq!(state.components().tags(DUMMY).count(), 0);
+}
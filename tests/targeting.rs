@@ -0,0 +1,331 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, Context, Target, TargetFilter};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::error::ActionError;
+use fierceful_atto::event::Event;
+use fierceful_atto::formation::{Formation, Position, Shape};
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+
+/// Picco attacks with the target chosen by the test, while everyone else waits.
+fn picco_attacks(target: Target) -> ChoiceCallback<Fighter> {
+    Box::new(move |_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(PICCO),
+            target.clone(),
+        )
+    })
+}
+
+fn brawl(target: Target) -> Battle<Fighter> {
+    seeded_brawl(target, 0)
+}
+
+fn seeded_brawl(target: Target, seed: u64) -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![
+                Fighter::new("Picco", 100, 10),
+                Fighter::new("Nocco", 100, 10),
+            ],
+        ),
+        Team::new(
+            String::from("Slimes"),
+            vec![Fighter::new("Bacco", 100, 1), Fighter::new("Lillo", 100, 1)],
+        ),
+        Team::new(String::from("Bats"), vec![Fighter::new("Tacco", 100, 1)]),
+    ];
+
+    last_team_standing(teams, picco_attacks(target))
+        .set_seed(seed)
+        .build()
+        .unwrap()
+}
+
+fn all_health(battle: &Battle<Fighter>) -> Vec<u64> {
+    vec![
+        health(battle.teams(), 0, 0),
+        health(battle.teams(), 0, 1),
+        health(battle.teams(), 1, 0),
+        health(battle.teams(), 1, 1),
+        health(battle.teams(), 2, 0),
+    ]
+}
+
+#[test]
+fn all_enemies_spans_every_other_team() {
+    let mut battle = brawl(Target::AllEnemiesOf(PICCO));
+    battle.play_turn().unwrap();
+
+    assert_eq!(all_health(&battle), vec![100, 100, 90, 90, 90]);
+}
+
+#[test]
+fn all_allies_leaves_the_member_out() {
+    let mut battle = brawl(Target::AllAlliesOf(PICCO));
+    battle.play_turn().unwrap();
+
+    assert_eq!(all_health(&battle), vec![100, 90, 100, 100, 100]);
+}
+
+#[test]
+fn performers_target_themselves() {
+    let mut battle = brawl(Target::Performers);
+    battle.play_turn().unwrap();
+
+    assert_eq!(all_health(&battle), vec![90, 100, 100, 100, 100]);
+}
+
+#[test]
+fn relative_targets_follow_removals() {
+    let mut battle = brawl(Target::None);
+
+    // Picco leaves before the action is resolved, so the target follows Nocco to its new identifier.
+    battle.queue_action((
+        Box::new(DirectAttack),
+        Target::Single(MemberIdentifier::new(1, 0)),
+        Target::AllEnemiesOf(MemberIdentifier::new(0, 1)),
+    ));
+    battle.remove_member(PICCO).unwrap();
+    battle.play_turn().unwrap();
+
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+    assert_eq!(health(battle.teams(), 1, 1), 99);
+    assert_eq!(health(battle.teams(), 2, 0), 99);
+}
+
+/// Slimes placed on a grid:
+///
+/// ```text
+/// front: 0 1 2
+/// back:  3 4 -
+/// ```
+fn grid(center: Position, shape: Shape) -> Battle<Fighter> {
+    let slimes = Team::new(
+        String::from("Slimes"),
+        (0..5)
+            .map(|i| Fighter::new(&format!("Slime {}", i), 100, 1))
+            .collect(),
+    )
+    .with_formation(Formation::new(vec![
+        Position::front(0),
+        Position::front(1),
+        Position::front(2),
+        Position::back(0),
+        Position::back(1),
+    ]));
+
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        slimes,
+    ];
+
+    let target = Target::Area {
+        team_id: 1,
+        center,
+        shape,
+    };
+
+    last_team_standing(teams, picco_attacks(target))
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+/// Returns the IDs of the slimes hit by Picco's first attack.
+fn hit_slimes(center: Position, shape: Shape) -> Vec<usize> {
+    let mut battle = grid(center, shape);
+    battle.play_turn().unwrap();
+
+    (0..5)
+        .filter(|m| health(battle.teams(), 1, *m) < 100)
+        .collect()
+}
+
+#[test]
+fn areas_cover_their_shape() {
+    assert_eq!(hit_slimes(Position::front(1), Shape::Cell), vec![1]);
+    assert_eq!(hit_slimes(Position::front(1), Shape::Line), vec![1, 4]);
+    assert_eq!(
+        hit_slimes(Position::front(1), Shape::Cross),
+        vec![0, 1, 2, 4]
+    );
+    assert_eq!(
+        hit_slimes(Position::back(0), Shape::Radius(2)),
+        vec![0, 1, 3, 4]
+    );
+}
+
+#[test]
+fn areas_may_be_centered_on_empty_cells() {
+    assert_eq!(
+        hit_slimes(Position::back(2), Shape::Cell),
+        Vec::<usize>::new()
+    );
+    assert_eq!(hit_slimes(Position::back(2), Shape::Cross), vec![2, 4]);
+}
+
+/// Heals by 20 every target below half of its health.
+struct PatchUp;
+
+impl Action<Fighter> for PatchUp {
+    fn act(&mut self, mut context: Context<'_, Fighter>) -> ActionState {
+        for id in context.targets_where(|m| m.health() < 50) {
+            context.heal(id, 20);
+        }
+
+        ActionState::Finished
+    }
+}
+
+/// Brawl where Bacco is badly wounded and Lillo is already defeated.
+fn wounded_brawl(target: Target) -> Battle<Fighter> {
+    let mut teams = brawl(Target::None).take_teams();
+
+    teams[1]
+        .member_mut(0)
+        .unwrap()
+        .member_properties_mut()
+        .health = 40;
+    teams[1]
+        .member_mut(1)
+        .unwrap()
+        .member_properties_mut()
+        .health = 0;
+
+    last_team_standing(teams, picco_attacks(target))
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn filtered_targets_only_hit_matching_members() {
+    let mut battle = wounded_brawl(
+        Target::AllEnemiesOf(PICCO).filtered(TargetFilter::HealthBelow { percent: 50 }),
+    );
+    battle.play_turn().unwrap();
+
+    assert_eq!(all_health(&battle), vec![100, 100, 30, 0, 100]);
+
+    let mut battle = wounded_brawl(Target::AllEnemiesOf(PICCO).filtered(TargetFilter::Alive));
+    battle.play_turn().unwrap();
+
+    let targeted: Vec<_> = battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::Targeted { member } => Some(*member),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        targeted,
+        vec![MemberIdentifier::new(1, 0), MemberIdentifier::new(2, 0)]
+    );
+}
+
+#[test]
+fn actions_can_filter_their_targets() {
+    let mut battle = wounded_brawl(Target::None);
+
+    battle.queue_action((
+        Box::new(PatchUp),
+        Target::Single(PICCO),
+        Target::AllEnemiesOf(PICCO),
+    ));
+    battle.play_turn().unwrap();
+
+    // Lillo is defeated, so it can't be healed.
+    assert_eq!(all_health(&battle), vec![100, 100, 60, 0, 100]);
+}
+
+#[test]
+fn random_targets_are_picked_by_the_battle() {
+    let hit = |seed: u64| {
+        let mut battle = seeded_brawl(Target::AllEnemiesOf(PICCO).random(2), seed);
+        battle.play_turn().unwrap();
+
+        all_health(&battle)
+    };
+
+    for seed in 0..8 {
+        let health = hit(seed);
+
+        assert_eq!(health[..2], [100, 100]);
+        assert_eq!(health.iter().filter(|h| **h == 90).count(), 2);
+        assert_eq!(hit(seed), health, "the choice must be reproducible");
+    }
+
+    // Enough seeds pick different members.
+    assert!((0..8).any(|seed| hit(seed) != hit(0)));
+}
+
+#[test]
+fn random_targets_never_exceed_the_pool() {
+    let mut battle = wounded_brawl(
+        Target::AllEnemiesOf(PICCO)
+            .filtered(TargetFilter::Alive)
+            .random(5),
+    );
+    battle.play_turn().unwrap();
+
+    assert_eq!(all_health(&battle), vec![100, 100, 30, 0, 90]);
+}
+
+/// Records the performer and target identifiers it is resolved with, even if some members are missing.
+struct Record(Rc<RefCell<Vec<Vec<MemberIdentifier>>>>);
+
+impl Action<Fighter> for Record {
+    fn validate(&self, _: &Context<'_, Fighter>) -> Result<(), ActionError> {
+        Ok(())
+    }
+
+    fn act(&mut self, context: Context<'_, Fighter>) -> ActionState {
+        let mut record = self.0.borrow_mut();
+
+        record.push(context.performer_ids());
+        record.push(context.target_ids());
+
+        ActionState::Finished
+    }
+}
+
+#[test]
+fn identifiers_are_sorted_and_unique() {
+    let mut battle = brawl(Target::None);
+    let record = Rc::new(RefCell::new(Vec::new()));
+    let id = MemberIdentifier::new;
+
+    battle.queue_action((
+        Box::new(Record(record.clone())),
+        Target::DiscreteMultiple(vec![id(0, 1), PICCO, id(0, 1), id(0, 5)]),
+        Target::DiscreteMultiple(vec![
+            id(2, 0),
+            id(1, 1),
+            id(1, 0),
+            id(1, 1),
+            id(3, 0),
+            id(1, 7),
+        ]),
+    ));
+    battle.play_turn().unwrap();
+
+    assert_eq!(
+        *record.borrow(),
+        [vec![PICCO, id(0, 1)], vec![id(1, 0), id(1, 1), id(2, 0)]]
+    );
+}
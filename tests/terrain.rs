@@ -0,0 +1,117 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::{Battle, Builder, EndCondition};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::state::BattleState;
+use fierceful_atto::terrain::StructureId;
+
+use common::{duel, health, last_team_standing};
+
+const CORE: StructureId = StructureId(1);
+
+/// Every performer attacks the core.
+fn siege() -> ChoiceCallback<common::Fighter> {
+    Box::new(|_, performer, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(performer.unwrap_or_default()),
+            Target::Structure(CORE),
+        )
+    })
+}
+
+fn battle() -> Battle<common::Fighter> {
+    Builder::new(
+        duel(),
+        None,
+        siege(),
+        EndCondition::StructureDestroyed(CORE),
+    )
+    .add_structure(String::from("Barricade"), 50)
+    .add_structure(String::from("Core"), 25)
+    .set_seed(0)
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn attacks_damage_targeted_structures() {
+    let mut battle = battle();
+    battle.play_turn().unwrap();
+
+    assert_eq!(battle.structures()[1].health(), 15);
+    assert_eq!(battle.structures()[0].health(), 50);
+
+    // Structures aren't members, so nobody else was hit.
+    assert_eq!(health(battle.teams(), 0, 0), 100);
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
+
+#[test]
+fn destroying_the_core_ends_the_battle() {
+    let mut battle = battle();
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    let core = &battle.structures()[1];
+    assert_eq!(core.name(), "Core");
+    assert!(core.is_destroyed());
+
+    let events = battle.events().events();
+    let destroyed = events
+        .iter()
+        .position(|e| *e == Event::StructureDestroyed { structure: CORE })
+        .unwrap();
+    assert!(matches!(
+        events[destroyed - 1],
+        Event::StructureDamaged {
+            structure: CORE,
+            ..
+        }
+    ));
+    assert_eq!(events.last(), Some(&Event::BattleFinished));
+}
+
+#[test]
+fn structures_are_replayed() {
+    let mut battle = battle();
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    let replayed = BattleState::replay(duel(), battle.events().events());
+
+    assert_eq!(replayed.structures(), battle.structures());
+    assert_eq!(
+        replayed
+            .events()
+            .events()
+            .iter()
+            .filter(|e| matches!(e, Event::StructureDestroyed { .. }))
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn missing_structures_are_ignored() {
+    let mut battle = last_team_standing(duel(), siege())
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    assert!(battle.structures().is_empty());
+    assert!(!battle
+        .events()
+        .events()
+        .iter()
+        .any(|e| matches!(e, Event::StructureDamaged { .. })));
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, Context, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::{DirectAttack, Taunt};
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::team::Team;
+
+use common::{health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const NOCCO: MemberIdentifier = MemberIdentifier::new(0, 1);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// Heals every target by 20.
+struct Mend;
+
+impl Action<Fighter> for Mend {
+    fn act(&mut self, mut context: Context<'_, Fighter>) -> ActionState {
+        for id in context.target_ids() {
+            context.heal(id, 20);
+        }
+
+        ActionState::Finished
+    }
+}
+
+/// Bacco always attacks the hero with the most threat, while the heroes act through queued actions only.
+fn battle() -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![
+                Fighter::new("Picco", 100, 10),
+                Fighter::new("Nocco", 100, 4),
+            ],
+        ),
+        Team::new(String::from("Slimes"), vec![Fighter::new("Bacco", 1000, 1)]),
+    ];
+
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(DirectAttack),
+            Target::Single(BACCO),
+            Target::MostThreatening(BACCO),
+        )
+    });
+
+    last_team_standing(teams, controller)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+fn queue(
+    battle: &mut Battle<Fighter>,
+    action: Box<dyn Action<Fighter>>,
+    performer: MemberIdentifier,
+    target: MemberIdentifier,
+) {
+    battle.queue_action((action, Target::Single(performer), Target::Single(target)));
+}
+
+#[test]
+fn damage_draws_the_attention_of_the_enemies() {
+    let mut battle = battle();
+
+    queue(&mut battle, Box::new(DirectAttack), PICCO, BACCO);
+    queue(&mut battle, Box::new(DirectAttack), NOCCO, BACCO);
+    battle.play_turn().unwrap();
+
+    let threat = battle.battle_state().threat();
+    assert_eq!(threat.threat(PICCO, 1), 10);
+    assert_eq!(threat.threat(NOCCO, 1), 4);
+    assert_eq!(threat.threat(BACCO, 0), 1);
+
+    // Bacco's chosen attack is resolved after the queued ones, so it already went after Picco.
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 0, 0), 98);
+    assert_eq!(health(battle.teams(), 0, 1), 100);
+}
+
+#[test]
+fn healing_and_taunts_generate_threat() {
+    let mut battle = battle();
+
+    queue(&mut battle, Box::new(DirectAttack), PICCO, BACCO);
+    queue(&mut battle, Box::new(Mend), NOCCO, PICCO);
+    battle.play_turn().unwrap();
+
+    // Half of the healing becomes threat.
+    assert_eq!(battle.battle_state().threat().threat(NOCCO, 1), 10);
+
+    queue(&mut battle, Box::new(Taunt { threat: 5 }), NOCCO, BACCO);
+    battle.play_turn().unwrap();
+
+    assert_eq!(battle.battle_state().threat().threat(NOCCO, 1), 15);
+    assert_eq!(health(battle.teams(), 0, 1), 99);
+}
+
+#[test]
+fn threat_follows_removals() {
+    let mut battle = battle();
+
+    queue(&mut battle, Box::new(DirectAttack), NOCCO, BACCO);
+    battle.play_turn().unwrap();
+    battle.remove_member(PICCO).unwrap();
+
+    assert_eq!(battle.battle_state().threat().threat(PICCO, 1), 4);
+    assert_eq!(battle.battle_state().most_threatening(1), Some(PICCO));
+}
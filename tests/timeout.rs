@@ -0,0 +1,124 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use fierceful_atto::action::{ChoiceReturn, Target};
+use fierceful_atto::battle::{Battle, TimeoutFallback};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::controller::{ChannelController, Controller};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::registry::ActionRegistry;
+use fierceful_atto::rng::BattleRng;
+use fierceful_atto::team::Team;
+
+use common::{duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+const TIMEOUT: Duration = Duration::from_millis(10);
+
+/// Picco attacks Bacco, after thinking about it for a while.
+fn slow(delay: Duration) -> impl Controller<Fighter> {
+    move |_: &[Team<Fighter>], _: Option<MemberIdentifier>, _: &mut BattleRng| {
+        thread::sleep(delay);
+
+        attack(BACCO)
+    }
+}
+
+/// Picco attacks the target.
+fn attack(target: MemberIdentifier) -> ChoiceReturn<Fighter> {
+    (
+        Box::new(DirectAttack),
+        Target::Single(PICCO),
+        Target::Single(target),
+    )
+}
+
+fn battle(
+    controller: impl Controller<Fighter> + 'static,
+    fallback: TimeoutFallback<Fighter>,
+) -> Battle<Fighter> {
+    last_team_standing(duel(), controller)
+        .set_choice_timeout(TIMEOUT, fallback)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+fn timed_out(battle: &Battle<Fighter>) -> bool {
+    battle.events().events().contains(&Event::ChoiceTimedOut {
+        member: Some(PICCO),
+    })
+}
+
+#[test]
+fn late_choices_skip_the_turn() {
+    let mut battle = battle(slow(TIMEOUT * 3), TimeoutFallback::SkipTurn);
+
+    battle.play_turn().unwrap();
+
+    assert!(timed_out(&battle));
+    assert!(battle.events().events().contains(&Event::TurnForfeited {
+        member: PICCO,
+        idle_turns: 1
+    }));
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
+
+#[test]
+fn late_choices_are_made_by_the_fallback_controller() {
+    let fallback =
+        |_: &[Team<Fighter>], _: Option<MemberIdentifier>, _: &mut BattleRng| attack(PICCO);
+    let mut battle = battle(
+        slow(TIMEOUT * 3),
+        TimeoutFallback::Controller(Box::new(fallback)),
+    );
+
+    battle.play_turn().unwrap();
+
+    assert!(timed_out(&battle));
+    assert_eq!(health(battle.teams(), 0, 0), 90);
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
+
+#[test]
+fn choices_in_time_are_kept() {
+    let mut battle = battle(slow(Duration::ZERO), TimeoutFallback::SkipTurn);
+
+    battle.play_turn().unwrap();
+
+    assert!(!timed_out(&battle));
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn channels_stop_waiting_by_the_deadline() {
+    let (request_sender, requests) = mpsc::channel();
+    let (choices, choice_receiver) = mpsc::channel();
+
+    let registry = ActionRegistry::new();
+    let controller = ChannelController::new(
+        registry,
+        request_sender,
+        choice_receiver,
+        slow(Duration::ZERO),
+    );
+
+    // Nobody answers the requests, but the channel is kept open.
+    let _choices = choices;
+
+    let mut battle = battle(controller, TimeoutFallback::SkipTurn);
+
+    battle.play_turn().unwrap();
+
+    assert!(requests.try_recv().is_ok());
+    assert!(timed_out(&battle));
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
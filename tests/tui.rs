@@ -0,0 +1,117 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::{ListState, StatefulWidget, Widget};
+
+use fierceful_atto::action::{Action, ActionState, Context};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::element::Element;
+use fierceful_atto::member::Member;
+use fierceful_atto::resource::{Cost, Resource};
+use fierceful_atto::round::RoundOrder;
+use fierceful_atto::team::Team;
+use fierceful_atto::tui::{ActionMenu, HealthBars, TurnOrder};
+
+use common::{attack_first, last_team_standing, Fighter};
+
+/// Expensive action with every kind of metadata.
+struct Fireball;
+
+impl<M> Action<M> for Fireball {
+    fn act(&mut self, _: Context<M>) -> ActionState {
+        ActionState::Finished
+    }
+
+    fn cost(&self) -> Vec<Cost> {
+        vec![Cost::new(Resource::Mana, 5)]
+    }
+
+    fn cooldown_turns(&self) -> u32 {
+        2
+    }
+
+    fn element(&self) -> Option<Element> {
+        Some(Element::new("Fire"))
+    }
+}
+
+/// Returns the rendered lines, without trailing spaces.
+fn lines(buf: &Buffer) -> Vec<String> {
+    (0..buf.area.height)
+        .map(|y| {
+            (0..buf.area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn health_bars_show_every_member() {
+    let mut team = Team::new(
+        String::from("Heroes"),
+        vec![
+            Fighter::new("Picco", 100, 10),
+            Fighter::new("Pocco", 40, 10),
+        ],
+    );
+    team.member_mut(0).unwrap().damage(50);
+
+    let mut buf = Buffer::empty(Rect::new(0, 0, 30, 3));
+    HealthBars::new(&team).render(buf.area, &mut buf);
+
+    let lines = lines(&buf);
+
+    assert!(lines[0].starts_with("Picco 50/100"));
+    assert!(lines[1].starts_with("Pocco 40/40"));
+    assert!(lines[2].is_empty());
+}
+
+#[test]
+fn turn_order_follows_the_round() {
+    let teams = vec![
+        Team::new(
+            String::from("Heroes"),
+            vec![Fighter::new("Picco", 100, 1), Fighter::new("Pocco", 100, 1)],
+        ),
+        Team::new(
+            String::from("Villains"),
+            vec![Fighter::new("Bacco", 100, 1), Fighter::new("Becco", 100, 1)],
+        ),
+    ];
+
+    let mut battle = last_team_standing(teams, attack_first)
+        .set_round_order(RoundOrder::TeamByTeam)
+        .set_seed(0)
+        .build()
+        .unwrap();
+
+    battle.play_turn().unwrap();
+
+    let mut buf = Buffer::empty(Rect::new(0, 0, 40, 1));
+    TurnOrder::new(battle.turn_system(), battle.teams()).render(buf.area, &mut buf);
+
+    assert_eq!(lines(&buf), ["Pocco > Bacco > Becco"]);
+}
+
+#[test]
+fn action_menu_shows_metadata() {
+    let actions: [&dyn Action<Fighter>; 2] = [&DirectAttack, &Fireball];
+
+    let mut state = ListState::default().with_selected(Some(1));
+    let mut buf = Buffer::empty(Rect::new(0, 0, 50, 2));
+    ActionMenu::new(actions).render(buf.area, &mut buf, &mut state);
+
+    assert_eq!(
+        lines(&buf),
+        [
+            "  DirectAttack",
+            "> Fireball (Fire, 5 Mana, 2 turns cooldown)"
+        ]
+    );
+}
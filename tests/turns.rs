@@ -0,0 +1,80 @@
+mod common;
+
+use fierceful_atto::action::{Action, ActionId};
+use fierceful_atto::battle::{Battle, State};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::MemberIdentifier;
+use fierceful_atto::team::Team;
+
+use common::{attack_first, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+fn battle() -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 50)]),
+        Team::new(
+            String::from("Villains"),
+            vec![Fighter::new("Bacco", 100, 10)],
+        ),
+    ];
+
+    last_team_standing(teams, attack_first)
+        .set_seed(0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn turns_sum_up_what_happened() {
+    let mut battle = battle();
+
+    let result = battle.play_turn().unwrap();
+
+    assert_eq!(result.turn_number, 1);
+    assert_eq!(result.performer, Some(PICCO));
+    assert_eq!(
+        result.action,
+        Some(Action::<Fighter>::action_id(&DirectAttack))
+    );
+    assert_eq!(result.targets, [BACCO]);
+    assert_eq!(result.state, State::InProgress);
+    assert_eq!(
+        result.events.first(),
+        Some(&Event::BattleStarted { seed: 0 })
+    );
+    assert!(result.events.contains(&Event::Damaged {
+        member: BACCO,
+        amount: 50
+    }));
+
+    let result = battle.play_turn().unwrap();
+
+    assert_eq!(result.performer, Some(BACCO));
+    assert_eq!(result.targets, [PICCO]);
+    assert!(!result.events.contains(&Event::BattleStarted { seed: 0 }));
+}
+
+#[test]
+fn turns_are_iterated_until_the_end() {
+    let mut battle = battle();
+
+    let results: Vec<_> = battle.turns().collect::<Result<_, _>>().unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results.last().unwrap().state, State::Finished);
+    assert!(results
+        .last()
+        .unwrap()
+        .events
+        .contains(&Event::BattleFinished));
+    assert!(battle.turns().next().is_none());
+
+    // Finished battles don't play any more turns.
+    let result = battle.play_turn().unwrap();
+
+    assert!(result.events.is_empty());
+    assert_eq!(result.action, None::<ActionId>);
+}
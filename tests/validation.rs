@@ -0,0 +1,175 @@
+mod common;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use fierceful_atto::action::{Action, ActionState, ChoiceCallback, ChoiceReturn, Context, Target};
+use fierceful_atto::battle::{Battle, InvalidActionFallback};
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::command::{BattleCommand, Rule};
+use fierceful_atto::error::{ActionError, BattleError};
+use fierceful_atto::member::MemberIdentifier;
+
+use common::{duel, health, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+const MISSING: MemberIdentifier = MemberIdentifier::new(1, 5);
+
+/// Action refusing to be performed, whoever the performers and targets are.
+struct Forbidden;
+
+impl Action<Fighter> for Forbidden {
+    fn act(&mut self, _: Context<'_, Fighter>) -> ActionState {
+        unreachable!("invalid actions shouldn't be performed");
+    }
+
+    fn validate(&self, _: &Context<'_, Fighter>) -> Result<(), ActionError> {
+        Err(ActionError::Rejected(String::from("forbidden")))
+    }
+}
+
+fn attack(target: MemberIdentifier) -> ChoiceReturn<Fighter> {
+    (
+        Box::new(DirectAttack),
+        Target::Single(PICCO),
+        Target::Single(target),
+    )
+}
+
+/// Battle whose callback targets a missing member for the first `invalid` choices, counting every call.
+fn battle(invalid: u32, fallback: InvalidActionFallback, calls: &Rc<Cell<u32>>) -> Battle<Fighter> {
+    let calls = calls.clone();
+
+    let controller: ChoiceCallback<Fighter> = Box::new(move |_, _, _| {
+        calls.set(calls.get() + 1);
+
+        if calls.get() <= invalid {
+            attack(MISSING)
+        } else {
+            attack(BACCO)
+        }
+    });
+
+    last_team_standing(duel(), controller)
+        .set_invalid_action_fallback(fallback)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn fails_on_missing_members_by_default() {
+    let calls = Rc::new(Cell::new(0));
+    let mut battle = battle(1, InvalidActionFallback::Fail, &calls);
+
+    assert_eq!(
+        battle.play_turn(),
+        Err(BattleError::InvalidTarget(Target::Single(MISSING)))
+    );
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+}
+
+#[test]
+fn skips_the_turn_of_invalid_actions() {
+    let calls = Rc::new(Cell::new(0));
+    let mut battle = battle(1, InvalidActionFallback::SkipTurn, &calls);
+
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 1, 0), 100);
+
+    battle.play_turn().unwrap();
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn asks_again_until_a_valid_action_is_chosen() {
+    let calls = Rc::new(Cell::new(0));
+    let mut battle = battle(2, InvalidActionFallback::AskAgain { attempts: 3 }, &calls);
+
+    battle.play_turn().unwrap();
+
+    assert_eq!(calls.get(), 3);
+    assert_eq!(health(battle.teams(), 1, 0), 90);
+}
+
+#[test]
+fn fails_after_running_out_of_attempts() {
+    let calls = Rc::new(Cell::new(0));
+    let mut battle = battle(5, InvalidActionFallback::AskAgain { attempts: 3 }, &calls);
+
+    assert_eq!(
+        battle.play_turn(),
+        Err(BattleError::InvalidTarget(Target::Single(MISSING)))
+    );
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn actions_can_reject_themselves() {
+    let controller: ChoiceCallback<Fighter> = Box::new(|_, _, _| {
+        (
+            Box::new(Forbidden),
+            Target::Single(PICCO),
+            Target::Single(BACCO),
+        )
+    });
+
+    let mut battle = last_team_standing(duel(), controller).build().unwrap();
+
+    assert_eq!(
+        battle.play_turn(),
+        Err(BattleError::InvalidAction(ActionError::Rejected(
+            String::from("forbidden")
+        )))
+    );
+}
+
+#[test]
+fn reports_list_every_rule_check() {
+    let mut battle = battle(0, InvalidActionFallback::Fail, &Rc::default());
+    let report = battle.validate_command(&BattleCommand::from(attack(BACCO)));
+
+    assert!(report.is_valid());
+    assert_eq!(
+        report.checks().iter().map(|c| c.rule).collect::<Vec<_>>(),
+        [
+            Rule::Timing,
+            Rule::Targeting,
+            Rule::Legality,
+            Rule::Costs,
+            Rule::Cooldown,
+            Rule::LineOfSight
+        ]
+    );
+}
+
+#[test]
+fn reports_describe_every_failure() {
+    let mut battle = battle(0, InvalidActionFallback::Fail, &Rc::default());
+    let command = BattleCommand::new(
+        Box::new(Forbidden),
+        Target::Single(BACCO),
+        Target::Single(MISSING),
+    );
+
+    let report = battle.validate_command(&command);
+
+    assert_eq!(
+        report.failures().collect::<Vec<_>>(),
+        [
+            (Rule::Timing, &ActionError::OutOfTurn { expected: PICCO }),
+            (
+                Rule::Targeting,
+                &ActionError::MissingMembers(Target::Single(MISSING))
+            ),
+            (
+                Rule::Legality,
+                &ActionError::Rejected(String::from("forbidden"))
+            ),
+        ]
+    );
+    assert_eq!(
+        report.into_result(),
+        Err(ActionError::OutOfTurn { expected: PICCO })
+    );
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fierceful_atto::action::{Action, ActionId, ChoiceReturn, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::controller::{ChoicePrompt, Controller};
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::visibility::{FogOfWar, TeamObserver};
+
+use common::{duel, last_team_standing, Fighter};
+
+const PICCO: MemberIdentifier = MemberIdentifier::new(0, 0);
+const BACCO: MemberIdentifier = MemberIdentifier::new(1, 0);
+
+/// What the acting team saw: the health of both members and the actions revealed by Picco.
+type Sighting = (u64, u64, Vec<ActionId>);
+
+/// Controller attacking the first member of the other team, remembering what it was shown.
+struct Spy(Rc<RefCell<Vec<Sighting>>>);
+
+impl Controller<Fighter> for Spy {
+    fn choose(&mut self, prompt: ChoicePrompt<Fighter>) -> ChoiceReturn<Fighter> {
+        let teams = prompt.teams();
+
+        self.0.borrow_mut().push((
+            teams[0].member(0).unwrap().health(),
+            teams[1].member(0).unwrap().health(),
+            prompt.revealed_actions(PICCO).cloned().collect(),
+        ));
+
+        let performer = prompt.performer().unwrap_or_default();
+
+        (
+            Box::new(DirectAttack),
+            Target::Single(performer),
+            Target::Single(MemberIdentifier::new(1 - performer.team_id, 0)),
+        )
+    }
+}
+
+fn battle(fog: FogOfWar, sightings: &Rc<RefCell<Vec<Sighting>>>) -> Battle<Fighter> {
+    last_team_standing(duel(), Spy(sightings.clone()))
+        .set_fog_of_war(fog)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn prompts_hide_the_health_of_opposing_members() {
+    let sightings = Rc::new(RefCell::new(Vec::new()));
+    let mut battle = battle(FogOfWar::new(), &sightings);
+    let attack = Action::<Fighter>::action_id(&DirectAttack);
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    // Bacco sees its own exact health, while Picco only sees Bacco standing.
+    assert_eq!(
+        *sightings.borrow(),
+        [
+            (100, 100, vec![]),
+            (100, 90, vec![attack.clone()]),
+            (100, 100, vec![attack]),
+        ]
+    );
+    assert_eq!(battle.teams()[1].member(0).unwrap().health(), 80);
+}
+
+#[test]
+fn visible_health_is_shown_exactly() {
+    let sightings = Rc::new(RefCell::new(Vec::new()));
+    let mut battle = battle(FogOfWar::new().with_visible_health(), &sightings);
+
+    for _ in 0..3 {
+        battle.play_turn().unwrap();
+    }
+
+    let seen: Vec<_> = sightings.borrow().iter().map(|s| s.1).collect();
+
+    assert_eq!(seen, [100, 90, 90]);
+}
+
+#[test]
+fn events_are_redacted_for_every_team() {
+    let mut battle = battle(FogOfWar::new(), &Rc::default());
+    battle.play_turn().unwrap();
+
+    let damage = |events: Vec<Event>| {
+        events
+            .into_iter()
+            .find_map(|e| match e {
+                Event::Damaged { member, amount } => Some((member, amount)),
+                _ => None,
+            })
+            .unwrap()
+    };
+
+    assert_eq!(damage(battle.events_seen_by(0)), (BACCO, 0));
+    assert_eq!(damage(battle.events_seen_by(1)), (BACCO, 10));
+    assert_eq!(battle.events_seen_by(0).len(), battle.events().len());
+}
+
+#[test]
+fn interrupted_actions_stay_hidden() {
+    let interrupted = Event::ActionInterrupted {
+        action: Action::<Fighter>::action_id(&DirectAttack),
+    };
+
+    assert_eq!(FogOfWar::new().redact_event(&interrupted, Some(0)), None);
+    assert_eq!(
+        FogOfWar::new()
+            .with_visible_actions()
+            .redact_event(&interrupted, Some(0)),
+        Some(interrupted)
+    );
+}
+
+#[test]
+fn performed_actions_are_revealed() {
+    let mut battle = battle(FogOfWar::new(), &Rc::default());
+    let attack = Action::<Fighter>::action_id(&DirectAttack);
+
+    battle.play_turn().unwrap();
+
+    let revealed = |member| {
+        battle
+            .components()
+            .revealed_actions(member)
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(revealed(PICCO), [attack]);
+    assert_eq!(revealed(BACCO), []);
+}
+
+#[test]
+fn team_observers_receive_redacted_events() {
+    let seen = [
+        Rc::new(RefCell::new(Vec::new())),
+        Rc::new(RefCell::new(Vec::new())),
+    ];
+    let mut builder =
+        last_team_standing(duel(), Spy(Rc::default())).set_fog_of_war(FogOfWar::new());
+
+    for (team_id, events) in seen.iter().enumerate() {
+        let events = events.clone();
+
+        builder = builder.add_observer(Box::new(TeamObserver::new(
+            team_id,
+            FogOfWar::new(),
+            move |e: &Event| events.borrow_mut().push(e.clone()),
+        )));
+    }
+
+    let mut battle = builder.build().unwrap();
+    battle.play_turn().unwrap();
+
+    assert_eq!(*seen[0].borrow(), battle.events_seen_by(0));
+    assert_eq!(*seen[1].borrow(), battle.events_seen_by(1));
+    assert_ne!(*seen[0].borrow(), *seen[1].borrow());
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use fierceful_atto::action::{ChoiceCallback, Target};
+use fierceful_atto::battle::Battle;
+use fierceful_atto::catalogue::actions::DirectAttack;
+use fierceful_atto::event::Event;
+use fierceful_atto::member::{Member, MemberIdentifier};
+use fierceful_atto::team::Team;
+use fierceful_atto::wave::Waves;
+
+use common::{health, last_team_standing, Fighter};
+
+/// Every performer attacks the first alive member of the other team.
+fn attack_first_alive() -> ChoiceCallback<Fighter> {
+    Box::new(|team_list, performer, _| {
+        let performer = performer.unwrap_or_default();
+        let team_id = 1 - performer.team_id;
+
+        let target = team_list[team_id]
+            .member_list()
+            .iter()
+            .position(|m| m.health() > 0)
+            .map_or(Target::None, |member_id| {
+                Target::Single(MemberIdentifier::new(team_id, member_id))
+            });
+
+        (Box::new(DirectAttack), Target::Single(performer), target)
+    })
+}
+
+fn horde() -> Battle<Fighter> {
+    let teams = vec![
+        Team::new(String::from("Heroes"), vec![Fighter::new("Picco", 100, 10)]),
+        Team::new(String::from("Horde"), vec![Fighter::new("Slime", 10, 1)]),
+    ];
+
+    let waves = Waves::new(1)
+        .with_wave(Box::new(|_| vec![Fighter::new("Goblin", 20, 2)]))
+        .with_wave(Box::new(|_| {
+            vec![Fighter::new("Orc", 10, 3), Fighter::new("Orc", 10, 3)]
+        }));
+
+    last_team_standing(teams, attack_first_alive())
+        .set_seed(0)
+        .set_waves(waves)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn end_condition_waits_for_last_wave() {
+    let mut battle = horde();
+
+    // The first wave falls right away, but the battle goes on.
+    battle.play_turn().unwrap();
+    assert!(!battle.is_finished());
+    assert_eq!(battle.teams()[1].member_list().len(), 2);
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    let cleared: Vec<_> = battle
+        .events()
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::WaveCleared { wave } => Some(*wave),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(cleared, vec![0, 1, 2]);
+    assert_eq!(battle.teams()[1].member_list().len(), 4);
+    assert!(health(battle.teams(), 0, 0) > 0);
+}
+
+#[test]
+fn statistics_are_recorded_per_wave() {
+    let mut battle = horde();
+
+    while !battle.is_finished() {
+        battle.play_turn().unwrap();
+    }
+
+    let statistics = battle.wave_statistics();
+    assert_eq!(statistics.len(), 3);
+    assert_eq!(statistics[0].damage_dealt, 10);
+    assert_eq!(statistics[1].damage_dealt, 20);
+    assert_eq!(statistics[2].damage_dealt, 20);
+}